@@ -0,0 +1,4 @@
+// Recompilar cuando cambien las migraciones que embebe `sqlx::migrate!`
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1,96 @@
+-- Núcleo: perfiles, páginas y sus permisos, catálogos y personas
+
+CREATE TABLE pagina (
+    idpag BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    codpag VARCHAR(60) NOT NULL,
+    nompag VARCHAR(100) NOT NULL,
+    rutpag VARCHAR(200) NOT NULL,
+    mospag BOOLEAN NOT NULL DEFAULT TRUE,
+    ordpag BIGINT NOT NULL DEFAULT 0,
+    icopag VARCHAR(60) NOT NULL DEFAULT '',
+    despag VARCHAR(255) NOT NULL DEFAULT '',
+    CONSTRAINT uq_pagina_codpag UNIQUE (codpag)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE perfil (
+    idpef BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    nompef VARCHAR(100) NOT NULL,
+    -- Página a la que entra el perfil después del login
+    pagpri BIGINT NOT NULL,
+    CONSTRAINT uq_perfil_nompef UNIQUE (nompef),
+    CONSTRAINT fk_perfil_pagpri FOREIGN KEY (pagpri) REFERENCES pagina (idpag)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE pagper (
+    idpef BIGINT NOT NULL,
+    idpag BIGINT NOT NULL,
+    can_create BOOLEAN NOT NULL DEFAULT FALSE,
+    can_read BOOLEAN NOT NULL DEFAULT FALSE,
+    can_update BOOLEAN NOT NULL DEFAULT FALSE,
+    can_delete BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (idpef, idpag),
+    INDEX ix_pagper_idpag (idpag),
+    CONSTRAINT fk_pagper_idpef FOREIGN KEY (idpef) REFERENCES perfil (idpef) ON DELETE CASCADE,
+    CONSTRAINT fk_pagper_idpag FOREIGN KEY (idpag) REFERENCES pagina (idpag) ON DELETE CASCADE
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+-- Los códigos coinciden con `TipoDocumento`
+CREATE TABLE tipo_documento (
+    idtdoc BIGINT NOT NULL PRIMARY KEY,
+    nomtdoc VARCHAR(100) NOT NULL,
+    acttdoc BOOLEAN NOT NULL DEFAULT TRUE
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+INSERT INTO tipo_documento (idtdoc, nomtdoc) VALUES
+    (1, 'Cédula de ciudadanía'),
+    (2, 'Tarjeta de identidad'),
+    (3, 'Cédula de extranjería'),
+    (4, 'Pasaporte'),
+    (5, 'NIT');
+
+-- DIVIPOLA (DANE); los datos se cargan con la importación de ubicaciones
+CREATE TABLE departamento (
+    coddep BIGINT NOT NULL PRIMARY KEY,
+    nomdep VARCHAR(100) NOT NULL
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE ubicacion (
+    codubi BIGINT NOT NULL PRIMARY KEY,
+    nomubi VARCHAR(100) NOT NULL,
+    coddep BIGINT NOT NULL,
+    INDEX ix_ubicacion_coddep (coddep),
+    CONSTRAINT fk_ubicacion_coddep FOREIGN KEY (coddep) REFERENCES departamento (coddep)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE persona (
+    idper BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    ndocper VARCHAR(20) NULL,
+    tdocper BIGINT NOT NULL,
+    nomper VARCHAR(100) NOT NULL,
+    apeper VARCHAR(100) NOT NULL,
+    dirper VARCHAR(200) NULL,
+    telper VARCHAR(30) NOT NULL,
+    telnor VARCHAR(20) NULL,
+    codubi BIGINT NOT NULL,
+    idpef BIGINT NOT NULL,
+    pass VARCHAR(255) NULL,
+    emaper VARCHAR(150) NOT NULL,
+    actper BOOLEAN NOT NULL DEFAULT TRUE,
+    verper BIGINT NOT NULL DEFAULT 1,
+    estper VARCHAR(20) NOT NULL DEFAULT 'activo',
+    motsus VARCHAR(500) NULL,
+    fecsus DATETIME(6) NULL,
+    avaper VARCHAR(64) NULL,
+    -- La anonimización deja ndocper en NULL, que no choca con el índice único
+    CONSTRAINT uq_persona_ndocper UNIQUE (ndocper),
+    CONSTRAINT uq_persona_emaper UNIQUE (emaper),
+    INDEX ix_persona_idpef (idpef),
+    INDEX ix_persona_codubi (codubi),
+    INDEX ix_persona_estper (estper),
+    INDEX ix_persona_telnor (telnor),
+    CONSTRAINT fk_persona_tdocper FOREIGN KEY (tdocper) REFERENCES tipo_documento (idtdoc),
+    CONSTRAINT fk_persona_codubi FOREIGN KEY (codubi) REFERENCES ubicacion (codubi),
+    CONSTRAINT fk_persona_idpef FOREIGN KEY (idpef) REFERENCES perfil (idpef),
+    CONSTRAINT ck_persona_estper
+        CHECK (estper IN ('pendiente', 'activo', 'suspendido', 'bloqueado', 'archivado'))
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
@@ -0,0 +1,62 @@
+-- Historial temporal de personas, auditoría de escrituras y registro de lecturas
+
+CREATE TABLE persona_historial (
+    idhis BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    idper BIGINT NOT NULL,
+    verper BIGINT NOT NULL,
+    ndocper VARCHAR(20) NULL,
+    tdocper BIGINT NOT NULL,
+    nomper VARCHAR(100) NOT NULL,
+    apeper VARCHAR(100) NOT NULL,
+    dirper VARCHAR(200) NULL,
+    telper VARCHAR(30) NOT NULL,
+    codubi BIGINT NOT NULL,
+    idpef BIGINT NOT NULL,
+    emaper VARCHAR(150) NOT NULL,
+    actper BOOLEAN NOT NULL,
+    estper VARCHAR(20) NOT NULL,
+    motsus VARCHAR(500) NULL,
+    fecsus DATETIME(6) NULL,
+    -- Vigencia [vigdes, vighas); la versión actual tiene vighas en NULL
+    vigdes DATETIME(6) NOT NULL,
+    vighas DATETIME(6) NULL,
+    INDEX ix_persona_historial_idper (idper, vigdes),
+    INDEX ix_persona_historial_verper (idper, verper),
+    CONSTRAINT fk_persona_historial_idper
+        FOREIGN KEY (idper) REFERENCES persona (idper) ON DELETE CASCADE
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+-- Solo lectura para la aplicación: no se actualiza ni se borra
+CREATE TABLE auditoria (
+    idaud BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    idactor BIGINT NOT NULL,
+    accion VARCHAR(40) NOT NULL,
+    entidad VARCHAR(60) NOT NULL,
+    identidad VARCHAR(100) NOT NULL,
+    antes JSON NULL,
+    despues JSON NULL,
+    cambios JSON NULL,
+    ip VARCHAR(45) NULL,
+    agente TEXT NULL,
+    idsolicitud VARCHAR(64) NULL,
+    fecaud DATETIME(6) NOT NULL,
+    INDEX ix_auditoria_fecaud (fecaud),
+    INDEX ix_auditoria_entidad (entidad, identidad),
+    INDEX ix_auditoria_idactor (idactor, fecaud),
+    INDEX ix_auditoria_idsolicitud (idsolicitud)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE acceso_persona (
+    idacc BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    idactor BIGINT NOT NULL,
+    idper BIGINT NOT NULL,
+    via VARCHAR(40) NOT NULL,
+    proposito VARCHAR(255) NULL,
+    campos TEXT NOT NULL,
+    ip VARCHAR(45) NULL,
+    idsolicitud VARCHAR(64) NULL,
+    fecacc DATETIME(6) NOT NULL,
+    INDEX ix_acceso_persona_idper (idper, fecacc),
+    INDEX ix_acceso_persona_idactor (idactor, fecacc),
+    INDEX ix_acceso_persona_fecacc (fecacc)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
@@ -0,0 +1,60 @@
+-- Ley 1581: políticas y consentimientos, solicitudes de habeas data y adjuntos
+
+CREATE TABLE politica_datos (
+    idpol BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    verpol VARCHAR(20) NOT NULL,
+    texpol TEXT NOT NULL,
+    -- Desde cuándo rige
+    fecpol DATETIME(6) NOT NULL,
+    CONSTRAINT uq_politica_datos_verpol UNIQUE (verpol)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE consentimiento (
+    idcon BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    idper BIGINT NOT NULL,
+    idpol BIGINT NOT NULL,
+    cancon VARCHAR(20) NOT NULL,
+    feccon DATETIME(6) NOT NULL,
+    fecrev DATETIME(6) NULL,
+    INDEX ix_consentimiento_idper (idper, fecrev),
+    INDEX ix_consentimiento_idpol (idpol, fecrev),
+    CONSTRAINT fk_consentimiento_idper FOREIGN KEY (idper) REFERENCES persona (idper),
+    CONSTRAINT fk_consentimiento_idpol FOREIGN KEY (idpol) REFERENCES politica_datos (idpol),
+    CONSTRAINT ck_consentimiento_cancon
+        CHECK (cancon IN ('web', 'movil', 'presencial', 'telefonico', 'escrito'))
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE habeas_data (
+    idsol BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    idper BIGINT NOT NULL,
+    tipsol VARCHAR(20) NOT NULL,
+    idsolicitante BIGINT NOT NULL,
+    fecsol DATETIME(6) NOT NULL,
+    INDEX ix_habeas_data_idper (idper),
+    CONSTRAINT fk_habeas_data_idper FOREIGN KEY (idper) REFERENCES persona (idper),
+    CONSTRAINT fk_habeas_data_idsolicitante
+        FOREIGN KEY (idsolicitante) REFERENCES persona (idper),
+    CONSTRAINT ck_habeas_data_tipsol CHECK (tipsol IN ('acceso', 'supresion'))
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE adjunto (
+    idadj BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    idper BIGINT NOT NULL,
+    catadj VARCHAR(20) NOT NULL,
+    nomadj VARCHAR(255) NOT NULL,
+    tipadj VARCHAR(100) NOT NULL,
+    tamadj BIGINT NOT NULL,
+    -- SHA-256 en hexadecimal
+    hashadj CHAR(64) NOT NULL,
+    -- Clave del objeto en el almacenamiento
+    clvadj VARCHAR(255) NOT NULL,
+    idsubidor BIGINT NOT NULL,
+    fecadj DATETIME(6) NOT NULL,
+    CONSTRAINT uq_adjunto_clvadj UNIQUE (clvadj),
+    INDEX ix_adjunto_idper (idper, fecadj),
+    CONSTRAINT fk_adjunto_idper FOREIGN KEY (idper) REFERENCES persona (idper),
+    CONSTRAINT fk_adjunto_idsubidor FOREIGN KEY (idsubidor) REFERENCES persona (idper),
+    CONSTRAINT ck_adjunto_catadj
+        CHECK (catadj IN ('identificacion', 'contrato', 'foto', 'otro')),
+    CONSTRAINT ck_adjunto_tamadj CHECK (tamadj >= 0)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
@@ -0,0 +1,40 @@
+-- Candidatos a persona duplicada y fusiones aplicadas
+
+CREATE TABLE duplicado (
+    iddup BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    -- El par se guarda ordenado (idper1 < idper2) para no repetirlo al revés
+    idper1 BIGINT NOT NULL,
+    idper2 BIGINT NOT NULL,
+    pundup DOUBLE NOT NULL,
+    -- Coincidencias separadas por coma (documento, email, telefono, nombre)
+    motdup VARCHAR(100) NOT NULL,
+    estdup VARCHAR(20) NOT NULL DEFAULT 'pendiente',
+    fecdup DATETIME(6) NOT NULL,
+    idrev BIGINT NULL,
+    fecrev DATETIME(6) NULL,
+    CONSTRAINT uq_duplicado_par UNIQUE (idper1, idper2),
+    INDEX ix_duplicado_estdup (estdup, pundup),
+    INDEX ix_duplicado_idper2 (idper2),
+    CONSTRAINT fk_duplicado_idper1 FOREIGN KEY (idper1) REFERENCES persona (idper),
+    CONSTRAINT fk_duplicado_idper2 FOREIGN KEY (idper2) REFERENCES persona (idper),
+    CONSTRAINT fk_duplicado_idrev FOREIGN KEY (idrev) REFERENCES persona (idper),
+    CONSTRAINT ck_duplicado_par CHECK (idper1 < idper2),
+    CONSTRAINT ck_duplicado_estdup CHECK (estdup IN ('pendiente', 'descartado', 'fusionado'))
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+
+CREATE TABLE fusion_persona (
+    idfus BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    idsup BIGINT NOT NULL,
+    idabs BIGINT NOT NULL,
+    iddup BIGINT NULL,
+    idactor BIGINT NOT NULL,
+    detfus JSON NOT NULL,
+    fecfus DATETIME(6) NOT NULL,
+    -- Una persona solo puede ser absorbida una vez
+    CONSTRAINT uq_fusion_persona_idabs UNIQUE (idabs),
+    INDEX ix_fusion_persona_idsup (idsup),
+    CONSTRAINT fk_fusion_persona_idsup FOREIGN KEY (idsup) REFERENCES persona (idper),
+    CONSTRAINT fk_fusion_persona_idabs FOREIGN KEY (idabs) REFERENCES persona (idper),
+    CONSTRAINT fk_fusion_persona_iddup FOREIGN KEY (iddup) REFERENCES duplicado (iddup),
+    CONSTRAINT fk_fusion_persona_idactor FOREIGN KEY (idactor) REFERENCES persona (idper)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
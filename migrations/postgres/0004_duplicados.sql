@@ -0,0 +1,35 @@
+-- Candidatos a persona duplicada y fusiones aplicadas
+
+CREATE TABLE duplicado (
+    iddup BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    -- El par se guarda ordenado (idper1 < idper2) para no repetirlo al revés
+    idper1 BIGINT NOT NULL REFERENCES persona (idper),
+    idper2 BIGINT NOT NULL REFERENCES persona (idper),
+    pundup DOUBLE PRECISION NOT NULL,
+    -- Coincidencias separadas por coma (documento, email, telefono, nombre)
+    motdup VARCHAR(100) NOT NULL,
+    estdup VARCHAR(20) NOT NULL DEFAULT 'pendiente',
+    fecdup TIMESTAMPTZ NOT NULL,
+    idrev BIGINT REFERENCES persona (idper),
+    fecrev TIMESTAMPTZ,
+    CONSTRAINT uq_duplicado_par UNIQUE (idper1, idper2),
+    CONSTRAINT ck_duplicado_par CHECK (idper1 < idper2),
+    CONSTRAINT ck_duplicado_estdup CHECK (estdup IN ('pendiente', 'descartado', 'fusionado'))
+);
+
+CREATE INDEX ix_duplicado_estdup ON duplicado (estdup, pundup);
+CREATE INDEX ix_duplicado_idper2 ON duplicado (idper2);
+
+CREATE TABLE fusion_persona (
+    idfus BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    idsup BIGINT NOT NULL REFERENCES persona (idper),
+    idabs BIGINT NOT NULL REFERENCES persona (idper),
+    iddup BIGINT REFERENCES duplicado (iddup),
+    idactor BIGINT NOT NULL REFERENCES persona (idper),
+    detfus JSONB NOT NULL,
+    fecfus TIMESTAMPTZ NOT NULL,
+    -- Una persona solo puede ser absorbida una vez
+    CONSTRAINT uq_fusion_persona_idabs UNIQUE (idabs)
+);
+
+CREATE INDEX ix_fusion_persona_idsup ON fusion_persona (idsup);
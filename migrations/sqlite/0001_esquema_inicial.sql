@@ -0,0 +1,93 @@
+-- Núcleo: perfiles, páginas y sus permisos, catálogos y personas
+-- En SQLite las fechas se guardan como texto RFC 3339 (UTC) y los JSON como TEXT
+
+CREATE TABLE pagina (
+    idpag INTEGER PRIMARY KEY AUTOINCREMENT,
+    codpag VARCHAR(60) NOT NULL,
+    nompag VARCHAR(100) NOT NULL,
+    rutpag VARCHAR(200) NOT NULL,
+    mospag BOOLEAN NOT NULL DEFAULT TRUE,
+    ordpag BIGINT NOT NULL DEFAULT 0,
+    icopag VARCHAR(60) NOT NULL DEFAULT '',
+    despag VARCHAR(255) NOT NULL DEFAULT '',
+    CONSTRAINT uq_pagina_codpag UNIQUE (codpag)
+);
+
+CREATE TABLE perfil (
+    idpef INTEGER PRIMARY KEY AUTOINCREMENT,
+    nompef VARCHAR(100) NOT NULL,
+    -- Página a la que entra el perfil después del login
+    pagpri BIGINT NOT NULL REFERENCES pagina (idpag),
+    CONSTRAINT uq_perfil_nompef UNIQUE (nompef)
+);
+
+CREATE TABLE pagper (
+    idpef BIGINT NOT NULL REFERENCES perfil (idpef) ON DELETE CASCADE,
+    idpag BIGINT NOT NULL REFERENCES pagina (idpag) ON DELETE CASCADE,
+    can_create BOOLEAN NOT NULL DEFAULT FALSE,
+    can_read BOOLEAN NOT NULL DEFAULT FALSE,
+    can_update BOOLEAN NOT NULL DEFAULT FALSE,
+    can_delete BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (idpef, idpag)
+);
+
+CREATE INDEX ix_pagper_idpag ON pagper (idpag);
+
+-- Los códigos coinciden con `TipoDocumento`
+CREATE TABLE tipo_documento (
+    idtdoc BIGINT PRIMARY KEY,
+    nomtdoc VARCHAR(100) NOT NULL,
+    acttdoc BOOLEAN NOT NULL DEFAULT TRUE
+);
+
+INSERT INTO tipo_documento (idtdoc, nomtdoc) VALUES
+    (1, 'Cédula de ciudadanía'),
+    (2, 'Tarjeta de identidad'),
+    (3, 'Cédula de extranjería'),
+    (4, 'Pasaporte'),
+    (5, 'NIT');
+
+-- DIVIPOLA (DANE); los datos se cargan con la importación de ubicaciones
+CREATE TABLE departamento (
+    coddep BIGINT PRIMARY KEY,
+    nomdep VARCHAR(100) NOT NULL
+);
+
+CREATE TABLE ubicacion (
+    codubi BIGINT PRIMARY KEY,
+    nomubi VARCHAR(100) NOT NULL,
+    coddep BIGINT NOT NULL REFERENCES departamento (coddep)
+);
+
+CREATE INDEX ix_ubicacion_coddep ON ubicacion (coddep);
+
+CREATE TABLE persona (
+    idper INTEGER PRIMARY KEY AUTOINCREMENT,
+    ndocper VARCHAR(20),
+    tdocper BIGINT NOT NULL REFERENCES tipo_documento (idtdoc),
+    nomper VARCHAR(100) NOT NULL,
+    apeper VARCHAR(100) NOT NULL,
+    dirper VARCHAR(200),
+    telper VARCHAR(30) NOT NULL,
+    telnor VARCHAR(20),
+    codubi BIGINT NOT NULL REFERENCES ubicacion (codubi),
+    idpef BIGINT NOT NULL REFERENCES perfil (idpef),
+    pass VARCHAR(255),
+    emaper VARCHAR(150) NOT NULL,
+    actper BOOLEAN NOT NULL DEFAULT TRUE,
+    verper BIGINT NOT NULL DEFAULT 1,
+    estper VARCHAR(20) NOT NULL DEFAULT 'activo',
+    motsus VARCHAR(500),
+    fecsus DATETIME,
+    avaper VARCHAR(64),
+    -- La anonimización deja ndocper en NULL, que no choca con el índice único
+    CONSTRAINT uq_persona_ndocper UNIQUE (ndocper),
+    CONSTRAINT uq_persona_emaper UNIQUE (emaper),
+    CONSTRAINT ck_persona_estper
+        CHECK (estper IN ('pendiente', 'activo', 'suspendido', 'bloqueado', 'archivado'))
+);
+
+CREATE INDEX ix_persona_idpef ON persona (idpef);
+CREATE INDEX ix_persona_codubi ON persona (codubi);
+CREATE INDEX ix_persona_estper ON persona (estper);
+CREATE INDEX ix_persona_telnor ON persona (telnor);
@@ -0,0 +1,63 @@
+-- Historial temporal de personas, auditoría de escrituras y registro de lecturas
+
+CREATE TABLE persona_historial (
+    idhis INTEGER PRIMARY KEY AUTOINCREMENT,
+    idper BIGINT NOT NULL REFERENCES persona (idper) ON DELETE CASCADE,
+    verper BIGINT NOT NULL,
+    ndocper VARCHAR(20),
+    tdocper BIGINT NOT NULL,
+    nomper VARCHAR(100) NOT NULL,
+    apeper VARCHAR(100) NOT NULL,
+    dirper VARCHAR(200),
+    telper VARCHAR(30) NOT NULL,
+    codubi BIGINT NOT NULL,
+    idpef BIGINT NOT NULL,
+    emaper VARCHAR(150) NOT NULL,
+    actper BOOLEAN NOT NULL,
+    estper VARCHAR(20) NOT NULL,
+    motsus VARCHAR(500),
+    fecsus DATETIME,
+    -- Vigencia [vigdes, vighas); la versión actual tiene vighas en NULL
+    vigdes DATETIME NOT NULL,
+    vighas DATETIME
+);
+
+CREATE INDEX ix_persona_historial_idper ON persona_historial (idper, vigdes);
+CREATE INDEX ix_persona_historial_verper ON persona_historial (idper, verper);
+
+-- Solo lectura para la aplicación: no se actualiza ni se borra
+CREATE TABLE auditoria (
+    idaud INTEGER PRIMARY KEY AUTOINCREMENT,
+    idactor BIGINT NOT NULL,
+    accion VARCHAR(40) NOT NULL,
+    entidad VARCHAR(60) NOT NULL,
+    identidad VARCHAR(100) NOT NULL,
+    antes TEXT,
+    despues TEXT,
+    cambios TEXT,
+    ip VARCHAR(45),
+    agente TEXT,
+    idsolicitud VARCHAR(64),
+    fecaud DATETIME NOT NULL
+);
+
+CREATE INDEX ix_auditoria_fecaud ON auditoria (fecaud);
+CREATE INDEX ix_auditoria_entidad ON auditoria (entidad, identidad);
+CREATE INDEX ix_auditoria_idactor ON auditoria (idactor, fecaud);
+CREATE INDEX ix_auditoria_idsolicitud ON auditoria (idsolicitud);
+
+CREATE TABLE acceso_persona (
+    idacc INTEGER PRIMARY KEY AUTOINCREMENT,
+    idactor BIGINT NOT NULL,
+    idper BIGINT NOT NULL,
+    via VARCHAR(40) NOT NULL,
+    proposito VARCHAR(255),
+    campos TEXT NOT NULL,
+    ip VARCHAR(45),
+    idsolicitud VARCHAR(64),
+    fecacc DATETIME NOT NULL
+);
+
+CREATE INDEX ix_acceso_persona_idper ON acceso_persona (idper, fecacc);
+CREATE INDEX ix_acceso_persona_idactor ON acceso_persona (idactor, fecacc);
+CREATE INDEX ix_acceso_persona_fecacc ON acceso_persona (fecacc);
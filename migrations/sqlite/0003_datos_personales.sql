@@ -0,0 +1,56 @@
+-- Ley 1581: políticas y consentimientos, solicitudes de habeas data y adjuntos
+
+CREATE TABLE politica_datos (
+    idpol INTEGER PRIMARY KEY AUTOINCREMENT,
+    verpol VARCHAR(20) NOT NULL,
+    texpol TEXT NOT NULL,
+    -- Desde cuándo rige
+    fecpol DATETIME NOT NULL,
+    CONSTRAINT uq_politica_datos_verpol UNIQUE (verpol)
+);
+
+CREATE TABLE consentimiento (
+    idcon INTEGER PRIMARY KEY AUTOINCREMENT,
+    idper BIGINT NOT NULL REFERENCES persona (idper),
+    idpol BIGINT NOT NULL REFERENCES politica_datos (idpol),
+    cancon VARCHAR(20) NOT NULL,
+    feccon DATETIME NOT NULL,
+    fecrev DATETIME,
+    CONSTRAINT ck_consentimiento_cancon
+        CHECK (cancon IN ('web', 'movil', 'presencial', 'telefonico', 'escrito'))
+);
+
+CREATE INDEX ix_consentimiento_idper ON consentimiento (idper, fecrev);
+CREATE INDEX ix_consentimiento_idpol ON consentimiento (idpol, fecrev);
+
+CREATE TABLE habeas_data (
+    idsol INTEGER PRIMARY KEY AUTOINCREMENT,
+    idper BIGINT NOT NULL REFERENCES persona (idper),
+    tipsol VARCHAR(20) NOT NULL,
+    idsolicitante BIGINT NOT NULL REFERENCES persona (idper),
+    fecsol DATETIME NOT NULL,
+    CONSTRAINT ck_habeas_data_tipsol CHECK (tipsol IN ('acceso', 'supresion'))
+);
+
+CREATE INDEX ix_habeas_data_idper ON habeas_data (idper);
+
+CREATE TABLE adjunto (
+    idadj INTEGER PRIMARY KEY AUTOINCREMENT,
+    idper BIGINT NOT NULL REFERENCES persona (idper),
+    catadj VARCHAR(20) NOT NULL,
+    nomadj VARCHAR(255) NOT NULL,
+    tipadj VARCHAR(100) NOT NULL,
+    tamadj BIGINT NOT NULL,
+    -- SHA-256 en hexadecimal
+    hashadj CHAR(64) NOT NULL,
+    -- Clave del objeto en el almacenamiento
+    clvadj VARCHAR(255) NOT NULL,
+    idsubidor BIGINT NOT NULL REFERENCES persona (idper),
+    fecadj DATETIME NOT NULL,
+    CONSTRAINT uq_adjunto_clvadj UNIQUE (clvadj),
+    CONSTRAINT ck_adjunto_catadj
+        CHECK (catadj IN ('identificacion', 'contrato', 'foto', 'otro')),
+    CONSTRAINT ck_adjunto_tamadj CHECK (tamadj >= 0)
+);
+
+CREATE INDEX ix_adjunto_idper ON adjunto (idper, fecadj);
@@ -0,0 +1,24 @@
+-- Datos mínimos para arrancar: el perfil superadministrador (idpef 1, el que
+-- reconocen los handlers) y una página por cada código (`pagina.codpag`) que
+-- los handlers autorizan. En una base con datos no pisa lo que ya exista
+
+INSERT OR IGNORE INTO pagina (codpag, nompag, rutpag, ordpag) VALUES
+    ('inicio', 'Inicio', '/', 0),
+    ('persona.activar', 'Activar personas', '/persona/activar', 10),
+    ('persona.suspender', 'Suspender personas', '/persona/suspender', 11),
+    ('persona.bloquear', 'Bloquear personas', '/persona/bloquear', 12),
+    ('persona.desbloquear', 'Desbloquear personas', '/persona/desbloquear', 13),
+    ('persona.archivar', 'Archivar personas', '/persona/archivar', 14),
+    ('persona.habeas_data', 'Habeas data', '/persona/habeas-data', 20),
+    ('persona.consentimiento', 'Consentimientos', '/persona/consentimiento', 21),
+    ('persona.historial', 'Historial de personas', '/persona/historial', 22),
+    ('persona.adjuntos', 'Adjuntos de personas', '/persona/adjuntos', 23),
+    ('persona.avatar', 'Avatares de personas', '/persona/avatar', 24);
+
+INSERT OR IGNORE INTO perfil (idpef, nompef, pagpri)
+SELECT 1, 'super_admin', idpag FROM pagina WHERE codpag = 'inicio';
+
+-- El superadministrador pasa todas las autorizaciones; sus permisos sirven al menú
+INSERT OR IGNORE INTO pagper (idpef, idpag, can_create, can_read, can_update, can_delete)
+SELECT 1, idpag, TRUE, TRUE, TRUE, TRUE FROM pagina
+WHERE EXISTS (SELECT 1 FROM perfil WHERE idpef = 1 AND nompef = 'super_admin');
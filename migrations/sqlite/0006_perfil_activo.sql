@@ -0,0 +1,4 @@
+-- Los perfiles se administran como catálogo: se desactivan en vez de borrarse,
+-- porque personas y permisos los referencian
+
+ALTER TABLE perfil ADD COLUMN actpef BOOLEAN NOT NULL DEFAULT TRUE;
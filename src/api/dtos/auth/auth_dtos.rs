@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Deserialize)]
 pub struct LoginRequestDTO {
   pub email: String,
+  #[allow(dead_code)] // Pendiente: verificar contra el hash almacenado
   pub password: String,
 }
 
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::domain::CanalConsentimiento;
+
+/// Cuerpo de POST /consentimiento/politicas
+#[derive(Debug, Deserialize, Validate)]
+pub struct PublicarPoliticaDTO {
+    #[validate(length(min = 1, max = 20, message = "La versión debe tener entre 1 y 20 caracteres"))]
+    pub verpol: String,
+    #[validate(length(min = 1, message = "El texto de la política es requerido"))]
+    pub texpol: String,
+    /// Inicio de vigencia; sin fecha rige desde ya
+    pub fecpol: Option<DateTime<Utc>>,
+}
+
+/// Cuerpo de POST /persona/:idper/consentimiento
+#[derive(Debug, Deserialize, Validate)]
+pub struct AceptarConsentimientoDTO {
+    /// Política que se le mostró a la persona
+    #[validate(range(min = 1, message = "Política inválida"))]
+    pub idpol: i64,
+    pub cancon: CanalConsentimiento,
+}
@@ -0,0 +1,2 @@
+mod consentimiento_dtos;
+pub use consentimiento_dtos::{AceptarConsentimientoDTO, PublicarPoliticaDTO};
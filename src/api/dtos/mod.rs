@@ -1,3 +1,7 @@
 mod auth;
+mod consentimiento;
+mod persona;
 
 pub use auth::*;
+pub use consentimiento::*;
+pub use persona::*;
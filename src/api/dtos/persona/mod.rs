@@ -0,0 +1,5 @@
+mod persona_dtos;
+pub use persona_dtos::{
+    BloquearPersonaDTO, CreatePersonaDTO, PatchPersonaDTO, PersonaLabels, PersonaResponseDTO,
+    SuspenderPersonaDTO, UpdatePersonaDTO,
+};
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::dtos::AceptarConsentimientoDTO,
+    core::services::persona::rules,
+    domain::{EstadoPersona, Persona},
+};
+
+fn default_true() -> bool {
+    true
+}
+
+/// Cuerpo de POST /persona
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePersonaDTO {
+    #[validate(custom(function = rules::documento))]
+    pub ndocper: Option<String>,
+    #[validate(custom(function = rules::tipo_documento))]
+    pub tdocper: i64,
+    #[validate(custom(function = rules::nombre))]
+    pub nomper: String,
+    #[validate(custom(function = rules::apellido))]
+    pub apeper: String,
+    #[validate(custom(function = rules::direccion))]
+    pub dirper: Option<String>,
+    pub telper: String,
+    #[validate(custom(function = rules::ubicacion))]
+    pub codubi: i64,
+    #[validate(custom(function = rules::perfil))]
+    pub idpef: i64,
+    #[validate(custom(function = rules::contrasena))]
+    pub pass: Option<String>,
+    #[validate(custom(function = rules::email))]
+    pub emaper: String,
+    #[serde(default = "default_true")]
+    pub actper: bool,
+    /// Aceptación de la política vigente, registrada junto con la persona
+    #[validate(nested)]
+    pub consentimiento: Option<AceptarConsentimientoDTO>,
+}
+
+impl From<CreatePersonaDTO> for Persona {
+    fn from(dto: CreatePersonaDTO) -> Self {
+        Persona {
+            idper: 0,
+            ndocper: dto.ndocper,
+            tdocper: dto.tdocper,
+            nomper: dto.nomper,
+            apeper: dto.apeper,
+            dirper: dto.dirper,
+            telper: dto.telper,
+            codubi: dto.codubi,
+            idpef: dto.idpef,
+            pass: dto.pass,
+            emaper: dto.emaper,
+            actper: dto.actper,
+            verper: 0,
+            estper: EstadoPersona::default(),
+            motsus: None,
+            fecsus: None,
+            avaper: None,
+            telnor: None,
+        }
+    }
+}
+
+/// Cuerpo de PUT /persona/:idper (reemplazo completo, sin contraseña)
+/// El estado de la cuenta no se edita aquí: va por los endpoints de transición
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePersonaDTO {
+    #[validate(custom(function = rules::documento))]
+    pub ndocper: Option<String>,
+    #[validate(custom(function = rules::tipo_documento))]
+    pub tdocper: i64,
+    #[validate(custom(function = rules::nombre))]
+    pub nomper: String,
+    #[validate(custom(function = rules::apellido))]
+    pub apeper: String,
+    #[validate(custom(function = rules::direccion))]
+    pub dirper: Option<String>,
+    pub telper: String,
+    #[validate(custom(function = rules::ubicacion))]
+    pub codubi: i64,
+    #[validate(custom(function = rules::perfil))]
+    pub idpef: i64,
+    #[validate(custom(function = rules::email))]
+    pub emaper: String,
+}
+
+impl UpdatePersonaDTO {
+    /// Reemplaza los campos editables de la persona existente
+    pub fn apply(self, persona: Persona) -> Persona {
+        Persona {
+            ndocper: self.ndocper,
+            tdocper: self.tdocper,
+            nomper: self.nomper,
+            apeper: self.apeper,
+            dirper: self.dirper,
+            telper: self.telper,
+            codubi: self.codubi,
+            idpef: self.idpef,
+            emaper: self.emaper,
+            ..persona
+        }
+    }
+}
+
+/// Cuerpo de PATCH /persona/:idper
+/// Solo se modifican los campos presentes
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct PatchPersonaDTO {
+    #[validate(custom(function = rules::documento))]
+    pub ndocper: Option<String>,
+    #[validate(custom(function = rules::tipo_documento))]
+    pub tdocper: Option<i64>,
+    #[validate(custom(function = rules::nombre))]
+    pub nomper: Option<String>,
+    #[validate(custom(function = rules::apellido))]
+    pub apeper: Option<String>,
+    #[validate(custom(function = rules::direccion))]
+    pub dirper: Option<String>,
+    pub telper: Option<String>,
+    #[validate(custom(function = rules::ubicacion))]
+    pub codubi: Option<i64>,
+    #[validate(custom(function = rules::perfil))]
+    pub idpef: Option<i64>,
+    #[validate(custom(function = rules::email))]
+    pub emaper: Option<String>,
+}
+
+impl PatchPersonaDTO {
+    /// Aplica los cambios parciales sobre la persona existente
+    pub fn apply(self, mut persona: Persona) -> Persona {
+        if let Some(ndocper) = self.ndocper {
+            persona.ndocper = Some(ndocper);
+        }
+        if let Some(tdocper) = self.tdocper {
+            persona.tdocper = tdocper;
+        }
+        if let Some(nomper) = self.nomper {
+            persona.nomper = nomper;
+        }
+        if let Some(apeper) = self.apeper {
+            persona.apeper = apeper;
+        }
+        if let Some(dirper) = self.dirper {
+            persona.dirper = Some(dirper);
+        }
+        if let Some(telper) = self.telper {
+            persona.telper = telper;
+        }
+        if let Some(codubi) = self.codubi {
+            persona.codubi = codubi;
+        }
+        if let Some(idpef) = self.idpef {
+            persona.idpef = idpef;
+        }
+        if let Some(emaper) = self.emaper {
+            persona.emaper = emaper;
+        }
+        persona
+    }
+}
+
+/// Etiquetas de catálogo para mostrar en las respuestas de persona
+#[derive(Debug, Default)]
+pub struct PersonaLabels {
+    pub tdocper: HashMap<i64, String>,
+    pub idpef: HashMap<i64, String>,
+}
+
+/// Respuesta de persona: sin `pass` y con las etiquetas de sus códigos
+#[derive(Debug, Serialize)]
+pub struct PersonaResponseDTO {
+    pub idper: i64,
+    pub ndocper: Option<String>,
+    pub tdocper: i64,
+    /// Nombre del tipo de documento, p. ej. "Cédula de ciudadanía"
+    pub tdocper_label: Option<String>,
+    pub nomper: String,
+    pub apeper: String,
+    pub dirper: Option<String>,
+    pub telper: String,
+    /// Teléfono en E.164, para integraciones de SMS/WhatsApp
+    pub telnor: Option<String>,
+    pub codubi: i64,
+    pub idpef: i64,
+    /// Nombre del perfil
+    pub idpef_label: Option<String>,
+    pub emaper: String,
+    pub actper: bool,
+    pub estper: EstadoPersona,
+    pub motsus: Option<String>,
+    pub fecsus: Option<DateTime<Utc>>,
+    pub verper: i64,
+    /// URL del avatar (tamaño mediano); incluye la versión para poder cachearlo
+    pub avatar_url: Option<String>,
+}
+
+impl PersonaResponseDTO {
+    pub fn new(persona: Persona, labels: &PersonaLabels) -> Self {
+        Self {
+            avatar_url: persona
+                .avaper
+                .as_ref()
+                .map(|v| format!("/api/v1/persona/{}/avatar?v={}", persona.idper, v)),
+            tdocper_label: labels.tdocper.get(&persona.tdocper).cloned(),
+            idpef_label: labels.idpef.get(&persona.idpef).cloned(),
+            idper: persona.idper,
+            ndocper: persona.ndocper,
+            tdocper: persona.tdocper,
+            nomper: persona.nomper,
+            apeper: persona.apeper,
+            dirper: persona.dirper,
+            telper: persona.telper,
+            telnor: persona.telnor,
+            codubi: persona.codubi,
+            idpef: persona.idpef,
+            emaper: persona.emaper,
+            actper: persona.actper,
+            estper: persona.estper,
+            motsus: persona.motsus,
+            fecsus: persona.fecsus,
+            verper: persona.verper,
+        }
+    }
+}
+
+/// Cuerpo de POST /persona/:idper/suspender
+#[derive(Debug, Deserialize, Validate)]
+pub struct SuspenderPersonaDTO {
+    #[validate(length(min = 1, max = 500, message = "El motivo debe tener entre 1 y 500 caracteres"))]
+    pub motivo: String,
+    /// Fin de la suspensión; sin fecha dura hasta que se reactive
+    pub hasta: Option<DateTime<Utc>>,
+}
+
+/// Cuerpo de POST /persona/:idper/bloquear
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct BloquearPersonaDTO {
+    #[validate(length(max = 500, message = "El motivo no puede superar 500 caracteres"))]
+    pub motivo: Option<String>,
+}
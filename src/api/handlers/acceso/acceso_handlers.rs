@@ -0,0 +1,48 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use std::sync::Arc;
+
+use crate::{
+    domain::{AccesoFilter, AccesoPersona, AuthUser},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// GET /api/v1/accesos/persona/:idper
+/// Quién consultó los datos sensibles de una persona (solo administradores)
+pub async fn list_accesos_persona(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(idper): Path<i64>,
+    Query(filtro): Query<AccesoFilter>,
+) -> AppResult<Json<Vec<AccesoPersona>>> {
+    require_admin(&auth_user)?;
+
+    let accesos = state.services.acceso.por_persona(idper, filtro).await?;
+    Ok(Json(accesos))
+}
+
+/// GET /api/v1/accesos/actor/:idactor
+/// Qué personas consultó un usuario (solo administradores)
+pub async fn list_accesos_actor(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(idactor): Path<i64>,
+    Query(filtro): Query<AccesoFilter>,
+) -> AppResult<Json<Vec<AccesoPersona>>> {
+    require_admin(&auth_user)?;
+
+    let accesos = state.services.acceso.por_actor(idactor, filtro).await?;
+    Ok(Json(accesos))
+}
+
+fn require_admin(auth_user: &AuthUser) -> AppResult<()> {
+    if !auth_user.is_admin() {
+        return Err(AppError::Forbidden(
+            "Solo un administrador puede consultar el registro de accesos".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod acceso_handlers;
+
+pub use acceso_handlers::*;
@@ -0,0 +1,28 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use std::sync::Arc;
+
+use crate::{
+    domain::{AuditoriaFilter, AuthUser, RegistroAuditoria},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// GET /api/v1/auditoria
+/// Consultar la bitácora de auditoría por actor, entidad, acción o fechas (solo administradores)
+pub async fn list_auditoria(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Query(filtro): Query<AuditoriaFilter>,
+) -> AppResult<Json<Vec<RegistroAuditoria>>> {
+    if !auth_user.is_admin() {
+        return Err(AppError::Forbidden(
+            "Solo un administrador puede consultar la auditoría".to_string(),
+        ));
+    }
+
+    let registros = state.services.auditoria.buscar(filtro).await?;
+    Ok(Json(registros))
+}
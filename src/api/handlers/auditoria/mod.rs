@@ -0,0 +1,3 @@
+mod auditoria_handlers;
+
+pub use auditoria_handlers::*;
@@ -20,6 +20,9 @@ pub async fn login_handler(
         .await?
         .ok_or_else(|| AppError::Unauthorized("Credenciales inválidas".to_string()))?;
 
+    // Solo las cuentas activas inician sesión (una suspensión vencida se reactiva aquí)
+    let persona = state.services.persona.verificar_acceso(persona).await?;
+
     let claims: Claims = Claims {
         sub: persona.idper,
         exp: 10000000000,
@@ -29,13 +32,11 @@ pub async fn login_handler(
         nompef: persona.idpef.to_string(),
         emaper: persona.emaper.clone(),
     };
-    let secret = std::env::var("JWT_SECRET")
-        .map_err(|_| AppError::Unauthorized("JWT_SECRET no configurado".to_string()))?;
-
+    // Mismo secreto con el que el extractor de AuthUser valida los tokens
     let token = jsonwebtoken::encode(
         &Header::default(),
         &claims,
-        &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+        &jsonwebtoken::EncodingKey::from_secret(state.jwt_secret.as_bytes()),
     )
     .map_err(|e| AppError::Internal(format!("Error al generar el token: {}", e)))?;
     let response = LoginResponseDTO { token };
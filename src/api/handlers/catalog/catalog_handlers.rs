@@ -0,0 +1,180 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH}},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{
+    api::middleware::if_none_match,
+    core::services::auditoria::EventoAuditoria,
+    domain::{AuthUser, CatalogItem, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Cuerpo de POST/PUT /catalog/:name
+#[derive(Debug, Deserialize)]
+pub struct CatalogItemDTO {
+    /// Solo se usa al crear; en PUT manda el id de la ruta
+    pub id: Option<i64>,
+    pub nombre: String,
+    #[serde(default = "default_true")]
+    pub activo: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl CatalogItemDTO {
+    fn into_item(self, id: i64) -> CatalogItem {
+        CatalogItem {
+            id,
+            nombre: self.nombre.trim().to_string(),
+            activo: self.activo,
+        }
+    }
+}
+
+/// GET /api/v1/catalog
+/// Listar los nombres de catálogo disponibles
+pub async fn list_catalogs(
+    _auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+) -> Json<Vec<&'static str>> {
+    Json(state.services.catalog.nombres())
+}
+
+/// GET /api/v1/catalog/:name
+/// Obtener un catálogo; responde 304 si el If-None-Match coincide con el ETag
+pub async fn get_catalog(
+    _auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    let snapshot = state.services.catalog.list(&name).await?;
+    let etag = HeaderValue::from_str(&snapshot.etag)
+        .map_err(|e| AppError::Internal(format!("ETag inválido: {}", e)))?;
+    let cache_headers = [
+        (ETAG, etag),
+        (CACHE_CONTROL, HeaderValue::from_static("private, no-cache")),
+    ];
+
+    let coincide = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| if_none_match(v, &snapshot.etag));
+    if coincide {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    Ok((cache_headers, Json(snapshot.items)).into_response())
+}
+
+/// POST /api/v1/catalog/:name
+/// Crear un elemento del catálogo (solo administradores)
+pub async fn create_catalog_item(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    Json(payload): Json<CatalogItemDTO>,
+) -> AppResult<(StatusCode, Json<CatalogItem>)> {
+    require_admin(&auth_user)?;
+
+    let id = payload
+        .id
+        .ok_or_else(|| AppError::validation("id", "El id es requerido"))?;
+    let item = state
+        .services
+        .catalog
+        .create(&name, payload.into_item(id))
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::create(&entidad(&name), item.id, &item),
+        )
+        .await;
+
+    tracing::info!("Usuario {} creó {} en el catálogo {}", auth_user.nomper, item.id, name);
+    Ok((StatusCode::CREATED, Json(item)))
+}
+
+/// PUT /api/v1/catalog/:name/:id
+/// Actualizar un elemento del catálogo (solo administradores)
+pub async fn update_catalog_item(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((name, id)): Path<(String, i64)>,
+    Json(payload): Json<CatalogItemDTO>,
+) -> AppResult<Json<CatalogItem>> {
+    require_admin(&auth_user)?;
+
+    let antes = state.services.catalog.get(&name, id).await?;
+    let item = state
+        .services
+        .catalog
+        .update(&name, id, payload.into_item(id))
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::update(&entidad(&name), id, &antes, &item),
+        )
+        .await;
+
+    tracing::info!("Usuario {} actualizó {} en el catálogo {}", auth_user.nomper, id, name);
+    Ok(Json(item))
+}
+
+/// DELETE /api/v1/catalog/:name/:id
+/// Eliminar (o desactivar) un elemento del catálogo (solo administradores)
+pub async fn delete_catalog_item(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((name, id)): Path<(String, i64)>,
+) -> AppResult<StatusCode> {
+    require_admin(&auth_user)?;
+
+    let antes = state.services.catalog.get(&name, id).await?;
+    state.services.catalog.delete(&name, id).await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::delete(&entidad(&name), id, &antes),
+        )
+        .await;
+
+    tracing::info!("Usuario {} eliminó {} del catálogo {}", auth_user.nomper, id, name);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Entidad auditada para los elementos de un catálogo
+fn entidad(name: &str) -> String {
+    format!("catalog:{}", name)
+}
+
+fn require_admin(auth_user: &AuthUser) -> AppResult<()> {
+    if !auth_user.is_admin() {
+        return Err(AppError::Forbidden(
+            "Solo un administrador puede modificar catálogos".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod catalog_handlers;
+
+pub use catalog_handlers::*;
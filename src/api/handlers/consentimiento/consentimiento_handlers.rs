@@ -0,0 +1,82 @@
+use axum::{Json, extract::State, http::StatusCode};
+use std::sync::Arc;
+
+use crate::{
+    api::{dtos::PublicarPoliticaDTO, middleware::ValidatedJson},
+    core::services::auditoria::EventoAuditoria,
+    domain::{AuthUser, PoliticaDatos, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// GET /api/v1/consentimiento/politica
+/// Obtener la política de tratamiento de datos vigente
+pub async fn get_politica_vigente(
+    _auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+) -> AppResult<Json<PoliticaDatos>> {
+    let politica = state
+        .services
+        .consentimiento
+        .politica_vigente()
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound("No hay una política de tratamiento de datos vigente".to_string())
+        })?;
+
+    Ok(Json(politica))
+}
+
+/// GET /api/v1/consentimiento/politicas
+/// Listar todas las versiones publicadas (solo administradores)
+pub async fn list_politicas(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+) -> AppResult<Json<Vec<PoliticaDatos>>> {
+    require_admin(&auth_user)?;
+
+    let politicas = state.services.consentimiento.politicas().await?;
+    Ok(Json(politicas))
+}
+
+/// POST /api/v1/consentimiento/politicas
+/// Publicar una nueva versión de la política (solo administradores)
+pub async fn publicar_politica(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    ValidatedJson(payload): ValidatedJson<PublicarPoliticaDTO>,
+) -> AppResult<(StatusCode, Json<PoliticaDatos>)> {
+    require_admin(&auth_user)?;
+
+    let politica = state
+        .services
+        .consentimiento
+        .publicar(&payload.verpol, &payload.texpol, payload.fecpol)
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::create("politica_datos", politica.idpol, &politica),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} publicó la política de datos {}",
+        auth_user.nomper,
+        politica.verpol
+    );
+    Ok((StatusCode::CREATED, Json(politica)))
+}
+
+fn require_admin(auth_user: &AuthUser) -> AppResult<()> {
+    if !auth_user.is_admin() {
+        return Err(AppError::Forbidden(
+            "Solo un administrador puede gestionar la política de datos".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod consentimiento_handlers;
+
+pub use consentimiento_handlers::*;
@@ -0,0 +1,27 @@
+use axum::{Json, extract::State};
+use std::sync::Arc;
+
+use crate::{
+    config::EstadisticasDb,
+    domain::AuthUser,
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// GET /api/v1/database/pool
+/// Uso de los pools de conexiones y esperas por conexión, para dimensionarlos (solo administradores)
+pub async fn get_pool_stats(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+) -> AppResult<Json<EstadisticasDb>> {
+    if !auth_user.is_admin() {
+        return Err(AppError::Forbidden(
+            "Solo un administrador puede consultar el pool de conexiones".to_string(),
+        ));
+    }
+
+    let db = state.db.as_ref().ok_or_else(|| {
+        AppError::NotFound("La aplicación no usa una base de datos SQL".to_string())
+    })?;
+    Ok(Json(db.estadisticas()))
+}
@@ -0,0 +1,3 @@
+mod database_handlers;
+
+pub use database_handlers::*;
@@ -0,0 +1,48 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::{
+    core::services::documento::{self, DocumentoValidado},
+    domain::AuthUser,
+    errors::AppResult,
+    infra::AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ValidarDocumentoQuery {
+    pub tdocper: i64,
+    pub ndocper: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidarDocumentoResponse {
+    #[serde(flatten)]
+    pub documento: DocumentoValidado,
+    /// Si ya existe una persona con ese documento
+    pub registrado: bool,
+}
+
+/// GET /api/v1/documento/validar?tdocper=5&ndocper=900.123.456-7
+/// Validar y normalizar un documento antes de enviar el formulario
+pub async fn validar_documento(
+    _auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ValidarDocumentoQuery>,
+) -> AppResult<Json<ValidarDocumentoResponse>> {
+    let documento = documento::validar(query.tdocper, &query.ndocper)?;
+    let registrado = state
+        .services
+        .persona
+        .get_by_document(&documento.almacenado())
+        .await?
+        .is_some();
+
+    Ok(Json(ValidarDocumentoResponse {
+        documento,
+        registrado,
+    }))
+}
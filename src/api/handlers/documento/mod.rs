@@ -0,0 +1,3 @@
+mod documento_handlers;
+
+pub use documento_handlers::*;
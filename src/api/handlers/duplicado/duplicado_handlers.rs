@@ -0,0 +1,264 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    api::dtos::{PersonaLabels, PersonaResponseDTO},
+    core::services::{auditoria::EventoAuditoria, duplicado::ReporteDeteccion},
+    domain::{
+        AuthUser, CATALOGO_PERFIL, CATALOGO_TIPO_DOCUMENTO, CandidatoDuplicado, DuplicadoFilter,
+        FusionPersona, Persona, RequestContext,
+    },
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Candidato con las dos personas, para compararlas al revisar
+#[derive(Debug, Serialize)]
+pub struct CandidatoRevisionDTO {
+    #[serde(flatten)]
+    pub candidato: CandidatoDuplicado,
+    pub persona1: PersonaResponseDTO,
+    pub persona2: PersonaResponseDTO,
+}
+
+/// Cuerpo de POST /duplicados/:iddup/fusionar
+#[derive(Debug, Deserialize)]
+pub struct FusionarCandidatoDTO {
+    /// Persona que se conserva; la otra del par queda absorbida
+    pub idsup: i64,
+}
+
+/// Cuerpo de POST /duplicados/fusionar (par que la detección no propuso)
+#[derive(Debug, Deserialize)]
+pub struct FusionarDTO {
+    pub idsup: i64,
+    pub idabs: i64,
+}
+
+/// GET /api/v1/duplicados?estdup=pendiente&idper=...
+/// Candidatos a duplicado con ambas personas (solo administradores)
+pub async fn list_duplicados(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Query(filtro): Query<DuplicadoFilter>,
+) -> AppResult<Json<Vec<CandidatoRevisionDTO>>> {
+    require_admin(&auth_user)?;
+
+    let candidatos = state.services.duplicado.listar(filtro).await?;
+    let mut personas: HashMap<i64, Persona> = HashMap::new();
+    for idper in candidatos.iter().flat_map(|c| [c.idper1, c.idper2]) {
+        if !personas.contains_key(&idper)
+            && let Some(persona) = state.services.persona.get_by_id(idper).await?
+        {
+            personas.insert(idper, persona);
+        }
+    }
+
+    // Los superadmins solo los ve otro superadmin
+    let visibles: Vec<(CandidatoDuplicado, &Persona, &Persona)> = candidatos
+        .into_iter()
+        .filter_map(|c| {
+            let persona1 = personas.get(&c.idper1)?;
+            let persona2 = personas.get(&c.idper2)?;
+            let visible =
+                auth_user.is_super_admin() || (persona1.idpef != 1 && persona2.idpef != 1);
+            visible.then_some((c, persona1, persona2))
+        })
+        .collect();
+
+    state
+        .services
+        .acceso
+        .registrar(
+            &auth_user,
+            &ctx,
+            "duplicados",
+            visibles.iter().flat_map(|(_, p1, p2)| [*p1, *p2]),
+        )
+        .await;
+
+    let labels = PersonaLabels {
+        tdocper: state.services.catalog.labels(CATALOGO_TIPO_DOCUMENTO).await,
+        idpef: state.services.catalog.labels(CATALOGO_PERFIL).await,
+    };
+    Ok(Json(
+        visibles
+            .into_iter()
+            .map(|(candidato, persona1, persona2)| CandidatoRevisionDTO {
+                candidato,
+                persona1: PersonaResponseDTO::new(persona1.clone(), &labels),
+                persona2: PersonaResponseDTO::new(persona2.clone(), &labels),
+            })
+            .collect(),
+    ))
+}
+
+/// POST /api/v1/duplicados/detectar
+/// Ejecutar ahora la detección de duplicados (solo administradores)
+pub async fn detectar_duplicados(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+) -> AppResult<Json<ReporteDeteccion>> {
+    require_admin(&auth_user)?;
+
+    let reporte = state.services.duplicado.detectar().await?;
+    if reporte.nuevos > 0 {
+        state
+            .services
+            .auditoria
+            .registrar(
+                &auth_user,
+                &ctx,
+                EventoAuditoria::new("detectar", "duplicado", "*").despues(&reporte),
+            )
+            .await;
+    }
+
+    tracing::info!(
+        "Usuario {} ejecutó la detección de duplicados",
+        auth_user.nomper
+    );
+    Ok(Json(reporte))
+}
+
+/// POST /api/v1/duplicados/:iddup/descartar
+/// Marcar el par como personas distintas
+pub async fn descartar_duplicado(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(iddup): Path<i64>,
+) -> AppResult<Json<CandidatoDuplicado>> {
+    require_admin(&auth_user)?;
+
+    let candidato = state.services.duplicado.get(iddup).await?;
+    check_superadmin(&auth_user, &state, &[candidato.idper1, candidato.idper2]).await?;
+
+    let descartado = state
+        .services
+        .duplicado
+        .descartar(iddup, auth_user.idper)
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::update("duplicado", iddup, &candidato, &descartado),
+        )
+        .await;
+
+    Ok(Json(descartado))
+}
+
+/// POST /api/v1/duplicados/:iddup/fusionar
+/// Fusionar las dos personas de un candidato conservando `idsup`
+pub async fn fusionar_duplicado(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(iddup): Path<i64>,
+    Json(payload): Json<FusionarCandidatoDTO>,
+) -> AppResult<Json<FusionPersona>> {
+    require_admin(&auth_user)?;
+
+    let candidato = state.services.duplicado.get(iddup).await?;
+    let idabs = if payload.idsup == candidato.idper1 {
+        candidato.idper2
+    } else {
+        candidato.idper1
+    };
+    fusionar(&auth_user, &state, &ctx, payload.idsup, idabs, Some(iddup)).await
+}
+
+/// POST /api/v1/duplicados/fusionar
+/// Fusionar dos personas que la detección no propuso
+pub async fn fusionar_personas(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Json(payload): Json<FusionarDTO>,
+) -> AppResult<Json<FusionPersona>> {
+    require_admin(&auth_user)?;
+
+    fusionar(&auth_user, &state, &ctx, payload.idsup, payload.idabs, None).await
+}
+
+/// GET /api/v1/duplicados/fusiones/:idper
+/// Fusiones en las que participó una persona
+pub async fn list_fusiones(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(idper): Path<i64>,
+) -> AppResult<Json<Vec<FusionPersona>>> {
+    require_admin(&auth_user)?;
+
+    let fusiones = state.services.duplicado.fusiones(idper).await?;
+    Ok(Json(fusiones))
+}
+
+async fn fusionar(
+    auth_user: &AuthUser,
+    state: &AppState,
+    ctx: &RequestContext,
+    idsup: i64,
+    idabs: i64,
+    iddup: Option<i64>,
+) -> AppResult<Json<FusionPersona>> {
+    check_superadmin(auth_user, state, &[idsup, idabs]).await?;
+
+    let fusion = state
+        .services
+        .duplicado
+        .fusionar(idsup, idabs, iddup, auth_user.idper)
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            auth_user,
+            ctx,
+            EventoAuditoria::new("fusionar", "persona", idabs).despues(&fusion),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} fusionó la persona {} en {}",
+        auth_user.nomper,
+        idabs,
+        idsup
+    );
+    Ok(Json(fusion))
+}
+
+fn require_admin(auth_user: &AuthUser) -> AppResult<()> {
+    if !auth_user.is_admin() {
+        return Err(AppError::Forbidden(
+            "Solo un administrador puede revisar y fusionar duplicados".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Solo un superadministrador revisa o fusiona a otro superadministrador
+async fn check_superadmin(auth_user: &AuthUser, state: &AppState, ids: &[i64]) -> AppResult<()> {
+    if auth_user.is_super_admin() {
+        return Ok(());
+    }
+    for idper in ids {
+        let persona = state.services.persona.get_by_id(*idper).await?;
+        if persona.is_some_and(|p| p.idpef == 1) {
+            return Err(AppError::Forbidden(
+                "No tiene permisos sobre un superadministrador".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod duplicado_handlers;
+
+pub use duplicado_handlers::*;
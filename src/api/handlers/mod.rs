@@ -1,5 +1,13 @@
 pub mod persona;
+pub mod acceso;
+pub mod auditoria;
 pub mod auth;
+pub mod catalog;
+pub mod consentimiento;
+pub mod database;
+pub mod documento;
+pub mod duplicado;
+pub mod ubicacion;
 
 // pub use persona::{
 //     get_persona,
@@ -0,0 +1,210 @@
+use axum::{
+    Json,
+    extract::{Multipart, Path, State},
+    http::{
+        HeaderName, HeaderValue, StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS},
+    },
+};
+use bytes::Bytes;
+use std::sync::Arc;
+
+use crate::{
+    core::services::{adjunto::ArchivoSubido, auditoria::EventoAuditoria},
+    domain::{Adjunto, AuthUser, CategoriaAdjunto, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Página (`pagina.codpag`) que autoriza gestionar los adjuntos de otras personas
+const CODPAG_ADJUNTOS: &str = "persona.adjuntos";
+
+/// POST /api/v1/persona/:idper/adjuntos
+/// Subir un adjunto (multipart: `file` y `categoria`)
+pub async fn upload_adjunto(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+    mut multipart: Multipart,
+) -> AppResult<(StatusCode, Json<Adjunto>)> {
+    authorize(&auth_user, &state, idper, "create").await?;
+
+    let mut archivo = None;
+    let mut categoria = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Multipart inválido: {}", e)))?
+    {
+        match field.name() {
+            Some("file") => {
+                let nombre = field.file_name().map(str::to_string);
+                let content_type = field.content_type().map(str::to_string);
+                let data = field.bytes().await.map_err(|e| {
+                    AppError::BadRequest(format!("No se pudo leer el archivo: {}", e))
+                })?;
+                archivo = Some(ArchivoSubido {
+                    nombre,
+                    content_type,
+                    data,
+                });
+            }
+            Some("categoria") => {
+                let valor = field.text().await.map_err(|e| {
+                    AppError::BadRequest(format!("No se pudo leer la categoría: {}", e))
+                })?;
+                categoria = Some(
+                    CategoriaAdjunto::try_from(valor.trim().to_lowercase())
+                        .map_err(|e| AppError::validation("categoria", e))?,
+                );
+            }
+            _ => continue,
+        }
+    }
+
+    let archivo =
+        archivo.ok_or_else(|| AppError::BadRequest("Falta el campo 'file'".to_string()))?;
+    let adjunto = state
+        .services
+        .adjunto
+        .subir(
+            idper,
+            categoria.unwrap_or(CategoriaAdjunto::Otro),
+            archivo,
+            auth_user.idper,
+        )
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::create("adjunto", adjunto.idadj, &adjunto),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} adjuntó {} a la persona {}",
+        auth_user.nomper,
+        adjunto.nomadj,
+        idper
+    );
+    Ok((StatusCode::CREATED, Json(adjunto)))
+}
+
+/// GET /api/v1/persona/:idper/adjuntos
+/// Listar los adjuntos de una persona
+pub async fn list_adjuntos(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(idper): Path<i64>,
+) -> AppResult<Json<Vec<Adjunto>>> {
+    authorize(&auth_user, &state, idper, "read").await?;
+
+    let adjuntos = state.services.adjunto.listar(idper).await?;
+    Ok(Json(adjuntos))
+}
+
+/// GET /api/v1/persona/:idper/adjuntos/:idadj
+/// Descargar el contenido de un adjunto
+pub async fn download_adjunto(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((idper, idadj)): Path<(i64, i64)>,
+) -> AppResult<([(HeaderName, HeaderValue); 3], Bytes)> {
+    authorize(&auth_user, &state, idper, "read").await?;
+
+    let (adjunto, data) = state.services.adjunto.descargar(idper, idadj).await?;
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "adjunto", [&adjunto])
+        .await;
+    let content_type = HeaderValue::from_str(&adjunto.tipadj)
+        .map_err(|e| AppError::Internal(format!("Header inválido: {}", e)))?;
+    // El nombre ya está saneado; las comillas se reemplazan por seguridad
+    let disposition = HeaderValue::from_str(&format!(
+        "attachment; filename=\"{}\"",
+        adjunto.nomadj.replace('"', "'")
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static("attachment"));
+
+    tracing::info!(
+        "Usuario {} descargó el adjunto {} de la persona {}",
+        auth_user.nomper,
+        idadj,
+        idper
+    );
+    Ok((
+        [
+            (CONTENT_TYPE, content_type),
+            (CONTENT_DISPOSITION, disposition),
+            (X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")),
+        ],
+        data,
+    ))
+}
+
+/// DELETE /api/v1/persona/:idper/adjuntos/:idadj
+/// Eliminar un adjunto y su contenido
+pub async fn delete_adjunto(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((idper, idadj)): Path<(i64, i64)>,
+) -> AppResult<StatusCode> {
+    authorize(&auth_user, &state, idper, "delete").await?;
+
+    let adjunto = state.services.adjunto.eliminar(idper, idadj).await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::delete("adjunto", idadj, &adjunto),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} eliminó el adjunto {} de la persona {}",
+        auth_user.nomper,
+        idadj,
+        idper
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// El titular puede ver y subir sus propios adjuntos; lo demás requiere permiso
+/// Solo un superadministrador gestiona los adjuntos de otro superadministrador
+async fn authorize(
+    auth_user: &AuthUser,
+    state: &AppState,
+    idper: i64,
+    action: &str,
+) -> AppResult<()> {
+    let propio = idper == auth_user.idper && action != "delete";
+    if !propio {
+        state
+            .services
+            .permission
+            .authorize(auth_user, CODPAG_ADJUNTOS, action)
+            .await?;
+    }
+
+    let persona = state
+        .services
+        .persona
+        .get_by_id(idper)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+    if persona.idpef == 1 && !auth_user.is_super_admin() {
+        return Err(AppError::Forbidden(
+            "No tiene permisos sobre los adjuntos de un superadministrador".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH, X_CONTENT_TYPE_OPTIONS},
+    },
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use super::persona_handlers::{VersionedPersona, versioned};
+
+use crate::{
+    api::middleware::if_none_match,
+    core::services::{auditoria::EventoAuditoria, avatar::TamanoAvatar},
+    domain::{AuthUser, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Página (`pagina.codpag`) que autoriza cambiar el avatar de otras personas
+const CODPAG_AVATAR: &str = "persona.avatar";
+
+/// Query de GET /persona/:idper/avatar
+#[derive(Debug, Deserialize)]
+pub struct AvatarQuery {
+    #[serde(default)]
+    pub tamano: TamanoAvatar,
+    /// Versión pedida (la que trae `avatar_url`); si coincide con la vigente
+    /// la respuesta se puede cachear indefinidamente
+    pub v: Option<String>,
+}
+
+/// PUT /api/v1/persona/:idper/avatar
+/// Subir o reemplazar el avatar (cuerpo: la imagen JPEG, PNG o WEBP)
+pub async fn update_avatar(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+    body: Bytes,
+) -> AppResult<VersionedPersona> {
+    authorize(&auth_user, &state, idper, "update").await?;
+
+    let persona = state.services.avatar.actualizar(idper, body).await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::new("update", "avatar", idper).despues(&persona.avaper),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} actualizó el avatar de la persona {}",
+        auth_user.nomper,
+        idper
+    );
+    Ok(versioned(&state, persona).await)
+}
+
+/// GET /api/v1/persona/:idper/avatar?tamano=mediano&v=...
+/// Obtener una miniatura del avatar; responde 304 si el If-None-Match coincide
+pub async fn get_avatar(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(idper): Path<i64>,
+    Query(query): Query<AvatarQuery>,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    superadmin(&auth_user, &state, idper).await?;
+
+    let (version, data) = state.services.avatar.obtener(idper, query.tamano).await?;
+    let etag = format!("\"{}-{}\"", version, query.tamano.as_str());
+    // Con la versión en la URL el contenido nunca cambia; sin ella hay que revalidar
+    let cache_control = if query.v.as_deref() == Some(version.as_str()) {
+        "private, max-age=31536000, immutable"
+    } else {
+        "private, no-cache"
+    };
+    let cache_headers = [
+        (
+            ETAG,
+            HeaderValue::from_str(&etag)
+                .map_err(|e| AppError::Internal(format!("ETag inválido: {}", e)))?,
+        ),
+        (CACHE_CONTROL, HeaderValue::from_static(cache_control)),
+    ];
+
+    let coincide = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| if_none_match(v, &etag));
+    if coincide {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    Ok((
+        cache_headers,
+        [
+            (CONTENT_TYPE, HeaderValue::from_static("image/jpeg")),
+            (X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+/// DELETE /api/v1/persona/:idper/avatar
+/// Quitar el avatar
+pub async fn delete_avatar(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+) -> AppResult<StatusCode> {
+    authorize(&auth_user, &state, idper, "delete").await?;
+
+    state.services.avatar.eliminar(idper).await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::new("delete", "avatar", idper),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} quitó el avatar de la persona {}",
+        auth_user.nomper,
+        idper
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// El titular cambia su propio avatar; el de otros requiere permiso
+async fn authorize(
+    auth_user: &AuthUser,
+    state: &AppState,
+    idper: i64,
+    action: &str,
+) -> AppResult<()> {
+    if idper != auth_user.idper {
+        state
+            .services
+            .permission
+            .authorize(auth_user, CODPAG_AVATAR, action)
+            .await?;
+    }
+    superadmin(auth_user, state, idper).await
+}
+
+/// Solo un superadministrador accede al avatar de otro superadministrador
+async fn superadmin(auth_user: &AuthUser, state: &AppState, idper: i64) -> AppResult<()> {
+    let persona = state
+        .services
+        .persona
+        .get_by_id(idper)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+    if persona.idpef == 1 && !auth_user.is_super_admin() {
+        return Err(AppError::Forbidden(
+            "No tiene permisos sobre el avatar de un superadministrador".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use std::sync::Arc;
+
+use crate::{
+    api::{dtos::AceptarConsentimientoDTO, middleware::ValidatedJson},
+    core::services::auditoria::EventoAuditoria,
+    core::services::consentimiento::EstadoConsentimiento,
+    domain::{AuthUser, Consentimiento, RequestContext},
+    errors::AppResult,
+    infra::AppState,
+};
+
+/// Página (`pagina.codpag`) que autoriza gestionar el consentimiento de otras personas
+const CODPAG_CONSENTIMIENTO: &str = "persona.consentimiento";
+
+/// GET /api/v1/persona/:idper/consentimiento
+/// Consultar si la persona tiene consentimiento vigente y su historial
+pub async fn get_consentimiento(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(idper): Path<i64>,
+) -> AppResult<Json<EstadoConsentimiento>> {
+    authorize(&auth_user, &state, idper, "read").await?;
+
+    let estado = state.services.consentimiento.estado(idper).await?;
+    Ok(Json(estado))
+}
+
+/// POST /api/v1/persona/:idper/consentimiento
+/// Registrar la aceptación de la política vigente
+pub async fn aceptar_consentimiento(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+    ValidatedJson(payload): ValidatedJson<AceptarConsentimientoDTO>,
+) -> AppResult<(StatusCode, Json<Consentimiento>)> {
+    authorize(&auth_user, &state, idper, "create").await?;
+
+    let consentimiento = state
+        .services
+        .consentimiento
+        .aceptar(idper, payload.idpol, payload.cancon)
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::new("aceptar", "consentimiento", idper).despues(&consentimiento),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} registró el consentimiento de {} por {}",
+        auth_user.nomper,
+        idper,
+        payload.cancon.as_str()
+    );
+    Ok((StatusCode::CREATED, Json(consentimiento)))
+}
+
+/// DELETE /api/v1/persona/:idper/consentimiento
+/// Revocar el consentimiento vigente
+pub async fn revocar_consentimiento(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+) -> AppResult<StatusCode> {
+    authorize(&auth_user, &state, idper, "delete").await?;
+
+    state.services.consentimiento.revocar(idper).await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::new("revocar", "consentimiento", idper),
+        )
+        .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// El titular gestiona su propio consentimiento; para otra persona hace falta permiso
+async fn authorize(
+    auth_user: &AuthUser,
+    state: &AppState,
+    idper: i64,
+    action: &str,
+) -> AppResult<()> {
+    if idper == auth_user.idper {
+        return Ok(());
+    }
+
+    state
+        .services
+        .permission
+        .authorize(auth_user, CODPAG_CONSENTIMIENTO, action)
+        .await
+}
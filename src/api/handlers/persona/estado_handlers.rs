@@ -0,0 +1,172 @@
+use axum::{
+    extract::{FromRequestParts, Path},
+    http::request::Parts,
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use super::persona_handlers::{VersionedPersona, versioned};
+use crate::{
+    api::{
+        dtos::{BloquearPersonaDTO, SuspenderPersonaDTO},
+        middleware::{IfMatch, ValidatedJson},
+    },
+    core::services::auditoria::EventoAuditoria,
+    domain::{AuthUser, Persona, RequestContext, TransicionPersona},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Verifica el permiso propio de la transición y las reglas sobre la cuenta destino
+/// Devuelve la persona tal como estaba antes de la transición
+pub(super) async fn authorize_transicion(
+    auth_user: &AuthUser,
+    state: &AppState,
+    idper: i64,
+    transicion: TransicionPersona,
+) -> AppResult<Persona> {
+    state
+        .services
+        .permission
+        .authorize(auth_user, transicion.codpag(), "update")
+        .await?;
+
+    let persona = state
+        .services
+        .persona
+        .get_by_id(idper)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+
+    if persona.idpef == 1 && !auth_user.is_super_admin() {
+        return Err(AppError::Forbidden(
+            "No tiene permisos para cambiar el estado de un superadministrador".to_string(),
+        ));
+    }
+
+    // Nadie puede suspender, bloquear o archivar su propia cuenta
+    if persona.idper == auth_user.idper && transicion != TransicionPersona::Activar {
+        return Err(AppError::Forbidden(
+            "No puede cambiar el estado de su propia cuenta".to_string(),
+        ));
+    }
+
+    Ok(persona)
+}
+
+/// Lo común a toda transición: quién la pide, sobre qué persona y con qué versión
+pub struct Solicitud {
+    auth_user: AuthUser,
+    state: Arc<AppState>,
+    ctx: RequestContext,
+    idper: i64,
+    verper: i64,
+}
+
+impl FromRequestParts<Arc<AppState>> for Solicitud {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let auth_user = AuthUser::from_request_parts(parts, state).await?;
+        let ctx = RequestContext::from_request_parts(parts, state).await?;
+        let Path(idper) = Path::<i64>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| AppError::BadRequest(e.body_text()))?;
+        let IfMatch(verper) = IfMatch::from_request_parts(parts, state).await?;
+
+        Ok(Self {
+            auth_user,
+            state: state.clone(),
+            ctx,
+            idper,
+            verper,
+        })
+    }
+}
+
+async fn transicionar(
+    solicitud: Solicitud,
+    transicion: TransicionPersona,
+    motivo: Option<String>,
+    hasta: Option<DateTime<Utc>>,
+) -> AppResult<VersionedPersona> {
+    let Solicitud {
+        auth_user,
+        state,
+        ctx,
+        idper,
+        verper,
+    } = solicitud;
+    let antes = authorize_transicion(&auth_user, &state, idper, transicion).await?;
+
+    let persona = state
+        .services
+        .persona
+        .transicion(idper, transicion, motivo, hasta, verper)
+        .await?;
+
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::new(transicion.nombre(), "persona", idper)
+                .antes(&antes)
+                .despues(&persona),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} aplicó {:?} a la persona {}",
+        auth_user.nomper,
+        transicion,
+        idper
+    );
+    Ok(versioned(&state, persona).await)
+}
+
+/// POST /api/v1/persona/:idper/activar
+/// Activar una cuenta pendiente o reactivar una suspendida (requiere If-Match)
+pub async fn activar_persona(solicitud: Solicitud) -> AppResult<VersionedPersona> {
+    transicionar(solicitud, TransicionPersona::Activar, None, None).await
+}
+
+/// POST /api/v1/persona/:idper/suspender
+/// Suspender una cuenta activa con motivo y fecha de fin opcional (requiere If-Match)
+pub async fn suspender_persona(
+    solicitud: Solicitud,
+    ValidatedJson(payload): ValidatedJson<SuspenderPersonaDTO>,
+) -> AppResult<VersionedPersona> {
+    transicionar(
+        solicitud,
+        TransicionPersona::Suspender,
+        Some(payload.motivo),
+        payload.hasta,
+    )
+    .await
+}
+
+/// POST /api/v1/persona/:idper/bloquear
+/// Bloquear una cuenta activa o suspendida (requiere If-Match)
+pub async fn bloquear_persona(
+    solicitud: Solicitud,
+    ValidatedJson(payload): ValidatedJson<BloquearPersonaDTO>,
+) -> AppResult<VersionedPersona> {
+    transicionar(solicitud, TransicionPersona::Bloquear, payload.motivo, None).await
+}
+
+/// POST /api/v1/persona/:idper/desbloquear
+/// Desbloquear una cuenta bloqueada (requiere If-Match)
+pub async fn desbloquear_persona(solicitud: Solicitud) -> AppResult<VersionedPersona> {
+    transicionar(solicitud, TransicionPersona::Desbloquear, None, None).await
+}
+
+/// POST /api/v1/persona/:idper/archivar
+/// Archivar una cuenta de forma definitiva (requiere If-Match)
+pub async fn archivar_persona(solicitud: Solicitud) -> AppResult<VersionedPersona> {
+    transicionar(solicitud, TransicionPersona::Archivar, None, None).await
+}
@@ -0,0 +1,137 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{
+        HeaderMap, HeaderValue,
+        header::{ACCEPT, CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+};
+use futures::{StreamExt, stream};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::{
+    core::services::persona::{
+        ExportFormat, csv_header, csv_line, export_cells, ndjson_line, write_xlsx,
+    },
+    domain::{AuthUser, PersonaFilter, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Filas (y trozos del archivo) en vuelo entre la consulta, el escritor XLSX y
+/// el cliente
+const XLSX_CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+    #[serde(default)]
+    pub format_numbers: bool,
+}
+
+/// GET /api/v1/persona/export
+/// Exportar personas filtradas como CSV, NDJSON o XLSX (por `?format=` o `Accept`)
+pub async fn export_personas(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    headers: HeaderMap,
+    Query(mut filtro): Query<PersonaFilter>,
+    Query(query): Query<ExportQuery>,
+) -> AppResult<Response> {
+    let format = match query.format.as_deref() {
+        Some(param) => ExportFormat::from_param(param)
+            .ok_or_else(|| AppError::BadRequest(format!("Formato '{}' no soportado", param)))?,
+        None => headers
+            .get(ACCEPT)
+            .and_then(|h| h.to_str().ok())
+            .and_then(ExportFormat::from_accept)
+            .unwrap_or(ExportFormat::Csv),
+    };
+
+    // Igual que en el listado: solo un superadmin ve a otros superadmins
+    if !auth_user.is_super_admin() {
+        filtro.excluir_idpef = Some(1);
+    }
+
+    // Exportar es tratamiento de datos: solo personas con consentimiento vigente
+    state.services.consentimiento.restringir(&mut filtro).await?;
+
+    tracing::info!(
+        "Usuario {} exportó personas en formato {:?}",
+        auth_user.nomper,
+        format
+    );
+
+    let format_numbers = query.format_numbers;
+    // Cada fila enviada queda registrada como lectura de datos sensibles
+    let mut lote = state.services.acceso.lote(&auth_user, ctx, "exportacion");
+    let personas = state.services.persona.export(filtro).inspect(move |p| {
+        if let Ok(persona) = p {
+            lote.anotar(persona);
+        }
+    });
+
+    let body = match format {
+        ExportFormat::Csv => {
+            let filas = personas
+                .map(move |p| p.and_then(|p| csv_line(&export_cells(&p, format_numbers))));
+            Body::from_stream(stream::once(async { csv_header() }).chain(filas))
+        }
+        ExportFormat::Ndjson => Body::from_stream(
+            personas.map(move |p| p.and_then(|p| ndjson_line(&export_cells(&p, format_numbers)))),
+        ),
+        // La respuesta sale de inmediato: las filas se consultan y escriben en
+        // segundo plano y los bytes llegan al cliente a medida que se empaquetan
+        // Un error corta el cuerpo, igual que en CSV
+        ExportFormat::Xlsx => {
+            let (filas_tx, filas_rx) = mpsc::channel(XLSX_CHANNEL_CAPACITY);
+            let (bytes_tx, bytes_rx) = mpsc::channel(XLSX_CHANNEL_CAPACITY);
+
+            // El error de la consulta llega al cliente antes que el libro truncado
+            let errores_consulta = bytes_tx.clone();
+            tokio::spawn(async move {
+                let mut personas = personas;
+                while let Some(persona) = personas.next().await {
+                    let celdas = match persona {
+                        Ok(persona) => export_cells(&persona, format_numbers),
+                        Err(e) => {
+                            let _ = errores_consulta.send(Err(e)).await;
+                            return;
+                        }
+                    };
+                    if filas_tx.send(celdas).await.is_err() {
+                        return;
+                    }
+                }
+            });
+
+            let errores_escritor = bytes_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                if let Err(e) = write_xlsx(filas_rx, bytes_tx) {
+                    let _ = errores_escritor.blocking_send(Err(e));
+                }
+            });
+
+            Body::from_stream(stream::unfold(bytes_rx, |mut rx| async move {
+                rx.recv().await.map(|trozo| (trozo, rx))
+            }))
+        }
+    };
+
+    let disposition = format!("attachment; filename=\"personas.{}\"", format.extension());
+    Ok((
+        [
+            (CONTENT_TYPE, HeaderValue::from_static(format.content_type())),
+            (
+                CONTENT_DISPOSITION,
+                HeaderValue::from_str(&disposition).expect("nombre de archivo ASCII"),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
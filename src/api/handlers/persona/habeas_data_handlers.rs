@@ -0,0 +1,133 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderValue, header::CONTENT_DISPOSITION},
+};
+use std::sync::Arc;
+
+use super::persona_handlers::{VersionedPersona, versioned};
+use crate::{
+    api::middleware::IfMatch,
+    core::services::auditoria::EventoAuditoria,
+    core::services::habeas_data::ArchivoPersonal,
+    domain::{AuthUser, Persona, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Página (`pagina.codpag`) que autoriza atender solicitudes de habeas data
+const CODPAG_HABEAS_DATA: &str = "persona.habeas_data";
+
+/// GET /api/v1/persona/:idper/datos-personales
+/// Descargar en JSON todo lo almacenado sobre una persona (Ley 1581)
+/// El titular puede descargar sus propios datos sin permisos adicionales
+pub async fn export_datos_personales(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+) -> AppResult<(
+    [(axum::http::HeaderName, HeaderValue); 1],
+    Json<ArchivoPersonal>,
+)> {
+    if idper != auth_user.idper {
+        state
+            .services
+            .permission
+            .authorize(&auth_user, CODPAG_HABEAS_DATA, "read")
+            .await?;
+        check_superadmin(&auth_user, &state, idper).await?;
+    }
+
+    let archivo = state
+        .services
+        .habeas_data
+        .archivo(idper, auth_user.idper)
+        .await?;
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "habeas_data", [&archivo.titular])
+        .await;
+
+    let disposition = HeaderValue::from_str(&format!(
+        "attachment; filename=\"datos-personales-{}.json\"",
+        idper
+    ))
+    .map_err(|e| AppError::Internal(format!("Header inválido: {}", e)))?;
+
+    tracing::info!(
+        "Usuario {} descargó los datos personales de {}",
+        auth_user.nomper,
+        idper
+    );
+    Ok(([(CONTENT_DISPOSITION, disposition)], Json(archivo)))
+}
+
+/// POST /api/v1/persona/:idper/anonimizar
+/// Anonimizar de forma irreversible los datos personales (requiere If-Match)
+pub async fn anonimizar_persona(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+    IfMatch(verper): IfMatch,
+) -> AppResult<VersionedPersona> {
+    state
+        .services
+        .permission
+        .authorize(&auth_user, CODPAG_HABEAS_DATA, "delete")
+        .await?;
+
+    if idper == auth_user.idper {
+        return Err(AppError::Forbidden(
+            "No puede anonimizar su propia cuenta".to_string(),
+        ));
+    }
+    let antes = check_superadmin(&auth_user, &state, idper).await?;
+
+    let persona = state
+        .services
+        .habeas_data
+        .anonimizar(idper, verper, auth_user.idper)
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::new("anonimizar", "persona", idper)
+                .antes(&antes)
+                .despues(&persona),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} anonimizó a la persona {}",
+        auth_user.nomper,
+        idper
+    );
+    Ok(versioned(&state, persona).await)
+}
+
+/// Solo un superadministrador atiende solicitudes sobre otro superadministrador
+async fn check_superadmin(
+    auth_user: &AuthUser,
+    state: &AppState,
+    idper: i64,
+) -> AppResult<Persona> {
+    let persona = state
+        .services
+        .persona
+        .get_by_id(idper)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+
+    if persona.idpef == 1 && !auth_user.is_super_admin() {
+        return Err(AppError::Forbidden(
+            "No tiene permisos sobre los datos de un superadministrador".to_string(),
+        ));
+    }
+    Ok(persona)
+}
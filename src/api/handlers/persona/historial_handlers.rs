@@ -0,0 +1,155 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use super::persona_handlers::{VersionedPersona, versioned};
+use crate::{
+    api::middleware::IfMatch,
+    core::services::auditoria::EventoAuditoria,
+    domain::{AuthUser, PersonaVersion, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Página (`pagina.codpag`) que autoriza consultar y restaurar el historial
+const CODPAG_HISTORIAL: &str = "persona.historial";
+
+#[derive(Debug, Deserialize)]
+pub struct AlQuery {
+    pub fecha: DateTime<Utc>,
+}
+
+/// GET /api/v1/persona/:idper/historial
+/// Listar las versiones de una persona, de la más reciente a la más antigua
+pub async fn list_historial(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+) -> AppResult<Json<Vec<PersonaVersion>>> {
+    authorize(&auth_user, &state, "read").await?;
+
+    let versiones = state.services.historial.versiones(idper).await?;
+    check_superadmin(&auth_user, &versiones)?;
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "historial", &versiones)
+        .await;
+    Ok(Json(versiones))
+}
+
+/// GET /api/v1/persona/:idper/historial/:verper
+/// Obtener una versión concreta de la persona
+pub async fn get_version_persona(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((idper, verper)): Path<(i64, i64)>,
+) -> AppResult<Json<PersonaVersion>> {
+    authorize(&auth_user, &state, "read").await?;
+
+    let version = state.services.historial.version(idper, verper).await?;
+    check_superadmin(&auth_user, std::slice::from_ref(&version))?;
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "historial", [&version])
+        .await;
+    Ok(Json(version))
+}
+
+/// GET /api/v1/persona/:idper/historial/al?fecha=2025-03-03T00:00:00Z
+/// Obtener la persona tal como estaba en una fecha
+pub async fn get_persona_al(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+    Query(query): Query<AlQuery>,
+) -> AppResult<Json<PersonaVersion>> {
+    authorize(&auth_user, &state, "read").await?;
+
+    let version = state.services.historial.al(idper, query.fecha).await?;
+    check_superadmin(&auth_user, std::slice::from_ref(&version))?;
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "historial", [&version])
+        .await;
+    Ok(Json(version))
+}
+
+/// POST /api/v1/persona/:idper/historial/:verper/restaurar
+/// Restaurar los datos de una versión anterior como versión nueva (requiere If-Match)
+pub async fn restaurar_version_persona(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((idper, version)): Path<(i64, i64)>,
+    IfMatch(verper): IfMatch,
+) -> AppResult<VersionedPersona> {
+    authorize(&auth_user, &state, "update").await?;
+
+    let antes = state
+        .services
+        .persona
+        .get_by_id(idper)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+    let anterior = state.services.historial.version(idper, version).await?;
+
+    // Ni la persona actual ni la restaurada pueden ser superadmin sin serlo uno mismo
+    if (antes.idpef == 1 || anterior.idpef == 1) && !auth_user.is_super_admin() {
+        return Err(AppError::Forbidden(
+            "No tiene permisos para modificar superadministradores".to_string(),
+        ));
+    }
+
+    let restaurada = state
+        .services
+        .historial
+        .restaurar(idper, version, verper)
+        .await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::new("restaurar", "persona", idper)
+                .antes(&antes)
+                .despues(&restaurada),
+        )
+        .await;
+
+    tracing::info!(
+        "Usuario {} restauró la versión {} de la persona {}",
+        auth_user.nomper,
+        version,
+        idper
+    );
+    Ok(versioned(&state, restaurada).await)
+}
+
+async fn authorize(auth_user: &AuthUser, state: &AppState, action: &str) -> AppResult<()> {
+    state
+        .services
+        .permission
+        .authorize(auth_user, CODPAG_HISTORIAL, action)
+        .await
+}
+
+/// El historial de un superadministrador solo lo ve otro superadministrador
+fn check_superadmin(auth_user: &AuthUser, versiones: &[PersonaVersion]) -> AppResult<()> {
+    if versiones.iter().any(|v| v.idpef == 1) && !auth_user.is_super_admin() {
+        return Err(AppError::Forbidden(
+            "No tiene permisos para ver este usuario".to_string(),
+        ));
+    }
+    Ok(())
+}
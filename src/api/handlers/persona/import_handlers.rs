@@ -0,0 +1,95 @@
+use axum::{
+    Json,
+    extract::{Multipart, Query, State},
+    http::StatusCode,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{
+    core::services::auditoria::EventoAuditoria,
+    core::services::persona::{ImportFormat, ImportOptions, ImportReport, parse_rows},
+    domain::{AuthUser, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+    pub batch_size: Option<usize>,
+}
+
+/// POST /api/v1/persona/import
+/// Importar personas desde un archivo CSV o XLSX (campo multipart `file`)
+pub async fn import_personas(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Query(query): Query<ImportQuery>,
+    mut multipart: Multipart,
+) -> AppResult<(StatusCode, Json<ImportReport>)> {
+    let mut archivo = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Multipart inválido: {}", e)))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let format = ImportFormat::detect(field.content_type(), field.file_name()).ok_or_else(
+            || AppError::BadRequest("Formato no soportado, use CSV o XLSX".to_string()),
+        )?;
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| AppError::BadRequest(format!("No se pudo leer el archivo: {}", e)))?;
+        archivo = Some((format, bytes));
+        break;
+    }
+
+    let (format, bytes) = archivo
+        .ok_or_else(|| AppError::BadRequest("Falta el campo 'file'".to_string()))?;
+    let filas = parse_rows(format, &bytes)?;
+
+    let options = ImportOptions {
+        dry_run: query.dry_run,
+        batch_size: query.batch_size,
+        allow_super_admin: auth_user.is_super_admin(),
+    };
+    let report = state.services.persona.import(filas, options).await?;
+    if !report.dry_run && report.importadas > 0 {
+        state
+            .services
+            .auditoria
+            .registrar(
+                &auth_user,
+                &ctx,
+                EventoAuditoria::new("importar", "persona", "*").despues(&report),
+            )
+            .await;
+    }
+
+    tracing::info!(
+        "Usuario {} importó personas: {} filas, {} errores, dry_run={}",
+        auth_user.nomper,
+        report.total,
+        report.errores.len(),
+        report.dry_run
+    );
+
+    // Lo escrito antes de la interrupción queda guardado y el reporte lo detalla
+    let status = if report.interrumpida.is_some() {
+        StatusCode::INTERNAL_SERVER_ERROR
+    } else if !report.errores.is_empty() {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else if report.dry_run {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+    Ok((status, Json(report)))
+}
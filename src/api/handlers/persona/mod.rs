@@ -1,3 +1,19 @@
 mod persona_handlers;
+mod adjunto_handlers;
+mod avatar_handlers;
+mod import_handlers;
+mod export_handlers;
+mod estado_handlers;
+mod habeas_data_handlers;
+mod consentimiento_handlers;
+mod historial_handlers;
 
 pub use persona_handlers::*;
+pub use import_handlers::*;
+pub use export_handlers::*;
+pub use estado_handlers::*;
+pub use habeas_data_handlers::*;
+pub use consentimiento_handlers::*;
+pub use historial_handlers::*;
+pub use adjunto_handlers::*;
+pub use avatar_handlers::*;
@@ -1,20 +1,56 @@
 use axum::{
     Json,
     extract::{Path, State},
+    http::{HeaderValue, header::ETAG},
 };
 use std::sync::Arc;
 
+use super::estado_handlers::authorize_transicion;
+
 use crate::{
-    domain::{AuthUser, Persona}, errors::{AppError, AppResult}, infra::AppState
+    api::{
+        dtos::{
+            CreatePersonaDTO, PatchPersonaDTO, PersonaLabels, PersonaResponseDTO, UpdatePersonaDTO,
+        },
+        middleware::{IfMatch, ValidatedJson, etag},
+    },
+    core::services::auditoria::EventoAuditoria,
+    domain::{
+        AuthUser, CATALOGO_PERFIL, CATALOGO_TIPO_DOCUMENTO, Persona, RequestContext,
+        TransicionPersona,
+    },
+    errors::{AppError, AppResult},
+    infra::AppState,
 };
 
+/// Respuesta de una persona acompañada de su ETag (versión)
+pub(super) type VersionedPersona = ([(axum::http::HeaderName, HeaderValue); 1], Json<PersonaResponseDTO>);
+
+/// Etiquetas de tipo de documento y perfil (desde el caché de catálogos)
+async fn labels(state: &AppState) -> PersonaLabels {
+    PersonaLabels {
+        tdocper: state.services.catalog.labels(CATALOGO_TIPO_DOCUMENTO).await,
+        idpef: state.services.catalog.labels(CATALOGO_PERFIL).await,
+    }
+}
+
+pub(super) async fn response(state: &AppState, persona: Persona) -> PersonaResponseDTO {
+    PersonaResponseDTO::new(persona, &labels(state).await)
+}
+
+pub(super) async fn versioned(state: &AppState, persona: Persona) -> VersionedPersona {
+    let version = etag(persona.verper);
+    ([(ETAG, version)], Json(response(state, persona).await))
+}
+
 /// GET /api/v1/persona/:idper
 /// Obtener una persona por su ID
 pub async fn get_persona(
     auth_user: AuthUser,
     State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
     Path(idper): Path<i64>,
-) -> AppResult<Json<Persona>> {
+) -> AppResult<VersionedPersona> {
     let persona = state
         .services
         .persona
@@ -29,7 +65,13 @@ pub async fn get_persona(
         ));
     }
 
-    Ok(Json(persona))
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "id", [&persona])
+        .await;
+
+    Ok(versioned(&state, persona).await)
 }
 
 /// GET /api/v1/persona
@@ -37,7 +79,8 @@ pub async fn get_persona(
 pub async fn list_personas(
     auth_user: AuthUser,
     State(state): State<Arc<AppState>>,
-) -> AppResult<Json<Vec<Persona>>> {
+    ctx: RequestContext,
+) -> AppResult<Json<Vec<PersonaResponseDTO>>> {
     // Por defecto: primeros 100 registros
     let mut personas = state.services.persona.list(100, 0).await?;
 
@@ -51,8 +94,19 @@ pub async fn list_personas(
 
     tracing::info!("Usuario {} listó personas", auth_user.nomper);
     tracing::info!("Usuario {:?}", auth_user);
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "listado", &personas)
+        .await;
 
-    Ok(Json(personas))
+    let labels = labels(&state).await;
+    Ok(Json(
+        personas
+            .into_iter()
+            .map(|p| PersonaResponseDTO::new(p, &labels))
+            .collect(),
+    ))
 }
 
 /// POST /api/v1/persona
@@ -60,8 +114,9 @@ pub async fn list_personas(
 pub async fn create_persona(
     auth_user: AuthUser,
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<Persona>,
-) -> AppResult<Json<Persona>> {
+    ctx: RequestContext,
+    ValidatedJson(mut payload): ValidatedJson<CreatePersonaDTO>,
+) -> AppResult<VersionedPersona> {
     // Si intenta crear un superadmin (idpef = 1) y no es superadmin, denegar
     if payload.idpef == 1 && !auth_user.is_super_admin() {
         return Err(AppError::Forbidden(
@@ -69,18 +124,52 @@ pub async fn create_persona(
         ));
     }
 
-    let nueva_persona = state.services.persona.create(payload).await?;
-    Ok(Json(nueva_persona))
+    let (nueva_persona, consentimiento) = match payload.consentimiento.take() {
+        Some(aceptacion) => {
+            let (persona, consentimiento) = state
+                .services
+                .persona
+                .create_con_consentimiento(payload.into(), aceptacion.idpol, aceptacion.cancon)
+                .await?;
+            (persona, Some(consentimiento))
+        }
+        None => (state.services.persona.create(payload.into()).await?, None),
+    };
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::create("persona", nueva_persona.idper, &nueva_persona),
+        )
+        .await;
+    if let Some(consentimiento) = consentimiento {
+        state
+            .services
+            .auditoria
+            .registrar(
+                &auth_user,
+                &ctx,
+                EventoAuditoria::new("aceptar", "consentimiento", nueva_persona.idper)
+                    .despues(&consentimiento),
+            )
+            .await;
+    }
+
+    Ok(versioned(&state, nueva_persona).await)
 }
 
 /// PUT /api/v1/persona/:idper
-/// Actualizar una persona
+/// Actualizar una persona (requiere If-Match con el ETag leído)
 pub async fn update_persona(
     auth_user: AuthUser,
     State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
     Path(idper): Path<i64>,
-    Json(payload): Json<Persona>,
-) -> AppResult<Json<Persona>> {
+    IfMatch(verper): IfMatch,
+    ValidatedJson(payload): ValidatedJson<UpdatePersonaDTO>,
+) -> AppResult<VersionedPersona> {
     // Verificar si la persona que se está actualizando es un superadmin
     let persona_existente = state
         .services
@@ -97,40 +186,94 @@ pub async fn update_persona(
         )); 
     }
 
-    let persona_actualizada = state.services.persona.update(idper, payload).await?;
-    Ok(Json(persona_actualizada))
+    let antes = persona_existente.clone();
+    let persona = payload.apply(persona_existente);
+    let persona_actualizada = state.services.persona.update(idper, persona, verper).await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::update("persona", idper, &antes, &persona_actualizada),
+        )
+        .await;
+
+    Ok(versioned(&state, persona_actualizada).await)
 }
 
-/// DELETE /api/v1/persona/:idper
-/// Eliminar una persona (desactivar)
-pub async fn delete_persona(
+/// PATCH /api/v1/persona/:idper
+/// Actualizar parcialmente una persona (requiere If-Match con el ETag leído)
+pub async fn patch_persona(
     auth_user: AuthUser,
     State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
     Path(idper): Path<i64>,
-) -> AppResult<()> {
-    let persona = state
+    IfMatch(verper): IfMatch,
+    ValidatedJson(payload): ValidatedJson<PatchPersonaDTO>,
+) -> AppResult<VersionedPersona> {
+    let persona_existente = state
         .services
         .persona
         .get_by_id(idper)
         .await?
         .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
-    // Prevenir eliminación de superadmin
-    if persona.idpef == 1 && !auth_user.is_super_admin() {
+
+    if (persona_existente.idpef == 1 || payload.idpef == Some(1)) && !auth_user.is_super_admin() {
         return Err(AppError::Forbidden(
-            "No se puede eliminar un superadministrador".to_string(),
+            "No tiene permisos para modificar superadministradores".to_string(),
         ));
     }
-    state.services.persona.delete(idper).await?;
+
+    let antes = persona_existente.clone();
+    let persona = payload.apply(persona_existente);
+    let persona_actualizada = state.services.persona.update(idper, persona, verper).await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::update("persona", idper, &antes, &persona_actualizada),
+        )
+        .await;
+
+    Ok(versioned(&state, persona_actualizada).await)
+}
+
+/// DELETE /api/v1/persona/:idper
+/// Eliminar una persona: equivale a archivarla (requiere If-Match con el ETag leído)
+pub async fn delete_persona(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(idper): Path<i64>,
+    IfMatch(verper): IfMatch,
+) -> AppResult<()> {
+    let antes =
+        authorize_transicion(&auth_user, &state, idper, TransicionPersona::Archivar).await?;
+    let archivada = state.services.persona.delete(idper, verper).await?;
+    state
+        .services
+        .auditoria
+        .registrar(
+            &auth_user,
+            &ctx,
+            EventoAuditoria::delete("persona", idper, &antes).despues(&archivada),
+        )
+        .await;
+
     Ok(())
 }
 
 /// GET /api/v1/persona/by-document/:ndocper
 /// Obtener una persona por su número de documento
 pub async fn get_persona_by_document(
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
     State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
     Path(ndocper): Path<String>,
-) -> AppResult<Json<Persona>> {
+) -> AppResult<Json<PersonaResponseDTO>> {
     let persona = state
         .services
         .persona
@@ -138,16 +281,23 @@ pub async fn get_persona_by_document(
         .await?
         .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
 
-    Ok(Json(persona))
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "documento", [&persona])
+        .await;
+
+    Ok(Json(response(&state, persona).await))
 }
 
 /// GET /api/v1/persona/by-email/:emaper
 /// Obtener una persona por su email
 pub async fn get_persona_by_email(
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
     State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
     Path(emaper): Path<String>,
-) -> AppResult<Json<Persona>> {
+) -> AppResult<Json<PersonaResponseDTO>> {
     let persona = state
         .services
         .persona
@@ -155,5 +305,41 @@ pub async fn get_persona_by_email(
         .await?
         .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
 
-    Ok(Json(persona))
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "email", [&persona])
+        .await;
+
+    Ok(Json(response(&state, persona).await))
+}
+
+/// GET /api/v1/persona/by-phone/:telefono
+/// Buscar personas por teléfono en cualquier formato (sin indicativo se asume la región por defecto)
+pub async fn get_personas_by_phone(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(telefono): Path<String>,
+) -> AppResult<Json<Vec<PersonaResponseDTO>>> {
+    let mut personas = state.services.persona.get_by_telefono(&telefono).await?;
+
+    // Los superadmins solo los ve otro superadmin
+    if !auth_user.is_super_admin() {
+        personas.retain(|p| p.idpef != 1);
+    }
+
+    state
+        .services
+        .acceso
+        .registrar(&auth_user, &ctx, "telefono", &personas)
+        .await;
+
+    let labels = labels(&state).await;
+    Ok(Json(
+        personas
+            .into_iter()
+            .map(|p| PersonaResponseDTO::new(p, &labels))
+            .collect(),
+    ))
 }
@@ -0,0 +1,3 @@
+mod ubicacion_handlers;
+
+pub use ubicacion_handlers::*;
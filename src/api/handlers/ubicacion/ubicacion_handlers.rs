@@ -0,0 +1,118 @@
+use axum::{
+    Json,
+    extract::{Multipart, Path, Query, State},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{
+    core::services::auditoria::EventoAuditoria,
+    core::services::ubicacion::ImportResumen,
+    domain::{AuthUser, Departamento, Municipio, RequestContext},
+    errors::{AppError, AppResult},
+    infra::AppState,
+};
+
+/// Resultados máximos del autocompletado
+const MAX_AUTOCOMPLETE: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct BuscarMunicipioQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/v1/ubicacion/departamentos
+/// Listar departamentos
+pub async fn list_departamentos(
+    _auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+) -> AppResult<Json<Vec<Departamento>>> {
+    let departamentos = state.services.ubicacion.departamentos().await?;
+    Ok(Json(departamentos))
+}
+
+/// GET /api/v1/ubicacion/departamentos/:coddep/municipios
+/// Listar municipios de un departamento
+pub async fn list_municipios(
+    _auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(coddep): Path<i64>,
+) -> AppResult<Json<Vec<Municipio>>> {
+    let municipios = state.services.ubicacion.municipios(coddep).await?;
+    Ok(Json(municipios))
+}
+
+/// GET /api/v1/ubicacion/municipios/:codubi
+/// Obtener un municipio por su código DANE
+pub async fn get_municipio(
+    _auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Path(codubi): Path<i64>,
+) -> AppResult<Json<Municipio>> {
+    let municipio = state
+        .services
+        .ubicacion
+        .municipio(codubi)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Municipio no encontrado".to_string()))?;
+
+    Ok(Json(municipio))
+}
+
+/// GET /api/v1/ubicacion/municipios?q=medel&limit=10
+/// Autocompletado de municipios por nombre
+pub async fn buscar_municipios(
+    _auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<BuscarMunicipioQuery>,
+) -> AppResult<Json<Vec<Municipio>>> {
+    let limit = query.limit.unwrap_or(10).clamp(1, MAX_AUTOCOMPLETE);
+    let municipios = state.services.ubicacion.buscar(&query.q, limit).await?;
+    Ok(Json(municipios))
+}
+
+/// POST /api/v1/ubicacion/import
+/// Cargar el CSV DIVIPOLA del DANE (campo multipart `file`, solo administradores)
+pub async fn import_divipola(
+    auth_user: AuthUser,
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    mut multipart: Multipart,
+) -> AppResult<Json<ImportResumen>> {
+    if !auth_user.is_admin() {
+        return Err(AppError::Forbidden(
+            "Solo un administrador puede cargar el catálogo DIVIPOLA".to_string(),
+        ));
+    }
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Multipart inválido: {}", e)))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| AppError::BadRequest(format!("No se pudo leer el archivo: {}", e)))?;
+        let resumen = state.services.ubicacion.import(&bytes).await?;
+        state
+            .services
+            .auditoria
+            .registrar(
+                &auth_user,
+                &ctx,
+                EventoAuditoria::new("importar", "ubicacion", "*").despues(&resumen),
+            )
+            .await;
+
+        tracing::info!("Usuario {} cargó el catálogo DIVIPOLA", auth_user.nomper);
+        return Ok(Json(resumen));
+    }
+
+    Err(AppError::BadRequest("Falta el campo 'file'".to_string()))
+}
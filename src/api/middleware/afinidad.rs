@@ -0,0 +1,37 @@
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{Request, State},
+    http::header::AUTHORIZATION,
+    middleware::Next,
+    response::Response,
+};
+
+use super::request_context::ip_cliente;
+use crate::{config::como_cliente, infra::AppState};
+
+/// Identifica al cliente de cada petición para la afinidad de lectura: después
+/// de escribir, sus lecturas van a la base primaria por un rato aunque haya
+/// réplicas (ver `config::Cluster`)
+/// El cliente es su token; sin token, su IP
+pub async fn afinidad_lectura(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut hasher = DefaultHasher::new();
+    match request.headers().get(AUTHORIZATION) {
+        Some(token) => token.as_bytes().hash(&mut hasher),
+        None => ip_cliente(
+            request.headers(),
+            request.extensions(),
+            &state.proxies_confiables,
+        )
+        .hash(&mut hasher),
+    }
+
+    como_cliente(hasher.finish(), next.run(request)).await
+}
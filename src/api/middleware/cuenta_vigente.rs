@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::{domain::AuthUser, errors::AppError, infra::AppState};
+
+/// El estado de la cuenta puede haber cambiado después de emitir el token:
+/// una cuenta suspendida, bloqueada o archivada deja de poder usarlo
+/// Sin token válido la petición sigue y la rechaza el extractor `AuthUser`
+/// si la ruta lo pide
+pub async fn cuenta_vigente(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let (mut parts, body) = request.into_parts();
+    if let Ok(auth_user) = AuthUser::from_request_parts(&mut parts, &state).await {
+        state
+            .services
+            .persona
+            .exigir_cuenta_vigente(auth_user.idper)
+            .await?;
+    }
+
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
@@ -0,0 +1,49 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{HeaderValue, header::IF_MATCH, request::Parts},
+};
+
+use crate::errors::AppError;
+
+/// Versión esperada por el cliente, extraída del header `If-Match`
+/// Obligatorio en PUT/PATCH/DELETE para evitar que dos escrituras se pisen
+#[derive(Debug, Clone, Copy)]
+pub struct IfMatch(pub i64);
+
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = parts.headers.get(IF_MATCH).ok_or_else(|| {
+            AppError::PreconditionRequired("El header If-Match es obligatorio".to_string())
+        })?;
+
+        header
+            .to_str()
+            .ok()
+            .and_then(parse_etag)
+            .map(IfMatch)
+            .ok_or_else(|| AppError::BadRequest("Header If-Match inválido".to_string()))
+    }
+}
+
+/// Construye el ETag fuerte de un recurso versionado
+pub fn etag(version: i64) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", version)).expect("un entero siempre es un ETag válido")
+}
+
+/// If-None-Match admite `*` o una lista de ETags, débiles o fuertes
+pub fn if_none_match(header: &str, etag: &str) -> bool {
+    header.split(',').map(str::trim).any(|candidato| {
+        candidato == "*" || candidato.strip_prefix("W/").unwrap_or(candidato) == etag
+    })
+}
+
+/// Interpreta un ETag fuerte (`"3"`); los débiles (`W/"3"`) no sirven para If-Match
+fn parse_etag(value: &str) -> Option<i64> {
+    value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .and_then(|v| v.parse().ok())
+}
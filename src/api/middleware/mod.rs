@@ -1,2 +1,12 @@
+mod afinidad;
 mod auth_middleware;
+mod cuenta_vigente;
+mod if_match;
+mod request_context;
+mod validated_json;
 
+pub use afinidad::afinidad_lectura;
+pub use cuenta_vigente::cuenta_vigente;
+pub use if_match::{IfMatch, etag, if_none_match};
+pub use request_context::{ACCESS_PURPOSE_HEADER, REQUEST_ID_HEADER};
+pub use validated_json::ValidatedJson;
@@ -0,0 +1,78 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{Extensions, HeaderMap, header::USER_AGENT, request::Parts},
+};
+use ipnet::IpNet;
+
+use crate::{domain::RequestContext, errors::AppError, infra::AppState};
+
+/// Header con el id de la petición (lo fija `SetRequestIdLayer` si el cliente no lo envía)
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Header con la finalidad de una consulta de datos personales
+pub const ACCESS_PURPOSE_HEADER: &str = "x-access-purpose";
+
+impl FromRequestParts<Arc<AppState>> for RequestContext {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let header = |headers: &HeaderMap, name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        Ok(RequestContext {
+            ip: ip_cliente(&parts.headers, &parts.extensions, &state.proxies_confiables)
+                .map(|ip| ip.to_string()),
+            user_agent: header(&parts.headers, USER_AGENT.as_str()),
+            request_id: header(&parts.headers, REQUEST_ID_HEADER),
+            proposito: header(&parts.headers, ACCESS_PURPOSE_HEADER),
+        })
+    }
+}
+
+/// IP del cliente: la de la conexión, salvo que venga de un proxy de confianza
+/// En ese caso se recorre X-Forwarded-For de derecha a izquierda saltando los
+/// proxies de confianza; lo que está más a la izquierda lo pudo escribir cualquiera
+pub(super) fn ip_cliente(
+    headers: &HeaderMap,
+    extensions: &Extensions,
+    proxies: &[IpNet],
+) -> Option<IpAddr> {
+    let ConnectInfo(conexion) = extensions.get::<ConnectInfo<SocketAddr>>()?;
+    let confiable = |ip: &IpAddr| proxies.iter().any(|red| red.contains(ip));
+
+    let mut ip = conexion.ip();
+    if !confiable(&ip) {
+        return Some(ip);
+    }
+
+    let reenviadas: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .collect();
+    for reenviada in reenviadas.into_iter().rev() {
+        match reenviada.trim().parse::<IpAddr>() {
+            Ok(anterior) => ip = anterior,
+            // Una entrada ilegible corta la cadena: queda el último salto conocido
+            Err(_) => break,
+        }
+        if !confiable(&ip) {
+            break;
+        }
+    }
+    Some(ip)
+}
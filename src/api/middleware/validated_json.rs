@@ -0,0 +1,30 @@
+use axum::{
+    Json,
+    extract::{FromRequest, Request},
+};
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+use crate::errors::AppError;
+
+/// Igual que `Json<T>` pero además ejecuta las reglas de `validator`
+/// Los errores salen como `AppError::Validation` con el detalle por campo
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|e| AppError::BadRequest(e.body_text()))?;
+
+        value.validate()?;
+        Ok(ValidatedJson(value))
+    }
+}
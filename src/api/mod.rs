@@ -1,7 +1,7 @@
 mod router;
 mod routes;
 mod middleware;
-mod dtos;
+pub mod dtos;
 mod handlers;
 
 pub use router::app_router;
@@ -3,16 +3,29 @@ use std::sync::Arc;
 use axum::{
     Router,
     http::{
-        Method,
-        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderName, Method,
+        header::{AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH},
     },
 };
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::{
+    cors::{Any, CorsLayer},
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+};
 
-use crate::{api::{auth_routes, persona_routes}, infra::AppState};
+use crate::{
+    api::{
+        acceso_routes, auditoria_routes, auth_routes, catalog_routes, consentimiento_routes,
+        database_routes, documento_routes, duplicado_routes,
+        middleware::{ACCESS_PURPOSE_HEADER, REQUEST_ID_HEADER, afinidad_lectura, cuenta_vigente},
+        persona_routes, ubicacion_routes,
+    },
+    infra::AppState,
+};
 
 /// Router principal de la aplicación
 pub fn app_router(state: Arc<AppState>) -> Router {
+    let request_id = HeaderName::from_static(REQUEST_ID_HEADER);
+
     // Configuración de CORS
     let cors = CorsLayer::new()
         .allow_origin(Any) // En producción: especificar orígenes exactos
@@ -23,13 +36,35 @@ pub fn app_router(state: Arc<AppState>) -> Router {
             Method::DELETE,
             Method::PATCH,
         ])
-        .allow_headers([AUTHORIZATION, CONTENT_TYPE]);
+        .allow_headers([
+            AUTHORIZATION,
+            CONTENT_TYPE,
+            IF_MATCH,
+            IF_NONE_MATCH,
+            request_id.clone(),
+            HeaderName::from_static(ACCESS_PURPOSE_HEADER),
+        ])
+        .expose_headers([ETAG, request_id.clone()]);
 
     // Combinar todas las rutas
     Router::new()
-        .nest("/api/v1", api_routes())
+        .nest(
+            "/api/v1",
+            api_routes().layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                cuenta_vigente,
+            )),
+        )
+        // Quien acaba de escribir lee de la base primaria aunque haya réplicas
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            afinidad_lectura,
+        ))
         .layer(cors)
         .layer(tower_http::trace::TraceLayer::new_for_http())
+        // El id de la petición se genera antes de todo y se devuelve en la respuesta
+        .layer(PropagateRequestIdLayer::new(request_id.clone()))
+        .layer(SetRequestIdLayer::new(request_id, MakeRequestUuid))
         .with_state(state)
 }
 
@@ -37,4 +72,12 @@ fn api_routes() -> Router<Arc<AppState>> {
     Router::new()
         .nest("/persona", persona_routes())
         .nest("/auth", auth_routes())
+        .nest("/documento", documento_routes())
+        .nest("/ubicacion", ubicacion_routes())
+        .nest("/catalog", catalog_routes())
+        .nest("/consentimiento", consentimiento_routes())
+        .nest("/auditoria", auditoria_routes())
+        .nest("/accesos", acceso_routes())
+        .nest("/duplicados", duplicado_routes())
+        .nest("/database", database_routes())
 }
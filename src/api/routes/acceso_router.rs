@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::{
+    api::handlers::acceso::{list_accesos_actor, list_accesos_persona},
+    infra::AppState,
+};
+
+/// Rutas del reporte de lecturas de datos sensibles
+pub fn acceso_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/persona/{idper}", get(list_accesos_persona))
+        .route("/actor/{idactor}", get(list_accesos_actor))
+}
@@ -0,0 +1,10 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::{api::handlers::auditoria::list_auditoria, infra::AppState};
+
+/// Rutas de consulta de la bitácora de auditoría
+pub fn auditoria_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/", get(list_auditoria))
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{get, put},
+};
+
+use crate::{
+    api::handlers::catalog::{
+        create_catalog_item, delete_catalog_item, get_catalog, list_catalogs, update_catalog_item,
+    },
+    infra::AppState,
+};
+
+/// Rutas de catálogos de referencia (tipos de documento, perfiles, ...)
+pub fn catalog_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_catalogs))
+        .route("/{name}", get(get_catalog).post(create_catalog_item))
+        .route(
+            "/{name}/{id}",
+            put(update_catalog_item).delete(delete_catalog_item),
+        )
+}
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::{
+    api::handlers::consentimiento::{get_politica_vigente, list_politicas, publicar_politica},
+    infra::AppState,
+};
+
+/// Rutas de la política de tratamiento de datos personales
+pub fn consentimiento_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/politica", get(get_politica_vigente))
+        .route("/politicas", get(list_politicas).post(publicar_politica))
+}
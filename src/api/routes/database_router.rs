@@ -0,0 +1,10 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::{api::handlers::database::get_pool_stats, infra::AppState};
+
+/// Rutas de diagnóstico de la base de datos
+pub fn database_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/pool", get(get_pool_stats))
+}
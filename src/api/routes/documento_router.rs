@@ -0,0 +1,10 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::{api::handlers::documento::validar_documento, infra::AppState};
+
+/// Rutas de validación de documentos de identidad
+pub fn documento_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/validar", get(validar_documento))
+}
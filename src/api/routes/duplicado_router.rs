@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::{
+    api::handlers::duplicado::{
+        descartar_duplicado, detectar_duplicados, fusionar_duplicado, fusionar_personas,
+        list_duplicados, list_fusiones,
+    },
+    infra::AppState,
+};
+
+/// Rutas de revisión de duplicados y fusión de personas
+pub fn duplicado_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_duplicados))
+        .route("/detectar", post(detectar_duplicados))
+        .route("/fusionar", post(fusionar_personas))
+        .route("/fusiones/{idper}", get(list_fusiones))
+        .route("/{iddup}/descartar", post(descartar_duplicado))
+        .route("/{iddup}/fusionar", post(fusionar_duplicado))
+}
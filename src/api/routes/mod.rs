@@ -1,6 +1,21 @@
+mod acceso_router;
+mod auditoria_router;
 mod auth_router;
+mod catalog_router;
+mod consentimiento_router;
+mod database_router;
+mod documento_router;
+mod duplicado_router;
 mod persona_router;
+mod ubicacion_router;
 
 pub use persona_router::persona_routes;
+pub use acceso_router::acceso_routes;
+pub use auditoria_router::auditoria_routes;
 pub use auth_router::auth_routes;
-
+pub use catalog_router::catalog_routes;
+pub use consentimiento_router::consentimiento_routes;
+pub use database_router::database_routes;
+pub use documento_router::documento_routes;
+pub use duplicado_router::duplicado_routes;
+pub use ubicacion_router::ubicacion_routes;
@@ -1,15 +1,34 @@
 use std::sync::Arc;
 
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+};
 
 use crate::{
     api::handlers::persona::{
-        create_persona, delete_persona, get_persona, get_persona_by_document, get_persona_by_email,
-        list_personas, update_persona,
+        aceptar_consentimiento, activar_persona, anonimizar_persona, archivar_persona,
+        bloquear_persona, create_persona, delete_adjunto, delete_avatar, delete_persona,
+        desbloquear_persona, download_adjunto, export_datos_personales, export_personas,
+        get_avatar, get_consentimiento, get_persona, get_persona_al, get_persona_by_document,
+        get_persona_by_email, get_personas_by_phone, get_version_persona, import_personas,
+        list_adjuntos, list_historial, list_personas, patch_persona, restaurar_version_persona,
+        revocar_consentimiento, suspender_persona, update_avatar, update_persona, upload_adjunto,
     },
+    core::services::{adjunto::MAX_TAMANO_ADJUNTO, avatar::MAX_TAMANO_AVATAR},
     infra::AppState,
 };
 
+/// Tamaño máximo del archivo de importación masiva
+const IMPORT_BODY_LIMIT: usize = 20 * 1024 * 1024;
+
+/// Margen para los demás campos y encabezados del multipart de un adjunto
+const ADJUNTO_BODY_LIMIT: usize = MAX_TAMANO_ADJUNTO + 64 * 1024;
+
+/// Un byte más que el máximo para que el servicio responda con su validación
+const AVATAR_BODY_LIMIT: usize = MAX_TAMANO_AVATAR + 1;
+
 /// Rutas del módulo Persona
 /// Retorna Router<AppState> para que sea compatible con el state global
 pub fn persona_routes() -> Router<Arc<AppState>> {
@@ -17,10 +36,61 @@ pub fn persona_routes() -> Router<Arc<AppState>> {
         // Rutas de búsqueda específica (deben ir primero para evitar conflictos)
         .route("/by-document/{ndocper}", get(get_persona_by_document))
         .route("/by-email/{emaper}", get(get_persona_by_email))
+        .route("/by-phone/{telefono}", get(get_personas_by_phone))
+        .route("/export", get(export_personas))
+        .route(
+            "/import",
+            post(import_personas).layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)),
+        )
         // Rutas CRUD estándar
         .route("/", get(list_personas).post(create_persona))
         .route(
             "/{idper}",
-            get(get_persona).put(update_persona).delete(delete_persona),
+            get(get_persona)
+                .put(update_persona)
+                .patch(patch_persona)
+                .delete(delete_persona),
+        )
+        // Transiciones del ciclo de vida de la cuenta
+        .route("/{idper}/activar", post(activar_persona))
+        .route("/{idper}/suspender", post(suspender_persona))
+        .route("/{idper}/bloquear", post(bloquear_persona))
+        .route("/{idper}/desbloquear", post(desbloquear_persona))
+        .route("/{idper}/archivar", post(archivar_persona))
+        // Habeas data (Ley 1581)
+        .route("/{idper}/datos-personales", get(export_datos_personales))
+        .route("/{idper}/anonimizar", post(anonimizar_persona))
+        .route(
+            "/{idper}/consentimiento",
+            get(get_consentimiento)
+                .post(aceptar_consentimiento)
+                .delete(revocar_consentimiento),
+        )
+        // Historial temporal (versiones, consulta a una fecha y restauración)
+        .route("/{idper}/historial", get(list_historial))
+        .route("/{idper}/historial/al", get(get_persona_al))
+        .route("/{idper}/historial/{verper}", get(get_version_persona))
+        .route(
+            "/{idper}/historial/{verper}/restaurar",
+            post(restaurar_version_persona),
+        )
+        // Archivos adjuntos (documentos de identidad, contratos, fotos)
+        .route(
+            "/{idper}/adjuntos",
+            get(list_adjuntos)
+                .post(upload_adjunto)
+                .layer(DefaultBodyLimit::max(ADJUNTO_BODY_LIMIT)),
+        )
+        .route(
+            "/{idper}/adjuntos/{idadj}",
+            get(download_adjunto).delete(delete_adjunto),
+        )
+        // Avatar (miniaturas cuadradas generadas al subir la imagen)
+        .route(
+            "/{idper}/avatar",
+            get(get_avatar)
+                .put(update_avatar)
+                .delete(delete_avatar)
+                .layer(DefaultBodyLimit::max(AVATAR_BODY_LIMIT)),
         )
 }
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+};
+
+use crate::{
+    api::handlers::ubicacion::{
+        buscar_municipios, get_municipio, import_divipola, list_departamentos, list_municipios,
+    },
+    infra::AppState,
+};
+
+/// Tamaño máximo del CSV DIVIPOLA
+const IMPORT_BODY_LIMIT: usize = 10 * 1024 * 1024;
+
+/// Rutas del catálogo de ubicaciones DIVIPOLA
+pub fn ubicacion_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/departamentos", get(list_departamentos))
+        .route("/departamentos/{coddep}/municipios", get(list_municipios))
+        .route("/municipios", get(buscar_municipios))
+        .route("/municipios/{codubi}", get(get_municipio))
+        .route(
+            "/import",
+            post(import_divipola).layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)),
+        )
+}
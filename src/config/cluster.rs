@@ -0,0 +1,288 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use sqlx::{Database, Pool, Transaction, pool::PoolConnection};
+
+use super::pool::{EstadisticasPool, Metricas};
+use crate::errors::AppResult;
+
+/// Tiempo por defecto que un cliente lee de la primaria después de escribir
+pub const AFINIDAD_POR_DEFECTO: Duration = Duration::from_secs(2);
+
+/// Tiempo por defecto que una réplica caída queda fuera antes de volver a probarla
+pub const REINTENTO_POR_DEFECTO: Duration = Duration::from_secs(30);
+
+/// Con más marcas que esto se descartan las vencidas antes de agregar otra
+const LIMPIAR_DESDE: usize = 1024;
+
+tokio::task_local! {
+    /// Cliente de la petición en curso; lo fija el middleware de la API
+    static CLIENTE: u64;
+}
+
+/// Ejecuta `operacion` a nombre de `cliente`: lo que escriba hace que sus
+/// lecturas siguientes (en esta y en otras peticiones) vayan a la primaria
+pub async fn como_cliente<F: Future>(cliente: u64, operacion: F) -> F::Output {
+    CLIENTE.scope(cliente, operacion).await
+}
+
+fn cliente_actual() -> Option<u64> {
+    CLIENTE.try_with(|cliente| *cliente).ok()
+}
+
+/// Pool primario con réplicas de solo lectura opcionales
+/// Las escrituras van siempre a la primaria. Las lecturas se reparten entre las
+/// réplicas, salvo las de un cliente que escribió hace menos de `afinidad`: esas
+/// van a la primaria para que lea lo que acaba de escribir aunque la réplica
+/// vaya atrasada. Una réplica que no entrega conexión queda fuera durante
+/// `reintento` y mientras tanto se lee de la primaria
+///
+/// No se mide el retraso de replicación: una réplica que responde pero va muy
+/// atrasada sigue recibiendo lecturas, y fuera de la ventana de `afinidad` se
+/// leen datos viejos. Vigilar el retraso (y sacar la réplica de
+/// `DATABASE_REPLICA_URLS`) queda del lado de la operación de la base
+pub struct Cluster<DB: Database> {
+    primaria: Pool<DB>,
+    compartido: Arc<Compartido<DB>>,
+}
+
+struct Compartido<DB: Database> {
+    metricas: Metricas,
+    replicas: Vec<Replica<DB>>,
+    turno: AtomicUsize,
+    afinidad: Duration,
+    reintento: Duration,
+    /// Cliente -> hasta cuándo sus lecturas van a la primaria
+    escrituras: Mutex<HashMap<u64, Instant>>,
+}
+
+struct Replica<DB: Database> {
+    pool: Pool<DB>,
+    metricas: Metricas,
+    fuera_hasta: Mutex<Option<Instant>>,
+}
+
+impl<DB: Database> Clone for Cluster<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            primaria: self.primaria.clone(),
+            compartido: self.compartido.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Debug for Cluster<DB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cluster")
+            .field("primaria", &self.primaria)
+            .field("replicas", &self.compartido.replicas.len())
+            .finish()
+    }
+}
+
+impl<DB: Database> From<Pool<DB>> for Cluster<DB> {
+    fn from(primaria: Pool<DB>) -> Self {
+        Self::new(
+            primaria,
+            Vec::new(),
+            AFINIDAD_POR_DEFECTO,
+            REINTENTO_POR_DEFECTO,
+        )
+    }
+}
+
+impl<DB: Database> Cluster<DB> {
+    pub fn new(
+        primaria: Pool<DB>,
+        replicas: Vec<Pool<DB>>,
+        afinidad: Duration,
+        reintento: Duration,
+    ) -> Self {
+        Self {
+            primaria,
+            compartido: Arc::new(Compartido {
+                metricas: Metricas::default(),
+                replicas: replicas
+                    .into_iter()
+                    .map(|pool| Replica {
+                        pool,
+                        metricas: Metricas::default(),
+                        fuera_hasta: Mutex::new(None),
+                    })
+                    .collect(),
+                turno: AtomicUsize::new(0),
+                afinidad,
+                reintento,
+                escrituras: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Pool primario sin más (migraciones, verificaciones)
+    pub fn primaria(&self) -> &Pool<DB> {
+        &self.primaria
+    }
+
+    pub fn replicas(&self) -> usize {
+        self.compartido.replicas.len()
+    }
+
+    /// Conexión para escribir: de la primaria
+    /// Deja marcado al cliente en curso para que lea de la primaria un rato
+    pub async fn escritura(&self) -> AppResult<PoolConnection<DB>> {
+        self.marcar_escritura();
+        Ok(adquirir(&self.primaria, &self.compartido.metricas).await?)
+    }
+
+    /// Transacción en la primaria; marca al cliente igual que `escritura`
+    pub async fn transaccion(&self) -> AppResult<Transaction<'static, DB>> {
+        self.marcar_escritura();
+        let inicio = Instant::now();
+        let tx = self.primaria.begin().await;
+        self.compartido
+            .metricas
+            .registrar(inicio.elapsed(), tx.is_ok());
+        Ok(tx?)
+    }
+
+    /// Uso y esperas de la primaria y de cada réplica
+    pub fn estadisticas(&self) -> (EstadisticasPool, Vec<EstadisticasPool>) {
+        let ahora = Instant::now();
+        let primaria = self.compartido.metricas.estadisticas(&self.primaria, true);
+        let replicas = self
+            .compartido
+            .replicas
+            .iter()
+            .map(|replica| {
+                replica
+                    .metricas
+                    .estadisticas(&replica.pool, replica.disponible(ahora))
+            })
+            .collect();
+        (primaria, replicas)
+    }
+
+    /// Conexión para leer: de una réplica disponible o, si no hay, de la primaria
+    pub async fn lectura(&self) -> AppResult<PoolConnection<DB>> {
+        self.lectura_con_afinidad(self.escribio_hace_poco()).await
+    }
+
+    /// Como `lectura`, con la afinidad decidida antes por `escribio_hace_poco`:
+    /// para leer cuando ya terminó el `como_cliente` de la petición
+    pub async fn lectura_con_afinidad(
+        &self,
+        de_la_primaria: bool,
+    ) -> AppResult<PoolConnection<DB>> {
+        if let Some((n, replica)) = self.elegir_replica(de_la_primaria) {
+            match adquirir(&replica.pool, &replica.metricas).await {
+                Ok(conn) => {
+                    replica.volver(n);
+                    return Ok(conn);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Réplica {} no disponible, se lee de la primaria durante {:?}: {}",
+                        n,
+                        self.compartido.reintento,
+                        e
+                    );
+                    replica.sacar(self.compartido.reintento);
+                }
+            }
+        }
+        Ok(adquirir(&self.primaria, &self.compartido.metricas).await?)
+    }
+
+    /// Siguiente réplica disponible por turnos; ninguna si hay que leer de la primaria
+    fn elegir_replica(&self, de_la_primaria: bool) -> Option<(usize, &Replica<DB>)> {
+        let replicas = &self.compartido.replicas;
+        if replicas.is_empty() || de_la_primaria {
+            return None;
+        }
+
+        let ahora = Instant::now();
+        let inicio = self.compartido.turno.fetch_add(1, Ordering::Relaxed);
+        (0..replicas.len())
+            .map(|i| (inicio + i) % replicas.len())
+            .map(|n| (n, &replicas[n]))
+            .find(|(_, replica)| replica.disponible(ahora))
+    }
+
+    fn marcar_escritura(&self) {
+        let Some(cliente) = cliente_actual() else {
+            return;
+        };
+        if self.compartido.replicas.is_empty() {
+            return;
+        }
+
+        let ahora = Instant::now();
+        let mut escrituras = self
+            .compartido
+            .escrituras
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if escrituras.len() >= LIMPIAR_DESDE {
+            escrituras.retain(|_, hasta| *hasta > ahora);
+        }
+        escrituras.insert(cliente, ahora + self.compartido.afinidad);
+    }
+
+    /// Si el cliente en curso escribió hace menos de `afinidad`
+    pub fn escribio_hace_poco(&self) -> bool {
+        let Some(cliente) = cliente_actual() else {
+            return false;
+        };
+        self.compartido
+            .escrituras
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&cliente)
+            .is_some_and(|hasta| *hasta > Instant::now())
+    }
+}
+
+/// Toma una conexión de `pool` y registra cuánto se esperó por ella
+async fn adquirir<DB: Database>(
+    pool: &Pool<DB>,
+    metricas: &Metricas,
+) -> Result<PoolConnection<DB>, sqlx::Error> {
+    let inicio = Instant::now();
+    let conn = pool.acquire().await;
+    metricas.registrar(inicio.elapsed(), conn.is_ok());
+    conn
+}
+
+impl<DB: Database> Replica<DB> {
+    fn disponible(&self, ahora: Instant) -> bool {
+        self.fuera_hasta
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_none_or(|hasta| hasta <= ahora)
+    }
+
+    fn sacar(&self, reintento: Duration) {
+        *self
+            .fuera_hasta
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Instant::now() + reintento);
+    }
+
+    fn volver(&self, n: usize) {
+        let mut fuera_hasta = self
+            .fuera_hasta
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if fuera_hasta.take().is_some() {
+            tracing::info!("Réplica {} disponible de nuevo", n);
+        }
+    }
+}
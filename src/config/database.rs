@@ -1,73 +1,349 @@
-use crate::errors::AppResult;
-use sqlx::{PgPool, postgres::PgPoolOptions};
+use crate::config::{Cluster, EstadisticasDb, OpcionesPool};
+use crate::errors::{AppError, AppResult};
+use log::LevelFilter;
+use sqlx::{ConnectOptions, pool::PoolOptions};
+#[cfg(feature = "mysql")]
+use sqlx::{Executor, MySql, MySqlConnection, mysql::MySqlConnectOptions};
+#[cfg(feature = "postgres")]
+use sqlx::{Postgres, postgres::PgConnectOptions};
+#[cfg(feature = "sqlite")]
+use sqlx::{
+    Sqlite,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode},
+};
+use std::str::FromStr;
+use std::time::Duration;
 
-/// Pool de conexiones a MySQL
+/// Espera máxima por una conexión de réplica antes de leer de la primaria
+const ESPERA_REPLICA: Duration = Duration::from_secs(3);
+
+#[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+compile_error!(
+    "Se necesita al menos un motor de base de datos: feature `postgres`, `mysql` o `sqlite`"
+);
+
+/// Pool de conexiones al motor indicado por el esquema de `DATABASE_URL`
+/// (`postgres://`/`postgresql://`, `mysql://`/`mariadb://` o `sqlite:`)
+/// con sus réplicas de lectura, si se configuraron (ver `Cluster`)
 /// En Rust, el pool maneja las conexiones de forma segura y eficiente
 /// No hay memory leaks ni conexiones colgadas
-#[derive(Clone)]
-pub struct Database {
-    pg_pool: PgPool,
-    // mysql_pool: MySqlPool,
+#[derive(Clone, Debug)]
+pub enum Database {
+    #[cfg(feature = "postgres")]
+    Postgres(Cluster<Postgres>),
+    #[cfg(feature = "mysql")]
+    MySql(Cluster<MySql>),
+    #[cfg(feature = "sqlite")]
+    Sqlite(Cluster<Sqlite>),
+}
+
+/// Motor de base de datos según el esquema de la URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motor {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Motor {
+    pub fn from_url(database_url: &str) -> AppResult<Self> {
+        let esquema = database_url
+            .split_once(':')
+            .map(|(esquema, _)| esquema.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        match esquema.as_str() {
+            "postgres" | "postgresql" => Ok(Motor::Postgres),
+            "mysql" | "mariadb" => Ok(Motor::MySql),
+            "sqlite" => Ok(Motor::Sqlite),
+            _ => Err(AppError::Internal(format!(
+                "DATABASE_URL con esquema no soportado: `{}` (use postgres://, mysql:// o sqlite:)",
+                esquema
+            ))),
+        }
+    }
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Motor::Postgres => "PostgreSQL",
+            Motor::MySql => "MySQL",
+            Motor::Sqlite => "SQLite",
+        }
+    }
+}
+
+/// Opciones de pool y de conexión a PostgreSQL
+/// El límite por sentencia lo aplica el servidor (`statement_timeout`)
+#[cfg(feature = "postgres")]
+fn postgres(
+    url: &str,
+    opciones: &OpcionesPool,
+) -> Result<(PoolOptions<Postgres>, PgConnectOptions), sqlx::Error> {
+    let mut conexion = PgConnectOptions::from_str(url)?
+        .log_slow_statements(LevelFilter::Warn, opciones.consulta_lenta);
+    if let Some(limite) = opciones.limite_sentencia {
+        conexion = conexion.options([("statement_timeout", limite.as_millis())]);
+    }
+    Ok((opciones.pool(), conexion))
+}
+
+/// Opciones de pool y de conexión a MySQL/MariaDB
+/// El límite por sentencia se fija en cada conexión nueva: MySQL solo lo
+/// aplica a SELECT (`max_execution_time`, en ms) y MariaDB a todas
+/// (`max_statement_time`, en segundos)
+#[cfg(feature = "mysql")]
+fn mysql(
+    url: &str,
+    opciones: &OpcionesPool,
+) -> Result<(PoolOptions<MySql>, MySqlConnectOptions), sqlx::Error> {
+    let conexion = MySqlConnectOptions::from_str(url)?
+        .log_slow_statements(LevelFilter::Warn, opciones.consulta_lenta);
+    let mut pool = opciones.pool();
+    if let Some(limite) = opciones.limite_sentencia {
+        let sql = if url.to_ascii_lowercase().starts_with("mariadb:") {
+            format!("SET SESSION max_statement_time = {}", limite.as_secs_f64())
+        } else {
+            format!("SET SESSION max_execution_time = {}", limite.as_millis())
+        };
+        pool = pool.after_connect(move |conn: &mut MySqlConnection, _| {
+            let sql = sql.clone();
+            Box::pin(async move {
+                conn.execute(sql.as_str()).await?;
+                Ok(())
+            })
+        });
+    }
+    Ok((pool, conexion))
+}
+
+/// Opciones de pool y de conexión a SQLite
+#[cfg(feature = "sqlite")]
+fn sqlite(
+    url: &str,
+    opciones: &OpcionesPool,
+) -> Result<(PoolOptions<Sqlite>, SqliteConnectOptions), sqlx::Error> {
+    // El archivo se crea si no existe; WAL deja leer mientras otro escribe
+    let conexion = SqliteConnectOptions::from_str(url)?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .foreign_keys(true)
+        .log_slow_statements(LevelFilter::Warn, opciones.consulta_lenta);
+    if opciones.limite_sentencia.is_some() {
+        tracing::warn!("SQLite no admite límite de duración por sentencia; se ignora");
+    }
+
+    let pool = opciones.pool();
+    // Cada conexión a `sqlite::memory:` abre una base distinta: una sola
+    // conexión que no se cierre nunca, o se pierden los datos
+    let pool = if url.contains(":memory:") {
+        pool.max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+    } else {
+        pool
+    };
+    Ok((pool, conexion))
 }
 
 impl Database {
     /// Crea una nueva conexión a la base de datos
-    pub async fn new(database_url: &str, max: u32) -> AppResult<Self> {
-        let pg_pool = PgPoolOptions::new()
-            .max_connections(max)
-            .connect(database_url)
-            .await
-            .map_err(|e| {
-                crate::errors::AppError::Internal(format!(
-                    "No se pudo conectar a la base de datos: {}",
-                    e
+    pub async fn new(database_url: &str, opciones: &OpcionesPool) -> AppResult<Self> {
+        let motor = Motor::from_url(database_url)?;
+        let conexion_fallida = |e: sqlx::Error| {
+            AppError::Internal(format!(
+                "No se pudo conectar a la base de datos {}: {}",
+                motor.nombre(),
+                e
+            ))
+        };
+
+        let db = match motor {
+            #[cfg(feature = "postgres")]
+            Motor::Postgres => {
+                let (pool, conexion) = postgres(database_url, opciones).map_err(conexion_fallida)?;
+                Database::Postgres(
+                    pool.connect_with(conexion)
+                        .await
+                        .map_err(conexion_fallida)?
+                        .into(),
+                )
+            }
+            #[cfg(feature = "mysql")]
+            Motor::MySql => {
+                let (pool, conexion) = mysql(database_url, opciones).map_err(conexion_fallida)?;
+                Database::MySql(
+                    pool.connect_with(conexion)
+                        .await
+                        .map_err(conexion_fallida)?
+                        .into(),
+                )
+            }
+            #[cfg(feature = "sqlite")]
+            Motor::Sqlite => {
+                let (pool, conexion) = sqlite(database_url, opciones).map_err(conexion_fallida)?;
+                Database::Sqlite(
+                    pool.connect_with(conexion)
+                        .await
+                        .map_err(conexion_fallida)?
+                        .into(),
+                )
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                return Err(AppError::Internal(format!(
+                    "Este binario se compiló sin soporte para {}",
+                    motor.nombre()
+                )));
+            }
+        };
+
+        tracing::info!(
+            "✅ Conexión a base de datos {} establecida (pool de {} conexiones)",
+            motor.nombre(),
+            opciones.max_conexiones
+        );
+        Ok(db)
+    }
+
+    /// Agrega réplicas de solo lectura del mismo motor
+    /// Se conectan a demanda: una réplica caída al arrancar no impide servir,
+    /// sus lecturas van a la primaria hasta que responda
+    /// `opciones`: las mismas de la primaria, con una espera por conexión corta
+    /// `afinidad`: cuánto lee de la primaria un cliente después de escribir
+    /// `reintento`: cuánto queda fuera una réplica que no respondió
+    // Con solo SQLite compilado no hay réplicas que armar
+    #[cfg_attr(
+        not(any(feature = "postgres", feature = "mysql")),
+        allow(unused_variables)
+    )]
+    pub fn con_replicas(
+        self,
+        urls: &[String],
+        opciones: &OpcionesPool,
+        afinidad: Duration,
+        reintento: Duration,
+    ) -> AppResult<Self> {
+        if urls.is_empty() {
+            return Ok(self);
+        }
+
+        // Un archivo local no tiene réplicas
+        let motor = self.motor();
+        if motor == Motor::Sqlite {
+            return Err(AppError::Internal(
+                "SQLite no admite réplicas de lectura".to_string(),
+            ));
+        }
+        for url in urls {
+            if Motor::from_url(url)? != motor {
+                return Err(AppError::Internal(format!(
+                    "Las réplicas deben ser del mismo motor que la primaria ({})",
+                    motor.nombre()
+                )));
+            }
+        }
+        let url_invalida = |e: sqlx::Error| {
+            AppError::Internal(format!("URL de réplica inválida: {}", e))
+        };
+
+        let espera = opciones.espera_conexion.min(ESPERA_REPLICA);
+
+        let db = match self {
+            #[cfg(feature = "postgres")]
+            Database::Postgres(cluster) => {
+                let replicas = urls
+                    .iter()
+                    .map(|url| {
+                        let (pool, conexion) = postgres(url, opciones)?;
+                        Ok(pool.acquire_timeout(espera).connect_lazy_with(conexion))
+                    })
+                    .collect::<Result<_, sqlx::Error>>()
+                    .map_err(url_invalida)?;
+                Database::Postgres(Cluster::new(
+                    cluster.primaria().clone(),
+                    replicas,
+                    afinidad,
+                    reintento,
+                ))
+            }
+            #[cfg(feature = "mysql")]
+            Database::MySql(cluster) => {
+                let replicas = urls
+                    .iter()
+                    .map(|url| {
+                        let (pool, conexion) = mysql(url, opciones)?;
+                        Ok(pool.acquire_timeout(espera).connect_lazy_with(conexion))
+                    })
+                    .collect::<Result<_, sqlx::Error>>()
+                    .map_err(url_invalida)?;
+                Database::MySql(Cluster::new(
+                    cluster.primaria().clone(),
+                    replicas,
+                    afinidad,
+                    reintento,
                 ))
-            })?;
-
-        tracing::info!("✅ Conexión a base de datos pg establecida");
-
-        // let mysql_pool = MySqlPoolOptions::new()
-        //     .max_connections(max)
-        //     .connect(database_url)
-        //     .await
-        //     .map_err(|e| {
-        //         crate::errors::AppError::Internal(format!(
-        //             "No se pudo conectar a la base de datos MySQL: {}",
-        //             e
-        //         ))
-        //     })?;
-
-        Ok(Self { pg_pool })
-        // Ok(Self { mysql_pool })
+            }
+            // Descartado arriba
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(cluster) => Database::Sqlite(cluster),
+        };
+
+        tracing::info!(
+            "✅ {} réplicas de lectura configuradas para {}",
+            urls.len(),
+            motor.nombre()
+        );
+        Ok(db)
     }
 
-    /// Obtiene el pool interno
-    /// En Rust, esto se llama "consumir" el objeto
-    pub fn pg_pool(&self) -> &PgPool {
-        &self.pg_pool
+    pub fn motor(&self) -> Motor {
+        match self {
+            #[cfg(feature = "postgres")]
+            Database::Postgres(_) => Motor::Postgres,
+            #[cfg(feature = "mysql")]
+            Database::MySql(_) => Motor::MySql,
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(_) => Motor::Sqlite,
+        }
     }
 
-    // pub fn mysql_pool(&self) -> &MySqlPool {
-    //     &self.mysql_pool
-    // }
+    /// Uso de los pools (primaria y réplicas) y esperas por conexión
+    pub fn estadisticas(&self) -> EstadisticasDb {
+        let (primaria, replicas) = match self {
+            #[cfg(feature = "postgres")]
+            Database::Postgres(db) => db.estadisticas(),
+            #[cfg(feature = "mysql")]
+            Database::MySql(db) => db.estadisticas(),
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(db) => db.estadisticas(),
+        };
+        EstadisticasDb {
+            motor: self.motor().nombre(),
+            primaria,
+            replicas,
+        }
+    }
 
     /// Verifica que la conexión esté funcionando
     pub async fn ping(&self) -> AppResult<()> {
-        sqlx::query("SELECT 1")
-            .fetch_one(&self.pg_pool)
-            .await
-            .map_err(|e| {
-                tracing::error!("❌ PostgreSQL ping failed: {}", e);
-                crate::errors::AppError::Internal(format!("PostgreSQL connection failed: {}", e))
-            })?;
-
-        // sqlx::query("SELECT 1")
-        //     .fetch_one(&self.mysql_pool)
-        //     .await
-        //     .map_err(|e| {
-        //         tracing::error!("❌ MySQL ping failed: {}", e);
-        //         crate::errors::AppError::Internal(format!("MySQL connection failed: {}", e))
-        //     })?;
+        let resultado = match self {
+            #[cfg(feature = "postgres")]
+            Database::Postgres(db) => sqlx::query("SELECT 1").execute(db.primaria()).await.map(|_| ()),
+            #[cfg(feature = "mysql")]
+            Database::MySql(db) => sqlx::query("SELECT 1").execute(db.primaria()).await.map(|_| ()),
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(db) => sqlx::query("SELECT 1").execute(db.primaria()).await.map(|_| ()),
+        };
+
+        resultado.map_err(|e| {
+            tracing::error!("❌ {} ping failed: {}", self.motor().nombre(), e);
+            AppError::Internal(format!(
+                "{} connection failed: {}",
+                self.motor().nombre(),
+                e
+            ))
+        })?;
 
         tracing::info!("✅ Database connections verified");
         Ok(())
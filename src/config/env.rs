@@ -1,5 +1,10 @@
+use std::{net::IpAddr, time::Duration};
+
+use ipnet::IpNet;
 use serde::Deserialize;
 
+use super::OpcionesPool;
+
 /// Configuración de la aplicación cargada desde .env
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
@@ -9,12 +14,115 @@ pub struct Config {
     pub app_env: String,
     pub port: u16,
     pub database_url: String,
+    /// Réplicas de solo lectura del mismo motor, separadas por comas
+    pub database_replica_urls: Option<String>,
+    /// Milisegundos que un cliente lee de la primaria después de escribir; 2000 por defecto
+    pub db_replica_sticky_ms: Option<u64>,
+    /// Segundos que una réplica que no respondió queda fuera; 30 por defecto
+    pub db_replica_retry_secs: Option<u64>,
+    /// Máximo de conexiones por pool (primaria y cada réplica); 0: 10
     pub db_pool_size: u32,
+    /// Conexiones abiertas aunque no se usen; 0 por defecto
+    pub db_pool_min: Option<u32>,
+    /// Segundos de espera por una conexión libre; 30 por defecto y 0 no se admite
+    pub db_acquire_timeout_secs: Option<u64>,
+    /// Segundos tras los que se cierra una conexión inactiva (0: nunca); 600 por defecto
+    pub db_idle_timeout_secs: Option<u64>,
+    /// Segundos de vida de una conexión antes de reemplazarla (0: sin límite); 1800 por defecto
+    pub db_max_lifetime_secs: Option<u64>,
+    /// Milisegundos máximos por sentencia en el servidor; sin valor (o 0) no hay límite
+    pub db_statement_timeout_ms: Option<u64>,
+    /// Milisegundos desde los que una sentencia se registra como lenta; 1000 por defecto
+    pub db_slow_query_ms: Option<u64>,
     pub jwt_expiration_hours: u32,
     pub jwt_secret: String,
+    /// Almacenamiento de adjuntos: `local` (por defecto) o `s3`
+    pub storage_backend: Option<String>,
+    /// Directorio raíz del almacenamiento local
+    pub storage_path: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_region: Option<String>,
+    /// Endpoint de un servicio compatible con S3 (p. ej. MinIO)
+    pub s3_endpoint: Option<String>,
+    /// Región (ISO 3166-1) para los teléfonos escritos sin indicativo; `CO` por defecto
+    pub phone_default_region: Option<String>,
+    /// Cada cuántas horas buscar personas duplicadas; sin valor (o 0) no se programa
+    pub duplicate_scan_hours: Option<u64>,
+    /// Aplicar las migraciones pendientes al arrancar; desactivado por defecto
+    pub run_migrations: Option<bool>,
+    /// CSV DIVIPOLA del DANE que se carga al arrancar si el catálogo está vacío
+    pub divipola_csv: Option<String>,
+    /// IPs o redes (CIDR) de los proxies de confianza, separadas por comas
+    /// Solo si la conexión viene de uno de ellos se lee X-Forwarded-For
+    pub trusted_proxies: Option<String>,
   }
 
 impl Config {
+    /// URLs de `DATABASE_REPLICA_URLS` sin entradas vacías
+    pub fn replica_urls(&self) -> Vec<String> {
+        self.database_replica_urls
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Redes de `TRUSTED_PROXIES`; una IP suelta cuenta como red de una sola dirección
+    pub fn trusted_proxies(&self) -> Result<Vec<IpNet>, String> {
+        self.trusted_proxies
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|proxy| !proxy.is_empty())
+            .map(|proxy| {
+                proxy
+                    .parse::<IpNet>()
+                    .or_else(|_| proxy.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|_| format!("Proxy inválido: {}", proxy))
+            })
+            .collect()
+    }
+
+    /// Opciones de los pools de conexiones; lo que no se configuró queda por defecto
+    /// La espera por una conexión no puede ser 0: el pool exige un plazo y con 0 fallaría siempre
+    pub fn pool_options(&self) -> Result<OpcionesPool, String> {
+        let defecto = OpcionesPool::default();
+        let segundos = |valor: Option<u64>, defecto: Option<Duration>| match valor {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => defecto,
+        };
+
+        let espera_conexion = match self.db_acquire_timeout_secs {
+            Some(0) => return Err("DB_ACQUIRE_TIMEOUT_SECS debe ser mayor que 0".to_string()),
+            Some(secs) => Duration::from_secs(secs),
+            None => defecto.espera_conexion,
+        };
+
+        Ok(OpcionesPool {
+            max_conexiones: match self.db_pool_size {
+                0 => defecto.max_conexiones,
+                max => max,
+            },
+            min_conexiones: self.db_pool_min.unwrap_or(defecto.min_conexiones),
+            espera_conexion,
+            inactividad: segundos(self.db_idle_timeout_secs, defecto.inactividad),
+            vida_maxima: segundos(self.db_max_lifetime_secs, defecto.vida_maxima),
+            limite_sentencia: self
+                .db_statement_timeout_ms
+                .filter(|ms| *ms > 0)
+                .map(Duration::from_millis),
+            consulta_lenta: self
+                .db_slow_query_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defecto.consulta_lenta),
+        })
+    }
+
     /// Carga la configuración desde las variables de entorno
     /// Rust nos fuerza a manejar errores explícitamente - esto previene bugs
     pub fn from_env() -> Result<Self, config::ConfigError> {
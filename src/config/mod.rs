@@ -1,5 +1,9 @@
 mod env;
+mod cluster;
 mod database;
+mod pool;
 
 pub use env::Config;
-pub use database::Database;
+pub use cluster::{AFINIDAD_POR_DEFECTO, Cluster, REINTENTO_POR_DEFECTO, como_cliente};
+pub use database::{Database, Motor};
+pub use pool::{EstadisticasDb, EstadisticasPool, OpcionesPool};
@@ -0,0 +1,130 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use serde::Serialize;
+use sqlx::{Database, Pool, pool::PoolOptions};
+
+/// Tamaño y tiempos de los pools de conexiones (primaria y réplicas)
+#[derive(Debug, Clone)]
+pub struct OpcionesPool {
+    pub max_conexiones: u32,
+    /// Conexiones que el pool mantiene abiertas aunque no se usen
+    pub min_conexiones: u32,
+    /// Espera máxima por una conexión libre antes de fallar
+    pub espera_conexion: Duration,
+    /// Una conexión inactiva por más de esto se cierra; `None`: nunca
+    pub inactividad: Option<Duration>,
+    /// Una conexión con más antigüedad que esto se reemplaza; `None`: nunca
+    pub vida_maxima: Option<Duration>,
+    /// Límite de duración de cada sentencia en el servidor; `None`: sin límite
+    /// En MySQL solo aplica a SELECT y SQLite no lo soporta
+    pub limite_sentencia: Option<Duration>,
+    /// Las sentencias que tardan más que esto se registran como advertencia
+    pub consulta_lenta: Duration,
+}
+
+impl Default for OpcionesPool {
+    fn default() -> Self {
+        Self {
+            max_conexiones: 10,
+            min_conexiones: 0,
+            espera_conexion: Duration::from_secs(30),
+            inactividad: Some(Duration::from_secs(10 * 60)),
+            vida_maxima: Some(Duration::from_secs(30 * 60)),
+            limite_sentencia: None,
+            consulta_lenta: Duration::from_secs(1),
+        }
+    }
+}
+
+impl OpcionesPool {
+    /// Opciones de SQLx para un pool de cualquier motor
+    pub(crate) fn pool<DB: Database>(&self) -> PoolOptions<DB> {
+        PoolOptions::new()
+            .max_connections(self.max_conexiones)
+            .min_connections(self.min_conexiones.min(self.max_conexiones))
+            .acquire_timeout(self.espera_conexion)
+            .idle_timeout(self.inactividad)
+            .max_lifetime(self.vida_maxima)
+    }
+}
+
+/// Foto del uso de un pool, para dimensionarlo
+#[derive(Debug, Clone, Serialize)]
+pub struct EstadisticasPool {
+    /// Máximo de conexiones configurado
+    pub maximo: u32,
+    /// Conexiones abiertas (en uso + inactivas)
+    pub abiertas: u32,
+    pub en_uso: u32,
+    pub inactivas: u32,
+    /// Conexiones entregadas desde el arranque
+    pub adquisiciones: u64,
+    /// Esperas que terminaron en error (pool agotado o servidor caído)
+    pub fallidas: u64,
+    pub espera_media_ms: f64,
+    pub espera_maxima_ms: f64,
+    /// Falso si es una réplica fuera de servicio
+    pub disponible: bool,
+}
+
+/// Estadísticas de la base: la primaria y cada réplica en orden
+#[derive(Debug, Clone, Serialize)]
+pub struct EstadisticasDb {
+    pub motor: &'static str,
+    pub primaria: EstadisticasPool,
+    pub replicas: Vec<EstadisticasPool>,
+}
+
+/// Acumulado de las esperas por conexión de un pool
+#[derive(Debug, Default)]
+pub(crate) struct Metricas {
+    adquisiciones: AtomicU64,
+    fallidas: AtomicU64,
+    espera_total_us: AtomicU64,
+    espera_maxima_us: AtomicU64,
+}
+
+impl Metricas {
+    pub(crate) fn registrar(&self, espera: Duration, ok: bool) {
+        let espera = espera.as_micros().min(u64::MAX as u128) as u64;
+        if ok {
+            self.adquisiciones.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.fallidas.fetch_add(1, Ordering::Relaxed);
+        }
+        self.espera_total_us.fetch_add(espera, Ordering::Relaxed);
+        self.espera_maxima_us.fetch_max(espera, Ordering::Relaxed);
+    }
+
+    pub(crate) fn estadisticas<DB: Database>(
+        &self,
+        pool: &Pool<DB>,
+        disponible: bool,
+    ) -> EstadisticasPool {
+        let adquisiciones = self.adquisiciones.load(Ordering::Relaxed);
+        let fallidas = self.fallidas.load(Ordering::Relaxed);
+        let espera_total_us = self.espera_total_us.load(Ordering::Relaxed);
+        let esperas = adquisiciones + fallidas;
+
+        let abiertas = pool.size();
+        let inactivas = pool.num_idle() as u32;
+        EstadisticasPool {
+            maximo: pool.options().get_max_connections(),
+            abiertas,
+            en_uso: abiertas.saturating_sub(inactivas),
+            inactivas,
+            adquisiciones,
+            fallidas,
+            espera_media_ms: if esperas == 0 {
+                0.0
+            } else {
+                espera_total_us as f64 / esperas as f64 / 1000.0
+            },
+            espera_maxima_ms: self.espera_maxima_us.load(Ordering::Relaxed) as f64 / 1000.0,
+            disponible,
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use crate::{
+    domain::{
+        AccesoFilter, AccesoPersona, AuthUser, DatosSensibles, NuevoAcceso, RequestContext,
+        db::AccesoRepository,
+    },
+    errors::AppResult,
+};
+
+/// Personas leídas en una sola petición a partir de las cuales se alerta
+const UMBRAL_POR_PETICION: usize = 500;
+
+/// Personas leídas por un mismo actor en la ventana a partir de las cuales se alerta
+const UMBRAL_POR_VENTANA: i64 = 1000;
+
+/// Ventana de tiempo del umbral por actor
+const VENTANA_MINUTOS: i64 = 60;
+
+/// Tamaño de los lotes que se escriben mientras avanza una exportación
+const TAMANO_LOTE: usize = 1000;
+
+/// Servicio del registro de lecturas de datos sensibles de personas
+pub struct AccesoService {
+    repo: Arc<dyn AccesoRepository>,
+}
+
+impl AccesoService {
+    pub fn new(repo: Arc<dyn AccesoRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Registra que `actor` leyó estas personas y evalúa las alertas
+    /// Igual que la auditoría, un fallo se reporta en el log sin afectar la petición
+    pub async fn registrar<'a>(
+        &self,
+        actor: &AuthUser,
+        contexto: &RequestContext,
+        via: &str,
+        personas: impl IntoIterator<Item = &'a (impl DatosSensibles + 'a)>,
+    ) {
+        let accesos = nuevos_accesos(actor.idper, contexto, via, personas);
+        let total = accesos.len();
+        if total == 0 {
+            return;
+        }
+
+        self.guardar(accesos).await;
+        self.evaluar(actor.idper, &actor.nomper, via, total).await;
+    }
+
+    /// Acumulador para lecturas que llegan de a una (exportaciones en streaming)
+    /// Escribe por lotes y al soltarse registra lo pendiente
+    pub fn lote(
+        self: &Arc<Self>,
+        actor: &AuthUser,
+        contexto: RequestContext,
+        via: &str,
+    ) -> LoteAccesos {
+        LoteAccesos {
+            servicio: self.clone(),
+            idactor: actor.idper,
+            nomactor: actor.nomper.clone(),
+            contexto,
+            via: via.to_string(),
+            pendientes: Vec::new(),
+            total: 0,
+        }
+    }
+
+    /// Quién consultó a una persona
+    pub async fn por_persona(
+        &self,
+        idper: i64,
+        mut filtro: AccesoFilter,
+    ) -> AppResult<Vec<AccesoPersona>> {
+        filtro.idper = Some(idper);
+        self.repo.buscar(filtro).await
+    }
+
+    /// Qué personas consultó un actor
+    pub async fn por_actor(
+        &self,
+        idactor: i64,
+        mut filtro: AccesoFilter,
+    ) -> AppResult<Vec<AccesoPersona>> {
+        filtro.idactor = Some(idactor);
+        self.repo.buscar(filtro).await
+    }
+
+    async fn guardar(&self, accesos: Vec<NuevoAcceso>) {
+        let total = accesos.len();
+        if let Err(e) = self.repo.registrar(accesos).await {
+            tracing::error!(
+                "No se pudieron registrar {} lecturas de personas: {:?}",
+                total,
+                e
+            );
+        }
+    }
+
+    /// Alerta sobre lecturas masivas en una petición o acumuladas en la ventana
+    async fn evaluar(&self, idactor: i64, nomactor: &str, via: &str, total: usize) {
+        if total > UMBRAL_POR_PETICION {
+            tracing::warn!(
+                target: "seguridad",
+                "Acceso masivo: {} (idper {}) leyó {} personas en una sola petición ({})",
+                nomactor,
+                idactor,
+                total,
+                via
+            );
+        }
+
+        let desde = Utc::now() - Duration::minutes(VENTANA_MINUTOS);
+        match self.repo.contar_por_actor(idactor, desde).await {
+            Ok(leidas) if leidas > UMBRAL_POR_VENTANA => tracing::warn!(
+                target: "seguridad",
+                "Acceso inusual: {} (idper {}) leyó {} personas en los últimos {} minutos",
+                nomactor,
+                idactor,
+                leidas,
+                VENTANA_MINUTOS
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::error!("No se pudo evaluar el volumen de lecturas: {:?}", e),
+        }
+    }
+}
+
+/// Lecturas pendientes de registrar durante una respuesta en streaming
+pub struct LoteAccesos {
+    servicio: Arc<AccesoService>,
+    idactor: i64,
+    nomactor: String,
+    contexto: RequestContext,
+    via: String,
+    pendientes: Vec<NuevoAcceso>,
+    total: usize,
+}
+
+impl LoteAccesos {
+    pub fn anotar(&mut self, persona: &impl DatosSensibles) {
+        self.pendientes.extend(nuevos_accesos(
+            self.idactor,
+            &self.contexto,
+            &self.via,
+            [persona],
+        ));
+        self.total += 1;
+
+        if self.pendientes.len() >= TAMANO_LOTE {
+            let servicio = self.servicio.clone();
+            let accesos = std::mem::take(&mut self.pendientes);
+            tokio::spawn(async move { servicio.guardar(accesos).await });
+        }
+    }
+}
+
+impl Drop for LoteAccesos {
+    fn drop(&mut self) {
+        if self.total == 0 {
+            return;
+        }
+
+        // El stream terminó (o el cliente se desconectó): lo leído hasta aquí queda registrado
+        let servicio = self.servicio.clone();
+        let accesos = std::mem::take(&mut self.pendientes);
+        let (idactor, nomactor, via, total) = (
+            self.idactor,
+            std::mem::take(&mut self.nomactor),
+            std::mem::take(&mut self.via),
+            self.total,
+        );
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    servicio.guardar(accesos).await;
+                    servicio.evaluar(idactor, &nomactor, &via, total).await;
+                });
+            }
+            Err(_) => tracing::error!(
+                "Sin runtime para registrar {} lecturas de {}",
+                accesos.len(),
+                nomactor
+            ),
+        }
+    }
+}
+
+fn nuevos_accesos<'a>(
+    idactor: i64,
+    contexto: &RequestContext,
+    via: &str,
+    personas: impl IntoIterator<Item = &'a (impl DatosSensibles + 'a)>,
+) -> Vec<NuevoAcceso> {
+    let fecacc = Utc::now();
+    personas
+        .into_iter()
+        .map(|persona| NuevoAcceso {
+            idactor,
+            idper: persona.idper(),
+            via: via.to_string(),
+            campos: persona.campos_sensibles(),
+            contexto: contexto.clone(),
+            fecacc,
+        })
+        .collect()
+}
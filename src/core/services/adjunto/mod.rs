@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    domain::{
+        Adjunto, CategoriaAdjunto, NuevoAdjunto, db::AdjuntoRepository, storage::ObjectStorage,
+    },
+    errors::{AppError, AppResult},
+};
+
+/// Tamaño máximo de un adjunto
+pub const MAX_TAMANO_ADJUNTO: usize = 10 * 1024 * 1024;
+
+/// Tipos de contenido aceptados (se verifican contra los bytes, no contra lo declarado)
+const TIPOS_PERMITIDOS: [&str; 4] = ["application/pdf", "image/jpeg", "image/png", "image/webp"];
+
+/// Archivo recibido en la petición, aún sin validar
+pub struct ArchivoSubido {
+    pub nombre: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Bytes,
+}
+
+/// Servicio de archivos adjuntos a personas
+pub struct AdjuntoService {
+    repo: Arc<dyn AdjuntoRepository>,
+    storage: Arc<dyn ObjectStorage>,
+}
+
+impl AdjuntoService {
+    pub fn new(repo: Arc<dyn AdjuntoRepository>, storage: Arc<dyn ObjectStorage>) -> Self {
+        Self { repo, storage }
+    }
+
+    /// Valida el archivo, lo guarda en el almacenamiento y registra sus metadatos
+    pub async fn subir(
+        &self,
+        idper: i64,
+        catadj: CategoriaAdjunto,
+        archivo: ArchivoSubido,
+        idsubidor: i64,
+    ) -> AppResult<Adjunto> {
+        let (tipadj, extension) = validar(&catadj, &archivo)?;
+        let clvadj = format!("personas/{}/{}.{}", idper, uuid::Uuid::new_v4(), extension);
+        let nuevo = NuevoAdjunto {
+            idper,
+            catadj,
+            nomadj: nombre_seguro(archivo.nombre.as_deref(), extension),
+            tipadj: tipadj.to_string(),
+            tamadj: archivo.data.len() as i64,
+            hashadj: hex::encode(Sha256::digest(&archivo.data)),
+            clvadj: clvadj.clone(),
+            idsubidor,
+            fecadj: Utc::now(),
+        };
+
+        self.storage.put(&clvadj, tipadj, archivo.data).await?;
+        match self.repo.create(nuevo).await {
+            Ok(adjunto) => Ok(adjunto),
+            Err(e) => {
+                // Sin metadatos el objeto quedaría huérfano
+                if let Err(e) = self.storage.delete(&clvadj).await {
+                    tracing::error!("No se pudo limpiar el objeto huérfano {}: {:?}", clvadj, e);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn listar(&self, idper: i64) -> AppResult<Vec<Adjunto>> {
+        self.repo.list_by_persona(idper).await
+    }
+
+    pub async fn get(&self, idper: i64, idadj: i64) -> AppResult<Adjunto> {
+        self.repo
+            .get(idper, idadj)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Adjunto no encontrado".to_string()))
+    }
+
+    /// Metadatos y contenido de un adjunto
+    pub async fn descargar(&self, idper: i64, idadj: i64) -> AppResult<(Adjunto, Bytes)> {
+        let adjunto = self.get(idper, idadj).await?;
+        let data = self.storage.get(&adjunto.clvadj).await?;
+        Ok((adjunto, data))
+    }
+
+    /// Elimina los metadatos y luego el objeto
+    /// Si el objeto no se puede borrar queda en el log para limpiarlo después
+    pub async fn eliminar(&self, idper: i64, idadj: i64) -> AppResult<Adjunto> {
+        let adjunto = self.get(idper, idadj).await?;
+        if !self.repo.delete(idper, idadj).await? {
+            return Err(AppError::NotFound("Adjunto no encontrado".to_string()));
+        }
+
+        if let Err(e) = self.storage.delete(&adjunto.clvadj).await {
+            tracing::error!(
+                "Adjunto {} eliminado pero su objeto {} sigue en el almacenamiento: {:?}",
+                idadj,
+                adjunto.clvadj,
+                e
+            );
+        }
+        Ok(adjunto)
+    }
+}
+
+/// Tipo de contenido real y extensión del archivo
+fn validar(
+    catadj: &CategoriaAdjunto,
+    archivo: &ArchivoSubido,
+) -> AppResult<(&'static str, &'static str)> {
+    if archivo.data.is_empty() {
+        return Err(AppError::validation("file", "El archivo está vacío"));
+    }
+    if archivo.data.len() > MAX_TAMANO_ADJUNTO {
+        return Err(AppError::validation(
+            "file",
+            "El archivo supera el tamaño máximo de 10 MB",
+        ));
+    }
+
+    let detectado = infer::get(&archivo.data)
+        .filter(|tipo| TIPOS_PERMITIDOS.contains(&tipo.mime_type()))
+        .ok_or_else(|| {
+            AppError::validation(
+                "file",
+                "Tipo de archivo no permitido, use PDF, JPEG, PNG o WEBP",
+            )
+        })?;
+
+    // Lo declarado por el cliente debe coincidir con el contenido
+    let declarado = archivo
+        .content_type
+        .as_deref()
+        .map(|c| c.split(';').next().unwrap_or(c).trim().to_lowercase())
+        .filter(|c| c != "application/octet-stream");
+    if declarado.is_some_and(|c| c != detectado.mime_type()) {
+        return Err(AppError::validation(
+            "file",
+            "El contenido del archivo no corresponde al tipo declarado",
+        ));
+    }
+
+    if *catadj == CategoriaAdjunto::Foto && !detectado.mime_type().starts_with("image/") {
+        return Err(AppError::validation("file", "Una foto debe ser una imagen"));
+    }
+
+    Ok((detectado.mime_type(), detectado.extension()))
+}
+
+/// Nombre para mostrar: sin rutas ni caracteres de control, con un máximo de 255 caracteres
+fn nombre_seguro(nombre: Option<&str>, extension: &str) -> String {
+    let base = nombre
+        .and_then(|n| n.rsplit(['/', '\\']).next())
+        .unwrap_or_default();
+    let limpio: String = base
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ' '))
+        .take(255)
+        .collect();
+    let limpio = limpio.trim().trim_start_matches('.');
+
+    if limpio.is_empty() {
+        format!("archivo.{}", extension)
+    } else {
+        limpio.to_string()
+    }
+}
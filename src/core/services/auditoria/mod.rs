@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    domain::{
+        AuditoriaFilter, AuthUser, NuevaAuditoria, RegistroAuditoria, RequestContext,
+        db::AuditoriaRepository,
+    },
+    errors::AppResult,
+};
+
+/// Campos que nunca se guardan en el registro (hashes, secretos)
+const CAMPOS_OCULTOS: [&str; 1] = ["pass"];
+
+/// Datos personales: el registro no se borra nunca, así que de ellos solo queda
+/// constancia de que estaban o cambiaron, no su valor (una supresión no puede
+/// dejar copia en la auditoría)
+const CAMPOS_PERSONALES: [&str; 8] = [
+    "ndocper", "nomper", "apeper", "dirper", "telper", "telnor", "emaper", "avaper",
+];
+
+/// Lo que se guarda en lugar de un dato personal
+const REDACTADO: &str = "[redactado]";
+
+/// Operación por auditar; las fotos antes/después se serializan al construirla
+#[derive(Debug, Clone)]
+pub struct EventoAuditoria {
+    accion: String,
+    entidad: String,
+    identidad: String,
+    antes: Option<Value>,
+    despues: Option<Value>,
+}
+
+impl EventoAuditoria {
+    pub fn create(entidad: &str, identidad: impl ToString, despues: &impl Serialize) -> Self {
+        Self::new("create", entidad, identidad).despues(despues)
+    }
+
+    pub fn update(
+        entidad: &str,
+        identidad: impl ToString,
+        antes: &impl Serialize,
+        despues: &impl Serialize,
+    ) -> Self {
+        Self::new("update", entidad, identidad)
+            .antes(antes)
+            .despues(despues)
+    }
+
+    pub fn delete(entidad: &str, identidad: impl ToString, antes: &impl Serialize) -> Self {
+        Self::new("delete", entidad, identidad).antes(antes)
+    }
+
+    /// Operación con nombre propio (suspender, anonimizar, importar...)
+    pub fn new(accion: &str, entidad: &str, identidad: impl ToString) -> Self {
+        Self {
+            accion: accion.to_string(),
+            entidad: entidad.to_string(),
+            identidad: identidad.to_string(),
+            antes: None,
+            despues: None,
+        }
+    }
+
+    pub fn antes(mut self, antes: &impl Serialize) -> Self {
+        self.antes = snapshot(antes);
+        self
+    }
+
+    pub fn despues(mut self, despues: &impl Serialize) -> Self {
+        self.despues = snapshot(despues);
+        self
+    }
+}
+
+/// Servicio del registro de auditoría de escrituras
+pub struct AuditoriaService {
+    repo: Arc<dyn AuditoriaRepository>,
+}
+
+impl AuditoriaService {
+    pub fn new(repo: Arc<dyn AuditoriaRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Registra una operación ya ejecutada
+    /// Un fallo aquí no deshace la operación: se reporta en el log y la petición sigue
+    pub async fn registrar(
+        &self,
+        actor: &AuthUser,
+        contexto: &RequestContext,
+        evento: EventoAuditoria,
+    ) {
+        // El diff se calcula sobre los valores reales para detectar qué cambió
+        let cambios = match (&evento.antes, &evento.despues) {
+            (Some(antes), Some(despues)) => Some(redactar(diff(antes, despues))),
+            _ => None,
+        };
+
+        let entrada = NuevaAuditoria {
+            idactor: actor.idper,
+            accion: evento.accion,
+            entidad: evento.entidad,
+            identidad: evento.identidad,
+            antes: evento.antes.map(redactar),
+            despues: evento.despues.map(redactar),
+            cambios,
+            contexto: contexto.clone(),
+            fecaud: Utc::now(),
+        };
+
+        if let Err(e) = self.repo.registrar(entrada.clone()).await {
+            tracing::error!(
+                "No se pudo registrar la auditoría de {} {} {} (solicitud {:?}): {:?}",
+                entrada.accion,
+                entrada.entidad,
+                entrada.identidad,
+                entrada.contexto.request_id,
+                e
+            );
+        }
+    }
+
+    pub async fn buscar(&self, filtro: AuditoriaFilter) -> AppResult<Vec<RegistroAuditoria>> {
+        self.repo.buscar(filtro).await
+    }
+}
+
+/// Serializa y quita los campos ocultos
+fn snapshot(valor: &impl Serialize) -> Option<Value> {
+    match serde_json::to_value(valor) {
+        Ok(mut valor) => {
+            if let Value::Object(campos) = &mut valor {
+                for campo in CAMPOS_OCULTOS {
+                    campos.remove(campo);
+                }
+            }
+            Some(valor)
+        }
+        Err(e) => {
+            tracing::error!("No se pudo serializar la foto de auditoría: {}", e);
+            None
+        }
+    }
+}
+
+/// Reemplaza los datos personales de cualquier nivel por una marca
+/// En un diff el campo conserva su nombre: se sabe que cambió, no cómo
+fn redactar(mut valor: Value) -> Value {
+    match &mut valor {
+        Value::Object(campos) => {
+            for (campo, valor) in campos.iter_mut() {
+                if CAMPOS_PERSONALES.contains(&campo.as_str()) {
+                    if !valor.is_null() {
+                        *valor = Value::String(REDACTADO.to_string());
+                    }
+                } else {
+                    *valor = redactar(valor.take());
+                }
+            }
+        }
+        Value::Array(elementos) => {
+            for elemento in elementos.iter_mut() {
+                *elemento = redactar(elemento.take());
+            }
+        }
+        _ => {}
+    }
+    valor
+}
+
+/// Campos de primer nivel que cambiaron: `{campo: {antes, despues}}`
+fn diff(antes: &Value, despues: &Value) -> Value {
+    let (Value::Object(antes), Value::Object(despues)) = (antes, despues) else {
+        return json!({ "antes": antes, "despues": despues });
+    };
+
+    let mut cambios = Map::new();
+    for (campo, nuevo) in despues {
+        let anterior = antes.get(campo).unwrap_or(&Value::Null);
+        if anterior != nuevo {
+            cambios.insert(
+                campo.clone(),
+                json!({ "antes": anterior, "despues": nuevo }),
+            );
+        }
+    }
+    for (campo, anterior) in antes {
+        if !despues.contains_key(campo) {
+            cambios.insert(
+                campo.clone(),
+                json!({ "antes": anterior, "despues": Value::Null }),
+            );
+        }
+    }
+
+    Value::Object(cambios)
+}
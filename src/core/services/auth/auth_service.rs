@@ -1,18 +1,17 @@
 use std::sync::Arc;
 
-use crate::{core::services::persona::PersonaService, domain::db::PersonaRepository};
+use crate::core::services::persona::PersonaService;
 
 pub struct AuthService {
-    pub persona_service: PersonaService,
+    pub persona_service: Arc<PersonaService>,
 }
 
 impl AuthService {
-    pub fn new(persona_repo: Arc<dyn PersonaRepository>) -> Self {
-        let persona_service = PersonaService::new(persona_repo);
+    pub fn new(persona_service: Arc<PersonaService>) -> Self {
         Self { persona_service }
     }
 
-    pub fn get_permissions(&self, idpef: i64) -> Vec<String> {
+    pub fn get_permissions(&self, _idpef: i64) -> Vec<String> {
         // Lógica para obtener permisos basada en el idper
         // Por simplicidad, retornamos permisos estáticos
         vec!["read".to_string(), "write".to_string()]
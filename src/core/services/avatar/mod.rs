@@ -0,0 +1,218 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use image::{
+    DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, Rgb, RgbImage,
+    codecs::jpeg::JpegEncoder, imageops::FilterType,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    domain::{Persona, db::PersonaRepository, storage::ObjectStorage},
+    errors::{AppError, AppResult},
+};
+
+/// Tamaño máximo de la imagen original
+pub const MAX_TAMANO_AVATAR: usize = 5 * 1024 * 1024;
+
+/// Dimensión máxima (ancho o alto) que se acepta decodificar
+const MAX_DIMENSION: u32 = 8000;
+
+/// Calidad JPEG de las miniaturas
+const CALIDAD_JPEG: u8 = 85;
+
+/// Tamaños en los que se guarda cada avatar (cuadrados)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TamanoAvatar {
+    Pequeno,
+    #[default]
+    Mediano,
+    Grande,
+}
+
+impl TamanoAvatar {
+    pub const TODOS: [TamanoAvatar; 3] = [Self::Pequeno, Self::Mediano, Self::Grande];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pequeno => "pequeno",
+            Self::Mediano => "mediano",
+            Self::Grande => "grande",
+        }
+    }
+
+    /// Lado en píxeles
+    pub fn pixeles(&self) -> u32 {
+        match self {
+            Self::Pequeno => 64,
+            Self::Mediano => 256,
+            Self::Grande => 512,
+        }
+    }
+}
+
+/// Servicio de avatares de persona
+/// Las miniaturas se generan una sola vez al subir la imagen y se guardan
+/// bajo una clave que incluye la versión, así que nunca cambian
+pub struct AvatarService {
+    persona_repo: Arc<dyn PersonaRepository>,
+    storage: Arc<dyn ObjectStorage>,
+}
+
+impl AvatarService {
+    pub fn new(persona_repo: Arc<dyn PersonaRepository>, storage: Arc<dyn ObjectStorage>) -> Self {
+        Self {
+            persona_repo,
+            storage,
+        }
+    }
+
+    /// Procesa la imagen, guarda sus miniaturas y la deja como avatar vigente
+    /// Devuelve la persona con la nueva versión del avatar
+    pub async fn actualizar(&self, idper: i64, data: Bytes) -> AppResult<Persona> {
+        let anterior = self.persona(idper).await?.avaper;
+        if data.is_empty() {
+            return Err(AppError::validation("avatar", "La imagen está vacía"));
+        }
+        if data.len() > MAX_TAMANO_AVATAR {
+            return Err(AppError::validation(
+                "avatar",
+                "La imagen supera el tamaño máximo de 5 MB",
+            ));
+        }
+
+        // Misma imagen, misma versión: una resubida no invalida las cachés
+        let version = hex::encode(&Sha256::digest(&data)[..8]);
+        let miniaturas = tokio::task::spawn_blocking(move || procesar(&data))
+            .await
+            .map_err(|e| AppError::Internal(format!("Error al procesar la imagen: {}", e)))??;
+
+        for (tamano, jpeg) in miniaturas {
+            self.storage
+                .put(&clave(idper, &version, tamano), "image/jpeg", jpeg)
+                .await?;
+        }
+        let persona = self.persona_repo.set_avatar(idper, Some(&version)).await?;
+
+        if let Some(anterior) = anterior.filter(|a| Some(a) != persona.avaper.as_ref()) {
+            self.descartar(idper, &anterior).await;
+        }
+        Ok(persona)
+    }
+
+    /// Miniatura del avatar vigente y su versión
+    pub async fn obtener(&self, idper: i64, tamano: TamanoAvatar) -> AppResult<(String, Bytes)> {
+        let version = self
+            .persona(idper)
+            .await?
+            .avaper
+            .ok_or_else(|| AppError::NotFound("La persona no tiene avatar".to_string()))?;
+        let data = self.storage.get(&clave(idper, &version, tamano)).await?;
+        Ok((version, data))
+    }
+
+    /// Quita el avatar vigente y borra sus miniaturas
+    pub async fn eliminar(&self, idper: i64) -> AppResult<Persona> {
+        let version = self
+            .persona(idper)
+            .await?
+            .avaper
+            .ok_or_else(|| AppError::NotFound("La persona no tiene avatar".to_string()))?;
+        let persona = self.persona_repo.set_avatar(idper, None).await?;
+        self.descartar(idper, &version).await;
+        Ok(persona)
+    }
+
+    /// Borra las miniaturas de una versión; los fallos quedan en el log
+    pub async fn descartar(&self, idper: i64, version: &str) {
+        for tamano in TamanoAvatar::TODOS {
+            let clave = clave(idper, version, tamano);
+            if let Err(e) = self.storage.delete(&clave).await {
+                tracing::error!("No se pudo borrar la miniatura {}: {:?}", clave, e);
+            }
+        }
+    }
+
+    async fn persona(&self, idper: i64) -> AppResult<Persona> {
+        self.persona_repo
+            .get_by_idper(idper)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))
+    }
+}
+
+fn clave(idper: i64, version: &str, tamano: TamanoAvatar) -> String {
+    format!(
+        "personas/{}/avatar/{}/{}.jpg",
+        idper,
+        version,
+        tamano.as_str()
+    )
+}
+
+/// Decodifica, corrige la orientación, recorta al cuadrado central y genera
+/// las miniaturas en JPEG
+/// Al volver a codificar se descartan los metadatos EXIF (ubicación, cámara, etc.)
+fn procesar(data: &[u8]) -> AppResult<Vec<(TamanoAvatar, Bytes)>> {
+    let invalida =
+        || AppError::validation("avatar", "La imagen no es válida, use JPEG, PNG o WEBP");
+
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|_| invalida())?;
+    if !matches!(
+        reader.format(),
+        Some(ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP)
+    ) {
+        return Err(invalida());
+    }
+
+    // Evita que una imagen pequeña en bytes agote la memoria al decodificarse
+    let mut limites = Limits::default();
+    limites.max_image_width = Some(MAX_DIMENSION);
+    limites.max_image_height = Some(MAX_DIMENSION);
+    reader.limits(limites);
+
+    let mut decoder = reader.into_decoder().map_err(|_| invalida())?;
+    let orientacion = decoder.orientation().map_err(|_| invalida())?;
+    let mut imagen = DynamicImage::from_decoder(decoder).map_err(|_| invalida())?;
+    imagen.apply_orientation(orientacion);
+
+    let lado = imagen.width().min(imagen.height());
+    let cuadrado = imagen.crop_imm(
+        (imagen.width() - lado) / 2,
+        (imagen.height() - lado) / 2,
+        lado,
+        lado,
+    );
+    let cuadrado = DynamicImage::ImageRgb8(sobre_blanco(&cuadrado));
+
+    TamanoAvatar::TODOS
+        .into_iter()
+        .map(|tamano| {
+            // No se amplían imágenes más pequeñas que la miniatura
+            let pixeles = tamano.pixeles().min(lado);
+            let miniatura = cuadrado.resize_exact(pixeles, pixeles, FilterType::Lanczos3);
+
+            let mut salida = Vec::new();
+            miniatura
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut salida, CALIDAD_JPEG))
+                .map_err(|e| AppError::Internal(format!("Error al codificar la imagen: {}", e)))?;
+            Ok((tamano, Bytes::from(salida)))
+        })
+        .collect()
+}
+
+/// JPEG no tiene transparencia: los píxeles transparentes se funden con blanco
+fn sobre_blanco(imagen: &DynamicImage) -> RgbImage {
+    let rgba = imagen.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let alfa = a as u16;
+        let fundir = |c: u8| ((c as u16 * alfa + 255 * (255 - alfa)) / 255) as u8;
+        Rgb([fundir(r), fundir(g), fundir(b)])
+    })
+}
@@ -0,0 +1,171 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    domain::{
+        CATALOGO_TIPO_DOCUMENTO, CATALOGOS, CatalogDef, CatalogItem, TipoDocumento,
+        cache::CacheRepository, db::CatalogRepository,
+    },
+    errors::{AppError, AppResult},
+};
+
+/// Los catálogos casi no cambian y el CRUD invalida su entrada
+const CACHE_TTL_SECONDS: usize = 3600;
+
+/// Contenido de un catálogo junto con su ETag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogSnapshot {
+    pub etag: String,
+    pub items: Vec<CatalogItem>,
+}
+
+/// Servicio genérico de catálogos con caché de lectura
+pub struct CatalogService<C: CacheRepository> {
+    repo: Arc<dyn CatalogRepository>,
+    cache: Arc<C>,
+}
+
+impl<C: CacheRepository> CatalogService<C> {
+    pub fn new(repo: Arc<dyn CatalogRepository>, cache: Arc<C>) -> Self {
+        Self { repo, cache }
+    }
+
+    /// Nombres de los catálogos disponibles
+    pub fn nombres(&self) -> Vec<&'static str> {
+        CATALOGOS.iter().map(|c| c.name).collect()
+    }
+
+    /// Lista un catálogo (desde caché si está disponible)
+    pub async fn list(&self, name: &str) -> AppResult<CatalogSnapshot> {
+        let catalogo = definicion(name)?;
+        let key = cache_key(catalogo.name);
+
+        if let Some(snapshot) = self.cache.get::<CatalogSnapshot>(&key).await? {
+            tracing::debug!("Catálogo obtenido desde caché: {}", catalogo.name);
+            return Ok(snapshot);
+        }
+
+        let items = self.repo.list(catalogo).await?;
+        let snapshot = CatalogSnapshot {
+            etag: calcular_etag(&items),
+            items,
+        };
+        self.cache.set(&key, &snapshot, CACHE_TTL_SECONDS).await?;
+        Ok(snapshot)
+    }
+
+    /// Mapa id → nombre para mostrar etiquetas en otras respuestas
+    /// Si el catálogo de tipos de documento no está disponible se usan los nombres fijos
+    pub async fn labels(&self, name: &str) -> HashMap<i64, String> {
+        match self.list(name).await {
+            Ok(snapshot) => snapshot
+                .items
+                .into_iter()
+                .map(|item| (item.id, item.nombre))
+                .collect(),
+            Err(e) => {
+                tracing::warn!("No se pudo cargar el catálogo {}: {}", name, e);
+                if name == CATALOGO_TIPO_DOCUMENTO {
+                    TipoDocumento::TODOS
+                        .iter()
+                        .map(|t| (t.code(), t.nombre().to_string()))
+                        .collect()
+                } else {
+                    HashMap::new()
+                }
+            }
+        }
+    }
+
+    /// Un elemento concreto, leído sin pasar por el caché
+    pub async fn get(&self, name: &str, id: i64) -> AppResult<CatalogItem> {
+        let catalogo = definicion(name)?;
+        self.repo
+            .get(catalogo, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Elemento de catálogo no encontrado".to_string()))
+    }
+
+    pub async fn create(&self, name: &str, item: CatalogItem) -> AppResult<CatalogItem> {
+        let catalogo = editable(name)?;
+        validar_item(&item)?;
+
+        if self.repo.get(catalogo, item.id).await?.is_some() {
+            return Err(AppError::validation(
+                "id",
+                "Ya existe un elemento con ese id en el catálogo",
+            ));
+        }
+
+        let creado = self.repo.create(catalogo, item).await?;
+        self.invalidar(catalogo).await?;
+        Ok(creado)
+    }
+
+    pub async fn update(&self, name: &str, id: i64, item: CatalogItem) -> AppResult<CatalogItem> {
+        let catalogo = editable(name)?;
+        validar_item(&item)?;
+
+        let actualizado = self.repo.update(catalogo, id, item).await?;
+        self.invalidar(catalogo).await?;
+        Ok(actualizado)
+    }
+
+    pub async fn delete(&self, name: &str, id: i64) -> AppResult<()> {
+        let catalogo = editable(name)?;
+
+        self.repo.delete(catalogo, id).await?;
+        self.invalidar(catalogo).await
+    }
+
+    async fn invalidar(&self, catalogo: &CatalogDef) -> AppResult<()> {
+        tracing::info!("Catálogo {} modificado, se invalida el caché", catalogo.name);
+        self.cache.delete(&cache_key(catalogo.name)).await
+    }
+}
+
+fn cache_key(name: &str) -> String {
+    format!("catalog:{}", name)
+}
+
+fn definicion(name: &str) -> AppResult<&'static CatalogDef> {
+    CatalogDef::find(name)
+        .ok_or_else(|| AppError::NotFound(format!("Catálogo '{}' no existe", name)))
+}
+
+fn editable(name: &str) -> AppResult<&'static CatalogDef> {
+    let catalogo = definicion(name)?;
+    if !catalogo.editable {
+        return Err(AppError::Forbidden(format!(
+            "El catálogo '{}' es de solo lectura",
+            name
+        )));
+    }
+    Ok(catalogo)
+}
+
+fn validar_item(item: &CatalogItem) -> AppResult<()> {
+    if item.id <= 0 {
+        return Err(AppError::validation("id", "El id debe ser positivo"));
+    }
+    if item.nombre.trim().is_empty() {
+        return Err(AppError::validation("nombre", "El nombre es requerido"));
+    }
+    Ok(())
+}
+
+/// ETag fuerte a partir del contenido: cambia solo si cambian los datos
+/// SHA-256 y no `DefaultHasher`, que puede cambiar entre versiones de Rust
+fn calcular_etag(items: &[CatalogItem]) -> String {
+    let mut hasher = Sha256::new();
+    for item in items {
+        hasher.update(item.id.to_le_bytes());
+        // El largo separa los nombres: ("ab", "c") y ("a", "bc") no coinciden
+        hasher.update((item.nombre.len() as u64).to_le_bytes());
+        hasher.update(item.nombre.as_bytes());
+        hasher.update([item.activo as u8]);
+    }
+    format!("\"{}\"", hex::encode(&hasher.finalize()[..16]))
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    domain::{
+        CanalConsentimiento, Consentimiento, PersonaFilter, PoliticaDatos,
+        db::ConsentimientoRepository,
+    },
+    errors::{AppError, AppResult},
+};
+
+/// Situación del consentimiento de una persona frente a la política vigente
+#[derive(Debug, Serialize)]
+pub struct EstadoConsentimiento {
+    /// Si aceptó la política vigente y no la ha revocado
+    pub vigente: bool,
+    pub politica: Option<PoliticaDatos>,
+    pub historial: Vec<Consentimiento>,
+}
+
+/// Servicio de consentimiento para el tratamiento de datos personales
+pub struct ConsentimientoService {
+    repo: Arc<dyn ConsentimientoRepository>,
+}
+
+impl ConsentimientoService {
+    pub fn new(repo: Arc<dyn ConsentimientoRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Política que rige hoy
+    pub async fn politica_vigente(&self) -> AppResult<Option<PoliticaDatos>> {
+        self.repo.get_politica_vigente(Utc::now()).await
+    }
+
+    pub async fn politicas(&self) -> AppResult<Vec<PoliticaDatos>> {
+        self.repo.list_politicas().await
+    }
+
+    /// Publica una nueva versión; sin fecha rige desde ya
+    /// Al entrar en vigencia, los consentimientos de versiones anteriores dejan de contar
+    pub async fn publicar(
+        &self,
+        verpol: &str,
+        texpol: &str,
+        fecpol: Option<DateTime<Utc>>,
+    ) -> AppResult<PoliticaDatos> {
+        let verpol = verpol.trim();
+        let texpol = texpol.trim();
+        if verpol.is_empty() {
+            return Err(AppError::validation("verpol", "La versión es requerida"));
+        }
+        if texpol.is_empty() {
+            return Err(AppError::validation(
+                "texpol",
+                "El texto de la política es requerido",
+            ));
+        }
+        if self.repo.get_politica_by_version(verpol).await?.is_some() {
+            return Err(AppError::validation(
+                "verpol",
+                "Esa versión ya fue publicada",
+            ));
+        }
+
+        let politica = self
+            .repo
+            .create_politica(verpol, texpol, fecpol.unwrap_or_else(Utc::now))
+            .await?;
+
+        tracing::info!("Política de datos {} publicada", politica.verpol);
+        Ok(politica)
+    }
+
+    /// Registra que la persona acepta la política vigente
+    /// `idpol` es la versión que se le mostró: si ya no es la vigente, debe volver a leerla
+    pub async fn aceptar(
+        &self,
+        idper: i64,
+        idpol: i64,
+        cancon: CanalConsentimiento,
+    ) -> AppResult<Consentimiento> {
+        let politica = self.exigir_politica().await?;
+        if politica.idpol != idpol {
+            return Err(AppError::validation(
+                "idpol",
+                "Solo se puede aceptar la política vigente",
+            ));
+        }
+
+        // Aceptar dos veces la misma versión no crea otro registro
+        let historial = self.repo.list_by_persona(idper).await?;
+        if let Some(actual) = historial
+            .into_iter()
+            .find(|c| c.idpol == idpol && c.fecrev.is_none())
+        {
+            return Ok(actual);
+        }
+
+        self.repo.aceptar(idper, idpol, cancon, Utc::now()).await
+    }
+
+    /// Revoca el consentimiento vigente de la persona
+    pub async fn revocar(&self, idper: i64) -> AppResult<()> {
+        if self.repo.revocar(idper, Utc::now()).await? == 0 {
+            return Err(AppError::NotFound(
+                "La persona no tiene un consentimiento vigente".to_string(),
+            ));
+        }
+
+        tracing::info!("Persona {} revocó su consentimiento", idper);
+        Ok(())
+    }
+
+    pub async fn estado(&self, idper: i64) -> AppResult<EstadoConsentimiento> {
+        let politica = self.politica_vigente().await?;
+        let historial = self.repo.list_by_persona(idper).await?;
+        let vigente = politica.as_ref().is_some_and(|p| {
+            historial
+                .iter()
+                .any(|c| c.idpol == p.idpol && c.fecrev.is_none())
+        });
+
+        Ok(EstadoConsentimiento {
+            vigente,
+            politica,
+            historial,
+        })
+    }
+
+    /// Restringe un filtro masivo (exportaciones) a personas con consentimiento vigente
+    /// Las exportaciones son el único tratamiento masivo; un flujo nuevo (p. ej.
+    /// notificaciones) debe pasar su filtro por aquí
+    pub async fn restringir(&self, filtro: &mut PersonaFilter) -> AppResult<()> {
+        let politica = self.exigir_politica().await?;
+        filtro.con_consentimiento = Some(politica.idpol);
+        Ok(())
+    }
+
+    async fn exigir_politica(&self) -> AppResult<PoliticaDatos> {
+        self.politica_vigente().await?.ok_or_else(|| {
+            AppError::PreconditionFailed(
+                "No hay una política de tratamiento de datos vigente".to_string(),
+            )
+        })
+    }
+}
@@ -0,0 +1,163 @@
+use std::ops::RangeInclusive;
+
+use serde::Serialize;
+
+use crate::{domain::TipoDocumento, errors::AppError};
+
+/// Pesos de la DIAN para el dígito de verificación, aplicados desde el último dígito
+const PESOS_DV: [u32; 15] = [3, 7, 13, 17, 19, 23, 29, 37, 41, 43, 47, 53, 59, 67, 71];
+
+/// Documento validado y normalizado
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentoValidado {
+    pub tipo: TipoDocumento,
+    pub tdocper: i64,
+    pub sigla: &'static str,
+    pub nombre: &'static str,
+    /// Número sin puntos, guiones ni espacios (en NIT, sin el dígito de verificación)
+    pub ndocper: String,
+    /// Dígito de verificación (solo NIT)
+    pub dv: Option<u8>,
+}
+
+impl DocumentoValidado {
+    /// Forma en que se guarda en `persona.ndocper`: el NIT conserva su DV (`900123456-8`)
+    pub fn almacenado(&self) -> String {
+        match self.dv {
+            Some(dv) => format!("{}-{}", self.ndocper, dv),
+            None => self.ndocper.clone(),
+        }
+    }
+}
+
+/// Quita puntos, guiones y espacios y pasa a mayúsculas
+pub fn normalizar(numero: &str) -> String {
+    numero
+        .chars()
+        .filter(|c| !matches!(c, '.' | '-' | ' ' | '\t'))
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Calcula el dígito de verificación de un NIT (algoritmo módulo 11 de la DIAN)
+/// `nit` debe contener solo dígitos y como máximo 15
+pub fn calcular_dv_nit(nit: &str) -> u8 {
+    let suma: u32 = nit
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .zip(PESOS_DV)
+        .map(|(digito, peso)| digito * peso)
+        .sum();
+
+    match suma % 11 {
+        residuo @ (0 | 1) => residuo as u8,
+        residuo => (11 - residuo) as u8,
+    }
+}
+
+/// Valida un número de documento según su tipo (`tdocper`) y lo devuelve normalizado
+/// Los errores son por campo (`tdocper` / `ndocper`) para mostrarlos en el formulario
+pub fn validar(tdocper: i64, numero: &str) -> Result<DocumentoValidado, AppError> {
+    let tipo = TipoDocumento::from_code(tdocper)
+        .ok_or_else(|| AppError::validation("tdocper", "Tipo de documento desconocido"))?;
+
+    let numero = numero.trim();
+    if numero.is_empty() {
+        return Err(AppError::validation("ndocper", "El número de documento es requerido"));
+    }
+
+    let (ndocper, dv) = match tipo {
+        TipoDocumento::Nit => validar_nit(numero)?,
+        TipoDocumento::CedulaCiudadania => {
+            let cedula = solo_digitos(tipo, numero, 3..=10)?;
+            if cedula.starts_with('0') {
+                return Err(error_formato(tipo));
+            }
+            (cedula, None)
+        }
+        TipoDocumento::TarjetaIdentidad => (solo_digitos(tipo, numero, 10..=11)?, None),
+        TipoDocumento::CedulaExtranjeria => (solo_digitos(tipo, numero, 3..=10)?, None),
+        TipoDocumento::Pasaporte => {
+            let pasaporte = normalizar(numero);
+            if !pasaporte.chars().all(|c| c.is_ascii_alphanumeric())
+                || !(5..=12).contains(&pasaporte.len())
+            {
+                return Err(error_formato(tipo));
+            }
+            (pasaporte, None)
+        }
+    };
+
+    Ok(DocumentoValidado {
+        tipo,
+        tdocper: tipo.code(),
+        sigla: tipo.sigla(),
+        nombre: tipo.nombre(),
+        ndocper,
+        dv,
+    })
+}
+
+/// Claves con las que un número escrito a mano puede estar guardado en `ndocper`
+/// Sin el tipo no se sabe si es un NIT: un número que podría serlo también se
+/// busca con su DV, y uno con DV también sin él
+pub fn claves_busqueda(numero: &str) -> Vec<String> {
+    let mut claves = Vec::with_capacity(2);
+    if let Ok((base, Some(dv))) = validar_nit(numero.trim()) {
+        claves.push(format!("{}-{}", base, dv));
+    }
+    let normalizado = normalizar(numero);
+    if !normalizado.is_empty() && !claves.contains(&normalizado) {
+        claves.push(normalizado);
+    }
+    claves
+}
+
+/// Números formados solo por dígitos, con el largo de su tipo
+fn solo_digitos(
+    tipo: TipoDocumento,
+    numero: &str,
+    largo: RangeInclusive<usize>,
+) -> Result<String, AppError> {
+    let numero = normalizar(numero);
+    if !numero.chars().all(|c| c.is_ascii_digit()) || !largo.contains(&numero.len()) {
+        return Err(error_formato(tipo));
+    }
+    Ok(numero)
+}
+
+/// Acepta `900.123.456-7`, `900123456-7` o `900123456`; si trae DV lo verifica
+fn validar_nit(numero: &str) -> Result<(String, Option<u8>), AppError> {
+    let (base, dv_recibido) = match numero.rsplit_once('-') {
+        Some((base, dv)) if dv.trim().len() == 1 => (normalizar(base), Some(dv.trim())),
+        _ => (normalizar(numero), None),
+    };
+
+    if !base.chars().all(|c| c.is_ascii_digit()) || !(5..=15).contains(&base.len()) {
+        return Err(error_formato(TipoDocumento::Nit));
+    }
+
+    let dv = calcular_dv_nit(&base);
+    if let Some(recibido) = dv_recibido
+        && recibido.parse::<u8>().ok() != Some(dv)
+    {
+        return Err(AppError::validation(
+            "ndocper",
+            "El dígito de verificación del NIT no es correcto",
+        ));
+    }
+
+    Ok((base, Some(dv)))
+}
+
+fn error_formato(tipo: TipoDocumento) -> AppError {
+    let mensaje = match tipo {
+        TipoDocumento::CedulaCiudadania => "La cédula de ciudadanía debe tener entre 3 y 10 dígitos",
+        TipoDocumento::TarjetaIdentidad => "La tarjeta de identidad debe tener 10 u 11 dígitos",
+        TipoDocumento::CedulaExtranjeria => "La cédula de extranjería debe tener entre 3 y 10 dígitos",
+        TipoDocumento::Pasaporte => "El pasaporte debe tener entre 5 y 12 letras o dígitos",
+        TipoDocumento::Nit => "El NIT debe tener entre 5 y 15 dígitos",
+    };
+    AppError::validation("ndocper", mensaje)
+}
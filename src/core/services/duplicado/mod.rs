@@ -0,0 +1,469 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    hash::Hash,
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use serde::Serialize;
+
+use crate::{
+    core::services::documento,
+    domain::{
+        CandidatoDuplicado, DetalleFusion, DuplicadoFilter, EstadoDuplicado, EstadoPersona,
+        FusionPersona, NuevoCandidato, Persona, PersonaFilter,
+        db::{DuplicadoRepository, NuevaFusion, PersonaRepository, UnitOfWork, en_transaccion},
+        esta_anonimizada,
+    },
+    errors::{AppError, AppResult},
+};
+
+/// Similitud (Jaro-Winkler) desde la que dos nombres cuentan como señal
+const UMBRAL_NOMBRE: f64 = 0.85;
+
+/// Similitud de nombre que, junto a teléfono o municipio, basta para proponer el par
+const UMBRAL_NOMBRE_FUERTE: f64 = 0.92;
+
+/// Letras del apellido normalizado que, junto al municipio, forman el bloque de
+/// comparación de nombres (solo se comparan nombres dentro del mismo bloque para
+/// no hacer n² comparaciones)
+const LETRAS_BLOQUE: usize = 3;
+
+/// Un grupo con más personas que esto comparte un valor de relleno (p. ej. un
+/// teléfono genérico), no una identidad: se omite para acotar las comparaciones
+const MAX_BLOQUE: usize = 200;
+
+/// Candidatos por INSERT
+const TAMANO_LOTE: usize = 1000;
+
+/// Peso de cada señal en el puntaje (se acota a 1)
+const PESO_DOCUMENTO: f64 = 0.6;
+const PESO_EMAIL: f64 = 0.5;
+const PESO_TELEFONO: f64 = 0.2;
+const PESO_NOMBRE: f64 = 0.4;
+
+/// Resultado de una pasada de detección
+#[derive(Debug, Clone, Serialize)]
+pub struct ReporteDeteccion {
+    /// Personas analizadas (las archivadas no participan)
+    pub revisadas: usize,
+    /// Pares que cumplen los criterios
+    pub candidatos: usize,
+    /// Pares que no se conocían (los ya revisados no se vuelven a proponer)
+    pub nuevos: u64,
+}
+
+/// Claves normalizadas de una persona para compararla con las demás
+pub struct Huella {
+    idper: i64,
+    documento: Option<String>,
+    email: String,
+    telefono: Option<String>,
+    nombre: String,
+    /// Municipio y comienzo del apellido: los nombres solo se comparan dentro del bloque
+    bloque: (i64, String),
+    codubi: i64,
+}
+
+impl From<&Persona> for Huella {
+    fn from(persona: &Persona) -> Self {
+        let apellido = normalizar_nombre(&persona.apeper);
+        Self {
+            idper: persona.idper,
+            documento: persona
+                .ndocper
+                .as_deref()
+                .map(documento::normalizar)
+                .filter(|d| !d.is_empty()),
+            email: normalizar_email(&persona.emaper),
+            telefono: persona.telnor.clone(),
+            nombre: format!("{} {}", normalizar_nombre(&persona.nomper), apellido),
+            bloque: (
+                persona.codubi,
+                apellido.chars().take(LETRAS_BLOQUE).collect(),
+            ),
+            codubi: persona.codubi,
+        }
+    }
+}
+
+/// Señales que coinciden entre dos personas
+#[derive(Debug, Default)]
+pub struct Senales {
+    pub documento: bool,
+    pub email: bool,
+    pub telefono: bool,
+    /// Similitud de los nombres completos; solo cuenta desde `UMBRAL_NOMBRE`
+    pub nombre: f64,
+}
+
+impl Senales {
+    /// Suma de los pesos de las señales presentes, acotada a 1
+    pub fn puntaje(&self) -> f64 {
+        let mut puntaje = 0.0;
+        if self.documento {
+            puntaje += PESO_DOCUMENTO;
+        }
+        if self.email {
+            puntaje += PESO_EMAIL;
+        }
+        if self.telefono {
+            puntaje += PESO_TELEFONO;
+        }
+        if self.nombre >= UMBRAL_NOMBRE {
+            puntaje += PESO_NOMBRE * self.nombre;
+        }
+        puntaje.min(1.0)
+    }
+
+    fn motivos(&self) -> String {
+        let mut motivos = Vec::with_capacity(4);
+        if self.documento {
+            motivos.push("documento");
+        }
+        if self.email {
+            motivos.push("email");
+        }
+        if self.telefono {
+            motivos.push("telefono");
+        }
+        if self.nombre >= UMBRAL_NOMBRE {
+            motivos.push("nombre");
+        }
+        motivos.join(",")
+    }
+}
+
+/// Servicio de detección de personas duplicadas y de fusión
+pub struct DuplicadoService {
+    repo: Arc<dyn DuplicadoRepository>,
+    persona_repo: Arc<dyn PersonaRepository>,
+    unit_of_work: Arc<dyn UnitOfWork>,
+}
+
+impl DuplicadoService {
+    pub fn new(
+        repo: Arc<dyn DuplicadoRepository>,
+        persona_repo: Arc<dyn PersonaRepository>,
+        unit_of_work: Arc<dyn UnitOfWork>,
+    ) -> Self {
+        Self {
+            repo,
+            persona_repo,
+            unit_of_work,
+        }
+    }
+
+    /// Recorre todas las personas y registra los pares que probablemente son la misma
+    /// Un par se propone si coincide el documento o el email normalizados, o si
+    /// el nombre es muy parecido y además coincide el teléfono o el municipio
+    pub async fn detectar(&self) -> AppResult<ReporteDeteccion> {
+        let huellas: Vec<Huella> = self
+            .persona_repo
+            .stream(PersonaFilter::default())
+            .try_filter(|p| futures::future::ready(p.estper != EstadoPersona::Archivado))
+            .map_ok(|p| Huella::from(&p))
+            .try_collect()
+            .await?;
+        let revisadas = huellas.len();
+
+        // Las comparaciones son CPU pura: fuera de los hilos del runtime
+        let candidatos = tokio::task::spawn_blocking(move || candidatos(&huellas, Utc::now()))
+            .await
+            .map_err(|e| AppError::Internal(format!("Error detectando duplicados: {}", e)))?;
+
+        let total = candidatos.len();
+        let mut nuevos = 0;
+        let mut pendientes = candidatos.into_iter().peekable();
+        while pendientes.peek().is_some() {
+            let lote: Vec<NuevoCandidato> = pendientes.by_ref().take(TAMANO_LOTE).collect();
+            nuevos += self.repo.registrar(lote).await?;
+        }
+
+        let reporte = ReporteDeteccion {
+            revisadas,
+            candidatos: total,
+            nuevos,
+        };
+        tracing::info!(
+            "Detección de duplicados: {} personas, {} candidatos ({} nuevos)",
+            reporte.revisadas,
+            reporte.candidatos,
+            reporte.nuevos
+        );
+        Ok(reporte)
+    }
+
+    /// Ejecuta la detección cada `cada`; pensado para lanzarse con `tokio::spawn`
+    pub async fn programar(&self, cada: Duration) {
+        let mut intervalo = tokio::time::interval(cada);
+        loop {
+            intervalo.tick().await;
+            if let Err(e) = self.detectar().await {
+                tracing::error!("Falló la detección programada de duplicados: {:?}", e);
+            }
+        }
+    }
+
+    pub async fn listar(&self, filtro: DuplicadoFilter) -> AppResult<Vec<CandidatoDuplicado>> {
+        self.repo.buscar(filtro).await
+    }
+
+    pub async fn get(&self, iddup: i64) -> AppResult<CandidatoDuplicado> {
+        self.repo
+            .get(iddup)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Candidato de duplicado no encontrado".to_string()))
+    }
+
+    /// Marca el par como "no son la misma persona"
+    pub async fn descartar(&self, iddup: i64, idrev: i64) -> AppResult<CandidatoDuplicado> {
+        self.get(iddup).await?;
+        if !self.repo.descartar(iddup, idrev, Utc::now()).await? {
+            return Err(AppError::Conflict(
+                "El candidato ya fue revisado".to_string(),
+            ));
+        }
+        self.get(iddup).await
+    }
+
+    /// Fusiona la persona `idabs` en `idsup`
+    /// La superviviente hereda el documento y la dirección si no los tiene, recibe
+    /// los consentimientos y adjuntos de la absorbida, y la absorbida queda
+    /// archivada. Si viene de un candidato, el par debe coincidir con él
+    /// Las personas anonimizadas no se fusionan
+    pub async fn fusionar(
+        &self,
+        idsup: i64,
+        idabs: i64,
+        iddup: Option<i64>,
+        idactor: i64,
+    ) -> AppResult<FusionPersona> {
+        if idsup == idabs {
+            return Err(AppError::validation(
+                "idabs",
+                "No se puede fusionar una persona consigo misma",
+            ));
+        }
+        // Lo que se comprueba se lee en la misma transacción que escribe la fusión
+        let fusion = en_transaccion(&*self.unit_of_work, |tx| {
+            Box::pin(async move {
+                let repo = tx.duplicado();
+                if let Some(iddup) = iddup {
+                    let candidato = repo.get(iddup).await?.ok_or_else(|| {
+                        AppError::NotFound("Candidato de duplicado no encontrado".to_string())
+                    })?;
+                    if candidato.estdup != EstadoDuplicado::Pendiente {
+                        return Err(AppError::Conflict(
+                            "El candidato ya fue revisado".to_string(),
+                        ));
+                    }
+                    if (idsup.min(idabs), idsup.max(idabs)) != (candidato.idper1, candidato.idper2)
+                    {
+                        return Err(AppError::validation(
+                            "idsup",
+                            "La superviviente debe ser una de las dos personas del candidato",
+                        ));
+                    }
+                }
+
+                let mut superviviente = persona(tx.persona(), idsup).await?;
+                let mut absorbida = persona(tx.persona(), idabs).await?;
+                if esta_anonimizada(&superviviente) || esta_anonimizada(&absorbida) {
+                    return Err(AppError::Conflict(
+                        "No se puede fusionar una persona anonimizada".to_string(),
+                    ));
+                }
+                if superviviente.estper == EstadoPersona::Archivado {
+                    return Err(AppError::Conflict(
+                        "La persona superviviente está archivada".to_string(),
+                    ));
+                }
+                if repo.fusiones(idabs).await?.iter().any(|f| f.idabs == idabs) {
+                    return Err(AppError::Conflict(
+                        "La persona ya fue fusionada en otra".to_string(),
+                    ));
+                }
+
+                let mut detalle = DetalleFusion::default();
+                // El número solo tiene sentido con su tipo: se hereda si el tipo coincide
+                if superviviente.ndocper.is_none()
+                    && absorbida.ndocper.is_some()
+                    && superviviente.tdocper == absorbida.tdocper
+                {
+                    superviviente.ndocper = absorbida.ndocper.take();
+                    detalle.campos.push("ndocper".to_string());
+                }
+                if superviviente.dirper.is_none() && absorbida.dirper.is_some() {
+                    superviviente.dirper = absorbida.dirper.clone();
+                    detalle.campos.push("dirper".to_string());
+                }
+
+                absorbida.estper = EstadoPersona::Archivado;
+                absorbida.actper = false;
+                absorbida.motsus = Some(format!("Fusionada en la persona {}", idsup));
+                absorbida.fecsus = None;
+
+                repo.fusionar(NuevaFusion {
+                    superviviente,
+                    absorbida,
+                    iddup,
+                    idactor,
+                    detalle,
+                    fecfus: Utc::now(),
+                })
+                .await
+            })
+        })
+        .await?;
+
+        tracing::info!("Persona {} fusionada en {} por {}", idabs, idsup, idactor);
+        Ok(fusion)
+    }
+
+    pub async fn fusiones(&self, idper: i64) -> AppResult<Vec<FusionPersona>> {
+        self.repo.fusiones(idper).await
+    }
+}
+
+async fn persona(repo: &dyn PersonaRepository, idper: i64) -> AppResult<Persona> {
+    repo.get_by_idper(idper)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Persona {} no encontrada", idper)))
+}
+
+/// Pares que probablemente son la misma persona
+/// Un par se propone si coincide el documento o el email normalizados, o si
+/// el nombre es muy parecido y además coincide el teléfono o el municipio
+pub fn candidatos(huellas: &[Huella], fecdup: DateTime<Utc>) -> Vec<NuevoCandidato> {
+    let mut pares: BTreeMap<(usize, usize), Senales> = BTreeMap::new();
+    por_bloque(
+        huellas,
+        |h| h.documento.as_deref(),
+        |i, j| {
+            pares.entry((i, j)).or_default().documento = true;
+        },
+    );
+    por_bloque(
+        huellas,
+        |h| Some(h.email.as_str()),
+        |i, j| {
+            pares.entry((i, j)).or_default().email = true;
+        },
+    );
+    por_bloque(
+        huellas,
+        |h| h.telefono.as_deref(),
+        |i, j| {
+            pares.entry((i, j)).or_default().telefono = true;
+        },
+    );
+
+    // Los pares que ya coinciden en algo se comparan por nombre aunque estén en
+    // bloques distintos; el resto solo dentro de su bloque
+    for ((i, j), senales) in pares.iter_mut() {
+        senales.nombre = similitud(&huellas[*i], &huellas[*j]);
+    }
+    por_bloque(
+        huellas,
+        |h| Some(&h.bloque),
+        |i, j| {
+            if pares.contains_key(&(i, j)) {
+                return;
+            }
+            let nombre = similitud(&huellas[i], &huellas[j]);
+            if nombre >= UMBRAL_NOMBRE {
+                pares.insert(
+                    (i, j),
+                    Senales {
+                        nombre,
+                        ..Senales::default()
+                    },
+                );
+            }
+        },
+    );
+
+    pares
+        .into_iter()
+        .filter(|((i, j), senales)| {
+            senales.documento
+                || senales.email
+                || (senales.nombre >= UMBRAL_NOMBRE_FUERTE
+                    && (senales.telefono || huellas[*i].codubi == huellas[*j].codubi))
+        })
+        .map(|((i, j), senales)| {
+            let (a, b) = (huellas[i].idper, huellas[j].idper);
+            NuevoCandidato {
+                idper1: a.min(b),
+                idper2: a.max(b),
+                pundup: senales.puntaje(),
+                motdup: senales.motivos(),
+                fecdup,
+            }
+        })
+        .collect()
+}
+
+/// Llama a `par(i, j)` (i < j) por cada par de huellas con la misma clave, bloque
+/// a bloque y sin juntar los pares; los bloques de más de `MAX_BLOQUE` se omiten
+fn por_bloque<'a, K: Eq + Hash + Debug + ?Sized + 'a>(
+    huellas: &'a [Huella],
+    clave: impl Fn(&'a Huella) -> Option<&'a K>,
+    mut par: impl FnMut(usize, usize),
+) {
+    let mut bloques: HashMap<&K, Vec<usize>> = HashMap::new();
+    for (i, huella) in huellas.iter().enumerate() {
+        if let Some(valor) = clave(huella) {
+            bloques.entry(valor).or_default().push(i);
+        }
+    }
+
+    for (valor, bloque) in bloques {
+        if bloque.len() > MAX_BLOQUE {
+            tracing::warn!(
+                "Detección de duplicados: se omite el bloque {:?} con {} personas",
+                valor,
+                bloque.len()
+            );
+            continue;
+        }
+        for (a, &i) in bloque.iter().enumerate() {
+            for &j in &bloque[a + 1..] {
+                par(i, j);
+            }
+        }
+    }
+}
+
+/// Similitud Jaro-Winkler de los nombres completos normalizados
+fn similitud(a: &Huella, b: &Huella) -> f64 {
+    strsim::jaro_winkler(&a.nombre, &b.nombre)
+}
+
+/// Minúsculas, sin tildes y con un solo espacio entre palabras
+pub fn normalizar_nombre(nombre: &str) -> String {
+    // Se separa antes de transliterar: deunicode descarta tabuladores y saltos de línea
+    nombre
+        .split_whitespace()
+        .map(|palabra| deunicode::deunicode(palabra).to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Minúsculas y sin la etiqueta `+algo`; en Gmail los puntos tampoco cuentan
+pub fn normalizar_email(email: &str) -> String {
+    let email = email.trim().to_lowercase();
+    let Some((usuario, dominio)) = email.rsplit_once('@') else {
+        return email;
+    };
+
+    let usuario = usuario.split('+').next().unwrap_or(usuario);
+    match dominio {
+        "gmail.com" | "googlemail.com" => format!("{}@gmail.com", usuario.replace('.', "")),
+        _ => format!("{}@{}", usuario, dominio),
+    }
+}
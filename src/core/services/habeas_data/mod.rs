@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    core::services::{adjunto::AdjuntoService, avatar::AvatarService},
+    domain::{
+        Adjunto, Consentimiento, DatosSensibles, EstadoPersona, Municipio, NuevaSolicitud, Persona, SolicitudHabeasData,
+        TipoSolicitud,
+        db::{
+            ConsentimientoRepository, HabeasDataRepository, PersonaRepository, UbicacionRepository,
+            UnitOfWork, en_transaccion,
+        },
+        campos_expuestos, esta_anonimizada,
+    },
+    errors::{AppError, AppResult},
+};
+
+/// Datos del titular tal como están almacenados; el hash de la contraseña no se entrega,
+/// solo si existe
+#[derive(Debug, Serialize)]
+pub struct DatosTitular {
+    pub idper: i64,
+    pub ndocper: Option<String>,
+    pub tdocper: i64,
+    pub nomper: String,
+    pub apeper: String,
+    pub dirper: Option<String>,
+    pub telper: String,
+    pub telnor: Option<String>,
+    pub codubi: i64,
+    pub idpef: i64,
+    pub emaper: String,
+    pub actper: bool,
+    pub estper: EstadoPersona,
+    pub motsus: Option<String>,
+    pub fecsus: Option<DateTime<Utc>>,
+    pub verper: i64,
+    pub tiene_contrasena: bool,
+}
+
+impl From<Persona> for DatosTitular {
+    fn from(persona: Persona) -> Self {
+        Self {
+            tiene_contrasena: persona.pass.is_some(),
+            idper: persona.idper,
+            ndocper: persona.ndocper,
+            tdocper: persona.tdocper,
+            nomper: persona.nomper,
+            apeper: persona.apeper,
+            dirper: persona.dirper,
+            telper: persona.telper,
+            telnor: persona.telnor,
+            codubi: persona.codubi,
+            idpef: persona.idpef,
+            emaper: persona.emaper,
+            actper: persona.actper,
+            estper: persona.estper,
+            motsus: persona.motsus,
+            fecsus: persona.fecsus,
+            verper: persona.verper,
+        }
+    }
+}
+
+impl DatosSensibles for DatosTitular {
+    fn idper(&self) -> i64 {
+        self.idper
+    }
+
+    fn campos_sensibles(&self) -> String {
+        campos_expuestos(self.ndocper.is_some(), self.dirper.is_some())
+    }
+}
+
+/// Archivo con todo lo que se guarda de una persona (consulta de habeas data)
+/// Cada módulo que almacene datos del titular agrega aquí su sección
+#[derive(Debug, Serialize)]
+pub struct ArchivoPersonal {
+    pub generado: DateTime<Utc>,
+    pub titular: DatosTitular,
+    pub ubicacion: Option<Municipio>,
+    /// Aceptaciones y revocaciones de la política de tratamiento de datos
+    pub consentimientos: Vec<Consentimiento>,
+    /// Solicitudes de habeas data del titular, incluida la actual
+    pub solicitudes: Vec<SolicitudHabeasData>,
+    /// Metadatos de los archivos adjuntos (el contenido se descarga aparte)
+    pub adjuntos: Vec<Adjunto>,
+}
+
+/// Servicio de habeas data: consultas y supresión de datos personales (Ley 1581)
+pub struct HabeasDataService {
+    habeas_data_repository: Arc<dyn HabeasDataRepository>,
+    persona_repository: Arc<dyn PersonaRepository>,
+    ubicacion_repository: Arc<dyn UbicacionRepository>,
+    consentimiento_repository: Arc<dyn ConsentimientoRepository>,
+    unit_of_work: Arc<dyn UnitOfWork>,
+    adjunto_service: Arc<AdjuntoService>,
+    avatar_service: Arc<AvatarService>,
+}
+
+impl HabeasDataService {
+    pub fn new(
+        habeas_data_repository: Arc<dyn HabeasDataRepository>,
+        persona_repository: Arc<dyn PersonaRepository>,
+        ubicacion_repository: Arc<dyn UbicacionRepository>,
+        consentimiento_repository: Arc<dyn ConsentimientoRepository>,
+        unit_of_work: Arc<dyn UnitOfWork>,
+        adjunto_service: Arc<AdjuntoService>,
+        avatar_service: Arc<AvatarService>,
+    ) -> Self {
+        Self {
+            habeas_data_repository,
+            persona_repository,
+            ubicacion_repository,
+            consentimiento_repository,
+            unit_of_work,
+            adjunto_service,
+            avatar_service,
+        }
+    }
+
+    /// Arma el archivo de datos personales y registra la consulta
+    pub async fn archivo(&self, idper: i64, idsolicitante: i64) -> AppResult<ArchivoPersonal> {
+        let persona = self.persona(idper).await?;
+
+        self.habeas_data_repository
+            .registrar(NuevaSolicitud {
+                idper,
+                tipsol: TipoSolicitud::Acceso,
+                idsolicitante,
+                fecsol: Utc::now(),
+            })
+            .await?;
+
+        let ubicacion = self
+            .ubicacion_repository
+            .get_municipio(persona.codubi)
+            .await?;
+        let consentimientos = self
+            .consentimiento_repository
+            .list_by_persona(idper)
+            .await?;
+        let solicitudes = self.habeas_data_repository.list_by_persona(idper).await?;
+        let adjuntos = self.adjunto_service.listar(idper).await?;
+
+        Ok(ArchivoPersonal {
+            generado: Utc::now(),
+            titular: persona.into(),
+            ubicacion,
+            consentimientos,
+            solicitudes,
+            adjuntos,
+        })
+    }
+
+    /// Anonimiza de forma irreversible los datos personales del titular
+    /// `verper` es la versión que el solicitante leyó (If-Match)
+    /// La comprobación y la escritura van en una transacción; los archivos
+    /// (adjuntos, avatar) se borran después, cuando ya quedó confirmada
+    pub async fn anonimizar(
+        &self,
+        idper: i64,
+        verper: i64,
+        idsolicitante: i64,
+    ) -> AppResult<Persona> {
+        let (anterior, persona) = en_transaccion(&*self.unit_of_work, |tx| {
+            Box::pin(async move {
+                let anterior = tx
+                    .persona()
+                    .get_by_idper(idper)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+                if esta_anonimizada(&anterior) {
+                    return Err(AppError::Conflict(
+                        "Los datos de la persona ya fueron anonimizados".to_string(),
+                    ));
+                }
+
+                let persona = tx
+                    .habeas_data()
+                    .anonimizar(
+                        idper,
+                        verper,
+                        NuevaSolicitud {
+                            idper,
+                            tipsol: TipoSolicitud::Supresion,
+                            idsolicitante,
+                            fecsol: Utc::now(),
+                        },
+                    )
+                    .await?;
+                Ok((anterior, persona))
+            })
+        })
+        .await?;
+
+        // Los adjuntos (documentos, fotos) también son datos personales
+        for adjunto in self.adjunto_service.listar(idper).await? {
+            if let Err(e) = self.adjunto_service.eliminar(idper, adjunto.idadj).await {
+                tracing::error!(
+                    "No se pudo eliminar el adjunto {} de la persona anonimizada {}: {:?}",
+                    adjunto.idadj,
+                    idper,
+                    e
+                );
+            }
+        }
+
+        // La fila ya no apunta al avatar; falta borrar sus miniaturas
+        if let Some(version) = anterior.avaper {
+            self.avatar_service.descartar(idper, &version).await;
+        }
+
+        tracing::info!(
+            "Persona {} anonimizada a solicitud de {}",
+            idper,
+            idsolicitante
+        );
+        Ok(persona)
+    }
+
+    async fn persona(&self, idper: i64) -> AppResult<Persona> {
+        self.persona_repository
+            .get_by_idper(idper)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))
+    }
+}
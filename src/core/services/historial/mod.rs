@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    core::services::persona::PersonaService,
+    domain::{Persona, PersonaVersion, db::PersonaHistorialRepository},
+    errors::{AppError, AppResult},
+};
+
+/// Servicio del historial temporal de personas (consulta y restauración)
+pub struct HistorialService {
+    repo: Arc<dyn PersonaHistorialRepository>,
+    persona_service: Arc<PersonaService>,
+}
+
+impl HistorialService {
+    pub fn new(
+        repo: Arc<dyn PersonaHistorialRepository>,
+        persona_service: Arc<PersonaService>,
+    ) -> Self {
+        Self {
+            repo,
+            persona_service,
+        }
+    }
+
+    /// Versiones de una persona, de la más reciente a la más antigua
+    pub async fn versiones(&self, idper: i64) -> AppResult<Vec<PersonaVersion>> {
+        let versiones = self.repo.list_by_persona(idper).await?;
+        if versiones.is_empty() {
+            return Err(AppError::NotFound(
+                "La persona no tiene historial".to_string(),
+            ));
+        }
+        Ok(versiones)
+    }
+
+    pub async fn version(&self, idper: i64, verper: i64) -> AppResult<PersonaVersion> {
+        self.repo
+            .get_version(idper, verper)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Versión no encontrada".to_string()))
+    }
+
+    /// Cómo estaba la persona en un instante dado
+    pub async fn al(&self, idper: i64, fecha: DateTime<Utc>) -> AppResult<PersonaVersion> {
+        if fecha > Utc::now() {
+            return Err(AppError::validation(
+                "fecha",
+                "La fecha no puede estar en el futuro",
+            ));
+        }
+
+        self.repo
+            .as_of(idper, fecha)
+            .await?
+            .ok_or_else(|| AppError::NotFound("La persona no existía en esa fecha".to_string()))
+    }
+
+    /// Restaura los datos de una versión anterior como una versión nueva
+    /// `verper` es la versión actual que el cliente leyó (If-Match)
+    pub async fn restaurar(&self, idper: i64, version: i64, verper: i64) -> AppResult<Persona> {
+        let actual = self
+            .persona_service
+            .get_by_id(idper)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+        if version == actual.verper {
+            return Err(AppError::Conflict(
+                "La versión indicada ya es la actual".to_string(),
+            ));
+        }
+
+        let anterior = self.version(idper, version).await?;
+        let restaurada = anterior.restaurar_sobre(actual);
+
+        // Pasa por la misma validación que una actualización normal
+        self.persona_service.update(idper, restaurada, verper).await
+    }
+}
@@ -1,3 +1,15 @@
 pub mod persona;
 pub mod auth;
 pub mod permission;
+pub mod documento;
+pub mod ubicacion;
+pub mod catalog;
+pub mod habeas_data;
+pub mod consentimiento;
+pub mod auditoria;
+pub mod historial;
+pub mod acceso;
+pub mod adjunto;
+pub mod avatar;
+pub mod telefono;
+pub mod duplicado;
@@ -2,8 +2,8 @@ use std::{collections::HashMap, sync::Arc};
 use tokio::sync::RwLock;
 
 use crate::{
-    domain::{Pagper, db::PagperRepository},
-    errors::AppResult,
+    domain::{AuthUser, Pagper, db::PagperRepository},
+    errors::{AppError, AppResult},
 };
 
 /// Servicio de permisos con caché en memoria
@@ -96,4 +96,21 @@ impl PermissionService {
             Ok(false)
         }
     }
+
+    /// Exige que el usuario tenga `action` sobre la página `codpag`
+    /// El superadministrador siempre pasa
+    pub async fn authorize(&self, auth_user: &AuthUser, codpag: &str, action: &str) -> AppResult<()> {
+        if auth_user.is_super_admin() {
+            return Ok(());
+        }
+
+        if self.repo.has_permission(auth_user.idpef, codpag, action).await? {
+            return Ok(());
+        }
+
+        Err(AppError::Forbidden(format!(
+            "No tiene permiso '{}' sobre {}",
+            action, codpag
+        )))
+    }
 }
@@ -0,0 +1,225 @@
+use std::io::{self, BufWriter, Write};
+
+use bytes::Bytes;
+use rust_xlsxwriter::Workbook;
+use serde_json::{Map, Value};
+use tokio::sync::mpsc::{Receiver, Sender};
+
+use crate::{domain::Persona, errors::AppError, utils::value_parser::parse_value};
+
+/// Control de `value_parser` para enteros con separador de miles
+const CONTROL_ENTERO: u32 = 19;
+
+/// Bytes del XLSX que se juntan antes de mandarlos al cliente
+const XLSX_TAMANO_TROZO: usize = 64 * 1024;
+
+/// Columnas exportadas; `pass` nunca se incluye
+pub const EXPORT_COLUMNS: [&str; 13] = [
+    "idper", "ndocper", "tdocper", "nomper", "apeper", "dirper", "telper", "codubi", "idpef",
+    "emaper", "actper", "estper", "verper",
+];
+
+/// Formatos de exportación soportados
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+    Xlsx,
+}
+
+impl ExportFormat {
+    /// Interpreta el parámetro `?format=`
+    pub fn from_param(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+
+    /// Negocia el formato a partir del header `Accept`
+    pub fn from_accept(accept: &str) -> Option<Self> {
+        accept
+            .split(',')
+            .map(|media| media.split(';').next().unwrap_or("").trim())
+            .find_map(|media| match media {
+                "text/csv" => Some(Self::Csv),
+                "application/x-ndjson" | "application/ndjson" => Some(Self::Ndjson),
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => {
+                    Some(Self::Xlsx)
+                }
+                _ => None,
+            })
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+            Self::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// Valor de una celda exportada
+#[derive(Debug, Clone)]
+pub enum ExportCell {
+    Number(i64),
+    Text(String),
+    Bool(bool),
+    Empty,
+}
+
+impl ExportCell {
+    fn as_text(&self) -> String {
+        match self {
+            Self::Number(n) => n.to_string(),
+            Self::Text(t) => t.clone(),
+            Self::Bool(b) => b.to_string(),
+            Self::Empty => String::new(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Number(n) => Value::from(*n),
+            Self::Text(t) => Value::from(t.as_str()),
+            Self::Bool(b) => Value::from(*b),
+            Self::Empty => Value::Null,
+        }
+    }
+}
+
+/// Convierte una persona en las celdas de `EXPORT_COLUMNS`
+/// Con `format_numbers` las cantidades salen como texto `1.234.567`; los ids y
+/// códigos (`idper`, `codubi`...) nunca, porque no son cantidades
+pub fn export_cells(persona: &Persona, format_numbers: bool) -> Vec<ExportCell> {
+    let cantidad = |n: i64| {
+        if format_numbers {
+            ExportCell::Text(parse_value(CONTROL_ENTERO, &n.to_string(), None))
+        } else {
+            ExportCell::Number(n)
+        }
+    };
+
+    vec![
+        ExportCell::Number(persona.idper),
+        persona
+            .ndocper
+            .clone()
+            .map(ExportCell::Text)
+            .unwrap_or(ExportCell::Empty),
+        ExportCell::Number(persona.tdocper),
+        ExportCell::Text(persona.nomper.clone()),
+        ExportCell::Text(persona.apeper.clone()),
+        persona
+            .dirper
+            .clone()
+            .map(ExportCell::Text)
+            .unwrap_or(ExportCell::Empty),
+        ExportCell::Text(persona.telper.clone()),
+        ExportCell::Number(persona.codubi),
+        ExportCell::Number(persona.idpef),
+        ExportCell::Text(persona.emaper.clone()),
+        ExportCell::Bool(persona.actper),
+        ExportCell::Text(persona.estper.as_str().to_string()),
+        cantidad(persona.verper),
+    ]
+}
+
+/// Línea de encabezados del CSV
+pub fn csv_header() -> Result<Vec<u8>, AppError> {
+    write_csv_record(EXPORT_COLUMNS.iter().map(|c| c.to_string()))
+}
+
+/// Una fila CSV terminada en salto de línea
+pub fn csv_line(cells: &[ExportCell]) -> Result<Vec<u8>, AppError> {
+    write_csv_record(cells.iter().map(ExportCell::as_text))
+}
+
+fn write_csv_record(fields: impl Iterator<Item = String>) -> Result<Vec<u8>, AppError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(fields)
+        .map_err(|e| AppError::Internal(format!("Error escribiendo CSV: {}", e)))?;
+    writer
+        .into_inner()
+        .map_err(|e| AppError::Internal(format!("Error escribiendo CSV: {}", e)))
+}
+
+/// Un objeto JSON por línea
+pub fn ndjson_line(cells: &[ExportCell]) -> Result<Vec<u8>, AppError> {
+    let objeto: Map<String, Value> = EXPORT_COLUMNS
+        .iter()
+        .zip(cells)
+        .map(|(columna, celda)| (columna.to_string(), celda.to_json()))
+        .collect();
+
+    let mut linea = serde_json::to_vec(&objeto)
+        .map_err(|e| AppError::Internal(format!("Error serializando NDJSON: {}", e)))?;
+    linea.push(b'\n');
+    Ok(linea)
+}
+
+/// Escribe un XLSX recibiendo las filas por el canal y mandando el archivo por
+/// `salida` a medida que se empaqueta (bloqueante: usar en spawn_blocking)
+/// La hoja usa modo de memoria constante, así que las filas nunca se acumulan en
+/// memoria; el formato obliga a empaquetar el libro cuando llegó la última fila
+pub fn write_xlsx(
+    mut filas: Receiver<Vec<ExportCell>>,
+    salida: Sender<Result<Bytes, AppError>>,
+) -> Result<(), AppError> {
+    let xlsx_err = |e: rust_xlsxwriter::XlsxError| {
+        AppError::Internal(format!("Error escribiendo XLSX: {}", e))
+    };
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+    worksheet.write_row(0, 0, EXPORT_COLUMNS).map_err(xlsx_err)?;
+
+    let mut fila: u32 = 1;
+    while let Some(celdas) = filas.blocking_recv() {
+        for (columna, celda) in celdas.iter().enumerate() {
+            let columna = columna as u16;
+            match celda {
+                ExportCell::Number(n) => worksheet.write_number(fila, columna, *n as f64),
+                ExportCell::Text(t) => worksheet.write_string(fila, columna, t),
+                ExportCell::Bool(b) => worksheet.write_boolean(fila, columna, *b),
+                ExportCell::Empty => continue,
+            }
+            .map_err(xlsx_err)?;
+        }
+        fila += 1;
+    }
+
+    let mut escritor = BufWriter::with_capacity(XLSX_TAMANO_TROZO, Canal(salida));
+    workbook.save_to_writer(&mut escritor).map_err(xlsx_err)?;
+    escritor
+        .flush()
+        .map_err(|e| AppError::Internal(format!("Error enviando XLSX: {}", e)))
+}
+
+/// Escritor que manda cada trozo por el canal del cuerpo de la respuesta
+struct Canal(Sender<Result<Bytes, AppError>>);
+
+impl Write for Canal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "el cliente se desconectó"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use std::io::Cursor;
+
+use calamine::{Data, Reader, Xlsx, open_workbook_from_rs};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::rules;
+use crate::{
+    domain::{EstadoPersona, Persona},
+    errors::{AppError, FieldErrors, field_errors},
+};
+
+/// Clave de los errores que no pertenecen a un campo concreto (fila ilegible, permisos...)
+pub const ROW_ERROR_KEY: &str = "_fila";
+
+/// Formatos de archivo aceptados para la importación masiva
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ImportFormat {
+    /// Detecta el formato por content-type o, en su defecto, por extensión
+    pub fn detect(content_type: Option<&str>, file_name: Option<&str>) -> Option<Self> {
+        match content_type {
+            Some("text/csv") | Some("application/csv") => return Some(Self::Csv),
+            Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet") => {
+                return Some(Self::Xlsx);
+            }
+            _ => {}
+        }
+
+        let extension = file_name?.rsplit('.').next()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+}
+
+/// Fila del archivo de importación; los encabezados usan los nombres de columna de persona
+/// Las reglas de cada campo son las de `rules`, las mismas de `CreatePersonaDTO`
+#[derive(Debug, Deserialize, Validate)]
+pub struct PersonaImportRow {
+    #[validate(custom(function = rules::documento))]
+    pub ndocper: Option<String>,
+    #[validate(custom(function = rules::tipo_documento))]
+    pub tdocper: i64,
+    #[validate(custom(function = rules::nombre))]
+    pub nomper: String,
+    #[validate(custom(function = rules::apellido))]
+    pub apeper: String,
+    #[validate(custom(function = rules::direccion))]
+    pub dirper: Option<String>,
+    pub telper: String,
+    #[validate(custom(function = rules::ubicacion))]
+    pub codubi: i64,
+    #[validate(custom(function = rules::perfil))]
+    pub idpef: i64,
+    #[validate(custom(function = rules::contrasena))]
+    pub pass: Option<String>,
+    #[validate(custom(function = rules::email))]
+    pub emaper: String,
+    pub actper: Option<bool>,
+}
+
+impl From<PersonaImportRow> for Persona {
+    fn from(row: PersonaImportRow) -> Self {
+        Persona {
+            idper: 0,
+            ndocper: row.ndocper.filter(|d| !d.trim().is_empty()),
+            tdocper: row.tdocper,
+            nomper: row.nomper,
+            apeper: row.apeper,
+            dirper: row.dirper.filter(|d| !d.trim().is_empty()),
+            telper: row.telper,
+            codubi: row.codubi,
+            idpef: row.idpef,
+            pass: row.pass.filter(|p| !p.is_empty()),
+            emaper: row.emaper,
+            actper: row.actper.unwrap_or(true),
+            verper: 0,
+            estper: EstadoPersona::default(),
+            motsus: None,
+            fecsus: None,
+            avaper: None,
+            telnor: None,
+        }
+    }
+}
+
+/// Fila leída del archivo: número de fila (1 = encabezados) y persona o errores por campo
+pub type ParsedRow = (usize, Result<Persona, FieldErrors>);
+
+/// Opciones de la importación
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportOptions {
+    /// Solo valida y reporta, no escribe nada
+    pub dry_run: bool,
+    /// Tamaño de lote por transacción; `None` = una sola transacción
+    pub batch_size: Option<usize>,
+    /// Si el usuario puede importar superadministradores
+    pub allow_super_admin: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    pub fila: usize,
+    pub campos: FieldErrors,
+}
+
+/// Reporte por fila de la importación
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub validas: usize,
+    pub importadas: usize,
+    pub lotes: usize,
+    pub errores: Vec<ImportRowError>,
+    /// Por qué se detuvo una importación después de escribir algunos lotes;
+    /// lo de `importadas` quedó guardado
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interrumpida: Option<String>,
+}
+
+/// Lee las filas de un archivo CSV o XLSX
+pub fn parse_rows(format: ImportFormat, bytes: &[u8]) -> Result<Vec<ParsedRow>, AppError> {
+    match format {
+        ImportFormat::Csv => parse_csv(bytes),
+        ImportFormat::Xlsx => parse_xlsx(bytes),
+    }
+}
+
+fn parse_csv(bytes: &[u8]) -> Result<Vec<ParsedRow>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(bytes);
+
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::BadRequest(format!("CSV inválido: {}", e)))?
+        .clone();
+
+    let filas = reader
+        .records()
+        .enumerate()
+        .map(|(i, record)| {
+            let persona = record
+                .map_err(|e| row_error(e.to_string()))
+                .and_then(|r| deserialize_record(&r, &headers));
+            (i + 2, persona)
+        })
+        .collect();
+
+    Ok(filas)
+}
+
+fn parse_xlsx(bytes: &[u8]) -> Result<Vec<ParsedRow>, AppError> {
+    let mut workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(bytes))
+        .map_err(|e| AppError::BadRequest(format!("XLSX inválido: {}", e)))?;
+
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| AppError::BadRequest("El XLSX no tiene hojas".to_string()))?
+        .map_err(|e| AppError::BadRequest(format!("XLSX inválido: {}", e)))?;
+
+    let mut rows = range.rows();
+    let headers = rows
+        .next()
+        .map(to_record)
+        .ok_or_else(|| AppError::BadRequest("El XLSX no tiene encabezados".to_string()))?;
+
+    // Las filas totalmente vacías al final de la hoja no cuentan
+    let filas = rows
+        .enumerate()
+        .filter(|(_, cells)| cells.iter().any(|c| !matches!(c, Data::Empty)))
+        .map(|(i, cells)| (i + 2, deserialize_record(&to_record(cells), &headers)))
+        .collect();
+
+    Ok(filas)
+}
+
+/// Convierte las celdas en un registro CSV para reutilizar la misma deserialización
+fn to_record(cells: &[Data]) -> csv::StringRecord {
+    cells
+        .iter()
+        .map(|c| match c {
+            Data::Empty => String::new(),
+            other => other.to_string().trim().to_string(),
+        })
+        .collect()
+}
+
+fn deserialize_record(
+    record: &csv::StringRecord,
+    headers: &csv::StringRecord,
+) -> Result<Persona, FieldErrors> {
+    let row = record
+        .deserialize::<PersonaImportRow>(Some(headers))
+        .map_err(|e| row_error(e.to_string()))?;
+
+    row.validate().map_err(|e| field_errors(&e))?;
+    Ok(Persona::from(row))
+}
+
+/// Error que aplica a la fila completa
+pub fn row_error(mensaje: impl Into<String>) -> FieldErrors {
+    FieldErrors::from([(ROW_ERROR_KEY.to_string(), vec![mensaje.into()])])
+}
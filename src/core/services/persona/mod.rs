@@ -1,16 +1,65 @@
-use std::sync::Arc;
+mod export;
+mod import;
+pub mod rules;
 
-use crate::{domain::{Persona, db::PersonaRepository}, errors::AppError};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use phonenumber::country;
+
+use crate::{
+    core::services::{documento, telefono},
+    domain::{
+        CambioEstado, CanalConsentimiento, Consentimiento, EstadoPersona, Persona, PersonaFilter,
+        TipoDocumento, TransicionPersona,
+        db::{PersonaRepository, UbicacionRepository, UnitOfWork, en_transaccion},
+    },
+    errors::{AppError, FieldErrors},
+};
+
+pub use export::{
+    EXPORT_COLUMNS, ExportCell, ExportFormat, csv_header, csv_line, export_cells, ndjson_line,
+    write_xlsx,
+};
+pub use import::{
+    ImportFormat, ImportOptions, ImportReport, ImportRowError, ParsedRow, PersonaImportRow,
+    ROW_ERROR_KEY, parse_rows, row_error,
+};
 
 /// Servicio de dominio para Persona
 /// Contiene la lógica de negocio y orquesta operaciones del repositorio
 pub struct PersonaService {
     persona_repository: Arc<dyn PersonaRepository>,
+    ubicacion_repository: Arc<dyn UbicacionRepository>,
+    unit_of_work: Arc<dyn UnitOfWork>,
+    /// Región para los teléfonos escritos sin indicativo
+    region_telefono: country::Id,
+}
+
+/// Lo ya visto durante una importación: emails del archivo y municipios consultados
+#[derive(Default)]
+struct ImportSeen {
+    emails: HashSet<String>,
+    municipios: HashMap<i64, bool>,
 }
 
 impl PersonaService {
-    pub fn new(persona_repository: Arc<dyn PersonaRepository>) -> Self {
-        Self { persona_repository }
+    pub fn new(
+        persona_repository: Arc<dyn PersonaRepository>,
+        ubicacion_repository: Arc<dyn UbicacionRepository>,
+        unit_of_work: Arc<dyn UnitOfWork>,
+        region_telefono: country::Id,
+    ) -> Self {
+        Self {
+            persona_repository,
+            ubicacion_repository,
+            unit_of_work,
+            region_telefono,
+        }
     }
 
     /// Listar personas con paginación
@@ -30,7 +79,13 @@ impl PersonaService {
 
     /// Obtener persona por documento
     pub async fn get_by_document(&self, ndocper: &str) -> Result<Option<Persona>, AppError> {
-        self.persona_repository.get_by_ndocper(ndocper).await
+        // Se guarda normalizado: `1.020.304.050` o un NIT sin su DV también la encuentran
+        for clave in documento::claves_busqueda(ndocper) {
+            if let Some(persona) = self.persona_repository.get_by_ndocper(&clave).await? {
+                return Ok(Some(persona));
+            }
+        }
+        Ok(None)
     }
 
     /// Obtener persona por email
@@ -38,50 +93,392 @@ impl PersonaService {
         self.persona_repository.get_by_emaper(emaper).await
     }
 
+    /// Buscar personas por teléfono escrito en cualquier formato
+    /// (`300 123 4567`, `+57 300-123-4567`, `(+57) 3001234567`, ...)
+    pub async fn get_by_telefono(&self, telefono: &str) -> Result<Vec<Persona>, AppError> {
+        let telefono = telefono::normalizar(telefono, self.region_telefono)?;
+        self.persona_repository.get_by_telnor(&telefono.e164).await
+    }
+
     /// Crear nueva persona
-    pub async fn create(&self, mut persona: Persona) -> Result<Persona, AppError> {
-        // Validaciones de negocio
-        persona.nomper = persona.nomper.trim().to_string();
-        persona.apeper = persona.apeper.trim().to_string();
-        persona.emaper = persona.emaper.trim().to_lowercase();
+    pub async fn create(&self, persona: Persona) -> Result<Persona, AppError> {
+        let persona = self.prepare_new(persona).await?;
+        self.persona_repository.create(persona).await
+    }
 
-        if persona.nomper.is_empty() {
-            return Err(AppError::BadRequest("El nombre es requerido".to_string()));
+    /// Crear una persona que acepta la política de datos al registrarse
+    /// La persona y su consentimiento se escriben en una sola transacción:
+    /// si la aceptación falla no queda una persona sin consentimiento
+    pub async fn create_con_consentimiento(
+        &self,
+        persona: Persona,
+        idpol: i64,
+        cancon: CanalConsentimiento,
+    ) -> Result<(Persona, Consentimiento), AppError> {
+        let persona = self.prepare_new(persona).await?;
+
+        en_transaccion(&*self.unit_of_work, |tx| {
+            Box::pin(async move {
+                let ahora = Utc::now();
+                let vigente = tx.consentimiento().get_politica_vigente(ahora).await?;
+                if vigente.map(|p| p.idpol) != Some(idpol) {
+                    return Err(AppError::validation(
+                        "idpol",
+                        "Solo se puede aceptar la política vigente",
+                    ));
+                }
+
+                let persona = tx.persona().create(persona).await?;
+                let consentimiento = tx
+                    .consentimiento()
+                    .aceptar(persona.idper, idpol, cancon, ahora)
+                    .await?;
+                Ok((persona, consentimiento))
+            })
+        })
+        .await
+    }
+
+    /// Importar personas en bloque aplicando las mismas reglas que `create`
+    /// Si alguna fila es inválida no se escribe nada y se devuelve el reporte
+    /// Cada lote es una transacción: si uno falla después de escribir otros, se
+    /// devuelve el reporte de lo escrito con `interrumpida`
+    pub async fn import(
+        &self,
+        filas: Vec<ParsedRow>,
+        options: ImportOptions,
+    ) -> Result<ImportReport, AppError> {
+        let total = filas.len();
+        let mut errores = Vec::new();
+        let mut validas = Vec::with_capacity(total);
+        let mut seen = ImportSeen::default();
+
+        for (fila, persona) in filas {
+            match self.validate_import_row(persona, &mut seen, options).await? {
+                Ok(persona) => validas.push(persona),
+                Err(campos) => errores.push(ImportRowError { fila, campos }),
+            }
         }
 
-        if persona.emaper.is_empty() {
-            return Err(AppError::BadRequest("El email es requerido".to_string()));
+        let mut report = ImportReport {
+            dry_run: options.dry_run,
+            total,
+            validas: validas.len(),
+            importadas: 0,
+            lotes: 0,
+            errores,
+            interrumpida: None,
+        };
+
+        if options.dry_run || !report.errores.is_empty() {
+            return Ok(report);
         }
 
-        // Verificar si el email ya existe
-        if let Some(_) = self.persona_repository.get_by_emaper(&persona.emaper).await? {
-            return Err(AppError::BadRequest("El email ya está registrado".to_string()));
+        let batch_size = options.batch_size.unwrap_or(validas.len()).max(1);
+        let mut pendientes = validas.into_iter().peekable();
+        while pendientes.peek().is_some() {
+            let lote: Vec<Persona> = pendientes.by_ref().take(batch_size).collect();
+            let creadas = match self.persona_repository.create_many(lote).await {
+                Ok(creadas) => creadas,
+                // Sin nada escrito es un error como cualquier otro
+                Err(e) if report.lotes == 0 => return Err(e),
+                Err(e) => {
+                    tracing::error!(
+                        "Importación interrumpida en el lote {} con {} personas ya guardadas: {:?}",
+                        report.lotes + 1,
+                        report.importadas,
+                        e
+                    );
+                    report.interrumpida = Some(motivo_interrupcion(report.lotes + 1, &e));
+                    return Ok(report);
+                }
+            };
+            report.importadas += creadas.len();
+            report.lotes += 1;
         }
 
-        self.persona_repository.create(persona).await
+        tracing::info!(
+            "Importación completada: {} personas en {} lotes",
+            report.importadas,
+            report.lotes
+        );
+        Ok(report)
     }
 
-    /// Actualizar persona
-    pub async fn update(&self, idper: i64, mut persona: Persona) -> Result<Persona, AppError> {
-        // Validaciones de negocio
+    /// Valida una fila de importación; el error externo es de infraestructura,
+    /// el interno es el mensaje que se reporta para esa fila
+    async fn validate_import_row(
+        &self,
+        persona: Result<Persona, FieldErrors>,
+        seen: &mut ImportSeen,
+        options: ImportOptions,
+    ) -> Result<Result<Persona, FieldErrors>, AppError> {
+        let mut persona = match persona {
+            Ok(persona) => persona,
+            Err(error) => return Ok(Err(error)),
+        };
+
+        if let Err(error) = self.normalize_new(&mut persona) {
+            return Ok(Err(into_field_errors(error)));
+        }
+
+        if persona.idpef == 1 && !options.allow_super_admin {
+            return Ok(Err(row_error("No tiene permisos para crear superadministradores")));
+        }
+
+        let existe_municipio = match seen.municipios.get(&persona.codubi) {
+            Some(existe) => *existe,
+            None => {
+                let existe = self
+                    .ubicacion_repository
+                    .get_municipio(persona.codubi)
+                    .await?
+                    .is_some();
+                seen.municipios.insert(persona.codubi, existe);
+                existe
+            }
+        };
+        if !existe_municipio {
+            self.exigir_divipola().await?;
+            return Ok(Err(field_error("codubi", "El municipio no existe")));
+        }
+
+        if !seen.emails.insert(persona.emaper.clone()) {
+            return Ok(Err(field_error("emaper", "El email está repetido en el archivo")));
+        }
+
+        if self.persona_repository.get_by_emaper(&persona.emaper).await?.is_some() {
+            return Ok(Err(field_error("emaper", "El email ya está registrado")));
+        }
+
+        Ok(Ok(persona))
+    }
+
+    /// Lo que `create` y `create_con_consentimiento` exigen antes de escribir:
+    /// campos normalizados, municipio existente y email sin registrar
+    async fn prepare_new(&self, mut persona: Persona) -> Result<Persona, AppError> {
+        self.normalize_new(&mut persona)?;
+        self.check_codubi(persona.codubi).await?;
+
+        if self.persona_repository.get_by_emaper(&persona.emaper).await?.is_some() {
+            return Err(AppError::validation("emaper", "El email ya está registrado"));
+        }
+
+        Ok(persona)
+    }
+
+    /// Verifica que `codubi` sea un municipio DIVIPOLA existente
+    async fn check_codubi(&self, codubi: i64) -> Result<(), AppError> {
+        if self.ubicacion_repository.get_municipio(codubi).await?.is_none() {
+            self.exigir_divipola().await?;
+            return Err(AppError::validation("codubi", "El municipio no existe"));
+        }
+        Ok(())
+    }
+
+    /// Sin el catálogo DIVIPOLA ningún municipio existe: se avisa que falta
+    /// cargarlo en vez de culpar al `codubi` de cada persona
+    async fn exigir_divipola(&self) -> Result<(), AppError> {
+        if self.ubicacion_repository.list_departamentos().await?.is_empty() {
+            return Err(AppError::Conflict(
+                "El catálogo DIVIPOLA no está cargado: impórtelo en /api/v1/ubicacion/import o con DIVIPOLA_CSV"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Normaliza y valida los campos de una persona nueva
+    /// Una persona nueva queda activa, o pendiente si se creó con `actper = false`
+    fn normalize_new(&self, persona: &mut Persona) -> Result<(), AppError> {
+        persona.emaper = persona.emaper.trim().to_lowercase();
+        persona.estper = if persona.actper {
+            EstadoPersona::Activo
+        } else {
+            EstadoPersona::Pendiente
+        };
+        persona.motsus = None;
+        persona.fecsus = None;
+        self.normalize(persona)?;
+
+        if persona.emaper.is_empty() {
+            return Err(AppError::validation("emaper", "El email es requerido"));
+        }
+
+        Ok(())
+    }
+
+    /// Normalización común a create/update; acumula los errores por campo
+    fn normalize(&self, persona: &mut Persona) -> Result<(), AppError> {
         persona.nomper = persona.nomper.trim().to_string();
         persona.apeper = persona.apeper.trim().to_string();
 
+        let mut errores = FieldErrors::new();
         if persona.nomper.is_empty() {
-            return Err(AppError::BadRequest("El nombre es requerido".to_string()));
+            errores
+                .entry("nomper".to_string())
+                .or_default()
+                .push("El nombre es requerido".to_string());
         }
+        if persona.apeper.is_empty() {
+            errores
+                .entry("apeper".to_string())
+                .or_default()
+                .push("El apellido es requerido".to_string());
+        }
+
+        // El teléfono se guarda en E.164 para búsquedas e integraciones
+        // y en formato internacional para mostrar
+        match telefono::normalizar(&persona.telper, self.region_telefono) {
+            Ok(telefono) => {
+                persona.telper = telefono.display;
+                persona.telnor = Some(telefono.e164);
+            }
+            Err(AppError::Validation(campos)) => {
+                for (campo, mensajes) in campos {
+                    errores.entry(campo).or_default().extend(mensajes);
+                }
+            }
+            Err(e) => return Err(e),
+        }
+
+        // El documento se guarda normalizado según las reglas de su tipo
+        let documento = match persona.ndocper.as_deref() {
+            Some(ndocper) => documento::validar(persona.tdocper, ndocper).map(|d| Some(d.almacenado())),
+            None if TipoDocumento::from_code(persona.tdocper).is_none() => Err(
+                AppError::validation("tdocper", "Tipo de documento desconocido"),
+            ),
+            None => Ok(None),
+        };
+        match documento {
+            Ok(ndocper) => persona.ndocper = ndocper,
+            Err(AppError::Validation(campos)) => {
+                for (campo, mensajes) in campos {
+                    errores.entry(campo).or_default().extend(mensajes);
+                }
+            }
+            Err(e) => return Err(e),
+        }
+
+        if errores.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(errores))
+        }
+    }
+
+    /// Actualizar persona
+    /// `verper` es la versión que el cliente leyó (If-Match); si cambió, falla con 412
+    pub async fn update(
+        &self,
+        idper: i64,
+        mut persona: Persona,
+        verper: i64,
+    ) -> Result<Persona, AppError> {
+        self.normalize(&mut persona)?;
+        self.check_codubi(persona.codubi).await?;
+
+        self.persona_repository.update(idper, persona, verper).await
+    }
 
-        self.persona_repository.update(idper, persona).await
+    /// Eliminar persona: archiva la cuenta (soft delete)
+    pub async fn delete(&self, idper: i64, verper: i64) -> Result<Persona, AppError> {
+        self.transicion(idper, TransicionPersona::Archivar, None, None, verper)
+            .await
     }
 
-    /// Eliminar persona (soft delete)
-    pub async fn delete(&self, idper: i64) -> Result<(), AppError> {
-        // Verificar que existe
-        if !self.persona_repository.exists(idper).await? {
-            return Err(AppError::NotFound("Persona no encontrada".to_string()));
+    /// Aplica una transición del ciclo de vida validando el estado de origen
+    /// `motivo` y `hasta` solo aplican a suspensión y bloqueo
+    pub async fn transicion(
+        &self,
+        idper: i64,
+        transicion: TransicionPersona,
+        motivo: Option<String>,
+        hasta: Option<DateTime<Utc>>,
+        verper: i64,
+    ) -> Result<Persona, AppError> {
+        let persona = self
+            .persona_repository
+            .get_by_idper(idper)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+
+        let ahora = Utc::now();
+        let origen = persona.estado_efectivo(ahora);
+        let estper = transicion.destino(origen).ok_or_else(|| {
+            AppError::Conflict(format!(
+                "La transición {:?} no es válida para una cuenta en estado {}",
+                transicion,
+                origen.as_str()
+            ))
+        })?;
+
+        let motivo = motivo.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+        let cambio = match transicion {
+            TransicionPersona::Suspender => {
+                if motivo.is_none() {
+                    return Err(AppError::validation("motivo", "El motivo de la suspensión es requerido"));
+                }
+                if hasta.is_some_and(|hasta| hasta <= ahora) {
+                    return Err(AppError::validation("hasta", "La fecha de fin debe ser futura"));
+                }
+                CambioEstado { estper, motsus: motivo, fecsus: hasta }
+            }
+            TransicionPersona::Bloquear => CambioEstado { estper, motsus: motivo, fecsus: None },
+            _ => CambioEstado { estper, motsus: None, fecsus: None },
+        };
+
+        let actualizada = self
+            .persona_repository
+            .change_estado(idper, cambio, verper)
+            .await?;
+
+        tracing::info!(
+            "Persona {}: {} -> {}",
+            idper,
+            origen.as_str(),
+            actualizada.estper.as_str()
+        );
+        Ok(actualizada)
+    }
+
+    /// Verifica que la cuenta pueda iniciar sesión según su estado
+    /// Si la suspensión ya venció, la reactiva antes de dejarla pasar
+    pub async fn verificar_acceso(&self, persona: Persona) -> Result<Persona, AppError> {
+        cuenta_vigente(&persona)?;
+        if persona.estper != EstadoPersona::Suspendido {
+            return Ok(persona);
         }
 
-        self.persona_repository.delete(idper).await
+        let cambio = CambioEstado {
+            estper: EstadoPersona::Activo,
+            motsus: None,
+            fecsus: None,
+        };
+        match self
+            .persona_repository
+            .change_estado(persona.idper, cambio, persona.verper)
+            .await
+        {
+            Ok(reactivada) => {
+                tracing::info!("Suspensión vencida, persona {} reactivada", persona.idper);
+                Ok(reactivada)
+            }
+            // Otra petición la reactivó primero
+            Err(AppError::PreconditionFailed(_)) => Ok(persona),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Verifica que el titular de un token siga con la cuenta activa
+    /// Solo lee: una suspensión vencida pasa y se reactiva en el próximo login
+    pub async fn exigir_cuenta_vigente(&self, idper: i64) -> Result<(), AppError> {
+        let persona = self
+            .persona_repository
+            .get_by_idper(idper)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("La cuenta ya no existe".to_string()))?;
+        cuenta_vigente(&persona)
     }
 
     /// Listar personas activas
@@ -89,8 +486,55 @@ impl PersonaService {
         self.persona_repository.get_active(limit, offset).await
     }
 
+    /// Personas filtradas como stream, para exportaciones sin cargar todo en memoria
+    pub fn export(&self, filtro: PersonaFilter) -> BoxStream<'static, Result<Persona, AppError>> {
+        self.persona_repository.stream(filtro)
+    }
+
     /// Contar total de personas
     pub async fn count(&self) -> Result<i64, AppError> {
         self.persona_repository.count().await
     }
 }
+
+/// Errores por campo de un error de validación, para el reporte por fila
+fn into_field_errors(error: AppError) -> FieldErrors {
+    match error {
+        AppError::Validation(campos) => campos,
+        AppError::BadRequest(msg) => row_error(msg),
+        other => row_error(other.to_string()),
+    }
+}
+
+fn field_error(campo: &str, mensaje: &str) -> FieldErrors {
+    FieldErrors::from([(campo.to_string(), vec![mensaje.to_string()])])
+}
+
+/// Mensaje para el cliente de un lote que no se pudo escribir; el detalle de
+/// infraestructura solo va al log
+fn motivo_interrupcion(lote: usize, error: &AppError) -> String {
+    let detalle = match error {
+        AppError::Conflict(msg) => msg.clone(),
+        _ => "error al guardar".to_string(),
+    };
+    format!("El lote {} no se guardó: {}", lote, detalle)
+}
+
+/// Solo las cuentas activas (o con la suspensión vencida) se usan
+fn cuenta_vigente(persona: &Persona) -> Result<(), AppError> {
+    match persona.estado_efectivo(Utc::now()) {
+        EstadoPersona::Activo => Ok(()),
+        EstadoPersona::Pendiente => Err(AppError::Forbidden(
+            "La cuenta está pendiente de activación".to_string(),
+        )),
+        EstadoPersona::Suspendido => Err(AppError::Forbidden(match persona.fecsus {
+            Some(hasta) => format!(
+                "La cuenta está suspendida hasta {}",
+                hasta.format("%Y-%m-%d %H:%M UTC")
+            ),
+            None => "La cuenta está suspendida".to_string(),
+        })),
+        EstadoPersona::Bloqueado => Err(AppError::Forbidden("La cuenta está bloqueada".to_string())),
+        EstadoPersona::Archivado => Err(AppError::Forbidden("La cuenta fue archivada".to_string())),
+    }
+}
@@ -0,0 +1,99 @@
+//! Reglas de formato de los campos de persona, compartidas por los DTOs de la API
+//! y las filas de importación: cada estructura las referencia con
+//! `#[validate(custom(function = ...))]` y los mensajes viven solo aquí
+//! El teléfono y el documento por tipo se validan en el servicio
+
+use std::borrow::Cow;
+
+use validator::{ValidateEmail, ValidationError};
+
+pub fn documento(ndocper: &str) -> Result<(), ValidationError> {
+    longitud(
+        ndocper,
+        1,
+        20,
+        "El número de documento debe tener entre 1 y 20 caracteres",
+    )
+}
+
+pub fn tipo_documento(tdocper: i64) -> Result<(), ValidationError> {
+    positivo(tdocper, "Tipo de documento inválido")
+}
+
+pub fn nombre(nomper: &str) -> Result<(), ValidationError> {
+    longitud(
+        nomper,
+        1,
+        100,
+        "El nombre debe tener entre 1 y 100 caracteres",
+    )
+}
+
+pub fn apellido(apeper: &str) -> Result<(), ValidationError> {
+    longitud(
+        apeper,
+        1,
+        100,
+        "El apellido debe tener entre 1 y 100 caracteres",
+    )
+}
+
+pub fn direccion(dirper: &str) -> Result<(), ValidationError> {
+    longitud(
+        dirper,
+        0,
+        200,
+        "La dirección no puede superar 200 caracteres",
+    )
+}
+
+pub fn ubicacion(codubi: i64) -> Result<(), ValidationError> {
+    positivo(codubi, "Ubicación inválida")
+}
+
+pub fn perfil(idpef: i64) -> Result<(), ValidationError> {
+    positivo(idpef, "Perfil inválido")
+}
+
+pub fn contrasena(pass: &str) -> Result<(), ValidationError> {
+    longitud(
+        pass,
+        8,
+        usize::MAX,
+        "La contraseña debe tener al menos 8 caracteres",
+    )
+}
+
+pub fn email(emaper: &str) -> Result<(), ValidationError> {
+    if emaper.validate_email() {
+        Ok(())
+    } else {
+        Err(error("email", "El email no es válido"))
+    }
+}
+
+/// Longitud en caracteres, como la regla `length` de validator
+fn longitud(
+    valor: &str,
+    min: usize,
+    max: usize,
+    mensaje: &'static str,
+) -> Result<(), ValidationError> {
+    if (min..=max).contains(&valor.chars().count()) {
+        Ok(())
+    } else {
+        Err(error("length", mensaje))
+    }
+}
+
+fn positivo(valor: i64, mensaje: &'static str) -> Result<(), ValidationError> {
+    if valor >= 1 {
+        Ok(())
+    } else {
+        Err(error("range", mensaje))
+    }
+}
+
+fn error(codigo: &'static str, mensaje: &'static str) -> ValidationError {
+    ValidationError::new(codigo).with_message(Cow::Borrowed(mensaje))
+}
@@ -0,0 +1,45 @@
+use phonenumber::{Mode, country};
+
+use crate::errors::AppError;
+
+/// Región que se asume para los números escritos sin indicativo (`+57`)
+pub const REGION_POR_DEFECTO: country::Id = country::Id::CO;
+
+/// Teléfono validado en sus dos formas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelefonoNormalizado {
+    /// Forma canónica para integraciones y búsquedas, p. ej. `+573001234567`
+    pub e164: String,
+    /// Forma legible para mostrar, p. ej. `+57 300 1234567`
+    pub display: String,
+}
+
+/// Interpreta un código de región ISO 3166-1 (`CO`, `MX`, ...)
+pub fn region(codigo: &str) -> Result<country::Id, String> {
+    codigo
+        .trim()
+        .to_uppercase()
+        .parse()
+        .map_err(|_| format!("Región telefónica desconocida: {}", codigo))
+}
+
+/// Valida un teléfono en cualquier formato y lo devuelve normalizado
+/// Los números sin indicativo se interpretan en `region`
+pub fn normalizar(telefono: &str, region: country::Id) -> Result<TelefonoNormalizado, AppError> {
+    let invalido = || AppError::validation("telper", "El teléfono no es un número válido");
+
+    let telefono = telefono.trim();
+    if telefono.is_empty() {
+        return Err(AppError::validation("telper", "El teléfono es requerido"));
+    }
+
+    let numero = phonenumber::parse(Some(region), telefono).map_err(|_| invalido())?;
+    if !phonenumber::is_valid(&numero) {
+        return Err(invalido());
+    }
+
+    Ok(TelefonoNormalizado {
+        e164: numero.format().mode(Mode::E164).to_string(),
+        display: numero.format().mode(Mode::International).to_string(),
+    })
+}
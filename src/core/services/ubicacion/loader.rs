@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    domain::{Departamento, Municipio},
+    errors::AppError,
+};
+
+/// Columnas del CSV DIVIPOLA que necesitamos
+struct Columnas {
+    coddep: usize,
+    nomdep: usize,
+    codmun: usize,
+    nommun: usize,
+}
+
+/// Lee el CSV oficial DIVIPOLA del DANE y devuelve departamentos y municipios
+///
+/// Acepta las variantes que publica el DANE: separador `,` o `;`, codificación
+/// UTF-8 o Latin-1, y el código de municipio con 5 dígitos (`05001`) o solo los
+/// 3 del municipio (`001`). Las columnas se ubican por nombre de encabezado.
+pub fn parse_divipola(bytes: &[u8]) -> Result<(Vec<Departamento>, Vec<Municipio>), AppError> {
+    let texto = decodificar(bytes);
+    let texto = texto.trim_start_matches('\u{feff}');
+
+    let primera_linea = texto.lines().next().unwrap_or("");
+    let delimitador = if primera_linea.matches(';').count() > primera_linea.matches(',').count() {
+        b';'
+    } else {
+        b','
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimitador)
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(texto.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::BadRequest(format!("CSV DIVIPOLA inválido: {}", e)))?
+        .clone();
+    let columnas = ubicar_columnas(&headers)?;
+
+    let mut departamentos = BTreeMap::new();
+    let mut municipios = BTreeMap::new();
+
+    for (i, record) in reader.records().enumerate() {
+        let fila = i + 2;
+        let record = record
+            .map_err(|e| AppError::BadRequest(format!("Fila {}: {}", fila, e)))?;
+
+        let campo = |idx: usize| record.get(idx).unwrap_or("").trim();
+        if campo(columnas.codmun).is_empty() {
+            continue;
+        }
+
+        let coddep = parse_codigo(campo(columnas.coddep), fila)?;
+        let codmun = parse_codigo(campo(columnas.codmun), fila)?;
+        let codubi = if codmun < 1000 { coddep * 1000 + codmun } else { codmun };
+
+        departamentos
+            .entry(coddep)
+            .or_insert_with(|| Departamento {
+                coddep,
+                nomdep: campo(columnas.nomdep).to_string(),
+            });
+        municipios.insert(
+            codubi,
+            Municipio {
+                codubi,
+                nomubi: campo(columnas.nommun).to_string(),
+                coddep,
+                nomdep: campo(columnas.nomdep).to_string(),
+            },
+        );
+    }
+
+    if municipios.is_empty() {
+        return Err(AppError::BadRequest(
+            "El archivo DIVIPOLA no contiene municipios".to_string(),
+        ));
+    }
+
+    Ok((
+        departamentos.into_values().collect(),
+        municipios.into_values().collect(),
+    ))
+}
+
+/// El DANE publica el archivo en Latin-1 con frecuencia
+fn decodificar(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(texto) => texto.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+fn ubicar_columnas(headers: &csv::StringRecord) -> Result<Columnas, AppError> {
+    let normalizados: Vec<String> = headers.iter().map(normalizar_encabezado).collect();
+    let buscar = |a: &str, b: &str| {
+        normalizados
+            .iter()
+            .position(|h| h.contains(a) && h.contains(b))
+            .ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "No se encontró la columna '{} {}' en el archivo DIVIPOLA",
+                    a, b
+                ))
+            })
+    };
+
+    Ok(Columnas {
+        coddep: buscar("codigo", "departamento")?,
+        nomdep: buscar("nombre", "departamento")?,
+        codmun: buscar("codigo", "municipio")?,
+        nommun: buscar("nombre", "municipio")?,
+    })
+}
+
+fn normalizar_encabezado(header: &str) -> String {
+    header
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' => 'a',
+            'é' => 'e',
+            'í' => 'i',
+            'ó' => 'o',
+            'ú' | 'ü' => 'u',
+            'ñ' => 'n',
+            other => other,
+        })
+        .collect()
+}
+
+fn parse_codigo(valor: &str, fila: usize) -> Result<i64, AppError> {
+    valor.parse::<i64>().map_err(|_| {
+        AppError::BadRequest(format!("Fila {}: código '{}' inválido", fila, valor))
+    })
+}
@@ -0,0 +1,124 @@
+mod loader;
+
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    domain::{Departamento, Municipio, cache::CacheRepository, db::UbicacionRepository},
+    errors::AppResult,
+};
+
+pub use loader::parse_divipola;
+
+/// El catálogo DIVIPOLA cambia muy poco
+const CACHE_TTL_SECONDS: usize = 3600;
+
+/// Resumen de una carga del archivo DIVIPOLA
+#[derive(Debug, Serialize)]
+pub struct ImportResumen {
+    pub departamentos: usize,
+    pub municipios: usize,
+}
+
+/// Servicio del catálogo de ubicaciones con caché de lectura
+pub struct UbicacionService<C: CacheRepository> {
+    repo: Arc<dyn UbicacionRepository>,
+    cache: Arc<C>,
+    // Se incluye en las claves de caché: al importar se incrementa y
+    // todas las entradas anteriores quedan huérfanas hasta expirar
+    generacion: AtomicU64,
+}
+
+impl<C: CacheRepository> UbicacionService<C> {
+    pub fn new(repo: Arc<dyn UbicacionRepository>, cache: Arc<C>) -> Self {
+        Self {
+            repo,
+            cache,
+            generacion: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn departamentos(&self) -> AppResult<Vec<Departamento>> {
+        self.cached("departamentos", || self.repo.list_departamentos())
+            .await
+    }
+
+    pub async fn municipios(&self, coddep: i64) -> AppResult<Vec<Municipio>> {
+        self.cached(&format!("municipios:{}", coddep), || {
+            self.repo.list_municipios(coddep)
+        })
+        .await
+    }
+
+    pub async fn municipio(&self, codubi: i64) -> AppResult<Option<Municipio>> {
+        self.cached(&format!("municipio:{}", codubi), || {
+            self.repo.get_municipio(codubi)
+        })
+        .await
+    }
+
+    /// Autocompletado de municipios por nombre
+    pub async fn buscar(&self, q: &str, limit: i64) -> AppResult<Vec<Municipio>> {
+        let q = q.trim().to_lowercase();
+        if q.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.cached(&format!("buscar:{}:{}", q, limit), || {
+            self.repo.search_municipios(&q, limit)
+        })
+        .await
+    }
+
+    /// Las personas necesitan el catálogo: sin municipios no hay `codubi` válido
+    pub async fn cargado(&self) -> AppResult<bool> {
+        Ok(!self.repo.list_departamentos().await?.is_empty())
+    }
+
+    /// Carga el CSV DIVIPOLA del DANE e invalida el caché
+    pub async fn import(&self, bytes: &[u8]) -> AppResult<ImportResumen> {
+        let (departamentos, municipios) = parse_divipola(bytes)?;
+        let (departamentos, municipios) = self.repo.import(departamentos, municipios).await?;
+
+        self.generacion.fetch_add(1, Ordering::SeqCst);
+        tracing::info!(
+            "Catálogo DIVIPOLA cargado: {} departamentos, {} municipios",
+            departamentos,
+            municipios
+        );
+
+        Ok(ImportResumen {
+            departamentos,
+            municipios,
+        })
+    }
+
+    async fn cached<T, F, Fut>(&self, key: &str, load: F) -> AppResult<T>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = AppResult<T>>,
+    {
+        let key = format!(
+            "ubicacion:{}:{}",
+            self.generacion.load(Ordering::SeqCst),
+            key
+        );
+
+        if let Some(value) = self.cache.get::<T>(&key).await? {
+            tracing::debug!("Ubicación obtenida desde caché: {}", key);
+            return Ok(value);
+        }
+
+        let value = load().await?;
+        self.cache.set(&key, &value, CACHE_TTL_SECONDS).await?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Adjunto, Persona, PersonaVersion, RequestContext};
+
+/// Registro de una lectura de datos sensibles de una persona
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AccesoPersona {
+    pub idacc: i64,
+    /// Persona que consultó
+    pub idactor: i64,
+    /// Persona consultada
+    pub idper: i64,
+    /// Cómo se consultó: id, documento, email, listado, exportacion, historial, habeas_data,
+    /// adjunto...
+    pub via: String,
+    /// Finalidad declarada por el cliente en `X-Access-Purpose`
+    pub proposito: Option<String>,
+    /// Campos sensibles expuestos, separados por comas
+    pub campos: String,
+    pub ip: Option<String>,
+    pub idsolicitud: Option<String>,
+    pub fecacc: DateTime<Utc>,
+}
+
+/// Lectura por registrar (el id lo asigna la base de datos)
+#[derive(Debug, Clone)]
+pub struct NuevoAcceso {
+    pub idactor: i64,
+    pub idper: i64,
+    pub via: String,
+    pub campos: String,
+    pub contexto: RequestContext,
+    pub fecacc: DateTime<Utc>,
+}
+
+/// Filtros del reporte de accesos
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AccesoFilter {
+    pub idactor: Option<i64>,
+    pub idper: Option<i64>,
+    pub via: Option<String>,
+    pub desde: Option<DateTime<Utc>>,
+    pub hasta: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Registro con datos sensibles de una persona cuya lectura se registra
+pub trait DatosSensibles {
+    fn idper(&self) -> i64;
+
+    /// Campos sensibles expuestos, separados por comas
+    fn campos_sensibles(&self) -> String;
+}
+
+impl DatosSensibles for Persona {
+    fn idper(&self) -> i64 {
+        self.idper
+    }
+
+    fn campos_sensibles(&self) -> String {
+        campos_expuestos(self.ndocper.is_some(), self.dirper.is_some())
+    }
+}
+
+impl DatosSensibles for PersonaVersion {
+    fn idper(&self) -> i64 {
+        self.idper
+    }
+
+    fn campos_sensibles(&self) -> String {
+        campos_expuestos(self.ndocper.is_some(), self.dirper.is_some())
+    }
+}
+
+/// Un adjunto expone su contenido, que puede ser una copia del documento de identidad
+impl DatosSensibles for Adjunto {
+    fn idper(&self) -> i64 {
+        self.idper
+    }
+
+    fn campos_sensibles(&self) -> String {
+        format!("adjunto:{}", self.catadj.as_str())
+    }
+}
+
+/// El teléfono y el email siempre están; documento y dirección son opcionales
+pub fn campos_expuestos(ndocper: bool, dirper: bool) -> String {
+    let mut campos = Vec::with_capacity(4);
+    if ndocper {
+        campos.push("ndocper");
+    }
+    campos.push("telper");
+    if dirper {
+        campos.push("dirper");
+    }
+    campos.push("emaper");
+    campos.join(",")
+}
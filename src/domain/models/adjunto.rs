@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Clase de documento adjunto a una persona
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CategoriaAdjunto {
+    /// Copia del documento de identidad
+    Identificacion,
+    Contrato,
+    Foto,
+    Otro,
+}
+
+impl CategoriaAdjunto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CategoriaAdjunto::Identificacion => "identificacion",
+            CategoriaAdjunto::Contrato => "contrato",
+            CategoriaAdjunto::Foto => "foto",
+            CategoriaAdjunto::Otro => "otro",
+        }
+    }
+}
+
+impl TryFrom<String> for CategoriaAdjunto {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "identificacion" => Ok(CategoriaAdjunto::Identificacion),
+            "contrato" => Ok(CategoriaAdjunto::Contrato),
+            "foto" => Ok(CategoriaAdjunto::Foto),
+            "otro" => Ok(CategoriaAdjunto::Otro),
+            otro => Err(format!("Categoría de adjunto desconocida: {}", otro)),
+        }
+    }
+}
+
+/// Metadatos de un archivo adjunto; el contenido vive en el almacenamiento de objetos
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Adjunto {
+    pub idadj: i64,
+    pub idper: i64,
+    #[sqlx(try_from = "String")]
+    pub catadj: CategoriaAdjunto,
+    /// Nombre original del archivo (saneado)
+    pub nomadj: String,
+    /// Content-Type verificado contra el contenido
+    pub tipadj: String,
+    /// Tamaño en bytes
+    pub tamadj: i64,
+    /// SHA-256 del contenido en hexadecimal
+    pub hashadj: String,
+    /// Clave del objeto en el almacenamiento (no se expone)
+    #[serde(skip)]
+    pub clvadj: String,
+    /// Persona que lo subió
+    pub idsubidor: i64,
+    pub fecadj: DateTime<Utc>,
+}
+
+/// Adjunto por registrar (el id lo asigna la base de datos)
+#[derive(Debug, Clone)]
+pub struct NuevoAdjunto {
+    pub idper: i64,
+    pub catadj: CategoriaAdjunto,
+    pub nomadj: String,
+    pub tipadj: String,
+    pub tamadj: i64,
+    pub hashadj: String,
+    pub clvadj: String,
+    pub idsubidor: i64,
+    pub fecadj: DateTime<Utc>,
+}
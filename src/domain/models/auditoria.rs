@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::types::Json;
+
+/// Datos de la petición HTTP que originó una operación
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RequestContext {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    /// Valor del header `x-request-id` (lo genera el servidor si no viene)
+    pub request_id: Option<String>,
+    /// Finalidad declarada de la consulta (header `x-access-purpose`)
+    pub proposito: Option<String>,
+}
+
+/// Entrada del registro de auditoría (solo se inserta, nunca se modifica)
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct RegistroAuditoria {
+    pub idaud: i64,
+    /// Persona que ejecutó la operación
+    pub idactor: i64,
+    /// create, update, delete o el nombre de la operación (suspender, anonimizar...)
+    pub accion: String,
+    /// Tipo de entidad afectada: persona, catalog:tipo_documento, ...
+    pub entidad: String,
+    /// Id de la entidad afectada (texto para admitir claves compuestas)
+    pub identidad: String,
+    pub antes: Option<Json<Value>>,
+    pub despues: Option<Json<Value>>,
+    /// Solo los campos que cambiaron: `{campo: {antes, despues}}`; de un dato personal
+    /// solo queda que cambió
+    pub cambios: Option<Json<Value>>,
+    pub ip: Option<String>,
+    pub agente: Option<String>,
+    pub idsolicitud: Option<String>,
+    pub fecaud: DateTime<Utc>,
+}
+
+/// Entrada por registrar (el id lo asigna la base de datos)
+#[derive(Debug, Clone)]
+pub struct NuevaAuditoria {
+    pub idactor: i64,
+    pub accion: String,
+    pub entidad: String,
+    pub identidad: String,
+    pub antes: Option<Value>,
+    pub despues: Option<Value>,
+    pub cambios: Option<Value>,
+    pub contexto: RequestContext,
+    pub fecaud: DateTime<Utc>,
+}
+
+/// Filtros de consulta del registro de auditoría
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditoriaFilter {
+    pub idactor: Option<i64>,
+    pub accion: Option<String>,
+    pub entidad: Option<String>,
+    pub identidad: Option<String>,
+    pub idsolicitud: Option<String>,
+    pub desde: Option<DateTime<Utc>>,
+    pub hasta: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// Elemento de un catálogo (tabla de referencia pequeña)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CatalogItem {
+    pub id: i64,
+    /// Texto para mostrar, p. ej. "Cédula de ciudadanía"
+    pub nombre: String,
+    pub activo: bool,
+}
+
+/// Definición de un catálogo: de qué tabla y columnas sale
+/// Los adaptadores SQL construyen las consultas a partir de estos datos,
+/// así que agregar un catálogo es agregar una entrada en `CATALOGOS`
+#[derive(Debug, Clone, Copy)]
+pub struct CatalogDef {
+    /// Nombre público, el que va en `/catalog/{name}`
+    pub name: &'static str,
+    pub table: &'static str,
+    pub id_col: &'static str,
+    pub label_col: &'static str,
+    /// Columna booleana de activo; sin ella el borrado es físico
+    pub active_col: Option<&'static str>,
+    /// Si admite CRUD administrativo
+    pub editable: bool,
+    /// Columnas obligatorias que el catálogo no expone: al crear toman esta expresión SQL
+    pub fijas: &'static [(&'static str, &'static str)],
+}
+
+impl CatalogDef {
+    pub fn find(name: &str) -> Option<&'static CatalogDef> {
+        CATALOGOS.iter().find(|c| c.name == name)
+    }
+}
+
+pub const CATALOGO_TIPO_DOCUMENTO: &str = "tipo_documento";
+pub const CATALOGO_PERFIL: &str = "perfil";
+
+/// Catálogos disponibles
+pub static CATALOGOS: &[CatalogDef] = &[
+    // Los tipos los fija `TipoDocumento`: cada uno trae sus reglas de validación en
+    // código, así que uno creado desde la administración no podría usarse
+    CatalogDef {
+        name: CATALOGO_TIPO_DOCUMENTO,
+        table: "tipo_documento",
+        id_col: "idtdoc",
+        label_col: "nomtdoc",
+        active_col: Some("acttdoc"),
+        editable: false,
+        fijas: &[],
+    },
+    // Un perfil nuevo entra a la página de inicio y no tiene permisos: se le
+    // conceden en pagper. Desactivarlo no lo borra, las personas lo referencian
+    CatalogDef {
+        name: CATALOGO_PERFIL,
+        table: "perfil",
+        id_col: "idpef",
+        label_col: "nompef",
+        active_col: Some("actpef"),
+        editable: true,
+        fijas: &[(
+            "pagpri",
+            "(SELECT idpag FROM pagina WHERE codpag = 'inicio')",
+        )],
+    },
+];
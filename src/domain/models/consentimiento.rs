@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Versión de la política de tratamiento de datos personales
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PoliticaDatos {
+    pub idpol: i64,
+    /// Versión publicada, p. ej. "2024-01"
+    pub verpol: String,
+    /// Texto completo de la política
+    pub texpol: String,
+    /// Fecha desde la que rige; la vigente es la más reciente ya iniciada
+    pub fecpol: DateTime<Utc>,
+}
+
+/// Medio por el que la persona aceptó la política
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CanalConsentimiento {
+    Web,
+    Movil,
+    Presencial,
+    Telefonico,
+    Escrito,
+}
+
+impl CanalConsentimiento {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CanalConsentimiento::Web => "web",
+            CanalConsentimiento::Movil => "movil",
+            CanalConsentimiento::Presencial => "presencial",
+            CanalConsentimiento::Telefonico => "telefonico",
+            CanalConsentimiento::Escrito => "escrito",
+        }
+    }
+}
+
+impl TryFrom<String> for CanalConsentimiento {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "web" => Ok(CanalConsentimiento::Web),
+            "movil" => Ok(CanalConsentimiento::Movil),
+            "presencial" => Ok(CanalConsentimiento::Presencial),
+            "telefonico" => Ok(CanalConsentimiento::Telefonico),
+            "escrito" => Ok(CanalConsentimiento::Escrito),
+            otro => Err(format!("Canal de consentimiento desconocido: {}", otro)),
+        }
+    }
+}
+
+/// Aceptación de una versión de la política por parte de una persona
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Consentimiento {
+    pub idcon: i64,
+    pub idper: i64,
+    pub idpol: i64,
+    #[sqlx(try_from = "String")]
+    pub cancon: CanalConsentimiento,
+    /// Fecha de aceptación
+    pub feccon: DateTime<Utc>,
+    /// Fecha de revocación; `None` mientras siga vigente
+    pub fecrev: Option<DateTime<Utc>>,
+}
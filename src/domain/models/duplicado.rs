@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+
+/// Estado de revisión de un par de posibles duplicados
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EstadoDuplicado {
+    /// Detectado, a la espera de que un administrador lo revise
+    Pendiente,
+    /// Revisado: no son la misma persona (no se vuelve a proponer)
+    Descartado,
+    /// Las dos personas se fusionaron
+    Fusionado,
+}
+
+impl EstadoDuplicado {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EstadoDuplicado::Pendiente => "pendiente",
+            EstadoDuplicado::Descartado => "descartado",
+            EstadoDuplicado::Fusionado => "fusionado",
+        }
+    }
+}
+
+impl TryFrom<String> for EstadoDuplicado {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "pendiente" => Ok(EstadoDuplicado::Pendiente),
+            "descartado" => Ok(EstadoDuplicado::Descartado),
+            "fusionado" => Ok(EstadoDuplicado::Fusionado),
+            otro => Err(format!("Estado de duplicado desconocido: {}", otro)),
+        }
+    }
+}
+
+/// Par de personas que probablemente son la misma
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct CandidatoDuplicado {
+    pub iddup: i64,
+    /// El menor de los dos idper (cada par se guarda una sola vez)
+    pub idper1: i64,
+    pub idper2: i64,
+    /// Confianza entre 0 y 1
+    pub pundup: f64,
+    /// Señales que coinciden, separadas por coma: documento, email, telefono, nombre
+    pub motdup: String,
+    #[sqlx(try_from = "String")]
+    pub estdup: EstadoDuplicado,
+    pub fecdup: DateTime<Utc>,
+    /// Administrador que lo descartó o fusionó
+    pub idrev: Option<i64>,
+    pub fecrev: Option<DateTime<Utc>>,
+}
+
+/// Candidato por registrar (el id lo asigna la base de datos)
+#[derive(Debug, Clone)]
+pub struct NuevoCandidato {
+    pub idper1: i64,
+    pub idper2: i64,
+    pub pundup: f64,
+    pub motdup: String,
+    pub fecdup: DateTime<Utc>,
+}
+
+/// Filtros de la lista de candidatos
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DuplicadoFilter {
+    /// Por defecto solo los pendientes
+    pub estdup: Option<EstadoDuplicado>,
+    pub idper: Option<i64>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Qué cambió al fusionar: campos heredados y referencias movidas
+/// Solo pasan a la superviviente los consentimientos y los adjuntos. Se quedan
+/// con la absorbida las solicitudes de habeas data (son del titular que las
+/// hizo), el registro de accesos y la auditoría (dicen a quién se consultó), su
+/// historial de versiones y su avatar (la superviviente conserva el suyo)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetalleFusion {
+    /// Campos vacíos de la superviviente que se completaron con los de la absorbida
+    pub campos: Vec<String>,
+    pub consentimientos: u64,
+    /// Aceptaciones vigentes de la absorbida de una política que la superviviente
+    /// también tenía vigente: se mueven cerradas con la fecha de la fusión
+    #[serde(default)]
+    pub consentimientos_cerrados: u64,
+    pub adjuntos: u64,
+}
+
+/// Registro de una fusión (solo se inserta)
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct FusionPersona {
+    pub idfus: i64,
+    /// Persona que se conserva
+    pub idsup: i64,
+    /// Persona absorbida (queda archivada)
+    pub idabs: i64,
+    /// Candidato que originó la fusión
+    pub iddup: Option<i64>,
+    pub idactor: i64,
+    pub detfus: Json<DetalleFusion>,
+    pub fecfus: DateTime<Utc>,
+}
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Estado del ciclo de vida de la cuenta de una persona (`Persona.estper`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EstadoPersona {
+    /// Registrada pero aún sin activar
+    Pendiente,
+    #[default]
+    Activo,
+    /// Suspendida temporalmente (`motsus`, hasta `fecsus`)
+    Suspendido,
+    /// Bloqueada hasta que un administrador la desbloquee
+    Bloqueado,
+    /// Retirada definitivamente; no admite más transiciones
+    Archivado,
+}
+
+impl EstadoPersona {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EstadoPersona::Pendiente => "pendiente",
+            EstadoPersona::Activo => "activo",
+            EstadoPersona::Suspendido => "suspendido",
+            EstadoPersona::Bloqueado => "bloqueado",
+            EstadoPersona::Archivado => "archivado",
+        }
+    }
+}
+
+impl TryFrom<String> for EstadoPersona {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "pendiente" => Ok(EstadoPersona::Pendiente),
+            "activo" => Ok(EstadoPersona::Activo),
+            "suspendido" => Ok(EstadoPersona::Suspendido),
+            "bloqueado" => Ok(EstadoPersona::Bloqueado),
+            "archivado" => Ok(EstadoPersona::Archivado),
+            otro => Err(format!("Estado de persona desconocido: {}", otro)),
+        }
+    }
+}
+
+/// Transiciones permitidas entre estados; cada una tiene su propio permiso
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransicionPersona {
+    /// pendiente o suspendido → activo
+    Activar,
+    /// activo → suspendido
+    Suspender,
+    /// activo o suspendido → bloqueado
+    Bloquear,
+    /// bloqueado → activo
+    Desbloquear,
+    /// cualquiera → archivado
+    Archivar,
+}
+
+impl TransicionPersona {
+    /// Código de página (`pagina.codpag`) cuyo permiso de actualización autoriza la transición
+    pub fn codpag(&self) -> &'static str {
+        match self {
+            TransicionPersona::Activar => "persona.activar",
+            TransicionPersona::Suspender => "persona.suspender",
+            TransicionPersona::Bloquear => "persona.bloquear",
+            TransicionPersona::Desbloquear => "persona.desbloquear",
+            TransicionPersona::Archivar => "persona.archivar",
+        }
+    }
+
+    /// Nombre de la acción, usado en la auditoría
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            TransicionPersona::Activar => "activar",
+            TransicionPersona::Suspender => "suspender",
+            TransicionPersona::Bloquear => "bloquear",
+            TransicionPersona::Desbloquear => "desbloquear",
+            TransicionPersona::Archivar => "archivar",
+        }
+    }
+
+    /// Estado de llegada si la transición es válida desde `origen`
+    pub fn destino(&self, origen: EstadoPersona) -> Option<EstadoPersona> {
+        use EstadoPersona::*;
+
+        match (self, origen) {
+            (TransicionPersona::Activar, Pendiente | Suspendido) => Some(Activo),
+            (TransicionPersona::Suspender, Activo) => Some(Suspendido),
+            (TransicionPersona::Bloquear, Activo | Suspendido) => Some(Bloqueado),
+            (TransicionPersona::Desbloquear, Bloqueado) => Some(Activo),
+            (TransicionPersona::Archivar, Pendiente | Activo | Suspendido | Bloqueado) => {
+                Some(Archivado)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Nuevo estado a persistir; `actper` se deriva de `estper`
+#[derive(Debug, Clone)]
+pub struct CambioEstado {
+    pub estper: EstadoPersona,
+    /// Motivo de la suspensión o bloqueo
+    pub motsus: Option<String>,
+    /// Fin de la suspensión
+    pub fecsus: Option<DateTime<Utc>>,
+}
+
+impl CambioEstado {
+    pub fn actper(&self) -> bool {
+        self.estper == EstadoPersona::Activo
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::Persona;
+
+/// Tipo de solicitud del titular según la Ley 1581 de 2012
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TipoSolicitud {
+    /// Consulta: entrega de todos los datos almacenados
+    Acceso,
+    /// Supresión: anonimización irreversible
+    Supresion,
+}
+
+impl TipoSolicitud {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TipoSolicitud::Acceso => "acceso",
+            TipoSolicitud::Supresion => "supresion",
+        }
+    }
+}
+
+impl TryFrom<String> for TipoSolicitud {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "acceso" => Ok(TipoSolicitud::Acceso),
+            "supresion" => Ok(TipoSolicitud::Supresion),
+            otro => Err(format!("Tipo de solicitud desconocido: {}", otro)),
+        }
+    }
+}
+
+/// Registro de una solicitud de habeas data atendida
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SolicitudHabeasData {
+    pub idsol: i64,
+    /// Titular de los datos
+    pub idper: i64,
+    #[sqlx(try_from = "String")]
+    pub tipsol: TipoSolicitud,
+    /// Persona que ejecutó la solicitud
+    pub idsolicitante: i64,
+    pub fecsol: DateTime<Utc>,
+}
+
+/// Solicitud por registrar (el id lo asigna la base de datos)
+#[derive(Debug, Clone)]
+pub struct NuevaSolicitud {
+    pub idper: i64,
+    pub tipsol: TipoSolicitud,
+    pub idsolicitante: i64,
+    pub fecsol: DateTime<Utc>,
+}
+
+/// Valor que reemplaza nombre y apellido al anonimizar
+pub const DATO_ANONIMO: &str = "Anonimizado";
+
+/// Valor que reemplaza el teléfono al anonimizar (la columna no admite NULL)
+pub const TELEFONO_ANONIMO: &str = "0000000";
+
+/// Email de reemplazo: único por persona para no chocar con el índice de `emaper`,
+/// y en un dominio reservado (`.invalid`) para que nunca se pueda enviar correo
+pub fn email_anonimo(idper: i64) -> String {
+    format!("anonimizado-{}@anonimo.invalid", idper)
+}
+
+/// Si los datos de la persona ya se anonimizaron: el email de reemplazo solo lo
+/// pone la anonimización
+pub fn esta_anonimizada(persona: &Persona) -> bool {
+    persona.emaper == email_anonimo(persona.idper)
+}
@@ -1,16 +1,47 @@
-/// Modelos de Dominio
-/// Entidades principales del sistema
+//! Modelos de Dominio
+//! Entidades principales del sistema
 
+mod acceso_persona;
+mod adjunto;
+mod auditoria;
 mod auth;
+mod catalog;
+mod consentimiento;
+mod duplicado;
+mod estado_persona;
+mod habeas_data;
 mod persona;
+mod persona_historial;
 mod perfil;
 mod pagina;
 mod pagper;
+mod tipo_documento;
+mod ubicacion;
 
+pub use acceso_persona::{
+    AccesoFilter, AccesoPersona, DatosSensibles, NuevoAcceso, campos_expuestos,
+};
+pub use adjunto::{Adjunto, CategoriaAdjunto, NuevoAdjunto};
+pub use auditoria::{AuditoriaFilter, NuevaAuditoria, RegistroAuditoria, RequestContext};
 pub use auth::AuthUser;
 pub use auth::Claims;
 
-pub use persona::Persona;
+pub use catalog::{CATALOGO_PERFIL, CATALOGO_TIPO_DOCUMENTO, CATALOGOS, CatalogDef, CatalogItem};
+
+pub use consentimiento::{CanalConsentimiento, Consentimiento, PoliticaDatos};
+pub use duplicado::{
+    CandidatoDuplicado, DetalleFusion, DuplicadoFilter, EstadoDuplicado, FusionPersona,
+    NuevoCandidato,
+};
+pub use estado_persona::{CambioEstado, EstadoPersona, TransicionPersona};
+pub use habeas_data::{
+    DATO_ANONIMO, NuevaSolicitud, SolicitudHabeasData, TELEFONO_ANONIMO, TipoSolicitud,
+    email_anonimo, esta_anonimizada,
+};
+pub use persona::{Persona, PersonaFilter};
+pub use persona_historial::PersonaVersion;
 pub use perfil::Perfil;
 pub use pagina::Pagina;
 pub use pagper::Pagper;
+pub use tipo_documento::TipoDocumento;
+pub use ubicacion::{Departamento, Municipio};
@@ -1,17 +1,71 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::domain::EstadoPersona;
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Persona {
     pub idper: i64,
-    pub ndocper: Option<i64>,
+    /// Número de documento normalizado (alfanumérico por los pasaportes)
+    pub ndocper: Option<String>,
     pub tdocper: i64,
     pub nomper: String,
     pub apeper: String,
     pub dirper: Option<String>,
+    /// Teléfono en la forma para mostrar (internacional, p. ej. `+57 300 1234567`)
     pub telper: String,
+    /// Teléfono normalizado en E.164 (`+573001234567`); vacío en filas
+    /// anteriores a la normalización hasta su próxima edición
+    #[serde(default)]
+    pub telnor: Option<String>,
     pub codubi: i64,
     pub idpef: i64,
     pub pass: Option<String>,
     pub emaper: String,
+    /// Se mantiene sincronizado con `estper` (true solo si está activo)
     pub actper: bool,
+    /// Versión de la fila para control de concurrencia optimista
+    #[serde(default)]
+    pub verper: i64,
+    /// Estado del ciclo de vida de la cuenta
+    #[serde(default)]
+    #[sqlx(try_from = "String")]
+    pub estper: EstadoPersona,
+    /// Motivo de la suspensión o bloqueo
+    #[serde(default)]
+    pub motsus: Option<String>,
+    /// Fecha hasta la que dura la suspensión
+    #[serde(default)]
+    pub fecsus: Option<DateTime<Utc>>,
+    /// Versión del avatar vigente; sin avatar si es `None`
+    #[serde(default)]
+    pub avaper: Option<String>,
+}
+
+impl Persona {
+    /// Estado vigente: una suspensión vencida ya cuenta como activa
+    pub fn estado_efectivo(&self, ahora: DateTime<Utc>) -> EstadoPersona {
+        match (self.estper, self.fecsus) {
+            (EstadoPersona::Suspendido, Some(hasta)) if hasta <= ahora => EstadoPersona::Activo,
+            (estado, _) => estado,
+        }
+    }
+}
+
+/// Filtros para consultas masivas de personas (exportación)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PersonaFilter {
+    pub idpef: Option<i64>,
+    pub actper: Option<bool>,
+    pub estper: Option<EstadoPersona>,
+    pub codubi: Option<i64>,
+    /// Búsqueda libre sobre nombre, apellido y email
+    pub q: Option<String>,
+    /// Perfil a excluir del resultado (lo fija el servidor, no el cliente)
+    #[serde(skip)]
+    pub excluir_idpef: Option<i64>,
+    /// Solo personas que aceptaron esta política (`idpol`) y no la revocaron
+    /// (lo fija el servidor, no el cliente)
+    #[serde(skip)]
+    pub con_consentimiento: Option<i64>,
 }
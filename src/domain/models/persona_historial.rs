@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::domain::{EstadoPersona, Persona};
+
+/// Versión histórica de una persona, vigente en el intervalo `[vigdes, vighas)`
+/// La contraseña nunca se guarda en el historial
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PersonaVersion {
+    pub idhis: i64,
+    pub idper: i64,
+    pub verper: i64,
+    pub ndocper: Option<String>,
+    pub tdocper: i64,
+    pub nomper: String,
+    pub apeper: String,
+    pub dirper: Option<String>,
+    pub telper: String,
+    pub codubi: i64,
+    pub idpef: i64,
+    pub emaper: String,
+    pub actper: bool,
+    #[sqlx(try_from = "String")]
+    pub estper: EstadoPersona,
+    pub motsus: Option<String>,
+    pub fecsus: Option<DateTime<Utc>>,
+    /// Inicio de la vigencia de la versión
+    pub vigdes: DateTime<Utc>,
+    /// Fin de la vigencia; `None` para la versión actual
+    pub vighas: Option<DateTime<Utc>>,
+}
+
+impl PersonaVersion {
+    /// Aplica los datos de la versión sobre la persona actual
+    /// Solo se restauran los datos editables: el estado del ciclo de vida,
+    /// la contraseña y la versión se conservan
+    pub fn restaurar_sobre(&self, actual: Persona) -> Persona {
+        Persona {
+            ndocper: self.ndocper.clone(),
+            tdocper: self.tdocper,
+            nomper: self.nomper.clone(),
+            apeper: self.apeper.clone(),
+            dirper: self.dirper.clone(),
+            telper: self.telper.clone(),
+            codubi: self.codubi,
+            idpef: self.idpef,
+            emaper: self.emaper.clone(),
+            ..actual
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+/// Tipos de documento de identidad colombianos (valor de `Persona.tdocper`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TipoDocumento {
+    CedulaCiudadania = 1,
+    TarjetaIdentidad = 2,
+    CedulaExtranjeria = 3,
+    Pasaporte = 4,
+    Nit = 5,
+}
+
+impl TipoDocumento {
+    pub const TODOS: [TipoDocumento; 5] = [
+        TipoDocumento::CedulaCiudadania,
+        TipoDocumento::TarjetaIdentidad,
+        TipoDocumento::CedulaExtranjeria,
+        TipoDocumento::Pasaporte,
+        TipoDocumento::Nit,
+    ];
+
+    /// Obtiene el tipo a partir del código almacenado en `tdocper`
+    pub fn from_code(code: i64) -> Option<Self> {
+        Self::TODOS.into_iter().find(|t| t.code() == code)
+    }
+
+    pub fn code(&self) -> i64 {
+        *self as i64
+    }
+
+    /// Sigla usada en formularios y reportes
+    pub fn sigla(&self) -> &'static str {
+        match self {
+            TipoDocumento::CedulaCiudadania => "CC",
+            TipoDocumento::TarjetaIdentidad => "TI",
+            TipoDocumento::CedulaExtranjeria => "CE",
+            TipoDocumento::Pasaporte => "PA",
+            TipoDocumento::Nit => "NIT",
+        }
+    }
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            TipoDocumento::CedulaCiudadania => "Cédula de ciudadanía",
+            TipoDocumento::TarjetaIdentidad => "Tarjeta de identidad",
+            TipoDocumento::CedulaExtranjeria => "Cédula de extranjería",
+            TipoDocumento::Pasaporte => "Pasaporte",
+            TipoDocumento::Nit => "NIT",
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Departamento según la codificación DIVIPOLA del DANE
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Departamento {
+    pub coddep: i64,
+    pub nomdep: String,
+}
+
+/// Municipio DIVIPOLA; `codubi` es el código DANE de 5 dígitos (2 del departamento + 3)
+/// y es el valor que se guarda en `Persona.codubi`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Municipio {
+    pub codubi: i64,
+    pub nomubi: String,
+    pub coddep: i64,
+    /// Nombre del departamento (se llena con un join al consultar)
+    #[serde(default)]
+    pub nomdep: String,
+}
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{AccesoFilter, AccesoPersona, NuevoAcceso},
+    errors::AppResult,
+};
+
+/// Puerto (interface) del registro de lecturas de datos sensibles
+/// Igual que la auditoría, solo admite inserciones
+#[async_trait]
+pub trait AccesoRepository: Send + Sync {
+    /// Registra varias lecturas en una sola inserción
+    async fn registrar(&self, accesos: Vec<NuevoAcceso>) -> AppResult<()>;
+
+    /// Lecturas que cumplen el filtro, de la más reciente a la más antigua
+    async fn buscar(&self, filtro: AccesoFilter) -> AppResult<Vec<AccesoPersona>>;
+
+    /// Cuántas lecturas hizo un actor desde una fecha
+    async fn contar_por_actor(&self, idactor: i64, desde: DateTime<Utc>) -> AppResult<i64>;
+}
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+
+use crate::{
+    domain::{Adjunto, NuevoAdjunto},
+    errors::AppResult,
+};
+
+/// Puerto (interface) de los metadatos de archivos adjuntos a personas
+#[async_trait]
+pub trait AdjuntoRepository: Send + Sync {
+    async fn create(&self, adjunto: NuevoAdjunto) -> AppResult<Adjunto>;
+
+    /// Adjuntos de una persona, del más reciente al más antiguo
+    async fn list_by_persona(&self, idper: i64) -> AppResult<Vec<Adjunto>>;
+
+    async fn get(&self, idper: i64, idadj: i64) -> AppResult<Option<Adjunto>>;
+
+    /// Elimina los metadatos; devuelve si existía
+    async fn delete(&self, idper: i64, idadj: i64) -> AppResult<bool>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::{
+    domain::{AuditoriaFilter, NuevaAuditoria, RegistroAuditoria},
+    errors::AppResult,
+};
+
+/// Puerto (interface) del registro de auditoría
+/// Es de solo inserción: no hay operaciones para modificar ni borrar entradas
+#[async_trait]
+pub trait AuditoriaRepository: Send + Sync {
+    /// Agrega una entrada al registro
+    async fn registrar(&self, entrada: NuevaAuditoria) -> AppResult<()>;
+
+    /// Consulta el registro, de lo más reciente a lo más antiguo
+    async fn buscar(&self, filtro: AuditoriaFilter) -> AppResult<Vec<RegistroAuditoria>>;
+}
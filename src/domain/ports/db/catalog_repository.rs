@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+
+use crate::{
+    domain::{CatalogDef, CatalogItem},
+    errors::AppResult,
+};
+
+/// Puerto (interface) genérico para catálogos de referencia
+/// Cada método recibe la definición del catálogo sobre el que opera
+#[async_trait]
+pub trait CatalogRepository: Send + Sync {
+    /// Lista todos los elementos del catálogo ordenados por id
+    async fn list(&self, catalogo: &CatalogDef) -> AppResult<Vec<CatalogItem>>;
+
+    /// Obtiene un elemento por id
+    async fn get(&self, catalogo: &CatalogDef, id: i64) -> AppResult<Option<CatalogItem>>;
+
+    /// Crea un elemento con el id indicado
+    async fn create(&self, catalogo: &CatalogDef, item: CatalogItem) -> AppResult<CatalogItem>;
+
+    /// Actualiza nombre y estado de un elemento
+    async fn update(&self, catalogo: &CatalogDef, id: i64, item: CatalogItem) -> AppResult<CatalogItem>;
+
+    /// Elimina un elemento (lógico si el catálogo tiene columna de activo)
+    async fn delete(&self, catalogo: &CatalogDef, id: i64) -> AppResult<()>;
+}
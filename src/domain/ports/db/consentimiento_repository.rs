@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{CanalConsentimiento, Consentimiento, PoliticaDatos},
+    errors::AppResult,
+};
+
+/// Puerto (interface) para políticas de tratamiento de datos y consentimientos
+#[async_trait]
+pub trait ConsentimientoRepository: Send + Sync {
+    /// Publica una nueva versión de la política
+    async fn create_politica(
+        &self,
+        verpol: &str,
+        texpol: &str,
+        fecpol: DateTime<Utc>,
+    ) -> AppResult<PoliticaDatos>;
+
+    /// Todas las versiones, de la más reciente a la más antigua
+    async fn list_politicas(&self) -> AppResult<Vec<PoliticaDatos>>;
+
+    /// Busca una versión por su texto de versión
+    async fn get_politica_by_version(&self, verpol: &str) -> AppResult<Option<PoliticaDatos>>;
+
+    /// Política vigente en `fecha`: la más reciente cuya vigencia ya inició
+    async fn get_politica_vigente(&self, fecha: DateTime<Utc>) -> AppResult<Option<PoliticaDatos>>;
+
+    /// Registra la aceptación de una política
+    async fn aceptar(
+        &self,
+        idper: i64,
+        idpol: i64,
+        cancon: CanalConsentimiento,
+        feccon: DateTime<Utc>,
+    ) -> AppResult<Consentimiento>;
+
+    /// Revoca todos los consentimientos vigentes de una persona; devuelve cuántos revocó
+    async fn revocar(&self, idper: i64, fecrev: DateTime<Utc>) -> AppResult<u64>;
+
+    /// Historial de consentimientos de una persona, del más antiguo al más reciente
+    async fn list_by_persona(&self, idper: i64) -> AppResult<Vec<Consentimiento>>;
+}
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        CandidatoDuplicado, DetalleFusion, DuplicadoFilter, FusionPersona, NuevoCandidato, Persona,
+    },
+    errors::AppResult,
+};
+
+/// Fusión por aplicar; las dos personas llevan la versión (`verper`) leída
+#[derive(Debug, Clone)]
+pub struct NuevaFusion {
+    /// Persona que se conserva, ya con los campos heredados
+    pub superviviente: Persona,
+    /// Persona absorbida, ya con los valores con los que queda archivada
+    pub absorbida: Persona,
+    pub iddup: Option<i64>,
+    pub idactor: i64,
+    /// Campos heredados (el resto del detalle lo completa el repositorio)
+    pub detalle: DetalleFusion,
+    pub fecfus: DateTime<Utc>,
+}
+
+/// Puerto (interface) de los candidatos a duplicado y de las fusiones
+#[async_trait]
+pub trait DuplicadoRepository: Send + Sync {
+    /// Registra los candidatos nuevos; los pares ya conocidos (en cualquier estado)
+    /// se ignoran. Devuelve cuántos se insertaron
+    async fn registrar(&self, candidatos: Vec<NuevoCandidato>) -> AppResult<u64>;
+
+    async fn buscar(&self, filtro: DuplicadoFilter) -> AppResult<Vec<CandidatoDuplicado>>;
+
+    async fn get(&self, iddup: i64) -> AppResult<Option<CandidatoDuplicado>>;
+
+    /// Marca un candidato pendiente como descartado; devuelve si estaba pendiente
+    async fn descartar(&self, iddup: i64, idrev: i64, fecrev: DateTime<Utc>) -> AppResult<bool>;
+
+    /// En una sola transacción: actualiza las dos personas (con control de versión),
+    /// mueve sus referencias a la superviviente (ver `DetalleFusion`) sin dejarle dos
+    /// aceptaciones vigentes de la misma política, registra ambas versiones en el
+    /// historial, cierra los candidatos de la absorbida y guarda la fusión
+    async fn fusionar(&self, fusion: NuevaFusion) -> AppResult<FusionPersona>;
+
+    /// Fusiones en las que participó una persona (como superviviente o absorbida)
+    async fn fusiones(&self, idper: i64) -> AppResult<Vec<FusionPersona>>;
+}
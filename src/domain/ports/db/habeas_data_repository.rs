@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::{
+    domain::{NuevaSolicitud, Persona, SolicitudHabeasData},
+    errors::AppResult,
+};
+
+/// Puerto (interface) para las solicitudes de habeas data (Ley 1581)
+#[async_trait]
+pub trait HabeasDataRepository: Send + Sync {
+    /// Registra una solicitud atendida
+    async fn registrar(&self, solicitud: NuevaSolicitud) -> AppResult<SolicitudHabeasData>;
+
+    /// Solicitudes de un titular, de la más antigua a la más reciente
+    async fn list_by_persona(&self, idper: i64) -> AppResult<Vec<SolicitudHabeasData>>;
+
+    /// Reemplaza los datos personales por valores neutros y registra la solicitud,
+    /// todo en una transacción; solo si la versión coincide con `verper`
+    async fn anonimizar(
+        &self,
+        idper: i64,
+        verper: i64,
+        solicitud: NuevaSolicitud,
+    ) -> AppResult<Persona>;
+}
@@ -1,5 +1,25 @@
+mod acceso_repository;
+mod adjunto_repository;
+mod auditoria_repository;
+mod catalog_repository;
+mod consentimiento_repository;
+mod duplicado_repository;
+mod habeas_data_repository;
 mod persona;
+mod persona_historial_repository;
 mod pagper_repository;
+mod ubicacion_repository;
+mod unit_of_work;
 
+pub use acceso_repository::AccesoRepository;
+pub use adjunto_repository::AdjuntoRepository;
+pub use auditoria_repository::AuditoriaRepository;
+pub use catalog_repository::CatalogRepository;
+pub use consentimiento_repository::ConsentimientoRepository;
+pub use duplicado_repository::{DuplicadoRepository, NuevaFusion};
+pub use habeas_data_repository::HabeasDataRepository;
 pub use persona::PersonaRepository;
+pub use persona_historial_repository::PersonaHistorialRepository;
 pub use pagper_repository::PagperRepository;
+pub use ubicacion_repository::UbicacionRepository;
+pub use unit_of_work::{Transaccion, UnitOfWork, en_transaccion};
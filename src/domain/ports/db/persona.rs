@@ -1,6 +1,8 @@
 /// Puerto para operaciones CRUD de Persona
 /// Define el contrato que cualquier repositorio de Persona debe cumplir
-use crate::{domain::Persona, errors::AppError};
+use futures::stream::BoxStream;
+
+use crate::{domain::{CambioEstado, Persona, PersonaFilter}, errors::AppError};
 
 #[async_trait::async_trait]
 pub trait PersonaRepository: Send + Sync {
@@ -13,6 +15,10 @@ pub trait PersonaRepository: Send + Sync {
     /// Get a person by their email
     async fn get_by_emaper(&self, emaper: &str) -> Result<Option<Persona>, AppError>;
 
+    /// Get the persons with a phone number, in E.164 (`+573001234567`)
+    /// A number may be shared (e.g. a family landline), so several can match
+    async fn get_by_telnor(&self, telnor: &str) -> Result<Vec<Persona>, AppError>;
+
     /// Get all persons (with possible pagination)
     async fn get_all(&self, limit: i64, offset: i64) -> Result<Vec<Persona>, AppError>;
     /// List persons by profile
@@ -21,17 +27,16 @@ pub trait PersonaRepository: Send + Sync {
     /// Create a new person
     async fn create(&self, persona: Persona) -> Result<Persona, AppError>;
 
-    /// Update a person
-    async fn update(&self, idper: i64, persona: Persona) -> Result<Persona, AppError>;
-
-    /// Delete a person (logical deletion if actper = 0)
-    async fn delete(&self, idper: i64) -> Result<(), AppError>;
+    /// Create several persons atomically in a single transaction
+    async fn create_many(&self, personas: Vec<Persona>) -> Result<Vec<Persona>, AppError>;
 
-    /// Activate a person
-    async fn activate(&self, idper: i64) -> Result<(), AppError>;
+    /// Update a person only if its current version matches `verper`
+    /// Returns `PreconditionFailed` when another write got there first
+    async fn update(&self, idper: i64, persona: Persona, verper: i64) -> Result<Persona, AppError>;
 
-    /// Deactivate a person
-    async fn deactivate(&self, idper: i64) -> Result<(), AppError>;
+    /// Change the lifecycle state (estper, motsus, fecsus and the derived actper)
+    /// only if the version matches `verper`
+    async fn change_estado(&self, idper: i64, cambio: CambioEstado, verper: i64) -> Result<Persona, AppError>;
 
     /// Check if a person exists
     async fn exists(&self, idper: i64) -> Result<bool, AppError>;
@@ -39,9 +44,17 @@ pub trait PersonaRepository: Send + Sync {
     /// Change a person's password
     async fn change_password(&self, idper: i64, new_password: &str) -> Result<(), AppError>;
 
+    /// Set (or clear with `None`) the current avatar version
+    /// Bumps `verper` and records a history version like every other write, so the
+    /// persona ETag follows `avatar_url`; not conditioned on `verper`
+    async fn set_avatar(&self, idper: i64, avaper: Option<&str>) -> Result<Persona, AppError>;
+
     /// Get the total number of persons
     async fn count(&self) -> Result<i64, AppError>;
 
     /// Get active persons
     async fn get_active(&self, limit: i64, offset: i64) -> Result<Vec<Persona>, AppError>;
+
+    /// Stream the persons matching a filter, ordered by idper, row by row
+    fn stream(&self, filtro: PersonaFilter) -> BoxStream<'static, Result<Persona, AppError>>;
 }
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{domain::PersonaVersion, errors::AppResult};
+
+/// Puerto (interface) de lectura del historial temporal de personas
+/// Las versiones las escribe el repositorio de Persona en la misma transacción
+/// que cada cambio, por eso aquí solo hay consultas
+#[async_trait]
+pub trait PersonaHistorialRepository: Send + Sync {
+    /// Versiones de una persona, de la más reciente a la más antigua
+    async fn list_by_persona(&self, idper: i64) -> AppResult<Vec<PersonaVersion>>;
+
+    /// Una versión concreta por su número (`verper`)
+    async fn get_version(&self, idper: i64, verper: i64) -> AppResult<Option<PersonaVersion>>;
+
+    /// La versión vigente en un instante dado
+    async fn as_of(&self, idper: i64, fecha: DateTime<Utc>) -> AppResult<Option<PersonaVersion>>;
+}
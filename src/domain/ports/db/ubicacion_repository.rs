@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+
+use crate::{
+    domain::{Departamento, Municipio},
+    errors::AppResult,
+};
+
+/// Puerto (interface) para el catálogo de ubicaciones DIVIPOLA
+#[async_trait]
+pub trait UbicacionRepository: Send + Sync {
+    /// Lista todos los departamentos ordenados por nombre
+    async fn list_departamentos(&self) -> AppResult<Vec<Departamento>>;
+
+    /// Lista los municipios de un departamento ordenados por nombre
+    async fn list_municipios(&self, coddep: i64) -> AppResult<Vec<Municipio>>;
+
+    /// Obtiene un municipio por su código DANE
+    async fn get_municipio(&self, codubi: i64) -> AppResult<Option<Municipio>>;
+
+    /// Busca municipios cuyo nombre contenga `q` (autocompletado)
+    async fn search_municipios(&self, q: &str, limit: i64) -> AppResult<Vec<Municipio>>;
+
+    /// Inserta o actualiza departamentos y municipios en una sola transacción
+    /// Devuelve cuántos departamentos y municipios se procesaron
+    async fn import(
+        &self,
+        departamentos: Vec<Departamento>,
+        municipios: Vec<Municipio>,
+    ) -> AppResult<(usize, usize)>;
+}
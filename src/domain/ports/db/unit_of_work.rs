@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+
+use crate::{
+    domain::db::{
+        ConsentimientoRepository, DuplicadoRepository, HabeasDataRepository, PersonaRepository,
+    },
+    errors::AppResult,
+};
+
+/// Puerto (interface) de unidad de trabajo: abre transacciones que agrupan
+/// llamadas a varios repositorios para que se confirmen o se deshagan juntas
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    async fn begin(&self) -> AppResult<Box<dyn Transaccion>>;
+}
+
+/// Transacción abierta por `UnitOfWork::begin`
+/// Sus repositorios escriben dentro de la transacción; mientras siga abierta,
+/// todas las llamadas de la operación deben pasar por ellos (el motor puede
+/// bloquear a los repositorios de fuera hasta que termine)
+/// Si se descarta sin `commit`, se deshace
+#[async_trait]
+pub trait Transaccion: Send + Sync {
+    fn persona(&self) -> &dyn PersonaRepository;
+
+    fn consentimiento(&self) -> &dyn ConsentimientoRepository;
+
+    fn duplicado(&self) -> &dyn DuplicadoRepository;
+
+    fn habeas_data(&self) -> &dyn HabeasDataRepository;
+
+    async fn commit(self: Box<Self>) -> AppResult<()>;
+
+    async fn rollback(self: Box<Self>) -> AppResult<()>;
+}
+
+/// Ejecuta `operacion` dentro de una transacción: confirma si devuelve `Ok`
+/// y deshace si devuelve `Err`
+///
+/// ```ignore
+/// en_transaccion(uow, |tx| Box::pin(async move {
+///     let persona = tx.persona().create(persona).await?;
+///     tx.consentimiento().aceptar(persona.idper, idpol, cancon, ahora).await?;
+///     Ok(persona)
+/// })).await
+/// ```
+pub async fn en_transaccion<T, F>(uow: &dyn UnitOfWork, operacion: F) -> AppResult<T>
+where
+    T: Send,
+    F: for<'t> FnOnce(&'t dyn Transaccion) -> BoxFuture<'t, AppResult<T>> + Send,
+{
+    let tx = uow.begin().await?;
+    match operacion(&*tx).await {
+        Ok(valor) => {
+            tx.commit().await?;
+            Ok(valor)
+        }
+        Err(e) => {
+            if let Err(error_rollback) = tx.rollback().await {
+                tracing::error!("No se pudo deshacer la transacción: {:?}", error_rollback);
+            }
+            Err(e)
+        }
+    }
+}
@@ -2,3 +2,4 @@
 /// Esto es parte de la arquitectura limpia, permitiendo abstracciones sin inyección de dependencias
 pub mod cache;
 pub mod db;
+pub mod storage;
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::errors::AppResult;
+
+/// Puerto (interface) de almacenamiento de objetos (sistema de archivos, S3, MinIO...)
+/// Las claves usan `/` como separador y nunca vienen del cliente
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    /// Guarda (o reemplaza) el objeto
+    async fn put(&self, key: &str, content_type: &str, data: Bytes) -> AppResult<()>;
+
+    /// Contenido del objeto; `NotFound` si no existe
+    async fn get(&self, key: &str) -> AppResult<Bytes>;
+
+    /// Elimina el objeto; no falla si ya no existía
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
@@ -4,6 +4,10 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use serde_json::json;
+use std::collections::BTreeMap;
+
+/// Errores de validación por campo: nombre del campo -> mensajes
+pub type FieldErrors = BTreeMap<String, Vec<String>>;
 
 /// Error personalizado de la aplicación
 /// thiserror genera automáticamente el trait Error para nosotros
@@ -27,8 +31,17 @@ pub enum AppError {
     #[error("Prohibido: {0}")]
     Forbidden(String),
 
-    #[error("Validación fallida: {0}")]
-    Validation(String),
+    #[error("Conflicto: {0}")]
+    Conflict(String),
+
+    #[error("Precondición fallida: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Precondición requerida: {0}")]
+    PreconditionRequired(String),
+
+    #[error("Validación fallida: {0:?}")]
+    Validation(FieldErrors),
 
     #[error("Error interno: {0}")]
     Internal(String),
@@ -38,6 +51,9 @@ pub enum AppError {
 /// Esto es lo que hace Rust tan poderoso: type safety hasta en los errores
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        // Solo los errores de validación llevan el detalle por campo
+        let mut campos = None;
+
         let (status, error_message) = match self {
             AppError::Database(err) => {
                 tracing::error!("Database error: {:?}", err);
@@ -66,9 +82,25 @@ impl IntoResponse for AppError {
                 tracing::warn!("Forbidden: {}", msg);
                 (StatusCode::FORBIDDEN, msg)
             }
-            AppError::Validation(msg) => {
-                tracing::warn!("Validation error: {}", msg);
-                (StatusCode::BAD_REQUEST, msg)
+            AppError::Conflict(msg) => {
+                tracing::warn!("Conflict: {}", msg);
+                (StatusCode::CONFLICT, msg)
+            }
+            AppError::PreconditionFailed(msg) => {
+                tracing::warn!("Precondition failed: {}", msg);
+                (StatusCode::PRECONDITION_FAILED, msg)
+            }
+            AppError::PreconditionRequired(msg) => {
+                tracing::warn!("Precondition required: {}", msg);
+                (StatusCode::PRECONDITION_REQUIRED, msg)
+            }
+            AppError::Validation(errores) => {
+                tracing::warn!("Validation error: {:?}", errores);
+                campos = Some(errores);
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "Validación fallida".to_string(),
+                )
             }
             AppError::Internal(msg) => {
                 tracing::error!("Internal error: {}", msg);
@@ -79,14 +111,76 @@ impl IntoResponse for AppError {
             }
         };
 
-        let body = Json(json!({
-            "error": error_message,
-        }));
+        let body = match campos {
+            Some(campos) => Json(json!({
+                "error": error_message,
+                "campos": campos,
+            })),
+            None => Json(json!({
+                "error": error_message,
+            })),
+        };
 
         (status, body).into_response()
     }
 }
 
+impl AppError {
+    /// Error de validación de un único campo
+    pub fn validation(campo: &str, mensaje: impl Into<String>) -> Self {
+        AppError::Validation(BTreeMap::from([(campo.to_string(), vec![mensaje.into()])]))
+    }
+}
+
+/// Convierte los errores del crate validator al mapa campo -> mensajes
+/// Los errores de validaciones a nivel de struct quedan bajo la clave `__all__`
+impl From<validator::ValidationErrors> for AppError {
+    fn from(errores: validator::ValidationErrors) -> Self {
+        AppError::Validation(field_errors(&errores))
+    }
+}
+
+/// Aplana `ValidationErrors` (incluidos structs anidados) a `campo -> mensajes`
+pub fn field_errors(errores: &validator::ValidationErrors) -> FieldErrors {
+    let mut campos = FieldErrors::new();
+    collect_field_errors(errores, "", &mut campos);
+    campos
+}
+
+fn collect_field_errors(
+    errores: &validator::ValidationErrors,
+    prefijo: &str,
+    campos: &mut FieldErrors,
+) {
+    use validator::ValidationErrorsKind;
+
+    for (campo, kind) in errores.errors() {
+        let ruta = if prefijo.is_empty() {
+            campo.to_string()
+        } else {
+            format!("{}.{}", prefijo, campo)
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(lista) => {
+                let mensajes = campos.entry(ruta).or_default();
+                mensajes.extend(lista.iter().map(|e| match &e.message {
+                    Some(mensaje) => mensaje.to_string(),
+                    None => e.code.to_string(),
+                }));
+            }
+            ValidationErrorsKind::Struct(anidado) => {
+                collect_field_errors(anidado, &ruta, campos);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (i, anidado) in items {
+                    collect_field_errors(anidado, &format!("{}[{}]", ruta, i), campos);
+                }
+            }
+        }
+    }
+}
+
 /// Tipo de resultado que usaremos en toda la app
 /// En lugar de Result<T, E> escribimos AppResult<T>
 pub type AppResult<T> = Result<T, AppError>;
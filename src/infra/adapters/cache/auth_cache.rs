@@ -25,6 +25,12 @@ impl MemoryCacheImpl {
     }
 }
 
+impl Default for MemoryCacheImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl CacheRepository for MemoryCacheImpl {
     async fn get<T>(&self, key: &str) -> AppResult<Option<T>>
@@ -0,0 +1,76 @@
+use std::cmp::Reverse;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::{MemoryDb, paginar};
+use crate::{
+    domain::{AccesoFilter, AccesoPersona, NuevoAcceso, db::AccesoRepository},
+    errors::AppResult,
+};
+
+/// Máximo de entradas por consulta
+const MAX_LIMIT: i64 = 500;
+
+pub struct AccesoRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl AccesoRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl AccesoRepository for AccesoRepositoryMemory {
+    async fn registrar(&self, accesos: Vec<NuevoAcceso>) -> AppResult<()> {
+        let mut tablas = self.db.tablas.write().await;
+        for acceso in accesos {
+            let idacc = tablas.siguiente("acceso_persona");
+            tablas.accesos.push(AccesoPersona {
+                idacc,
+                idactor: acceso.idactor,
+                idper: acceso.idper,
+                via: acceso.via,
+                proposito: acceso.contexto.proposito,
+                campos: acceso.campos,
+                ip: acceso.contexto.ip,
+                idsolicitud: acceso.contexto.request_id,
+                fecacc: acceso.fecacc,
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn buscar(&self, filtro: AccesoFilter) -> AppResult<Vec<AccesoPersona>> {
+        let tablas = self.db.tablas.read().await;
+        let mut accesos: Vec<_> = tablas
+            .accesos
+            .iter()
+            .filter(|a| {
+                filtro.idactor.is_none_or(|idactor| a.idactor == idactor)
+                    && filtro.idper.is_none_or(|idper| a.idper == idper)
+                    && filtro.via.as_ref().is_none_or(|via| &a.via == via)
+                    && filtro.desde.is_none_or(|desde| a.fecacc >= desde)
+                    && filtro.hasta.is_none_or(|hasta| a.fecacc < hasta)
+            })
+            .cloned()
+            .collect();
+        accesos.sort_by_key(|x| Reverse((x.fecacc, x.idacc)));
+
+        Ok(paginar(accesos, filtro.limit, filtro.offset, MAX_LIMIT))
+    }
+
+    async fn contar_por_actor(&self, idactor: i64, desde: DateTime<Utc>) -> AppResult<i64> {
+        let tablas = self.db.tablas.read().await;
+        let total = tablas
+            .accesos
+            .iter()
+            .filter(|a| a.idactor == idactor && a.fecacc >= desde)
+            .count();
+
+        Ok(total as i64)
+    }
+}
@@ -0,0 +1,73 @@
+use std::cmp::Reverse;
+
+use async_trait::async_trait;
+
+use super::MemoryDb;
+use crate::{
+    domain::{Adjunto, NuevoAdjunto, db::AdjuntoRepository},
+    errors::AppResult,
+};
+
+pub struct AdjuntoRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl AdjuntoRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl AdjuntoRepository for AdjuntoRepositoryMemory {
+    async fn create(&self, adjunto: NuevoAdjunto) -> AppResult<Adjunto> {
+        let mut tablas = self.db.tablas.write().await;
+        let creado = Adjunto {
+            idadj: tablas.siguiente("adjunto"),
+            idper: adjunto.idper,
+            catadj: adjunto.catadj,
+            nomadj: adjunto.nomadj,
+            tipadj: adjunto.tipadj,
+            tamadj: adjunto.tamadj,
+            hashadj: adjunto.hashadj,
+            clvadj: adjunto.clvadj,
+            idsubidor: adjunto.idsubidor,
+            fecadj: adjunto.fecadj,
+        };
+        tablas.adjuntos.push(creado.clone());
+
+        Ok(creado)
+    }
+
+    async fn list_by_persona(&self, idper: i64) -> AppResult<Vec<Adjunto>> {
+        let tablas = self.db.tablas.read().await;
+        let mut adjuntos: Vec<_> = tablas
+            .adjuntos
+            .iter()
+            .filter(|a| a.idper == idper)
+            .cloned()
+            .collect();
+        adjuntos.sort_by_key(|x| Reverse((x.fecadj, x.idadj)));
+
+        Ok(adjuntos)
+    }
+
+    async fn get(&self, idper: i64, idadj: i64) -> AppResult<Option<Adjunto>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas
+            .adjuntos
+            .iter()
+            .find(|a| a.idper == idper && a.idadj == idadj)
+            .cloned())
+    }
+
+    async fn delete(&self, idper: i64, idadj: i64) -> AppResult<bool> {
+        let mut tablas = self.db.tablas.write().await;
+        let antes = tablas.adjuntos.len();
+        tablas
+            .adjuntos
+            .retain(|a| !(a.idper == idper && a.idadj == idadj));
+
+        Ok(tablas.adjuntos.len() < antes)
+    }
+}
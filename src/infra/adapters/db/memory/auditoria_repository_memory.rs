@@ -0,0 +1,74 @@
+use std::cmp::Reverse;
+
+use async_trait::async_trait;
+use sqlx::types::Json;
+
+use super::{MemoryDb, paginar};
+use crate::{
+    domain::{AuditoriaFilter, NuevaAuditoria, RegistroAuditoria, db::AuditoriaRepository},
+    errors::AppResult,
+};
+
+/// Máximo de entradas por consulta
+const MAX_LIMIT: i64 = 500;
+
+pub struct AuditoriaRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl AuditoriaRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl AuditoriaRepository for AuditoriaRepositoryMemory {
+    async fn registrar(&self, entrada: NuevaAuditoria) -> AppResult<()> {
+        let mut tablas = self.db.tablas.write().await;
+        let idaud = tablas.siguiente("auditoria");
+        tablas.auditoria.push(RegistroAuditoria {
+            idaud,
+            idactor: entrada.idactor,
+            accion: entrada.accion,
+            entidad: entrada.entidad,
+            identidad: entrada.identidad,
+            antes: entrada.antes.map(Json),
+            despues: entrada.despues.map(Json),
+            cambios: entrada.cambios.map(Json),
+            ip: entrada.contexto.ip,
+            agente: entrada.contexto.user_agent,
+            idsolicitud: entrada.contexto.request_id,
+            fecaud: entrada.fecaud,
+        });
+
+        Ok(())
+    }
+
+    async fn buscar(&self, filtro: AuditoriaFilter) -> AppResult<Vec<RegistroAuditoria>> {
+        let tablas = self.db.tablas.read().await;
+        let mut entradas: Vec<_> = tablas
+            .auditoria
+            .iter()
+            .filter(|e| {
+                filtro.idactor.is_none_or(|idactor| e.idactor == idactor)
+                    && filtro.accion.as_ref().is_none_or(|accion| &e.accion == accion)
+                    && filtro.entidad.as_ref().is_none_or(|entidad| &e.entidad == entidad)
+                    && filtro
+                        .identidad
+                        .as_ref()
+                        .is_none_or(|identidad| &e.identidad == identidad)
+                    && filtro
+                        .idsolicitud
+                        .as_ref()
+                        .is_none_or(|idsolicitud| e.idsolicitud.as_ref() == Some(idsolicitud))
+                    && filtro.desde.is_none_or(|desde| e.fecaud >= desde)
+                    && filtro.hasta.is_none_or(|hasta| e.fecaud < hasta)
+            })
+            .cloned()
+            .collect();
+        entradas.sort_by_key(|x| Reverse((x.fecaud, x.idaud)));
+
+        Ok(paginar(entradas, filtro.limit, filtro.offset, MAX_LIMIT))
+    }
+}
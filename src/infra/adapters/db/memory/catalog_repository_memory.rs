@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+
+use super::MemoryDb;
+use crate::{
+    domain::{CatalogDef, CatalogItem, db::CatalogRepository},
+    errors::{AppError, AppResult},
+};
+
+/// Catálogos en memoria, uno por `CatalogDef::name`
+/// Sin columna de activo los elementos siempre están activos y el borrado es físico,
+/// igual que en los adaptadores SQL
+pub struct CatalogRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl CatalogRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+fn no_encontrado(catalogo: &CatalogDef, id: i64) -> AppError {
+    AppError::NotFound(format!(
+        "Elemento {} no encontrado en el catálogo {}",
+        id, catalogo.name
+    ))
+}
+
+#[async_trait]
+impl CatalogRepository for CatalogRepositoryMemory {
+    async fn list(&self, catalogo: &CatalogDef) -> AppResult<Vec<CatalogItem>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas
+            .catalogos
+            .get(catalogo.name)
+            .map(|items| items.values().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn get(&self, catalogo: &CatalogDef, id: i64) -> AppResult<Option<CatalogItem>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas
+            .catalogos
+            .get(catalogo.name)
+            .and_then(|items| items.get(&id))
+            .cloned())
+    }
+
+    async fn create(&self, catalogo: &CatalogDef, item: CatalogItem) -> AppResult<CatalogItem> {
+        let mut tablas = self.db.tablas.write().await;
+        let items = tablas.catalogos.entry(catalogo.name).or_default();
+        if items.contains_key(&item.id) {
+            return Err(AppError::Conflict(format!(
+                "Ya existe el elemento {} en el catálogo {}",
+                item.id, catalogo.name
+            )));
+        }
+
+        let creado = CatalogItem {
+            activo: catalogo.active_col.is_none() || item.activo,
+            ..item
+        };
+        items.insert(creado.id, creado.clone());
+        Ok(creado)
+    }
+
+    async fn update(
+        &self,
+        catalogo: &CatalogDef,
+        id: i64,
+        item: CatalogItem,
+    ) -> AppResult<CatalogItem> {
+        let mut tablas = self.db.tablas.write().await;
+        let actual = tablas
+            .catalogos
+            .get_mut(catalogo.name)
+            .and_then(|items| items.get_mut(&id))
+            .ok_or_else(|| no_encontrado(catalogo, id))?;
+
+        actual.nombre = item.nombre;
+        if catalogo.active_col.is_some() {
+            actual.activo = item.activo;
+        }
+        Ok(actual.clone())
+    }
+
+    async fn delete(&self, catalogo: &CatalogDef, id: i64) -> AppResult<()> {
+        let mut tablas = self.db.tablas.write().await;
+        let items = tablas
+            .catalogos
+            .get_mut(catalogo.name)
+            .ok_or_else(|| no_encontrado(catalogo, id))?;
+
+        match catalogo.active_col {
+            Some(_) => {
+                items
+                    .get_mut(&id)
+                    .ok_or_else(|| no_encontrado(catalogo, id))?
+                    .activo = false;
+            }
+            None => {
+                items.remove(&id).ok_or_else(|| no_encontrado(catalogo, id))?;
+            }
+        }
+
+        Ok(())
+    }
+}
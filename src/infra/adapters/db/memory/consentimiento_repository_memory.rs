@@ -0,0 +1,120 @@
+use std::cmp::Reverse;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::MemoryDb;
+use crate::{
+    domain::{CanalConsentimiento, Consentimiento, PoliticaDatos, db::ConsentimientoRepository},
+    errors::{AppError, AppResult},
+};
+
+pub struct ConsentimientoRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl ConsentimientoRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+/// Políticas de la más reciente a la más antigua
+fn ordenar(politicas: &mut [PoliticaDatos]) {
+    politicas.sort_by_key(|x| Reverse((x.fecpol, x.idpol)));
+}
+
+#[async_trait]
+impl ConsentimientoRepository for ConsentimientoRepositoryMemory {
+    async fn create_politica(
+        &self,
+        verpol: &str,
+        texpol: &str,
+        fecpol: DateTime<Utc>,
+    ) -> AppResult<PoliticaDatos> {
+        let mut tablas = self.db.tablas.write().await;
+        if tablas.politicas.iter().any(|p| p.verpol == verpol) {
+            return Err(AppError::Conflict(format!(
+                "Ya existe la versión {} de la política",
+                verpol
+            )));
+        }
+
+        let politica = PoliticaDatos {
+            idpol: tablas.siguiente("politica_datos"),
+            verpol: verpol.to_string(),
+            texpol: texpol.to_string(),
+            fecpol,
+        };
+        tablas.politicas.push(politica.clone());
+        Ok(politica)
+    }
+
+    async fn list_politicas(&self) -> AppResult<Vec<PoliticaDatos>> {
+        let mut politicas = self.db.tablas.read().await.politicas.clone();
+        ordenar(&mut politicas);
+
+        Ok(politicas)
+    }
+
+    async fn get_politica_by_version(&self, verpol: &str) -> AppResult<Option<PoliticaDatos>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas.politicas.iter().find(|p| p.verpol == verpol).cloned())
+    }
+
+    async fn get_politica_vigente(&self, fecha: DateTime<Utc>) -> AppResult<Option<PoliticaDatos>> {
+        let mut politicas = self.db.tablas.read().await.politicas.clone();
+        ordenar(&mut politicas);
+
+        Ok(politicas.into_iter().find(|p| p.fecpol <= fecha))
+    }
+
+    async fn aceptar(
+        &self,
+        idper: i64,
+        idpol: i64,
+        cancon: CanalConsentimiento,
+        feccon: DateTime<Utc>,
+    ) -> AppResult<Consentimiento> {
+        let mut tablas = self.db.tablas.write().await;
+        let consentimiento = Consentimiento {
+            idcon: tablas.siguiente("consentimiento"),
+            idper,
+            idpol,
+            cancon,
+            feccon,
+            fecrev: None,
+        };
+        tablas.consentimientos.push(consentimiento.clone());
+
+        Ok(consentimiento)
+    }
+
+    async fn revocar(&self, idper: i64, fecrev: DateTime<Utc>) -> AppResult<u64> {
+        let mut tablas = self.db.tablas.write().await;
+        let mut revocados = 0;
+        for consentimiento in tablas
+            .consentimientos
+            .iter_mut()
+            .filter(|c| c.idper == idper && c.fecrev.is_none())
+        {
+            consentimiento.fecrev = Some(fecrev);
+            revocados += 1;
+        }
+
+        Ok(revocados)
+    }
+
+    async fn list_by_persona(&self, idper: i64) -> AppResult<Vec<Consentimiento>> {
+        let tablas = self.db.tablas.read().await;
+        let mut consentimientos: Vec<_> = tablas
+            .consentimientos
+            .iter()
+            .filter(|c| c.idper == idper)
+            .cloned()
+            .collect();
+        consentimientos.sort_by_key(|c| (c.feccon, c.idcon));
+
+        Ok(consentimientos)
+    }
+}
@@ -0,0 +1,246 @@
+use std::cmp::Reverse;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+
+use super::{
+    MemoryDb, Tablas, paginar, persona_historial_repository_memory::registrar_version,
+};
+use crate::{
+    domain::{
+        CandidatoDuplicado, DuplicadoFilter, EstadoDuplicado, FusionPersona, NuevoCandidato,
+        Persona,
+        db::{DuplicadoRepository, NuevaFusion},
+    },
+    errors::{AppError, AppResult},
+};
+
+/// Máximo de filas por consulta
+const MAX_LIMIT: i64 = 500;
+
+pub struct DuplicadoRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl DuplicadoRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+/// Persona guardada con los cambios de la fusión, si su versión no cambió
+fn fusionada(tablas: &Tablas, persona: &Persona) -> AppResult<Persona> {
+    let actual = tablas
+        .personas
+        .get(&persona.idper)
+        .filter(|p| p.verper == persona.verper)
+        .ok_or_else(|| {
+            AppError::PreconditionFailed(format!(
+                "La persona {} fue modificada por otro usuario",
+                persona.idper
+            ))
+        })?;
+
+    Ok(Persona {
+        ndocper: persona.ndocper.clone(),
+        dirper: persona.dirper.clone(),
+        estper: persona.estper,
+        actper: persona.actper,
+        motsus: persona.motsus.clone(),
+        fecsus: persona.fecsus,
+        verper: actual.verper + 1,
+        ..actual.clone()
+    })
+}
+
+/// Cuenta y cambia de dueño las filas de la persona absorbida
+fn mover<'a, T: 'a>(
+    filas: impl Iterator<Item = &'a mut T>,
+    idper: impl Fn(&mut T) -> &mut i64,
+    idsup: i64,
+    idabs: i64,
+) -> u64 {
+    let mut movidas = 0;
+    for fila in filas {
+        let id = idper(fila);
+        if *id == idabs {
+            *id = idsup;
+            movidas += 1;
+        }
+    }
+    movidas
+}
+
+#[async_trait]
+impl DuplicadoRepository for DuplicadoRepositoryMemory {
+    async fn registrar(&self, candidatos: Vec<NuevoCandidato>) -> AppResult<u64> {
+        let mut tablas = self.db.tablas.write().await;
+        let mut insertados = 0;
+        for candidato in candidatos {
+            let conocido = tablas
+                .duplicados
+                .iter()
+                .any(|d| d.idper1 == candidato.idper1 && d.idper2 == candidato.idper2);
+            if conocido {
+                continue;
+            }
+
+            let iddup = tablas.siguiente("duplicado");
+            tablas.duplicados.push(CandidatoDuplicado {
+                iddup,
+                idper1: candidato.idper1,
+                idper2: candidato.idper2,
+                pundup: candidato.pundup,
+                motdup: candidato.motdup,
+                estdup: EstadoDuplicado::Pendiente,
+                fecdup: candidato.fecdup,
+                idrev: None,
+                fecrev: None,
+            });
+            insertados += 1;
+        }
+
+        Ok(insertados)
+    }
+
+    async fn buscar(&self, filtro: DuplicadoFilter) -> AppResult<Vec<CandidatoDuplicado>> {
+        let tablas = self.db.tablas.read().await;
+        let estdup = filtro.estdup.unwrap_or(EstadoDuplicado::Pendiente);
+        let mut candidatos: Vec<_> = tablas
+            .duplicados
+            .iter()
+            .filter(|d| {
+                d.estdup == estdup
+                    && filtro
+                        .idper
+                        .is_none_or(|idper| d.idper1 == idper || d.idper2 == idper)
+            })
+            .cloned()
+            .collect();
+        candidatos.sort_by(|a, b| {
+            b.pundup
+                .total_cmp(&a.pundup)
+                .then_with(|| a.iddup.cmp(&b.iddup))
+        });
+
+        Ok(paginar(candidatos, filtro.limit, filtro.offset, MAX_LIMIT))
+    }
+
+    async fn get(&self, iddup: i64) -> AppResult<Option<CandidatoDuplicado>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas.duplicados.iter().find(|d| d.iddup == iddup).cloned())
+    }
+
+    async fn descartar(&self, iddup: i64, idrev: i64, fecrev: DateTime<Utc>) -> AppResult<bool> {
+        let mut tablas = self.db.tablas.write().await;
+        let Some(candidato) = tablas
+            .duplicados
+            .iter_mut()
+            .find(|d| d.iddup == iddup && d.estdup == EstadoDuplicado::Pendiente)
+        else {
+            return Ok(false);
+        };
+
+        candidato.estdup = EstadoDuplicado::Descartado;
+        candidato.idrev = Some(idrev);
+        candidato.fecrev = Some(fecrev);
+        Ok(true)
+    }
+
+    async fn fusionar(&self, fusion: NuevaFusion) -> AppResult<FusionPersona> {
+        let NuevaFusion {
+            superviviente,
+            absorbida,
+            iddup,
+            idactor,
+            mut detalle,
+            fecfus,
+        } = fusion;
+        let (idsup, idabs) = (superviviente.idper, absorbida.idper);
+        let mut tablas = self.db.tablas.write().await;
+
+        // Todo se valida antes de escribir para que un fallo no deje la fusión a medias
+        if tablas.fusiones.iter().any(|f| f.idabs == idabs) {
+            return Err(AppError::Conflict(format!(
+                "La persona {} ya fue absorbida en otra fusión",
+                idabs
+            )));
+        }
+        let absorbida = fusionada(&tablas, &absorbida)?;
+        let superviviente = fusionada(&tablas, &superviviente)?;
+
+        tablas.personas.insert(idabs, absorbida.clone());
+        tablas.personas.insert(idsup, superviviente.clone());
+
+        // Sin dos aceptaciones vigentes de la misma política en la superviviente
+        let vigentes: Vec<i64> = tablas
+            .consentimientos
+            .iter()
+            .filter(|c| c.idper == idsup && c.fecrev.is_none())
+            .map(|c| c.idpol)
+            .collect();
+        for consentimiento in tablas.consentimientos.iter_mut() {
+            if consentimiento.idper == idabs
+                && consentimiento.fecrev.is_none()
+                && vigentes.contains(&consentimiento.idpol)
+            {
+                consentimiento.fecrev = Some(fecfus);
+                detalle.consentimientos_cerrados += 1;
+            }
+        }
+        detalle.consentimientos = mover(
+            tablas.consentimientos.iter_mut(),
+            |c| &mut c.idper,
+            idsup,
+            idabs,
+        );
+        detalle.adjuntos = mover(tablas.adjuntos.iter_mut(), |a| &mut a.idper, idsup, idabs);
+
+        registrar_version(&mut tablas, &absorbida, fecfus);
+        registrar_version(&mut tablas, &superviviente, fecfus);
+
+        // El candidato que originó la fusión queda resuelto; los demás pendientes
+        // de la absorbida ya no tienen sentido
+        for candidato in tablas.duplicados.iter_mut() {
+            let estdup = if Some(candidato.iddup) == iddup {
+                EstadoDuplicado::Fusionado
+            } else if candidato.estdup == EstadoDuplicado::Pendiente
+                && (candidato.idper1 == idabs || candidato.idper2 == idabs)
+            {
+                EstadoDuplicado::Descartado
+            } else {
+                continue;
+            };
+            candidato.estdup = estdup;
+            candidato.idrev = Some(idactor);
+            candidato.fecrev = Some(fecfus);
+        }
+
+        let registro = FusionPersona {
+            idfus: tablas.siguiente("fusion_persona"),
+            idsup,
+            idabs,
+            iddup,
+            idactor,
+            detfus: Json(detalle),
+            fecfus,
+        };
+        tablas.fusiones.push(registro.clone());
+
+        Ok(registro)
+    }
+
+    async fn fusiones(&self, idper: i64) -> AppResult<Vec<FusionPersona>> {
+        let tablas = self.db.tablas.read().await;
+        let mut fusiones: Vec<_> = tablas
+            .fusiones
+            .iter()
+            .filter(|f| f.idsup == idper || f.idabs == idper)
+            .cloned()
+            .collect();
+        fusiones.sort_by_key(|x| Reverse((x.fecfus, x.idfus)));
+
+        Ok(fusiones)
+    }
+}
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{
+    MemoryDb, Tablas,
+    persona_historial_repository_memory::{purgar_historial, registrar_version},
+};
+use crate::{
+    domain::{
+        DATO_ANONIMO, EstadoPersona, NuevaSolicitud, Persona, SolicitudHabeasData,
+        TELEFONO_ANONIMO, db::HabeasDataRepository, email_anonimo,
+    },
+    errors::{AppError, AppResult},
+};
+
+pub struct HabeasDataRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl HabeasDataRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+fn insertar(tablas: &mut Tablas, solicitud: NuevaSolicitud) -> SolicitudHabeasData {
+    let registrada = SolicitudHabeasData {
+        idsol: tablas.siguiente("habeas_data"),
+        idper: solicitud.idper,
+        tipsol: solicitud.tipsol,
+        idsolicitante: solicitud.idsolicitante,
+        fecsol: solicitud.fecsol,
+    };
+    tablas.solicitudes.push(registrada.clone());
+    registrada
+}
+
+#[async_trait]
+impl HabeasDataRepository for HabeasDataRepositoryMemory {
+    async fn registrar(&self, solicitud: NuevaSolicitud) -> AppResult<SolicitudHabeasData> {
+        Ok(insertar(&mut *self.db.tablas.write().await, solicitud))
+    }
+
+    async fn list_by_persona(&self, idper: i64) -> AppResult<Vec<SolicitudHabeasData>> {
+        let tablas = self.db.tablas.read().await;
+        let mut solicitudes: Vec<_> = tablas
+            .solicitudes
+            .iter()
+            .filter(|s| s.idper == idper)
+            .cloned()
+            .collect();
+        solicitudes.sort_by_key(|s| (s.fecsol, s.idsol));
+
+        Ok(solicitudes)
+    }
+
+    async fn anonimizar(
+        &self,
+        idper: i64,
+        verper: i64,
+        solicitud: NuevaSolicitud,
+    ) -> AppResult<Persona> {
+        let mut tablas = self.db.tablas.write().await;
+
+        // La fila se conserva (mismo idper) para no romper las referencias
+        let persona = tablas
+            .personas
+            .get_mut(&idper)
+            .filter(|p| p.verper == verper)
+            .ok_or_else(|| {
+                AppError::PreconditionFailed(
+                    "La persona fue modificada por otro usuario".to_string(),
+                )
+            })?;
+        *persona = Persona {
+            nomper: DATO_ANONIMO.to_string(),
+            apeper: DATO_ANONIMO.to_string(),
+            dirper: None,
+            telper: TELEFONO_ANONIMO.to_string(),
+            emaper: email_anonimo(idper),
+            ndocper: None,
+            pass: None,
+            estper: EstadoPersona::Archivado,
+            actper: false,
+            motsus: None,
+            fecsus: None,
+            avaper: None,
+            telnor: None,
+            verper: persona.verper + 1,
+            ..persona.clone()
+        };
+        let persona = persona.clone();
+
+        insertar(&mut tablas, solicitud);
+
+        // Las versiones anteriores también contienen datos personales
+        purgar_historial(&mut tablas, idper);
+        registrar_version(&mut tablas, &persona, Utc::now());
+
+        Ok(persona)
+    }
+}
@@ -0,0 +1,153 @@
+mod acceso_repository_memory;
+mod adjunto_repository_memory;
+mod auditoria_repository_memory;
+mod catalog_repository_memory;
+mod consentimiento_repository_memory;
+mod duplicado_repository_memory;
+mod habeas_data_repository_memory;
+mod pagper_repository_memory;
+mod persona_historial_repository_memory;
+mod persona_repository;
+mod ubicacion_repository_memory;
+mod unit_of_work_memory;
+
+pub use acceso_repository_memory::AccesoRepositoryMemory;
+pub use adjunto_repository_memory::AdjuntoRepositoryMemory;
+pub use auditoria_repository_memory::AuditoriaRepositoryMemory;
+pub use catalog_repository_memory::CatalogRepositoryMemory;
+pub use consentimiento_repository_memory::ConsentimientoRepositoryMemory;
+pub use duplicado_repository_memory::DuplicadoRepositoryMemory;
+pub use habeas_data_repository_memory::HabeasDataRepositoryMemory;
+pub use pagper_repository_memory::PagperRepositoryMemory;
+pub use persona_historial_repository_memory::PersonaHistorialRepositoryMemory;
+pub use persona_repository::PersonaRepositoryMemory;
+pub use ubicacion_repository_memory::UbicacionRepositoryMemory;
+pub use unit_of_work_memory::UnitOfWorkMemory;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use tokio::sync::RwLock;
+
+use crate::domain::{
+    AccesoPersona, Adjunto, CATALOGO_PERFIL, CATALOGO_TIPO_DOCUMENTO, CandidatoDuplicado,
+    CatalogItem, Consentimiento, Departamento, FusionPersona, Pagper, Persona, PersonaVersion,
+    PoliticaDatos, RegistroAuditoria, SolicitudHabeasData, TipoDocumento,
+};
+
+/// Base de datos en memoria compartida por todos los adaptadores `*Memory`
+/// Hace el papel del pool: cada adaptador guarda un clon y todos ven las mismas
+/// tablas, así las operaciones que cruzan tablas (historial, fusiones,
+/// anonimización) se comportan como en los motores SQL
+/// Cada operación toma el candado una sola vez, lo que equivale a una transacción
+#[derive(Clone)]
+pub struct MemoryDb {
+    tablas: Arc<RwLock<Tablas>>,
+}
+
+impl MemoryDb {
+    /// Base vacía con los catálogos que siembran las migraciones
+    pub fn new() -> Self {
+        let mut tablas = Tablas::default();
+        tablas.catalogos.insert(
+            CATALOGO_TIPO_DOCUMENTO,
+            TipoDocumento::TODOS
+                .into_iter()
+                .map(|t| {
+                    let item = CatalogItem {
+                        id: t.code(),
+                        nombre: t.nombre().to_string(),
+                        activo: true,
+                    };
+                    (item.id, item)
+                })
+                .collect(),
+        );
+        tablas.catalogos.insert(CATALOGO_PERFIL, BTreeMap::new());
+
+        Self {
+            tablas: Arc::new(RwLock::new(tablas)),
+        }
+    }
+
+    /// Registra una página (`pagina.codpag`) y devuelve su id
+    /// Si ya existe devuelve el id que tenía
+    pub async fn agregar_pagina(&self, codpag: &str) -> i64 {
+        let mut tablas = self.tablas.write().await;
+        if let Some(&idpag) = tablas.paginas.get(codpag) {
+            return idpag;
+        }
+        let idpag = tablas.siguiente("pagina");
+        tablas.paginas.insert(codpag.to_string(), idpag);
+        idpag
+    }
+
+    /// Registra un perfil con el id indicado (el 1 es el superadministrador)
+    pub async fn agregar_perfil(&self, idpef: i64, nompef: &str) {
+        let mut tablas = self.tablas.write().await;
+        tablas.catalogos.entry(CATALOGO_PERFIL).or_default().insert(
+            idpef,
+            CatalogItem {
+                id: idpef,
+                nombre: nompef.to_string(),
+                activo: true,
+            },
+        );
+    }
+
+    /// Concede (o reemplaza) los permisos de un perfil sobre una página
+    pub async fn conceder(&self, permiso: Pagper) {
+        let mut tablas = self.tablas.write().await;
+        tablas
+            .pagper
+            .retain(|p| !(p.idpef == permiso.idpef && p.idpag == permiso.idpag));
+        tablas.pagper.push(permiso);
+    }
+}
+
+impl Default for MemoryDb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Filas de cada tabla; los ids los asigna `siguiente` como lo haría una secuencia
+#[derive(Clone, Default)]
+struct Tablas {
+    secuencias: HashMap<&'static str, i64>,
+    personas: BTreeMap<i64, Persona>,
+    historial: Vec<PersonaVersion>,
+    /// codpag -> idpag
+    paginas: HashMap<String, i64>,
+    pagper: Vec<Pagper>,
+    departamentos: BTreeMap<i64, Departamento>,
+    /// codubi -> (nomubi, coddep)
+    municipios: BTreeMap<i64, (String, i64)>,
+    /// Elementos por nombre de catálogo
+    catalogos: HashMap<&'static str, BTreeMap<i64, CatalogItem>>,
+    politicas: Vec<PoliticaDatos>,
+    consentimientos: Vec<Consentimiento>,
+    solicitudes: Vec<SolicitudHabeasData>,
+    auditoria: Vec<RegistroAuditoria>,
+    accesos: Vec<AccesoPersona>,
+    adjuntos: Vec<Adjunto>,
+    duplicados: Vec<CandidatoDuplicado>,
+    fusiones: Vec<FusionPersona>,
+}
+
+impl Tablas {
+    fn siguiente(&mut self, tabla: &'static str) -> i64 {
+        let actual = self.secuencias.entry(tabla).or_insert(0);
+        *actual += 1;
+        *actual
+    }
+}
+
+/// Página de resultados con los mismos límites que los adaptadores SQL
+fn paginar<T>(filas: Vec<T>, limit: Option<i64>, offset: Option<i64>, max: i64) -> Vec<T> {
+    let limit = limit.unwrap_or(100).clamp(1, max) as usize;
+    let offset = offset.unwrap_or(0).max(0) as usize;
+    filas.into_iter().skip(offset).take(limit).collect()
+}
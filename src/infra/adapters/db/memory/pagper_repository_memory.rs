@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+
+use super::MemoryDb;
+use crate::{
+    domain::{Pagper, db::PagperRepository},
+    errors::AppResult,
+};
+
+/// Permisos página-perfil en memoria; las páginas y permisos se cargan con
+/// `MemoryDb::agregar_pagina` y `MemoryDb::conceder`
+pub struct PagperRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl PagperRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl PagperRepository for PagperRepositoryMemory {
+    async fn find_by_perfil(&self, idpef: i64) -> AppResult<Vec<Pagper>> {
+        let tablas = self.db.tablas.read().await;
+        let pagpers: Vec<Pagper> = tablas
+            .pagper
+            .iter()
+            .filter(|p| p.idpef == idpef && tablas.paginas.values().any(|&id| id == p.idpag))
+            .cloned()
+            .collect();
+
+        tracing::debug!(
+            "Permisos cargados para perfil {}: {} registros",
+            idpef,
+            pagpers.len()
+        );
+        Ok(pagpers)
+    }
+
+    async fn has_permission(&self, idpef: i64, codpag: &str, action: &str) -> AppResult<bool> {
+        let tablas = self.db.tablas.read().await;
+        let Some(&idpag) = tablas.paginas.get(codpag) else {
+            return Ok(false);
+        };
+        let Some(permiso) = tablas
+            .pagper
+            .iter()
+            .find(|p| p.idpef == idpef && p.idpag == idpag)
+        else {
+            return Ok(false);
+        };
+
+        Ok(match action {
+            "create" => permiso.can_create,
+            "read" => permiso.can_read,
+            "update" => permiso.can_update,
+            "delete" => permiso.can_delete,
+            _ => false,
+        })
+    }
+}
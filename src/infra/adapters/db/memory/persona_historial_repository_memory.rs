@@ -0,0 +1,93 @@
+use std::cmp::Reverse;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::{MemoryDb, Tablas};
+use crate::{
+    domain::{Persona, PersonaVersion, db::PersonaHistorialRepository},
+    errors::AppResult,
+};
+
+pub struct PersonaHistorialRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl PersonaHistorialRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+/// Cierra la versión vigente y abre una nueva con el estado actual de la persona
+/// Se llama con el candado de escritura ya tomado por la operación que la origina
+pub(super) fn registrar_version(tablas: &mut Tablas, persona: &Persona, desde: DateTime<Utc>) {
+    tablas
+        .historial
+        .iter_mut()
+        .filter(|v| v.idper == persona.idper && v.vighas.is_none())
+        .for_each(|v| v.vighas = Some(desde));
+
+    let idhis = tablas.siguiente("persona_historial");
+    tablas.historial.push(PersonaVersion {
+        idhis,
+        idper: persona.idper,
+        verper: persona.verper,
+        ndocper: persona.ndocper.clone(),
+        tdocper: persona.tdocper,
+        nomper: persona.nomper.clone(),
+        apeper: persona.apeper.clone(),
+        dirper: persona.dirper.clone(),
+        telper: persona.telper.clone(),
+        codubi: persona.codubi,
+        idpef: persona.idpef,
+        emaper: persona.emaper.clone(),
+        actper: persona.actper,
+        estper: persona.estper,
+        motsus: persona.motsus.clone(),
+        fecsus: persona.fecsus,
+        vigdes: desde,
+        vighas: None,
+    });
+}
+
+/// Elimina todas las versiones de una persona (anonimización)
+pub(super) fn purgar_historial(tablas: &mut Tablas, idper: i64) {
+    tablas.historial.retain(|v| v.idper != idper);
+}
+
+/// Versiones de una persona, de la más reciente a la más antigua
+fn versiones(tablas: &Tablas, idper: i64) -> Vec<PersonaVersion> {
+    let mut versiones: Vec<_> = tablas
+        .historial
+        .iter()
+        .filter(|v| v.idper == idper)
+        .cloned()
+        .collect();
+    versiones.sort_by_key(|x| Reverse((x.vigdes, x.idhis)));
+    versiones
+}
+
+#[async_trait]
+impl PersonaHistorialRepository for PersonaHistorialRepositoryMemory {
+    async fn list_by_persona(&self, idper: i64) -> AppResult<Vec<PersonaVersion>> {
+        Ok(versiones(&*self.db.tablas.read().await, idper))
+    }
+
+    async fn get_version(&self, idper: i64, verper: i64) -> AppResult<Option<PersonaVersion>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas
+            .historial
+            .iter()
+            .filter(|v| v.idper == idper && v.verper == verper)
+            .max_by_key(|v| v.idhis)
+            .cloned())
+    }
+
+    async fn as_of(&self, idper: i64, fecha: DateTime<Utc>) -> AppResult<Option<PersonaVersion>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(versiones(&tablas, idper)
+            .into_iter()
+            .find(|v| v.vigdes <= fecha && v.vighas.is_none_or(|hasta| hasta > fecha)))
+    }
+}
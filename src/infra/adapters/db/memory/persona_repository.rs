@@ -0,0 +1,261 @@
+/// Implementación del Puerto PersonaRepository sobre la base en memoria
+/// Sirve para pruebas y demostraciones: no persiste nada al terminar el proceso
+use crate::{domain::{CambioEstado, Persona, PersonaFilter, db::PersonaRepository}, errors::AppError};
+use chrono::Utc;
+use futures::stream::BoxStream;
+
+use super::{MemoryDb, Tablas, persona_historial_repository_memory::registrar_version};
+
+/// Repositorio de Persona que guarda las filas en memoria
+pub struct PersonaRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl PersonaRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+/// Las mismas restricciones únicas que el esquema SQL (email y documento)
+fn verificar_unicos(tablas: &Tablas, persona: &Persona) -> Result<(), AppError> {
+    let choca = tablas.personas.values().find(|p| {
+        p.idper != persona.idper
+            && (p.emaper == persona.emaper
+                || (p.ndocper.is_some() && p.ndocper == persona.ndocper))
+    });
+    match choca {
+        Some(_) => Err(AppError::Conflict(
+            "Ya existe una persona con ese email o documento".to_string(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Inserta una persona nueva como lo hace el INSERT de los adaptadores SQL:
+/// el id y la versión los asigna la base, el avatar y la suspensión empiezan vacíos
+fn insertar(tablas: &mut Tablas, persona: Persona) -> Result<Persona, AppError> {
+    let mut nueva = Persona {
+        idper: 0,
+        verper: 1,
+        motsus: None,
+        fecsus: None,
+        avaper: None,
+        ..persona
+    };
+    verificar_unicos(tablas, &nueva)?;
+    nueva.idper = tablas.siguiente("persona");
+    tablas.personas.insert(nueva.idper, nueva.clone());
+    registrar_version(tablas, &nueva, Utc::now());
+    Ok(nueva)
+}
+
+/// Persona a modificar si existe y su versión coincide con `verper`
+pub(super) fn vigente(tablas: &Tablas, idper: i64, verper: i64) -> Result<Persona, AppError> {
+    match tablas.personas.get(&idper) {
+        Some(persona) if persona.verper == verper => Ok(persona.clone()),
+        Some(_) => Err(AppError::PreconditionFailed(
+            "La persona fue modificada por otro usuario".to_string(),
+        )),
+        None => Err(AppError::NotFound("Persona no encontrada".to_string())),
+    }
+}
+
+/// Guarda la persona con la versión siguiente y registra la versión en el historial
+pub(super) fn guardar(tablas: &mut Tablas, mut persona: Persona) -> Result<Persona, AppError> {
+    verificar_unicos(tablas, &persona)?;
+    persona.verper += 1;
+    tablas.personas.insert(persona.idper, persona.clone());
+    registrar_version(tablas, &persona, Utc::now());
+    Ok(persona)
+}
+
+fn cumple(tablas: &Tablas, persona: &Persona, filtro: &PersonaFilter) -> bool {
+    let q = filtro
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(str::to_lowercase);
+
+    filtro.idpef.is_none_or(|idpef| persona.idpef == idpef)
+        && filtro.actper.is_none_or(|actper| persona.actper == actper)
+        && filtro.estper.is_none_or(|estper| persona.estper == estper)
+        && filtro.codubi.is_none_or(|codubi| persona.codubi == codubi)
+        && q.is_none_or(|q| {
+            [&persona.nomper, &persona.apeper, &persona.emaper]
+                .iter()
+                .any(|campo| campo.to_lowercase().contains(&q))
+        })
+        && filtro.excluir_idpef.is_none_or(|idpef| persona.idpef != idpef)
+        && filtro.con_consentimiento.is_none_or(|idpol| {
+            tablas.consentimientos.iter().any(|c| {
+                c.idper == persona.idper && c.idpol == idpol && c.fecrev.is_none()
+            })
+        })
+}
+
+fn pagina(personas: impl Iterator<Item = Persona>, limit: i64, offset: i64) -> Vec<Persona> {
+    personas
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl PersonaRepository for PersonaRepositoryMemory {
+    async fn get_by_idper(&self, idper: i64) -> Result<Option<Persona>, AppError> {
+        Ok(self.db.tablas.read().await.personas.get(&idper).cloned())
+    }
+
+    async fn get_by_ndocper(&self, ndocper: &str) -> Result<Option<Persona>, AppError> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas
+            .personas
+            .values()
+            .find(|p| p.ndocper.as_deref() == Some(ndocper))
+            .cloned())
+    }
+
+    async fn get_by_emaper(&self, emaper: &str) -> Result<Option<Persona>, AppError> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas.personas.values().find(|p| p.emaper == emaper).cloned())
+    }
+
+    async fn get_by_telnor(&self, telnor: &str) -> Result<Vec<Persona>, AppError> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas
+            .personas
+            .values()
+            .filter(|p| p.telnor.as_deref() == Some(telnor))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_all(&self, limit: i64, offset: i64) -> Result<Vec<Persona>, AppError> {
+        let tablas = self.db.tablas.read().await;
+        Ok(pagina(tablas.personas.values().cloned(), limit, offset))
+    }
+
+    async fn get_all_by_idpef(&self, idpef: i64) -> Result<Vec<Persona>, AppError> {
+        let tablas = self.db.tablas.read().await;
+        Ok(tablas
+            .personas
+            .values()
+            .filter(|p| p.idpef == idpef && p.actper)
+            .cloned()
+            .collect())
+    }
+
+    async fn create(&self, persona: Persona) -> Result<Persona, AppError> {
+        let mut tablas = self.db.tablas.write().await;
+        insertar(&mut tablas, persona)
+    }
+
+    async fn create_many(&self, personas: Vec<Persona>) -> Result<Vec<Persona>, AppError> {
+        let mut tablas = self.db.tablas.write().await;
+
+        // Si una falla se restauran las tablas, como el rollback de los adaptadores SQL
+        let respaldo = (
+            tablas.secuencias.clone(),
+            tablas.personas.clone(),
+            tablas.historial.clone(),
+        );
+        let creadas = personas
+            .into_iter()
+            .map(|persona| insertar(&mut tablas, persona))
+            .collect::<Result<Vec<_>, _>>();
+        if creadas.is_err() {
+            (tablas.secuencias, tablas.personas, tablas.historial) = respaldo;
+        }
+        creadas
+    }
+
+    async fn update(&self, idper: i64, persona: Persona, verper: i64) -> Result<Persona, AppError> {
+        let mut tablas = self.db.tablas.write().await;
+        let actual = vigente(&tablas, idper, verper)?;
+        let cambios = Persona {
+            ndocper: persona.ndocper,
+            tdocper: persona.tdocper,
+            nomper: persona.nomper,
+            apeper: persona.apeper,
+            dirper: persona.dirper,
+            telper: persona.telper,
+            telnor: persona.telnor,
+            codubi: persona.codubi,
+            idpef: persona.idpef,
+            emaper: persona.emaper,
+            ..actual
+        };
+        guardar(&mut tablas, cambios)
+    }
+
+    async fn change_estado(&self, idper: i64, cambio: CambioEstado, verper: i64) -> Result<Persona, AppError> {
+        let mut tablas = self.db.tablas.write().await;
+        let actual = vigente(&tablas, idper, verper)?;
+        let cambios = Persona {
+            actper: cambio.actper(),
+            estper: cambio.estper,
+            motsus: cambio.motsus,
+            fecsus: cambio.fecsus,
+            ..actual
+        };
+        guardar(&mut tablas, cambios)
+    }
+
+    async fn exists(&self, idper: i64) -> Result<bool, AppError> {
+        Ok(self.db.tablas.read().await.personas.contains_key(&idper))
+    }
+
+    async fn change_password(&self, idper: i64, new_password: &str) -> Result<(), AppError> {
+        if let Some(persona) = self.db.tablas.write().await.personas.get_mut(&idper) {
+            persona.pass = Some(new_password.to_string());
+        }
+
+        Ok(())
+    }
+
+    async fn set_avatar(&self, idper: i64, avaper: Option<&str>) -> Result<Persona, AppError> {
+        let mut tablas = self.db.tablas.write().await;
+        let actual = tablas
+            .personas
+            .get(&idper)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Persona no encontrada".to_string()))?;
+        let cambios = Persona {
+            avaper: avaper.map(str::to_string),
+            ..actual
+        };
+        guardar(&mut tablas, cambios)
+    }
+
+    async fn count(&self) -> Result<i64, AppError> {
+        Ok(self.db.tablas.read().await.personas.len() as i64)
+    }
+
+    async fn get_active(&self, limit: i64, offset: i64) -> Result<Vec<Persona>, AppError> {
+        let tablas = self.db.tablas.read().await;
+        let activas = tablas.personas.values().filter(|p| p.actper).cloned();
+        Ok(pagina(activas, limit, offset))
+    }
+
+    fn stream(&self, filtro: PersonaFilter) -> BoxStream<'static, Result<Persona, AppError>> {
+        let db = self.db.clone();
+
+        Box::pin(async_stream::try_stream! {
+            // Se toma una foto de las filas para no retener el candado mientras se consume
+            let personas: Vec<Persona> = {
+                let tablas = db.tablas.read().await;
+                tablas
+                    .personas
+                    .values()
+                    .filter(|p| cumple(&tablas, p, &filtro))
+                    .cloned()
+                    .collect()
+            };
+            for persona in personas {
+                yield persona;
+            }
+        })
+    }
+}
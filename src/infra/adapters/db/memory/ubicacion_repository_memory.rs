@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+
+use super::{MemoryDb, Tablas};
+use crate::{
+    domain::{Departamento, Municipio, db::UbicacionRepository},
+    errors::AppResult,
+};
+
+pub struct UbicacionRepositoryMemory {
+    db: MemoryDb,
+}
+
+impl UbicacionRepositoryMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+/// Municipios que cumplen `filtro` con el nombre del departamento (el join de SQL),
+/// ordenados por nombre
+fn municipios(tablas: &Tablas, filtro: impl Fn(i64, &str, i64) -> bool) -> Vec<Municipio> {
+    let mut municipios: Vec<Municipio> = tablas
+        .municipios
+        .iter()
+        .filter(|(codubi, (nomubi, coddep))| filtro(**codubi, nomubi, *coddep))
+        .filter_map(|(codubi, (nomubi, coddep))| {
+            let departamento = tablas.departamentos.get(coddep)?;
+            Some(Municipio {
+                codubi: *codubi,
+                nomubi: nomubi.clone(),
+                coddep: *coddep,
+                nomdep: departamento.nomdep.clone(),
+            })
+        })
+        .collect();
+    municipios.sort_by(|a, b| a.nomubi.cmp(&b.nomubi));
+    municipios
+}
+
+#[async_trait]
+impl UbicacionRepository for UbicacionRepositoryMemory {
+    async fn list_departamentos(&self) -> AppResult<Vec<Departamento>> {
+        let tablas = self.db.tablas.read().await;
+        let mut departamentos: Vec<_> = tablas.departamentos.values().cloned().collect();
+        departamentos.sort_by(|a, b| a.nomdep.cmp(&b.nomdep));
+
+        Ok(departamentos)
+    }
+
+    async fn list_municipios(&self, coddep: i64) -> AppResult<Vec<Municipio>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(municipios(&tablas, |_, _, c| c == coddep))
+    }
+
+    async fn get_municipio(&self, codubi: i64) -> AppResult<Option<Municipio>> {
+        let tablas = self.db.tablas.read().await;
+        Ok(municipios(&tablas, |c, _, _| c == codubi).pop())
+    }
+
+    async fn search_municipios(&self, q: &str, limit: i64) -> AppResult<Vec<Municipio>> {
+        let tablas = self.db.tablas.read().await;
+        let q = q.to_lowercase();
+        let mut encontrados = municipios(&tablas, |_, nomubi, _| nomubi.to_lowercase().contains(&q));
+        encontrados.truncate(limit.max(0) as usize);
+
+        Ok(encontrados)
+    }
+
+    async fn import(
+        &self,
+        departamentos: Vec<Departamento>,
+        municipios: Vec<Municipio>,
+    ) -> AppResult<(usize, usize)> {
+        let mut tablas = self.db.tablas.write().await;
+        let total = (departamentos.len(), municipios.len());
+
+        for departamento in departamentos {
+            tablas.departamentos.insert(departamento.coddep, departamento);
+        }
+        for municipio in municipios {
+            tablas
+                .municipios
+                .insert(municipio.codubi, (municipio.nomubi, municipio.coddep));
+        }
+
+        Ok(total)
+    }
+}
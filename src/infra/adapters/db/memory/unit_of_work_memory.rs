@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::{OwnedRwLockWriteGuard, RwLock};
+
+use super::{
+    ConsentimientoRepositoryMemory, DuplicadoRepositoryMemory, HabeasDataRepositoryMemory,
+    MemoryDb, PersonaRepositoryMemory, Tablas,
+};
+use crate::{
+    domain::db::{
+        ConsentimientoRepository, DuplicadoRepository, HabeasDataRepository, PersonaRepository,
+        Transaccion, UnitOfWork,
+    },
+    errors::AppResult,
+};
+
+/// Unidad de trabajo sobre la base en memoria
+pub struct UnitOfWorkMemory {
+    db: MemoryDb,
+}
+
+impl UnitOfWorkMemory {
+    pub fn new(db: MemoryDb) -> Self {
+        Self { db }
+    }
+}
+
+/// La transacción trabaja sobre una copia de las tablas y retiene el candado de
+/// escritura de la base hasta terminar: nadie más la lee ni la escribe mientras
+/// tanto. El commit reemplaza las tablas por la copia; el rollback la descarta
+struct TransaccionMemory {
+    original: OwnedRwLockWriteGuard<Tablas>,
+    copia: MemoryDb,
+    persona: PersonaRepositoryMemory,
+    consentimiento: ConsentimientoRepositoryMemory,
+    duplicado: DuplicadoRepositoryMemory,
+    habeas_data: HabeasDataRepositoryMemory,
+}
+
+#[async_trait]
+impl UnitOfWork for UnitOfWorkMemory {
+    async fn begin(&self) -> AppResult<Box<dyn Transaccion>> {
+        let original = self.db.tablas.clone().write_owned().await;
+        let copia = MemoryDb {
+            tablas: Arc::new(RwLock::new(original.clone())),
+        };
+
+        Ok(Box::new(TransaccionMemory {
+            original,
+            persona: PersonaRepositoryMemory::new(copia.clone()),
+            consentimiento: ConsentimientoRepositoryMemory::new(copia.clone()),
+            duplicado: DuplicadoRepositoryMemory::new(copia.clone()),
+            habeas_data: HabeasDataRepositoryMemory::new(copia.clone()),
+            copia,
+        }))
+    }
+}
+
+#[async_trait]
+impl Transaccion for TransaccionMemory {
+    fn persona(&self) -> &dyn PersonaRepository {
+        &self.persona
+    }
+
+    fn consentimiento(&self) -> &dyn ConsentimientoRepository {
+        &self.consentimiento
+    }
+
+    fn duplicado(&self) -> &dyn DuplicadoRepository {
+        &self.duplicado
+    }
+
+    fn habeas_data(&self) -> &dyn HabeasDataRepository {
+        &self.habeas_data
+    }
+
+    async fn commit(self: Box<Self>) -> AppResult<()> {
+        let TransaccionMemory {
+            mut original,
+            copia,
+            ..
+        } = *self;
+        *original = std::mem::take(&mut *copia.tablas.write().await);
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> AppResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+/// Implementación del Puerto AdjuntoRepository sobre MySQL
+/// Las sentencias y el código vienen de `sql::adjunto`, común a todos los motores
+use sqlx::MySql;
+
+use crate::infra::adapters::db::sql::adjunto_repository_sql;
+
+adjunto_repository_sql!(
+    /// Repositorio de adjuntos que usa MySQL a través de SQLx
+    AdjuntoRepositoryMySQL,
+    MySql
+);
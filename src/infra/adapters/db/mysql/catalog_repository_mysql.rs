@@ -0,0 +1,11 @@
+/// Implementación del Puerto CatalogRepository sobre MySQL
+/// Las sentencias y el código vienen de `sql::catalog`, común a todos los motores
+use sqlx::MySql;
+
+use crate::infra::adapters::db::sql::catalog_repository_sql;
+
+catalog_repository_sql!(
+    /// Repositorio de catálogos que usa MySQL a través de SQLx
+    CatalogRepositoryMySQL,
+    MySql
+);
@@ -0,0 +1,11 @@
+/// Implementación del Puerto ConsentimientoRepository sobre MySQL
+/// Las sentencias y el código vienen de `sql::consentimiento`, común a todos los motores
+use sqlx::MySql;
+
+use crate::infra::adapters::db::sql::consentimiento_repository_sql;
+
+consentimiento_repository_sql!(
+    /// Repositorio de consentimientos que usa MySQL a través de SQLx
+    ConsentimientoRepositoryMySQL,
+    MySql
+);
@@ -0,0 +1,13 @@
+/// Implementación del Puerto DuplicadoRepository sobre MySQL
+/// Las sentencias y el código vienen de `sql::duplicado`, común a todos los motores
+use sqlx::MySql;
+
+use super::persona_historial_repository_mysql::registrar_version;
+use crate::infra::adapters::db::sql::duplicado_repository_sql;
+
+duplicado_repository_sql!(
+    /// Repositorio de duplicados que usa MySQL a través de SQLx
+    DuplicadoRepositoryMySQL,
+    MySql,
+    registrar_version
+);
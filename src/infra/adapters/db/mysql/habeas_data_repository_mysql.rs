@@ -0,0 +1,14 @@
+/// Implementación del Puerto HabeasDataRepository sobre MySQL
+/// Las sentencias y el código vienen de `sql::habeas_data`, común a todos los motores
+use sqlx::MySql;
+
+use super::persona_historial_repository_mysql::{purgar_historial, registrar_version};
+use crate::infra::adapters::db::sql::habeas_data_repository_sql;
+
+habeas_data_repository_sql!(
+    /// Repositorio de habeas data que usa MySQL a través de SQLx
+    HabeasDataRepositoryMySQL,
+    MySql,
+    registrar_version,
+    purgar_historial
+);
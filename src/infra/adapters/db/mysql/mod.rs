@@ -1,3 +1,28 @@
+mod acceso_repository_mysql;
+mod adjunto_repository_mysql;
+mod auditoria_repository_mysql;
+mod catalog_repository_mysql;
+mod consentimiento_repository_mysql;
+mod duplicado_repository_mysql;
+mod habeas_data_repository_mysql;
+mod pagper_repository_mysql;
+mod persona_historial_repository_mysql;
 pub mod persona_repository;
+mod ubicacion_repository_mysql;
+mod unit_of_work_mysql;
 
+pub use acceso_repository_mysql::AccesoRepositoryMySQL;
+pub use adjunto_repository_mysql::AdjuntoRepositoryMySQL;
+pub use auditoria_repository_mysql::AuditoriaRepositoryMySQL;
+pub use catalog_repository_mysql::CatalogRepositoryMySQL;
+pub use consentimiento_repository_mysql::ConsentimientoRepositoryMySQL;
+pub use duplicado_repository_mysql::DuplicadoRepositoryMySQL;
+pub use habeas_data_repository_mysql::HabeasDataRepositoryMySQL;
+pub use pagper_repository_mysql::PagperRepositoryMySQL;
+pub use persona_historial_repository_mysql::PersonaHistorialRepositoryMySQL;
 pub use persona_repository::PersonaRepositoryMySQL;
+pub use ubicacion_repository_mysql::UbicacionRepositoryMySQL;
+pub use unit_of_work_mysql::UnitOfWorkMySQL;
+
+/// Migraciones del esquema para MySQL (`migrations/mysql`), embebidas en el binario
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations/mysql");
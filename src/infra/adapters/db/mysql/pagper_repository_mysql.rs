@@ -0,0 +1,11 @@
+/// Implementación del Puerto PagperRepository sobre MySQL
+/// Las sentencias y el código vienen de `sql::pagper`, común a todos los motores
+use sqlx::MySql;
+
+use crate::infra::adapters::db::sql::pagper_repository_sql;
+
+pagper_repository_sql!(
+    /// Repositorio de permisos por página que usa MySQL a través de SQLx
+    PagperRepositoryMySQL,
+    MySql
+);
@@ -0,0 +1,11 @@
+/// Implementación del Puerto PersonaHistorialRepository sobre MySQL
+/// Las sentencias y el código vienen de `sql::historial`, común a todos los motores
+use sqlx::MySql;
+
+use crate::infra::adapters::db::sql::historial_repository_sql;
+
+historial_repository_sql!(
+    /// Repositorio del historial de personas que usa MySQL a través de SQLx
+    PersonaHistorialRepositoryMySQL,
+    MySql
+);
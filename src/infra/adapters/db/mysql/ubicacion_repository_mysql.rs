@@ -0,0 +1,11 @@
+/// Implementación del Puerto UbicacionRepository sobre MySQL
+/// Las sentencias y el código vienen de `sql::ubicacion`, común a todos los motores
+use sqlx::MySql;
+
+use crate::infra::adapters::db::sql::ubicacion_repository_sql;
+
+ubicacion_repository_sql!(
+    /// Repositorio de ubicaciones que usa MySQL a través de SQLx
+    UbicacionRepositoryMySQL,
+    MySql
+);
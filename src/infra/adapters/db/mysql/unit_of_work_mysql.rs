@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use sqlx::MySql;
+
+use super::{
+    ConsentimientoRepositoryMySQL, DuplicadoRepositoryMySQL, HabeasDataRepositoryMySQL,
+    PersonaRepositoryMySQL,
+};
+use crate::{
+    config::Cluster,
+    domain::db::{Transaccion, UnitOfWork},
+    errors::AppResult,
+    infra::adapters::db::sql::{Conexion, TransaccionSql},
+};
+
+/// Unidad de trabajo sobre MySQL: cada transacción toma una conexión del pool
+/// y la comparten sus repositorios hasta el commit o el rollback
+pub struct UnitOfWorkMySQL {
+    pool: Cluster<MySql>,
+}
+
+impl UnitOfWorkMySQL {
+    pub fn new(pool: impl Into<Cluster<MySql>>) -> Self {
+        Self { pool: pool.into() }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for UnitOfWorkMySQL {
+    async fn begin(&self) -> AppResult<Box<dyn Transaccion>> {
+        let conexion = Conexion::<MySql>::transaccion(self.pool.transaccion().await?);
+
+        Ok(Box::new(TransaccionSql::new(
+            conexion.clone(),
+            Box::new(PersonaRepositoryMySQL::con_conexion(conexion.clone())),
+            Box::new(ConsentimientoRepositoryMySQL::con_conexion(conexion.clone())),
+            Box::new(DuplicadoRepositoryMySQL::con_conexion(conexion.clone())),
+            Box::new(HabeasDataRepositoryMySQL::con_conexion(conexion)),
+        )))
+    }
+}
@@ -0,0 +1,11 @@
+/// Implementación del Puerto AccesoRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::acceso`, común a todos los motores
+use sqlx::Postgres;
+
+use crate::infra::adapters::db::sql::acceso_repository_sql;
+
+acceso_repository_sql!(
+    /// Repositorio de accesos a datos personales que usa PostgreSQL a través de SQLx
+    AccesoRepositoryPg,
+    Postgres
+);
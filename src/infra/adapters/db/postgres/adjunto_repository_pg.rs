@@ -0,0 +1,11 @@
+/// Implementación del Puerto AdjuntoRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::adjunto`, común a todos los motores
+use sqlx::Postgres;
+
+use crate::infra::adapters::db::sql::adjunto_repository_sql;
+
+adjunto_repository_sql!(
+    /// Repositorio de adjuntos que usa PostgreSQL a través de SQLx
+    AdjuntoRepositoryPg,
+    Postgres
+);
@@ -0,0 +1,11 @@
+/// Implementación del Puerto AuditoriaRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::auditoria`, común a todos los motores
+use sqlx::Postgres;
+
+use crate::infra::adapters::db::sql::auditoria_repository_sql;
+
+auditoria_repository_sql!(
+    /// Repositorio de auditoría que usa PostgreSQL a través de SQLx
+    AuditoriaRepositoryPg,
+    Postgres
+);
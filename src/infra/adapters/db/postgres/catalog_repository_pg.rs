@@ -0,0 +1,11 @@
+/// Implementación del Puerto CatalogRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::catalog`, común a todos los motores
+use sqlx::Postgres;
+
+use crate::infra::adapters::db::sql::catalog_repository_sql;
+
+catalog_repository_sql!(
+    /// Repositorio de catálogos que usa PostgreSQL a través de SQLx
+    CatalogRepositoryPg,
+    Postgres
+);
@@ -0,0 +1,11 @@
+/// Implementación del Puerto ConsentimientoRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::consentimiento`, común a todos los motores
+use sqlx::Postgres;
+
+use crate::infra::adapters::db::sql::consentimiento_repository_sql;
+
+consentimiento_repository_sql!(
+    /// Repositorio de consentimientos que usa PostgreSQL a través de SQLx
+    ConsentimientoRepositoryPg,
+    Postgres
+);
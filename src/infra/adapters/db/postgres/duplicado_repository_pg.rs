@@ -0,0 +1,13 @@
+/// Implementación del Puerto DuplicadoRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::duplicado`, común a todos los motores
+use sqlx::Postgres;
+
+use super::persona_historial_repository_pg::registrar_version;
+use crate::infra::adapters::db::sql::duplicado_repository_sql;
+
+duplicado_repository_sql!(
+    /// Repositorio de duplicados que usa PostgreSQL a través de SQLx
+    DuplicadoRepositoryPg,
+    Postgres,
+    registrar_version
+);
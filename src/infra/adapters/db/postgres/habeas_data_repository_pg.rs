@@ -0,0 +1,14 @@
+/// Implementación del Puerto HabeasDataRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::habeas_data`, común a todos los motores
+use sqlx::Postgres;
+
+use super::persona_historial_repository_pg::{purgar_historial, registrar_version};
+use crate::infra::adapters::db::sql::habeas_data_repository_sql;
+
+habeas_data_repository_sql!(
+    /// Repositorio de habeas data que usa PostgreSQL a través de SQLx
+    HabeasDataRepositoryPg,
+    Postgres,
+    registrar_version,
+    purgar_historial
+);
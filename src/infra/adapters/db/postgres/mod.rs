@@ -1,5 +1,28 @@
+mod acceso_repository_pg;
+mod adjunto_repository_pg;
+mod auditoria_repository_pg;
+mod catalog_repository_pg;
+mod consentimiento_repository_pg;
+mod duplicado_repository_pg;
+mod habeas_data_repository_pg;
+mod persona_historial_repository_pg;
 mod persona_repository;
 mod pagper_repository_pg;
+mod ubicacion_repository_pg;
+mod unit_of_work_pg;
 
+pub use acceso_repository_pg::AccesoRepositoryPg;
+pub use adjunto_repository_pg::AdjuntoRepositoryPg;
+pub use auditoria_repository_pg::AuditoriaRepositoryPg;
+pub use catalog_repository_pg::CatalogRepositoryPg;
+pub use consentimiento_repository_pg::ConsentimientoRepositoryPg;
+pub use duplicado_repository_pg::DuplicadoRepositoryPg;
+pub use habeas_data_repository_pg::HabeasDataRepositoryPg;
+pub use persona_historial_repository_pg::PersonaHistorialRepositoryPg;
 pub use persona_repository::PersonaRepositoryPg;
 pub use pagper_repository_pg::PagperRepositoryPg;
+pub use ubicacion_repository_pg::UbicacionRepositoryPg;
+pub use unit_of_work_pg::UnitOfWorkPg;
+
+/// Migraciones del esquema para PostgreSQL (`migrations/postgres`), embebidas en el binario
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations/postgres");
@@ -0,0 +1,11 @@
+/// Implementación del Puerto PersonaHistorialRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::historial`, común a todos los motores
+use sqlx::Postgres;
+
+use crate::infra::adapters::db::sql::historial_repository_sql;
+
+historial_repository_sql!(
+    /// Repositorio del historial de personas que usa PostgreSQL a través de SQLx
+    PersonaHistorialRepositoryPg,
+    Postgres
+);
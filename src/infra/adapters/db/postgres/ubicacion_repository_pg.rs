@@ -0,0 +1,11 @@
+/// Implementación del Puerto UbicacionRepository sobre PostgreSQL
+/// Las sentencias y el código vienen de `sql::ubicacion`, común a todos los motores
+use sqlx::Postgres;
+
+use crate::infra::adapters::db::sql::ubicacion_repository_sql;
+
+ubicacion_repository_sql!(
+    /// Repositorio de ubicaciones que usa PostgreSQL a través de SQLx
+    UbicacionRepositoryPg,
+    Postgres
+);
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use sqlx::Postgres;
+
+use super::{
+    ConsentimientoRepositoryPg, DuplicadoRepositoryPg, HabeasDataRepositoryPg,
+    PersonaRepositoryPg,
+};
+use crate::{
+    config::Cluster,
+    domain::db::{Transaccion, UnitOfWork},
+    errors::AppResult,
+    infra::adapters::db::sql::{Conexion, TransaccionSql},
+};
+
+/// Unidad de trabajo sobre PostgreSQL: cada transacción toma una conexión del pool
+/// y la comparten sus repositorios hasta el commit o el rollback
+pub struct UnitOfWorkPg {
+    pool: Cluster<Postgres>,
+}
+
+impl UnitOfWorkPg {
+    pub fn new(pool: impl Into<Cluster<Postgres>>) -> Self {
+        Self { pool: pool.into() }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for UnitOfWorkPg {
+    async fn begin(&self) -> AppResult<Box<dyn Transaccion>> {
+        let conexion = Conexion::<Postgres>::transaccion(self.pool.transaccion().await?);
+
+        Ok(Box::new(TransaccionSql::new(
+            conexion.clone(),
+            Box::new(PersonaRepositoryPg::con_conexion(conexion.clone())),
+            Box::new(ConsentimientoRepositoryPg::con_conexion(conexion.clone())),
+            Box::new(DuplicadoRepositoryPg::con_conexion(conexion.clone())),
+            Box::new(HabeasDataRepositoryPg::con_conexion(conexion)),
+        )))
+    }
+}
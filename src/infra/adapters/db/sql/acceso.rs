@@ -0,0 +1,129 @@
+/// Definición única del adaptador SQL de AccesoRepository
+/// Los filtros se arman con `QueryBuilder`, que ya pone los marcadores de cada motor
+use super::Dialecto;
+
+/// Máximo de entradas por consulta
+pub const MAX_LIMIT_ACCESO: i64 = 500;
+
+/// Sentencias fijas del repositorio de accesos para un dialecto
+pub struct ConsultasAcceso {
+    pub insertar: String,
+    pub seleccionar: String,
+    pub contar_por_actor: String,
+}
+
+impl ConsultasAcceso {
+    pub fn new(d: Dialecto) -> Self {
+        Self {
+            insertar: "INSERT INTO acceso_persona (idactor, idper, via, proposito, campos, ip, idsolicitud, fecacc) ".to_string(),
+            seleccionar: "SELECT idacc, idactor, idper, via, proposito, campos, ip, idsolicitud, fecacc
+                 FROM acceso_persona WHERE 1 = 1"
+                .to_string(),
+            contar_por_actor: d.sql(
+                "SELECT COUNT(*) FROM acceso_persona WHERE idactor = $1 AND fecacc >= $2",
+            ),
+        }
+    }
+}
+
+/// Genera `AccesoRepository` para un motor de SQLx: `acceso_repository_sql!(Nombre, Motor)`
+macro_rules! acceso_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasAcceso> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasAcceso::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            pool: $crate::config::Cluster<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(pool: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { pool: pool.into() }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::AccesoRepository for $nombre {
+            async fn registrar(&self, accesos: Vec<$crate::domain::NuevoAcceso>) -> $crate::errors::AppResult<()> {
+                if accesos.is_empty() {
+                    return Ok(());
+                }
+
+                let mut query = sqlx::QueryBuilder::<$motor>::new(CONSULTAS.insertar.as_str());
+                query.push_values(accesos, |mut fila, acceso| {
+                    fila.push_bind(acceso.idactor)
+                        .push_bind(acceso.idper)
+                        .push_bind(acceso.via)
+                        .push_bind(acceso.contexto.proposito)
+                        .push_bind(acceso.campos)
+                        .push_bind(acceso.contexto.ip)
+                        .push_bind(acceso.contexto.request_id)
+                        .push_bind(acceso.fecacc);
+                });
+                query
+                    .build()
+                    .execute(&mut *self.pool.escritura().await?)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn buscar(
+                &self,
+                filtro: $crate::domain::AccesoFilter,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::AccesoPersona>> {
+                use $crate::infra::adapters::db::sql::MAX_LIMIT_ACCESO;
+
+                let mut query = sqlx::QueryBuilder::<$motor>::new(CONSULTAS.seleccionar.as_str());
+                if let Some(idactor) = filtro.idactor {
+                    query.push(" AND idactor = ").push_bind(idactor);
+                }
+                if let Some(idper) = filtro.idper {
+                    query.push(" AND idper = ").push_bind(idper);
+                }
+                if let Some(via) = filtro.via {
+                    query.push(" AND via = ").push_bind(via);
+                }
+                if let Some(desde) = filtro.desde {
+                    query.push(" AND fecacc >= ").push_bind(desde);
+                }
+                if let Some(hasta) = filtro.hasta {
+                    query.push(" AND fecacc < ").push_bind(hasta);
+                }
+                query
+                    .push(" ORDER BY fecacc DESC, idacc DESC LIMIT ")
+                    .push_bind(filtro.limit.unwrap_or(100).clamp(1, MAX_LIMIT_ACCESO))
+                    .push(" OFFSET ")
+                    .push_bind(filtro.offset.unwrap_or(0).max(0));
+
+                let accesos = query
+                    .build_query_as::<$crate::domain::AccesoPersona>()
+                    .fetch_all(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(accesos)
+            }
+
+            async fn contar_por_actor(
+                &self,
+                idactor: i64,
+                desde: chrono::DateTime<chrono::Utc>,
+            ) -> $crate::errors::AppResult<i64> {
+                let total = sqlx::query_scalar::<_, i64>(&CONSULTAS.contar_por_actor)
+                    .bind(idactor)
+                    .bind(desde)
+                    .fetch_one(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(total)
+            }
+        }
+    };
+}
+
+pub(crate) use acceso_repository_sql;
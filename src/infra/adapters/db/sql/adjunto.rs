@@ -0,0 +1,122 @@
+/// Definición única del adaptador SQL de AdjuntoRepository
+use super::Dialecto;
+
+const COLUMNAS: &str =
+    "idadj, idper, catadj, nomadj, tipadj, tamadj, hashadj, clvadj, idsubidor, fecadj";
+
+/// Sentencias del repositorio de adjuntos para un dialecto
+pub struct ConsultasAdjunto {
+    pub insertar: String,
+    pub por_idadj: String,
+    pub por_persona: String,
+    pub de_persona: String,
+    pub eliminar: String,
+}
+
+impl ConsultasAdjunto {
+    pub fn new(d: Dialecto) -> Self {
+        Self {
+            insertar: format!(
+                "{}{}",
+                d.sql(
+                    "INSERT INTO adjunto (idper, catadj, nomadj, tipadj, tamadj, hashadj, clvadj, idsubidor, fecadj)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+                ),
+                d.returning(COLUMNAS)
+            ),
+            por_idadj: d.sql(&format!("SELECT {COLUMNAS} FROM adjunto WHERE idadj = $1")),
+            por_persona: d.sql(&format!(
+                "SELECT {COLUMNAS} FROM adjunto WHERE idper = $1 ORDER BY fecadj DESC, idadj DESC"
+            )),
+            de_persona: d.sql(&format!(
+                "SELECT {COLUMNAS} FROM adjunto WHERE idper = $1 AND idadj = $2"
+            )),
+            eliminar: d.sql("DELETE FROM adjunto WHERE idper = $1 AND idadj = $2"),
+        }
+    }
+}
+
+/// Genera `AdjuntoRepository` para un motor de SQLx: `adjunto_repository_sql!(Nombre, Motor)`
+macro_rules! adjunto_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasAdjunto> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasAdjunto::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            pool: $crate::config::Cluster<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(pool: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { pool: pool.into() }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::AdjuntoRepository for $nombre {
+            async fn create(
+                &self,
+                adjunto: $crate::domain::NuevoAdjunto,
+            ) -> $crate::errors::AppResult<$crate::domain::Adjunto> {
+                let mut conn = self.pool.escritura().await?;
+                let consulta = sqlx::query(&CONSULTAS.insertar)
+                    .bind(adjunto.idper)
+                    .bind(adjunto.catadj.as_str())
+                    .bind(&adjunto.nomadj)
+                    .bind(&adjunto.tipadj)
+                    .bind(adjunto.tamadj)
+                    .bind(&adjunto.hashadj)
+                    .bind(&adjunto.clvadj)
+                    .bind(adjunto.idsubidor)
+                    .bind(adjunto.fecadj);
+
+                $crate::infra::adapters::db::sql::fila_escrita!(
+                    $motor, conn, consulta, &CONSULTAS.por_idadj, None, $crate::domain::Adjunto
+                )
+                .ok_or_else(|| {
+                    $crate::errors::AppError::Internal("No se pudo leer el adjunto creado".to_string())
+                })
+            }
+
+            async fn list_by_persona(&self, idper: i64) -> $crate::errors::AppResult<Vec<$crate::domain::Adjunto>> {
+                let adjuntos = sqlx::query_as::<_, $crate::domain::Adjunto>(&CONSULTAS.por_persona)
+                    .bind(idper)
+                    .fetch_all(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(adjuntos)
+            }
+
+            async fn get(
+                &self,
+                idper: i64,
+                idadj: i64,
+            ) -> $crate::errors::AppResult<Option<$crate::domain::Adjunto>> {
+                let adjunto = sqlx::query_as::<_, $crate::domain::Adjunto>(&CONSULTAS.de_persona)
+                    .bind(idper)
+                    .bind(idadj)
+                    .fetch_optional(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(adjunto)
+            }
+
+            async fn delete(&self, idper: i64, idadj: i64) -> $crate::errors::AppResult<bool> {
+                let resultado = sqlx::query(&CONSULTAS.eliminar)
+                    .bind(idper)
+                    .bind(idadj)
+                    .execute(&mut *self.pool.escritura().await?)
+                    .await?;
+
+                Ok(resultado.rows_affected() > 0)
+            }
+        }
+    };
+}
+
+pub(crate) use adjunto_repository_sql;
@@ -0,0 +1,119 @@
+/// Definición única del adaptador SQL de AuditoriaRepository
+/// Los filtros se arman con `QueryBuilder`, que ya pone los marcadores de cada motor
+use super::Dialecto;
+
+/// Máximo de entradas por consulta
+pub const MAX_LIMIT_AUDITORIA: i64 = 500;
+
+/// Sentencias fijas del repositorio de auditoría para un dialecto
+pub struct ConsultasAuditoria {
+    pub insertar: String,
+    pub seleccionar: String,
+}
+
+impl ConsultasAuditoria {
+    pub fn new(d: Dialecto) -> Self {
+        Self {
+            insertar: d.sql(
+                "INSERT INTO auditoria
+                    (idactor, accion, entidad, identidad, antes, despues, cambios, ip, agente, idsolicitud, fecaud)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+            ),
+            seleccionar: "SELECT idaud, idactor, accion, entidad, identidad, antes, despues, cambios, ip, agente, idsolicitud, fecaud
+                 FROM auditoria WHERE 1 = 1"
+                .to_string(),
+        }
+    }
+}
+
+/// Genera `AuditoriaRepository` para un motor de SQLx:
+/// `auditoria_repository_sql!(Nombre, Motor)`
+macro_rules! auditoria_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasAuditoria> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasAuditoria::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            db: $crate::infra::adapters::db::sql::Conexion<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(db: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { db: $crate::infra::adapters::db::sql::Conexion::Pool(db.into()) }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::AuditoriaRepository for $nombre {
+            async fn registrar(&self, entrada: $crate::domain::NuevaAuditoria) -> $crate::errors::AppResult<()> {
+                use sqlx::types::Json;
+
+                sqlx::query(&CONSULTAS.insertar)
+                    .bind(entrada.idactor)
+                    .bind(&entrada.accion)
+                    .bind(&entrada.entidad)
+                    .bind(&entrada.identidad)
+                    .bind(entrada.antes.map(Json))
+                    .bind(entrada.despues.map(Json))
+                    .bind(entrada.cambios.map(Json))
+                    .bind(&entrada.contexto.ip)
+                    .bind(&entrada.contexto.user_agent)
+                    .bind(&entrada.contexto.request_id)
+                    .bind(entrada.fecaud)
+                    .execute(&mut *self.db.adquirir().await?)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn buscar(
+                &self,
+                filtro: $crate::domain::AuditoriaFilter,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::RegistroAuditoria>> {
+                use $crate::infra::adapters::db::sql::MAX_LIMIT_AUDITORIA;
+
+                let mut query = sqlx::QueryBuilder::<$motor>::new(CONSULTAS.seleccionar.as_str());
+                if let Some(idactor) = filtro.idactor {
+                    query.push(" AND idactor = ").push_bind(idactor);
+                }
+                if let Some(accion) = filtro.accion {
+                    query.push(" AND accion = ").push_bind(accion);
+                }
+                if let Some(entidad) = filtro.entidad {
+                    query.push(" AND entidad = ").push_bind(entidad);
+                }
+                if let Some(identidad) = filtro.identidad {
+                    query.push(" AND identidad = ").push_bind(identidad);
+                }
+                if let Some(idsolicitud) = filtro.idsolicitud {
+                    query.push(" AND idsolicitud = ").push_bind(idsolicitud);
+                }
+                if let Some(desde) = filtro.desde {
+                    query.push(" AND fecaud >= ").push_bind(desde);
+                }
+                if let Some(hasta) = filtro.hasta {
+                    query.push(" AND fecaud < ").push_bind(hasta);
+                }
+                query
+                    .push(" ORDER BY fecaud DESC, idaud DESC LIMIT ")
+                    .push_bind(filtro.limit.unwrap_or(100).clamp(1, MAX_LIMIT_AUDITORIA))
+                    .push(" OFFSET ")
+                    .push_bind(filtro.offset.unwrap_or(0).max(0));
+
+                let entradas = query
+                    .build_query_as::<$crate::domain::RegistroAuditoria>()
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(entradas)
+            }
+        }
+    };
+}
+
+pub(crate) use auditoria_repository_sql;
@@ -0,0 +1,221 @@
+/// Definición única del adaptador SQL de CatalogRepository
+/// Las consultas se arman con los nombres de tabla y columnas de `CatalogDef`,
+/// que vienen del registro estático y nunca del usuario
+use super::Dialecto;
+use crate::domain::CatalogDef;
+
+/// Sentencias de un catálogo para un dialecto
+pub struct ConsultasCatalogo {
+    pub listar: String,
+    pub por_id: String,
+    pub insertar: String,
+    /// Nombre y, si el catálogo tiene columna de activo, también activo; luego el id
+    pub actualizar: String,
+    pub eliminar: String,
+}
+
+impl ConsultasCatalogo {
+    pub fn new(d: Dialecto, catalogo: &CatalogDef) -> Self {
+        let seleccionar = format!(
+            "SELECT {id} AS id, {label} AS nombre, {activo} AS activo FROM {table}",
+            id = catalogo.id_col,
+            label = catalogo.label_col,
+            activo = catalogo.active_col.unwrap_or("TRUE"),
+            table = catalogo.table,
+        );
+
+        // Las columnas fijas van al final del INSERT, después de los parámetros
+        let (fijas, valores_fijos): (String, String) = catalogo
+            .fijas
+            .iter()
+            .map(|(columna, valor)| (format!(", {}", columna), format!(", {}", valor)))
+            .unzip();
+
+        let (insertar, actualizar, eliminar) = match catalogo.active_col {
+            Some(activo) => (
+                format!(
+                    "INSERT INTO {} ({}, {}, {}{}) VALUES ($1, $2, $3{})",
+                    catalogo.table,
+                    catalogo.id_col,
+                    catalogo.label_col,
+                    activo,
+                    fijas,
+                    valores_fijos
+                ),
+                format!(
+                    "UPDATE {} SET {} = $1, {} = $2 WHERE {} = $3",
+                    catalogo.table, catalogo.label_col, activo, catalogo.id_col
+                ),
+                format!(
+                    "UPDATE {} SET {} = FALSE WHERE {} = $1",
+                    catalogo.table, activo, catalogo.id_col
+                ),
+            ),
+            None => (
+                format!(
+                    "INSERT INTO {} ({}, {}{}) VALUES ($1, $2{})",
+                    catalogo.table, catalogo.id_col, catalogo.label_col, fijas, valores_fijos
+                ),
+                format!(
+                    "UPDATE {} SET {} = $1 WHERE {} = $2",
+                    catalogo.table, catalogo.label_col, catalogo.id_col
+                ),
+                format!(
+                    "DELETE FROM {} WHERE {} = $1",
+                    catalogo.table, catalogo.id_col
+                ),
+            ),
+        };
+
+        Self {
+            listar: format!("{} ORDER BY {}", seleccionar, catalogo.id_col),
+            por_id: d.sql(&format!("{} WHERE {} = $1", seleccionar, catalogo.id_col)),
+            insertar: d.sql(&insertar),
+            actualizar: d.sql(&actualizar),
+            eliminar: d.sql(&eliminar),
+        }
+    }
+}
+
+/// Genera `CatalogRepository` para un motor de SQLx: `catalog_repository_sql!(Nombre, Motor)`
+/// MySQL cuenta como afectadas solo las filas que cambian, así que un UPDATE o
+/// DELETE sin filas afectadas se confirma releyendo el elemento
+macro_rules! catalog_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty) => {
+        /// Sentencias de cada catálogo, en el orden de `CATALOGOS`
+        static CONSULTAS: std::sync::LazyLock<Vec<$crate::infra::adapters::db::sql::ConsultasCatalogo>> =
+            std::sync::LazyLock::new(|| {
+                $crate::domain::CATALOGOS
+                    .iter()
+                    .map(|catalogo| {
+                        $crate::infra::adapters::db::sql::ConsultasCatalogo::new(
+                            <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                            catalogo,
+                        )
+                    })
+                    .collect()
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            pool: $crate::config::Cluster<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(pool: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { pool: pool.into() }
+            }
+
+            fn consultas(
+                catalogo: &$crate::domain::CatalogDef,
+            ) -> &'static $crate::infra::adapters::db::sql::ConsultasCatalogo {
+                let i = $crate::domain::CATALOGOS
+                    .iter()
+                    .position(|c| c.name == catalogo.name)
+                    .expect("los catálogos vienen del registro estático");
+                &CONSULTAS[i]
+            }
+
+            /// Después de un UPDATE o DELETE sin filas afectadas: NotFound si el
+            /// elemento no existe
+            async fn confirmar_existe(
+                &self,
+                catalogo: &$crate::domain::CatalogDef,
+                id: i64,
+            ) -> $crate::errors::AppResult<()> {
+                use $crate::domain::db::CatalogRepository;
+
+                match self.get(catalogo, id).await? {
+                    Some(_) => Ok(()),
+                    None => Err($crate::errors::AppError::NotFound(format!(
+                        "Elemento {} no encontrado en el catálogo {}",
+                        id, catalogo.name
+                    ))),
+                }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::CatalogRepository for $nombre {
+            async fn list(
+                &self,
+                catalogo: &$crate::domain::CatalogDef,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::CatalogItem>> {
+                let items = sqlx::query_as::<_, $crate::domain::CatalogItem>(&Self::consultas(catalogo).listar)
+                    .fetch_all(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(items)
+            }
+
+            async fn get(
+                &self,
+                catalogo: &$crate::domain::CatalogDef,
+                id: i64,
+            ) -> $crate::errors::AppResult<Option<$crate::domain::CatalogItem>> {
+                let item = sqlx::query_as::<_, $crate::domain::CatalogItem>(&Self::consultas(catalogo).por_id)
+                    .bind(id)
+                    .fetch_optional(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(item)
+            }
+
+            async fn create(
+                &self,
+                catalogo: &$crate::domain::CatalogDef,
+                item: $crate::domain::CatalogItem,
+            ) -> $crate::errors::AppResult<$crate::domain::CatalogItem> {
+                let mut query = sqlx::query(&Self::consultas(catalogo).insertar)
+                    .bind(item.id)
+                    .bind(&item.nombre);
+                if catalogo.active_col.is_some() {
+                    query = query.bind(item.activo);
+                }
+                query.execute(&mut *self.pool.escritura().await?).await?;
+
+                self.get(catalogo, item.id).await?.ok_or_else(|| {
+                    $crate::errors::AppError::Internal("No se pudo leer el elemento creado".to_string())
+                })
+            }
+
+            async fn update(
+                &self,
+                catalogo: &$crate::domain::CatalogDef,
+                id: i64,
+                item: $crate::domain::CatalogItem,
+            ) -> $crate::errors::AppResult<$crate::domain::CatalogItem> {
+                let mut query = sqlx::query(&Self::consultas(catalogo).actualizar).bind(&item.nombre);
+                if catalogo.active_col.is_some() {
+                    query = query.bind(item.activo);
+                }
+                let result = query.bind(id).execute(&mut *self.pool.escritura().await?).await?;
+                if result.rows_affected() == 0 {
+                    self.confirmar_existe(catalogo, id).await?;
+                }
+
+                self.get(catalogo, id).await?.ok_or_else(|| {
+                    $crate::errors::AppError::Internal("No se pudo leer el elemento actualizado".to_string())
+                })
+            }
+
+            async fn delete(
+                &self,
+                catalogo: &$crate::domain::CatalogDef,
+                id: i64,
+            ) -> $crate::errors::AppResult<()> {
+                let result = sqlx::query(&Self::consultas(catalogo).eliminar)
+                    .bind(id)
+                    .execute(&mut *self.pool.escritura().await?)
+                    .await?;
+                if result.rows_affected() == 0 {
+                    self.confirmar_existe(catalogo, id).await?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use catalog_repository_sql;
@@ -0,0 +1,141 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use sqlx::{Database, Pool, Transaction, pool::PoolConnection};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::{
+    config::Cluster,
+    errors::{AppError, AppResult},
+};
+
+/// De dónde saca un adaptador SQL su conexión: del pool (cada llamada toma una,
+/// de la primaria o de una réplica según sea escritura o lectura) o de una
+/// transacción abierta por la unidad de trabajo, compartida por todos los
+/// repositorios de esa transacción
+pub enum Conexion<DB: Database> {
+    Pool(Cluster<DB>),
+    Transaccion(Arc<Mutex<Option<Transaction<'static, DB>>>>),
+}
+
+impl<DB: Database> Clone for Conexion<DB> {
+    fn clone(&self) -> Self {
+        match self {
+            Conexion::Pool(pool) => Conexion::Pool(pool.clone()),
+            Conexion::Transaccion(tx) => Conexion::Transaccion(tx.clone()),
+        }
+    }
+}
+
+impl<DB: Database> From<Cluster<DB>> for Conexion<DB> {
+    fn from(cluster: Cluster<DB>) -> Self {
+        Conexion::Pool(cluster)
+    }
+}
+
+impl<DB: Database> From<Pool<DB>> for Conexion<DB> {
+    fn from(pool: Pool<DB>) -> Self {
+        Conexion::Pool(pool.into())
+    }
+}
+
+impl<DB: Database> Conexion<DB> {
+    pub fn transaccion(tx: Transaction<'static, DB>) -> Self {
+        Conexion::Transaccion(Arc::new(Mutex::new(Some(tx))))
+    }
+
+    /// Conexión para ejecutar una o varias sentencias que escriben
+    /// En una transacción espera a que la suelte la llamada anterior
+    pub async fn adquirir(&self) -> AppResult<Adquirida<DB>> {
+        match self {
+            Conexion::Pool(cluster) => Ok(Adquirida::Pool(cluster.escritura().await?)),
+            Conexion::Transaccion(tx) => {
+                let guardia = tx.clone().lock_owned().await;
+                if guardia.is_none() {
+                    return Err(terminada());
+                }
+                Ok(Adquirida::Transaccion(guardia))
+            }
+        }
+    }
+
+    /// Conexión para sentencias que solo leen: una réplica si la hay
+    /// En una transacción es la misma de `adquirir`, para leer lo ya escrito en ella
+    pub async fn lectura(&self) -> AppResult<Adquirida<DB>> {
+        match self {
+            Conexion::Pool(cluster) => Ok(Adquirida::Pool(cluster.lectura().await?)),
+            Conexion::Transaccion(_) => self.adquirir().await,
+        }
+    }
+
+    /// Lectura que se hace más tarde, fuera de la petición (un stream se consume
+    /// cuando el handler ya respondió): la afinidad del cliente se decide al llamarla
+    pub fn lectura_diferida(
+        &self,
+    ) -> impl Future<Output = AppResult<Adquirida<DB>>> + Send + 'static {
+        let conexion = self.clone();
+        let de_la_primaria = match self {
+            Conexion::Pool(cluster) => cluster.escribio_hace_poco(),
+            Conexion::Transaccion(_) => true,
+        };
+        async move {
+            match &conexion {
+                Conexion::Pool(cluster) => Ok(Adquirida::Pool(
+                    cluster.lectura_con_afinidad(de_la_primaria).await?,
+                )),
+                Conexion::Transaccion(_) => conexion.adquirir().await,
+            }
+        }
+    }
+
+    /// Confirma la transacción; sobre el pool no hace nada
+    pub async fn commit(&self) -> AppResult<()> {
+        if let Conexion::Transaccion(tx) = self {
+            let tx = tx.lock().await.take().ok_or_else(terminada)?;
+            tx.commit().await?;
+        }
+        Ok(())
+    }
+
+    /// Deshace la transacción; sobre el pool no hace nada
+    pub async fn rollback(&self) -> AppResult<()> {
+        if let Conexion::Transaccion(tx) = self {
+            let tx = tx.lock().await.take().ok_or_else(terminada)?;
+            tx.rollback().await?;
+        }
+        Ok(())
+    }
+}
+
+fn terminada() -> AppError {
+    AppError::Internal("La transacción ya terminó".to_string())
+}
+
+/// Conexión tomada por `Conexion::adquirir`; se devuelve al soltarla
+pub enum Adquirida<DB: Database> {
+    Pool(PoolConnection<DB>),
+    Transaccion(OwnedMutexGuard<Option<Transaction<'static, DB>>>),
+}
+
+impl<DB: Database> Deref for Adquirida<DB> {
+    type Target = DB::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Adquirida::Pool(conn) => conn,
+            // `adquirir` ya comprobó que la transacción sigue abierta
+            Adquirida::Transaccion(tx) => tx.as_ref().expect("transacción abierta"),
+        }
+    }
+}
+
+impl<DB: Database> DerefMut for Adquirida<DB> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Adquirida::Pool(conn) => conn,
+            Adquirida::Transaccion(tx) => tx.as_mut().expect("transacción abierta"),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+/// Definición única del adaptador SQL de ConsentimientoRepository
+use super::Dialecto;
+
+const COLUMNAS_POLITICA: &str = "idpol, verpol, texpol, fecpol";
+const COLUMNAS: &str = "idcon, idper, idpol, cancon, feccon, fecrev";
+
+/// Sentencias del repositorio de consentimientos para un dialecto
+pub struct ConsultasConsentimiento {
+    pub insertar_politica: String,
+    pub politica_por_id: String,
+    pub politicas: String,
+    pub politica_por_version: String,
+    pub politica_vigente: String,
+    pub aceptar: String,
+    pub por_idcon: String,
+    pub revocar: String,
+    pub por_persona: String,
+}
+
+impl ConsultasConsentimiento {
+    pub fn new(d: Dialecto) -> Self {
+        Self {
+            insertar_politica: format!(
+                "{}{}",
+                d.sql("INSERT INTO politica_datos (verpol, texpol, fecpol) VALUES ($1, $2, $3)"),
+                d.returning(COLUMNAS_POLITICA)
+            ),
+            politica_por_id: d.sql(
+                "SELECT idpol, verpol, texpol, fecpol FROM politica_datos WHERE idpol = $1",
+            ),
+            politicas: "SELECT idpol, verpol, texpol, fecpol FROM politica_datos ORDER BY fecpol DESC, idpol DESC".to_string(),
+            politica_por_version: d.sql(
+                "SELECT idpol, verpol, texpol, fecpol FROM politica_datos WHERE verpol = $1",
+            ),
+            politica_vigente: d.sql(
+                "SELECT idpol, verpol, texpol, fecpol FROM politica_datos
+                 WHERE fecpol <= $1
+                 ORDER BY fecpol DESC, idpol DESC
+                 LIMIT 1",
+            ),
+            aceptar: format!(
+                "{}{}",
+                d.sql(
+                    "INSERT INTO consentimiento (idper, idpol, cancon, feccon) VALUES ($1, $2, $3, $4)"
+                ),
+                d.returning(COLUMNAS)
+            ),
+            por_idcon: d.sql(&format!("SELECT {COLUMNAS} FROM consentimiento WHERE idcon = $1")),
+            revocar: d.sql(
+                "UPDATE consentimiento SET fecrev = $1 WHERE idper = $2 AND fecrev IS NULL",
+            ),
+            por_persona: d.sql(&format!(
+                "SELECT {COLUMNAS} FROM consentimiento WHERE idper = $1 ORDER BY feccon, idcon"
+            )),
+        }
+    }
+}
+
+/// Genera `ConsentimientoRepository` para un motor de SQLx:
+/// `consentimiento_repository_sql!(Nombre, Motor)`, con `con_conexion` para
+/// usarlo dentro de una transacción de la unidad de trabajo
+macro_rules! consentimiento_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasConsentimiento> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasConsentimiento::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            db: $crate::infra::adapters::db::sql::Conexion<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(db: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { db: $crate::infra::adapters::db::sql::Conexion::Pool(db.into()) }
+            }
+
+            /// Repositorio que trabaja sobre la conexión indicada (p. ej. una transacción)
+            pub fn con_conexion(db: $crate::infra::adapters::db::sql::Conexion<$motor>) -> Self {
+                Self { db }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::ConsentimientoRepository for $nombre {
+            async fn create_politica(
+                &self,
+                verpol: &str,
+                texpol: &str,
+                fecpol: chrono::DateTime<chrono::Utc>,
+            ) -> $crate::errors::AppResult<$crate::domain::PoliticaDatos> {
+                let mut conn = self.db.adquirir().await?;
+                let consulta = sqlx::query(&CONSULTAS.insertar_politica)
+                    .bind(verpol)
+                    .bind(texpol)
+                    .bind(fecpol);
+
+                $crate::infra::adapters::db::sql::fila_escrita!(
+                    $motor, conn, consulta, &CONSULTAS.politica_por_id, None, $crate::domain::PoliticaDatos
+                )
+                .ok_or_else(|| {
+                    $crate::errors::AppError::Internal("No se pudo leer la política creada".to_string())
+                })
+            }
+
+            async fn list_politicas(&self) -> $crate::errors::AppResult<Vec<$crate::domain::PoliticaDatos>> {
+                let politicas = sqlx::query_as::<_, $crate::domain::PoliticaDatos>(&CONSULTAS.politicas)
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(politicas)
+            }
+
+            async fn get_politica_by_version(
+                &self,
+                verpol: &str,
+            ) -> $crate::errors::AppResult<Option<$crate::domain::PoliticaDatos>> {
+                let politica = sqlx::query_as::<_, $crate::domain::PoliticaDatos>(&CONSULTAS.politica_por_version)
+                    .bind(verpol)
+                    .fetch_optional(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(politica)
+            }
+
+            async fn get_politica_vigente(
+                &self,
+                fecha: chrono::DateTime<chrono::Utc>,
+            ) -> $crate::errors::AppResult<Option<$crate::domain::PoliticaDatos>> {
+                let politica = sqlx::query_as::<_, $crate::domain::PoliticaDatos>(&CONSULTAS.politica_vigente)
+                    .bind(fecha)
+                    .fetch_optional(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(politica)
+            }
+
+            async fn aceptar(
+                &self,
+                idper: i64,
+                idpol: i64,
+                cancon: $crate::domain::CanalConsentimiento,
+                feccon: chrono::DateTime<chrono::Utc>,
+            ) -> $crate::errors::AppResult<$crate::domain::Consentimiento> {
+                let mut conn = self.db.adquirir().await?;
+                let consulta = sqlx::query(&CONSULTAS.aceptar)
+                    .bind(idper)
+                    .bind(idpol)
+                    .bind(cancon.as_str())
+                    .bind(feccon);
+
+                $crate::infra::adapters::db::sql::fila_escrita!(
+                    $motor, conn, consulta, &CONSULTAS.por_idcon, None, $crate::domain::Consentimiento
+                )
+                .ok_or_else(|| {
+                    $crate::errors::AppError::Internal("No se pudo leer el consentimiento creado".to_string())
+                })
+            }
+
+            async fn revocar(
+                &self,
+                idper: i64,
+                fecrev: chrono::DateTime<chrono::Utc>,
+            ) -> $crate::errors::AppResult<u64> {
+                let resultado = sqlx::query(&CONSULTAS.revocar)
+                    .bind(fecrev)
+                    .bind(idper)
+                    .execute(&mut *self.db.adquirir().await?)
+                    .await?;
+
+                Ok(resultado.rows_affected())
+            }
+
+            async fn list_by_persona(
+                &self,
+                idper: i64,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::Consentimiento>> {
+                let consentimientos = sqlx::query_as::<_, $crate::domain::Consentimiento>(&CONSULTAS.por_persona)
+                    .bind(idper)
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(consentimientos)
+            }
+        }
+    };
+}
+
+pub(crate) use consentimiento_repository_sql;
@@ -0,0 +1,183 @@
+/// Diferencias de sintaxis entre los motores SQL soportados
+/// Los adaptadores arman sus sentencias con estas piezas en lugar de repetir
+/// el mismo SQL con otros marcadores en cada motor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialecto {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Dialecto {
+    /// Marcador del parámetro `n` (empieza en 1): `$n`, `?` o `?n`
+    pub fn marcador(self, n: usize) -> String {
+        match self {
+            Dialecto::Postgres => format!("${}", n),
+            Dialecto::MySql => "?".to_string(),
+            Dialecto::Sqlite => format!("?{}", n),
+        }
+    }
+
+    /// Traduce una sentencia escrita con marcadores `$n` a los del dialecto
+    /// En MySQL los marcadores son posicionales: cada `$n` debe aparecer una sola
+    /// vez y en el orden en que se enlazan los parámetros
+    pub fn sql(self, sentencia: &str) -> String {
+        if self == Dialecto::Postgres {
+            return sentencia.to_string();
+        }
+
+        let mut traducida = String::with_capacity(sentencia.len());
+        let mut resto = sentencia;
+        while let Some(inicio) = resto.find('$') {
+            traducida.push_str(&resto[..inicio]);
+            let digitos = resto[inicio + 1..]
+                .bytes()
+                .take_while(u8::is_ascii_digit)
+                .count();
+            match resto[inicio + 1..inicio + 1 + digitos].parse() {
+                Ok(n) => traducida.push_str(&self.marcador(n)),
+                Err(_) => traducida.push('$'),
+            }
+            resto = &resto[inicio + 1 + digitos..];
+        }
+        traducida.push_str(resto);
+        traducida
+    }
+
+    /// `cuantos` marcadores separados por comas, empezando en el parámetro `desde`
+    pub fn marcadores(self, desde: usize, cuantos: usize) -> String {
+        (desde..desde + cuantos)
+            .map(|n| self.marcador(n))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// `columna = marcador` para cada columna, empezando en el parámetro `desde`
+    pub fn asignaciones(self, columnas: &[&str], desde: usize) -> String {
+        columnas
+            .iter()
+            .enumerate()
+            .map(|(i, columna)| format!("{} = {}", columna, self.marcador(desde + i)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// MySQL no soporta `RETURNING`: hay que releer la fila después de escribirla
+    pub fn soporta_returning(self) -> bool {
+        !matches!(self, Dialecto::MySql)
+    }
+
+    /// Cláusula `RETURNING` con las columnas indicadas, vacía si el motor no la soporta
+    pub fn returning(self, columnas: &str) -> String {
+        if self.soporta_returning() {
+            format!(" RETURNING {}", columnas)
+        } else {
+            String::new()
+        }
+    }
+
+    /// Cláusula que convierte un INSERT en upsert: si ya existe una fila con la
+    /// misma `clave` se actualizan las `columnas` con los valores insertados
+    pub fn upsert(self, clave: &[&str], columnas: &[&str]) -> String {
+        match self {
+            Dialecto::Postgres | Dialecto::Sqlite => format!(
+                " ON CONFLICT ({}) DO UPDATE SET {}",
+                clave.join(", "),
+                columnas
+                    .iter()
+                    .map(|c| format!("{} = EXCLUDED.{}", c, c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Dialecto::MySql => format!(
+                " ON DUPLICATE KEY UPDATE {}",
+                columnas
+                    .iter()
+                    .map(|c| format!("{} = VALUES({})", c, c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Inicio de un INSERT que descarta las filas repetidas en silencio
+    /// (MySQL); los demás motores lo hacen con `ignorar_conflicto`
+    pub fn insertar_ignorando(self) -> &'static str {
+        match self {
+            Dialecto::MySql => "INSERT IGNORE INTO",
+            Dialecto::Postgres | Dialecto::Sqlite => "INSERT INTO",
+        }
+    }
+
+    /// Cláusula final que descarta las filas que chocan con la `clave` única
+    pub fn ignorar_conflicto(self, clave: &[&str]) -> String {
+        match self {
+            Dialecto::Postgres | Dialecto::Sqlite => {
+                format!(" ON CONFLICT ({}) DO NOTHING", clave.join(", "))
+            }
+            Dialecto::MySql => String::new(),
+        }
+    }
+
+    /// `LIMIT` y `OFFSET` como parámetros `desde` y `desde + 1`
+    pub fn paginar(self, desde: usize) -> String {
+        format!(
+            " LIMIT {} OFFSET {}",
+            self.marcador(desde),
+            self.marcador(desde + 1)
+        )
+    }
+}
+
+/// Cláusula que acompaña a cada `LIKE` armado con `contiene`
+/// `!` en vez de `\`: en MySQL la barra también escapa dentro del literal
+pub const ESCAPE_LIKE: &str = " ESCAPE '!'";
+
+/// Patrón `LIKE` que busca `texto` tal cual: `%`, `_` y `!` dejan de ser comodines
+pub fn contiene(texto: &str) -> String {
+    let mut patron = String::with_capacity(texto.len() + 2);
+    patron.push('%');
+    for c in texto.chars() {
+        if matches!(c, '%' | '_' | '!') {
+            patron.push('!');
+        }
+        patron.push(c);
+    }
+    patron.push('%');
+    patron
+}
+
+/// Motor de SQLx con su dialecto, para los adaptadores generados por macro
+pub trait MotorSql: sqlx::Database {
+    const DIALECTO: Dialecto;
+
+    /// Id que asignó la base al último INSERT, en los motores sin `RETURNING`
+    fn id_insertado(resultado: &Self::QueryResult) -> Option<i64>;
+}
+
+#[cfg(feature = "postgres")]
+impl MotorSql for sqlx::Postgres {
+    const DIALECTO: Dialecto = Dialecto::Postgres;
+
+    fn id_insertado(_resultado: &Self::QueryResult) -> Option<i64> {
+        None
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl MotorSql for sqlx::MySql {
+    const DIALECTO: Dialecto = Dialecto::MySql;
+
+    fn id_insertado(resultado: &Self::QueryResult) -> Option<i64> {
+        Some(resultado.last_insert_id() as i64)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl MotorSql for sqlx::Sqlite {
+    const DIALECTO: Dialecto = Dialecto::Sqlite;
+
+    fn id_insertado(resultado: &Self::QueryResult) -> Option<i64> {
+        Some(resultado.last_insert_rowid())
+    }
+}
@@ -0,0 +1,354 @@
+/// Definición única del adaptador SQL de DuplicadoRepository
+use super::{COLUMNAS_PERSONA, Dialecto};
+
+const COLUMNAS: &str = "iddup, idper1, idper2, pundup, motdup, estdup, fecdup, idrev, fecrev";
+
+const COLUMNAS_FUSION: &str = "idfus, idsup, idabs, iddup, idactor, detfus, fecfus";
+
+/// Máximo de filas por consulta
+pub const MAX_LIMIT_DUPLICADO: i64 = 500;
+
+/// Sentencias del repositorio de duplicados para un dialecto
+pub struct ConsultasDuplicado {
+    /// Inicio del INSERT por lotes; los valores los agrega `QueryBuilder`
+    pub insertar: String,
+    /// Final del INSERT: descarta los pares que ya existen
+    pub sin_repetidos: String,
+    pub filtrar: String,
+    pub por_iddup: String,
+    pub descartar: String,
+    pub actualizar_persona: String,
+    pub persona: String,
+    /// Cierra las aceptaciones vigentes de la absorbida ($2) de las políticas que
+    /// la superviviente ($3) también tiene vigentes
+    pub cerrar_consentimientos_repetidos: String,
+    /// Pasan las filas de cada tabla de la persona absorbida a la superviviente
+    pub mover_consentimientos: String,
+    pub mover_adjuntos: String,
+    pub resolver: String,
+    /// La persona absorbida se enlaza dos veces: idper1 e idper2
+    pub descartar_de: String,
+    pub insertar_fusion: String,
+    pub fusion_por_idfus: String,
+    /// La persona se enlaza dos veces: superviviente y absorbida
+    pub fusiones: String,
+}
+
+impl ConsultasDuplicado {
+    pub fn new(d: Dialecto) -> Self {
+        Self {
+            insertar: format!(
+                "{} duplicado (idper1, idper2, pundup, motdup, estdup, fecdup) ",
+                d.insertar_ignorando()
+            ),
+            sin_repetidos: d.ignorar_conflicto(&["idper1", "idper2"]),
+            filtrar: format!("SELECT {COLUMNAS} FROM duplicado WHERE estdup = "),
+            por_iddup: d.sql(&format!("SELECT {COLUMNAS} FROM duplicado WHERE iddup = $1")),
+            descartar: d.sql(
+                "UPDATE duplicado SET estdup = $1, idrev = $2, fecrev = $3
+                 WHERE iddup = $4 AND estdup = $5",
+            ),
+            actualizar_persona: format!(
+                "{}{}",
+                d.sql(
+                    "UPDATE persona
+                     SET ndocper = $1, dirper = $2, estper = $3, actper = $4, motsus = $5, fecsus = $6,
+                         verper = verper + 1
+                     WHERE idper = $7 AND verper = $8"
+                ),
+                d.returning(COLUMNAS_PERSONA)
+            ),
+            persona: d.sql(&format!(
+                "SELECT {} FROM persona WHERE idper = $1",
+                COLUMNAS_PERSONA
+            )),
+            // La tabla de la subconsulta va envuelta: MySQL no deja leer directamente
+            // la tabla que se está actualizando
+            cerrar_consentimientos_repetidos: d.sql(
+                "UPDATE consentimiento SET fecrev = $1
+                 WHERE idper = $2 AND fecrev IS NULL AND idpol IN (
+                     SELECT idpol FROM (
+                         SELECT idpol FROM consentimiento WHERE idper = $3 AND fecrev IS NULL
+                     ) AS vigentes
+                 )",
+            ),
+            mover_consentimientos: d.sql("UPDATE consentimiento SET idper = $1 WHERE idper = $2"),
+            mover_adjuntos: d.sql("UPDATE adjunto SET idper = $1 WHERE idper = $2"),
+            resolver: d.sql(
+                "UPDATE duplicado SET estdup = $1, idrev = $2, fecrev = $3 WHERE iddup = $4",
+            ),
+            descartar_de: d.sql(
+                "UPDATE duplicado SET estdup = $1, idrev = $2, fecrev = $3
+                 WHERE estdup = $4 AND (idper1 = $5 OR idper2 = $6)",
+            ),
+            insertar_fusion: format!(
+                "{}{}",
+                d.sql(
+                    "INSERT INTO fusion_persona (idsup, idabs, iddup, idactor, detfus, fecfus)
+                     VALUES ($1, $2, $3, $4, $5, $6)"
+                ),
+                d.returning(COLUMNAS_FUSION)
+            ),
+            fusion_por_idfus: d.sql(&format!(
+                "SELECT {COLUMNAS_FUSION} FROM fusion_persona WHERE idfus = $1"
+            )),
+            fusiones: d.sql(&format!(
+                "SELECT {COLUMNAS_FUSION} FROM fusion_persona
+                 WHERE idsup = $1 OR idabs = $2 ORDER BY fecfus DESC, idfus DESC"
+            )),
+        }
+    }
+}
+
+/// Genera `DuplicadoRepository` para un motor de SQLx:
+/// `duplicado_repository_sql!(Nombre, Motor, registrar_version)`
+/// donde `registrar_version` es la del historial del mismo motor; con
+/// `con_conexion` para usarlo dentro de una transacción de la unidad de trabajo
+macro_rules! duplicado_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty, $registrar_version:path) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasDuplicado> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasDuplicado::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            db: $crate::infra::adapters::db::sql::Conexion<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(db: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { db: $crate::infra::adapters::db::sql::Conexion::Pool(db.into()) }
+            }
+
+            /// Repositorio que trabaja sobre la conexión indicada (p. ej. una transacción)
+            pub fn con_conexion(db: $crate::infra::adapters::db::sql::Conexion<$motor>) -> Self {
+                Self { db }
+            }
+
+            /// Aplica los cambios de una de las personas de la fusión si su versión no cambió
+            async fn actualizar_persona(
+                conn: &mut <$motor as sqlx::Database>::Connection,
+                persona: &$crate::domain::Persona,
+            ) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                let consulta = sqlx::query(&CONSULTAS.actualizar_persona)
+                    .bind(&persona.ndocper)
+                    .bind(&persona.dirper)
+                    .bind(persona.estper.as_str())
+                    .bind(persona.actper)
+                    .bind(&persona.motsus)
+                    .bind(persona.fecsus)
+                    .bind(persona.idper)
+                    .bind(persona.verper);
+
+                $crate::infra::adapters::db::sql::fila_escrita!(
+                    $motor, conn, consulta, &CONSULTAS.persona, Some(persona.idper), $crate::domain::Persona
+                )
+                .ok_or_else(|| {
+                    $crate::errors::AppError::PreconditionFailed(format!(
+                        "La persona {} fue modificada por otro usuario",
+                        persona.idper
+                    ))
+                })
+            }
+
+            /// Pasa las filas de una tabla de la persona absorbida a la superviviente
+            async fn mover(
+                conn: &mut <$motor as sqlx::Database>::Connection,
+                consulta: &str,
+                idsup: i64,
+                idabs: i64,
+            ) -> $crate::errors::AppResult<u64> {
+                let resultado = sqlx::query(consulta)
+                    .bind(idsup)
+                    .bind(idabs)
+                    .execute(&mut *conn)
+                    .await?;
+
+                Ok(resultado.rows_affected())
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::DuplicadoRepository for $nombre {
+            async fn registrar(
+                &self,
+                candidatos: Vec<$crate::domain::NuevoCandidato>,
+            ) -> $crate::errors::AppResult<u64> {
+                use $crate::domain::EstadoDuplicado;
+
+                if candidatos.is_empty() {
+                    return Ok(0);
+                }
+
+                let mut query = sqlx::QueryBuilder::<$motor>::new(CONSULTAS.insertar.as_str());
+                query.push_values(candidatos, |mut fila, candidato| {
+                    fila.push_bind(candidato.idper1)
+                        .push_bind(candidato.idper2)
+                        .push_bind(candidato.pundup)
+                        .push_bind(candidato.motdup)
+                        .push_bind(EstadoDuplicado::Pendiente.as_str())
+                        .push_bind(candidato.fecdup);
+                });
+                query.push(CONSULTAS.sin_repetidos.as_str());
+                let resultado = query.build().execute(&mut *self.db.adquirir().await?).await?;
+
+                Ok(resultado.rows_affected())
+            }
+
+            async fn buscar(
+                &self,
+                filtro: $crate::domain::DuplicadoFilter,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::CandidatoDuplicado>> {
+                use $crate::{domain::EstadoDuplicado, infra::adapters::db::sql::MAX_LIMIT_DUPLICADO};
+
+                let mut query = sqlx::QueryBuilder::<$motor>::new(CONSULTAS.filtrar.as_str());
+                query.push_bind(filtro.estdup.unwrap_or(EstadoDuplicado::Pendiente).as_str());
+                if let Some(idper) = filtro.idper {
+                    query
+                        .push(" AND (idper1 = ")
+                        .push_bind(idper)
+                        .push(" OR idper2 = ")
+                        .push_bind(idper)
+                        .push(")");
+                }
+                query
+                    .push(" ORDER BY pundup DESC, iddup LIMIT ")
+                    .push_bind(filtro.limit.unwrap_or(100).clamp(1, MAX_LIMIT_DUPLICADO))
+                    .push(" OFFSET ")
+                    .push_bind(filtro.offset.unwrap_or(0).max(0));
+
+                let candidatos = query
+                    .build_query_as::<$crate::domain::CandidatoDuplicado>()
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(candidatos)
+            }
+
+            async fn get(
+                &self,
+                iddup: i64,
+            ) -> $crate::errors::AppResult<Option<$crate::domain::CandidatoDuplicado>> {
+                let candidato = sqlx::query_as::<_, $crate::domain::CandidatoDuplicado>(&CONSULTAS.por_iddup)
+                    .bind(iddup)
+                    .fetch_optional(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(candidato)
+            }
+
+            async fn descartar(
+                &self,
+                iddup: i64,
+                idrev: i64,
+                fecrev: chrono::DateTime<chrono::Utc>,
+            ) -> $crate::errors::AppResult<bool> {
+                use $crate::domain::EstadoDuplicado;
+
+                let resultado = sqlx::query(&CONSULTAS.descartar)
+                    .bind(EstadoDuplicado::Descartado.as_str())
+                    .bind(idrev)
+                    .bind(fecrev)
+                    .bind(iddup)
+                    .bind(EstadoDuplicado::Pendiente.as_str())
+                    .execute(&mut *self.db.adquirir().await?)
+                    .await?;
+
+                Ok(resultado.rows_affected() > 0)
+            }
+
+            async fn fusionar(
+                &self,
+                fusion: $crate::domain::db::NuevaFusion,
+            ) -> $crate::errors::AppResult<$crate::domain::FusionPersona> {
+                use $crate::domain::{EstadoDuplicado, db::NuevaFusion};
+
+                let NuevaFusion {
+                    superviviente,
+                    absorbida,
+                    iddup,
+                    idactor,
+                    mut detalle,
+                    fecfus,
+                } = fusion;
+                let (idsup, idabs) = (superviviente.idper, absorbida.idper);
+                // Dentro de una transacción de la unidad de trabajo esto es un savepoint
+                let mut conn = self.db.adquirir().await?;
+                let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+
+                // Primero la absorbida: si cede su documento debe soltarlo antes
+                let absorbida = Self::actualizar_persona(&mut tx, &absorbida).await?;
+                let superviviente = Self::actualizar_persona(&mut tx, &superviviente).await?;
+
+                detalle.consentimientos_cerrados = sqlx::query(&CONSULTAS.cerrar_consentimientos_repetidos)
+                    .bind(fecfus)
+                    .bind(idabs)
+                    .bind(idsup)
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected();
+                detalle.consentimientos = Self::mover(&mut tx, &CONSULTAS.mover_consentimientos, idsup, idabs).await?;
+                detalle.adjuntos = Self::mover(&mut tx, &CONSULTAS.mover_adjuntos, idsup, idabs).await?;
+
+                $registrar_version(&mut tx, &absorbida, fecfus).await?;
+                $registrar_version(&mut tx, &superviviente, fecfus).await?;
+
+                // El candidato que originó la fusión queda resuelto; los demás pendientes
+                // de la absorbida ya no tienen sentido
+                if let Some(iddup) = iddup {
+                    sqlx::query(&CONSULTAS.resolver)
+                        .bind(EstadoDuplicado::Fusionado.as_str())
+                        .bind(idactor)
+                        .bind(fecfus)
+                        .bind(iddup)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                sqlx::query(&CONSULTAS.descartar_de)
+                    .bind(EstadoDuplicado::Descartado.as_str())
+                    .bind(idactor)
+                    .bind(fecfus)
+                    .bind(EstadoDuplicado::Pendiente.as_str())
+                    .bind(idabs)
+                    .bind(idabs)
+                    .execute(&mut *tx)
+                    .await?;
+
+                let consulta = sqlx::query(&CONSULTAS.insertar_fusion)
+                    .bind(idsup)
+                    .bind(idabs)
+                    .bind(iddup)
+                    .bind(idactor)
+                    .bind(sqlx::types::Json(detalle))
+                    .bind(fecfus);
+                let registro = $crate::infra::adapters::db::sql::fila_escrita!(
+                    $motor, tx, consulta, &CONSULTAS.fusion_por_idfus, None, $crate::domain::FusionPersona
+                )
+                .ok_or_else(|| {
+                    $crate::errors::AppError::Internal("No se pudo leer la fusión registrada".to_string())
+                })?;
+
+                tx.commit().await?;
+                Ok(registro)
+            }
+
+            async fn fusiones(
+                &self,
+                idper: i64,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::FusionPersona>> {
+                let fusiones = sqlx::query_as::<_, $crate::domain::FusionPersona>(&CONSULTAS.fusiones)
+                    .bind(idper)
+                    .bind(idper)
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(fusiones)
+            }
+        }
+    };
+}
+
+pub(crate) use duplicado_repository_sql;
@@ -0,0 +1,39 @@
+/// Ejecuta un INSERT o un UPDATE y devuelve la fila escrita como `Option<$tipo>`
+/// (`None` si no afectó filas): con `RETURNING` en los motores que lo soportan
+/// y, en los demás, releyéndola con `$releer` por `$id` o por el id insertado
+/// `fila_escrita!(Motor, conexion, consulta, releer, id, Tipo)`
+macro_rules! fila_escrita {
+    ($motor:ty, $conn:expr, $consulta:expr, $releer:expr, $id:expr, $tipo:ty) => {{
+        use $crate::infra::adapters::db::sql::MotorSql;
+
+        let consulta = $consulta;
+        if <$motor as MotorSql>::DIALECTO.soporta_returning() {
+            match consulta.fetch_optional(&mut *$conn).await? {
+                Some(fila) => Some(<$tipo as sqlx::FromRow<'_, _>>::from_row(&fila)?),
+                None => None,
+            }
+        } else {
+            let resultado = consulta.execute(&mut *$conn).await?;
+            if resultado.rows_affected() == 0 {
+                None
+            } else {
+                let id: Option<i64> = $id;
+                let id = id
+                    .or_else(|| <$motor as MotorSql>::id_insertado(&resultado))
+                    .ok_or_else(|| {
+                        $crate::errors::AppError::Internal(
+                            "El motor no informó el id insertado".to_string(),
+                        )
+                    })?;
+                Some(
+                    sqlx::query_as::<_, $tipo>($releer)
+                        .bind(id)
+                        .fetch_one(&mut *$conn)
+                        .await?,
+                )
+            }
+        }
+    }};
+}
+
+pub(crate) use fila_escrita;
@@ -0,0 +1,161 @@
+/// Definición única del adaptador SQL de HabeasDataRepository
+use super::{COLUMNAS_PERSONA, Dialecto};
+
+const COLUMNAS: &str = "idsol, idper, tipsol, idsolicitante, fecsol";
+
+/// Sentencias del repositorio de habeas data para un dialecto
+pub struct ConsultasHabeasData {
+    pub insertar: String,
+    pub insertar_devolviendo: String,
+    pub por_idsol: String,
+    pub por_persona: String,
+    /// El dato anónimo se enlaza dos veces: nombre y apellido
+    pub anonimizar: String,
+    pub persona: String,
+}
+
+impl ConsultasHabeasData {
+    pub fn new(d: Dialecto) -> Self {
+        let insertar = d.sql(
+            "INSERT INTO habeas_data (idper, tipsol, idsolicitante, fecsol)
+             VALUES ($1, $2, $3, $4)",
+        );
+
+        Self {
+            insertar_devolviendo: format!("{}{}", insertar, d.returning(COLUMNAS)),
+            insertar,
+            por_idsol: d.sql(&format!("SELECT {COLUMNAS} FROM habeas_data WHERE idsol = $1")),
+            por_persona: d.sql(&format!(
+                "SELECT {COLUMNAS} FROM habeas_data WHERE idper = $1 ORDER BY fecsol, idsol"
+            )),
+            // La fila se conserva (mismo idper) para no romper las referencias
+            anonimizar: format!(
+                "{}{}",
+                d.sql(
+                    "UPDATE persona
+                     SET nomper = $1, apeper = $2, dirper = NULL, telper = $3, emaper = $4,
+                         ndocper = NULL, pass = NULL, estper = $5, actper = FALSE,
+                         motsus = NULL, fecsus = NULL, avaper = NULL, telnor = NULL, verper = verper + 1
+                     WHERE idper = $6 AND verper = $7"
+                ),
+                d.returning(COLUMNAS_PERSONA)
+            ),
+            persona: d.sql(&format!(
+                "SELECT {} FROM persona WHERE idper = $1",
+                COLUMNAS_PERSONA
+            )),
+        }
+    }
+}
+
+/// Genera `HabeasDataRepository` para un motor de SQLx:
+/// `habeas_data_repository_sql!(Nombre, Motor, registrar_version, purgar_historial)`
+/// con las funciones del historial del mismo motor; con `con_conexion` para
+/// usarlo dentro de una transacción de la unidad de trabajo
+macro_rules! habeas_data_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty, $registrar_version:path, $purgar_historial:path) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasHabeasData> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasHabeasData::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            db: $crate::infra::adapters::db::sql::Conexion<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(db: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { db: $crate::infra::adapters::db::sql::Conexion::Pool(db.into()) }
+            }
+
+            /// Repositorio que trabaja sobre la conexión indicada (p. ej. una transacción)
+            pub fn con_conexion(db: $crate::infra::adapters::db::sql::Conexion<$motor>) -> Self {
+                Self { db }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::HabeasDataRepository for $nombre {
+            async fn registrar(
+                &self,
+                solicitud: $crate::domain::NuevaSolicitud,
+            ) -> $crate::errors::AppResult<$crate::domain::SolicitudHabeasData> {
+                let mut conn = self.db.adquirir().await?;
+                let consulta = sqlx::query(&CONSULTAS.insertar_devolviendo)
+                    .bind(solicitud.idper)
+                    .bind(solicitud.tipsol.as_str())
+                    .bind(solicitud.idsolicitante)
+                    .bind(solicitud.fecsol);
+
+                $crate::infra::adapters::db::sql::fila_escrita!(
+                    $motor, conn, consulta, &CONSULTAS.por_idsol, None, $crate::domain::SolicitudHabeasData
+                )
+                .ok_or_else(|| {
+                    $crate::errors::AppError::Internal("No se pudo leer la solicitud creada".to_string())
+                })
+            }
+
+            async fn list_by_persona(
+                &self,
+                idper: i64,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::SolicitudHabeasData>> {
+                let solicitudes = sqlx::query_as::<_, $crate::domain::SolicitudHabeasData>(&CONSULTAS.por_persona)
+                    .bind(idper)
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(solicitudes)
+            }
+
+            async fn anonimizar(
+                &self,
+                idper: i64,
+                verper: i64,
+                solicitud: $crate::domain::NuevaSolicitud,
+            ) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                use $crate::domain::{DATO_ANONIMO, EstadoPersona, TELEFONO_ANONIMO, email_anonimo};
+
+                // Dentro de una transacción de la unidad de trabajo esto es un savepoint
+                let mut conn = self.db.adquirir().await?;
+                let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+
+                let consulta = sqlx::query(&CONSULTAS.anonimizar)
+                    .bind(DATO_ANONIMO)
+                    .bind(DATO_ANONIMO)
+                    .bind(TELEFONO_ANONIMO)
+                    .bind(email_anonimo(idper))
+                    .bind(EstadoPersona::Archivado.as_str())
+                    .bind(idper)
+                    .bind(verper);
+                let persona = $crate::infra::adapters::db::sql::fila_escrita!(
+                    $motor, tx, consulta, &CONSULTAS.persona, Some(idper), $crate::domain::Persona
+                )
+                .ok_or_else(|| {
+                    $crate::errors::AppError::PreconditionFailed(
+                        "La persona fue modificada por otro usuario".to_string(),
+                    )
+                })?;
+
+                sqlx::query(&CONSULTAS.insertar)
+                    .bind(solicitud.idper)
+                    .bind(solicitud.tipsol.as_str())
+                    .bind(solicitud.idsolicitante)
+                    .bind(solicitud.fecsol)
+                    .execute(&mut *tx)
+                    .await?;
+
+                // Las versiones anteriores también contienen datos personales
+                $purgar_historial(&mut tx, idper).await?;
+                $registrar_version(&mut tx, &persona, chrono::Utc::now()).await?;
+
+                tx.commit().await?;
+                Ok(persona)
+            }
+        }
+    };
+}
+
+pub(crate) use habeas_data_repository_sql;
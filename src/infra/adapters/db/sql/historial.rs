@@ -0,0 +1,166 @@
+/// Definición única del adaptador SQL de PersonaHistorialRepository y de las
+/// escrituras del historial que hacen los demás repositorios en sus transacciones
+use super::Dialecto;
+
+const COLUMNAS: &str = "idhis, idper, verper, ndocper, tdocper, nomper, apeper, dirper, telper, codubi, idpef, emaper, actper, estper, motsus, fecsus, vigdes, vighas";
+
+/// Sentencias del historial de personas para un dialecto
+pub struct ConsultasHistorial {
+    pub cerrar_vigente: String,
+    pub insertar: String,
+    pub purgar: String,
+    pub por_persona: String,
+    pub version: String,
+    /// La fecha se enlaza dos veces: desde y hasta
+    pub vigente_en: String,
+}
+
+impl ConsultasHistorial {
+    pub fn new(d: Dialecto) -> Self {
+        Self {
+            cerrar_vigente: d.sql(
+                "UPDATE persona_historial SET vighas = $1
+                 WHERE idper = $2 AND vighas IS NULL",
+            ),
+            insertar: d.sql(
+                "INSERT INTO persona_historial (idper, verper, ndocper, tdocper, nomper, apeper, dirper, telper, codubi, idpef, emaper, actper, estper, motsus, fecsus, vigdes)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)",
+            ),
+            purgar: d.sql("DELETE FROM persona_historial WHERE idper = $1"),
+            por_persona: d.sql(&format!(
+                "SELECT {COLUMNAS} FROM persona_historial
+                 WHERE idper = $1 ORDER BY vigdes DESC, idhis DESC"
+            )),
+            version: d.sql(&format!(
+                "SELECT {COLUMNAS} FROM persona_historial
+                 WHERE idper = $1 AND verper = $2
+                 ORDER BY idhis DESC LIMIT 1"
+            )),
+            vigente_en: d.sql(&format!(
+                "SELECT {COLUMNAS} FROM persona_historial
+                 WHERE idper = $1 AND vigdes <= $2 AND (vighas IS NULL OR vighas > $3)
+                 ORDER BY vigdes DESC, idhis DESC LIMIT 1"
+            )),
+        }
+    }
+}
+
+/// Genera `PersonaHistorialRepository` para un motor de SQLx junto con
+/// `registrar_version` y `purgar_historial`, que usan los demás repositorios
+/// del mismo motor: `historial_repository_sql!(Nombre, Motor)`
+macro_rules! historial_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasHistorial> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasHistorial::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            pool: $crate::config::Cluster<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(pool: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { pool: pool.into() }
+            }
+        }
+
+        /// Cierra la versión vigente y abre una nueva con el estado actual de la persona
+        /// Se ejecuta dentro de la transacción de la escritura que la origina
+        pub(super) async fn registrar_version(
+            conn: &mut <$motor as sqlx::Database>::Connection,
+            persona: &$crate::domain::Persona,
+            desde: chrono::DateTime<chrono::Utc>,
+        ) -> $crate::errors::AppResult<()> {
+            sqlx::query(&CONSULTAS.cerrar_vigente)
+                .bind(desde)
+                .bind(persona.idper)
+                .execute(&mut *conn)
+                .await?;
+
+            sqlx::query(&CONSULTAS.insertar)
+                .bind(persona.idper)
+                .bind(persona.verper)
+                .bind(&persona.ndocper)
+                .bind(persona.tdocper)
+                .bind(&persona.nomper)
+                .bind(&persona.apeper)
+                .bind(&persona.dirper)
+                .bind(&persona.telper)
+                .bind(persona.codubi)
+                .bind(persona.idpef)
+                .bind(&persona.emaper)
+                .bind(persona.actper)
+                .bind(persona.estper.as_str())
+                .bind(&persona.motsus)
+                .bind(persona.fecsus)
+                .bind(desde)
+                .execute(&mut *conn)
+                .await?;
+
+            Ok(())
+        }
+
+        /// Elimina todas las versiones de una persona (anonimización)
+        pub(super) async fn purgar_historial(
+            conn: &mut <$motor as sqlx::Database>::Connection,
+            idper: i64,
+        ) -> $crate::errors::AppResult<()> {
+            sqlx::query(&CONSULTAS.purgar)
+                .bind(idper)
+                .execute(&mut *conn)
+                .await?;
+
+            Ok(())
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::PersonaHistorialRepository for $nombre {
+            async fn list_by_persona(
+                &self,
+                idper: i64,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::PersonaVersion>> {
+                let versiones = sqlx::query_as::<_, $crate::domain::PersonaVersion>(&CONSULTAS.por_persona)
+                    .bind(idper)
+                    .fetch_all(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(versiones)
+            }
+
+            async fn get_version(
+                &self,
+                idper: i64,
+                verper: i64,
+            ) -> $crate::errors::AppResult<Option<$crate::domain::PersonaVersion>> {
+                let version = sqlx::query_as::<_, $crate::domain::PersonaVersion>(&CONSULTAS.version)
+                    .bind(idper)
+                    .bind(verper)
+                    .fetch_optional(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(version)
+            }
+
+            async fn as_of(
+                &self,
+                idper: i64,
+                fecha: chrono::DateTime<chrono::Utc>,
+            ) -> $crate::errors::AppResult<Option<$crate::domain::PersonaVersion>> {
+                let version = sqlx::query_as::<_, $crate::domain::PersonaVersion>(&CONSULTAS.vigente_en)
+                    .bind(idper)
+                    .bind(fecha)
+                    .bind(fecha)
+                    .fetch_optional(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(version)
+            }
+        }
+    };
+}
+
+pub(crate) use historial_repository_sql;
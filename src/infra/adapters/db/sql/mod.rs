@@ -0,0 +1,45 @@
+//! Piezas comunes a los adaptadores SQL: el dialecto de cada motor, la conexión
+//! (pool o transacción) y las definiciones de los repositorios que se generan
+//! para todos ellos
+mod acceso;
+mod adjunto;
+mod auditoria;
+mod catalog;
+mod conexion;
+mod consentimiento;
+mod dialecto;
+mod duplicado;
+mod fila;
+mod habeas_data;
+mod historial;
+mod pagper;
+mod persona;
+mod transaccion;
+mod ubicacion;
+
+pub(crate) use acceso::acceso_repository_sql;
+pub use acceso::{ConsultasAcceso, MAX_LIMIT_ACCESO};
+pub use adjunto::ConsultasAdjunto;
+pub(crate) use adjunto::adjunto_repository_sql;
+pub(crate) use auditoria::auditoria_repository_sql;
+pub use auditoria::{ConsultasAuditoria, MAX_LIMIT_AUDITORIA};
+pub use catalog::ConsultasCatalogo;
+pub(crate) use catalog::catalog_repository_sql;
+pub use conexion::{Adquirida, Conexion};
+pub use consentimiento::ConsultasConsentimiento;
+pub(crate) use consentimiento::consentimiento_repository_sql;
+pub use dialecto::{Dialecto, ESCAPE_LIKE, MotorSql, contiene};
+pub(crate) use duplicado::duplicado_repository_sql;
+pub use duplicado::{ConsultasDuplicado, MAX_LIMIT_DUPLICADO};
+pub(crate) use fila::fila_escrita;
+pub use habeas_data::ConsultasHabeasData;
+pub(crate) use habeas_data::habeas_data_repository_sql;
+pub use historial::ConsultasHistorial;
+pub(crate) use historial::historial_repository_sql;
+pub use pagper::ConsultasPagper;
+pub(crate) use pagper::pagper_repository_sql;
+pub(crate) use persona::persona_repository_sql;
+pub use persona::{COLUMNAS_PERSONA, ConsultasPersona, error_escritura};
+pub use transaccion::TransaccionSql;
+pub use ubicacion::ConsultasUbicacion;
+pub(crate) use ubicacion::ubicacion_repository_sql;
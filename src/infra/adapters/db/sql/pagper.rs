@@ -0,0 +1,112 @@
+/// Definición única del adaptador SQL de PagperRepository
+use super::Dialecto;
+
+/// Sentencias del repositorio de permisos por página para un dialecto
+pub struct ConsultasPagper {
+    pub por_perfil: String,
+    /// Una por acción, en el orden de `ACCIONES`
+    pub permiso: [String; 4],
+}
+
+/// Acción de `has_permission` y la columna de pagper que la concede
+const ACCIONES: [(&str, &str); 4] = [
+    ("create", "can_create"),
+    ("read", "can_read"),
+    ("update", "can_update"),
+    ("delete", "can_delete"),
+];
+
+impl ConsultasPagper {
+    pub fn new(d: Dialecto) -> Self {
+        Self {
+            por_perfil: d.sql(
+                "SELECT pp.idpef, pp.idpag, pp.can_create, pp.can_read, pp.can_update, pp.can_delete
+                 FROM pagper AS pp
+                 INNER JOIN pagina p ON pp.idpag = p.idpag
+                 WHERE pp.idpef = $1",
+            ),
+            permiso: ACCIONES.map(|(_, columna)| {
+                d.sql(&format!(
+                    "SELECT pp.{} FROM pagper pp
+                     INNER JOIN pagina p ON pp.idpag = p.idpag
+                     WHERE pp.idpef = $1 AND p.codpag = $2",
+                    columna
+                ))
+            }),
+        }
+    }
+
+    /// Consulta del permiso para `action`, o `None` si la acción no existe
+    pub fn permiso(&self, action: &str) -> Option<&str> {
+        ACCIONES
+            .iter()
+            .position(|(accion, _)| *accion == action)
+            .map(|i| self.permiso[i].as_str())
+    }
+}
+
+/// Genera `PagperRepository` para un motor de SQLx: `pagper_repository_sql!(Nombre, Motor)`
+macro_rules! pagper_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasPagper> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasPagper::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            pool: $crate::config::Cluster<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(pool: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { pool: pool.into() }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::PagperRepository for $nombre {
+            async fn find_by_perfil(&self, idpef: i64) -> $crate::errors::AppResult<Vec<$crate::domain::Pagper>> {
+                let pagpers = sqlx::query_as::<_, $crate::domain::Pagper>(&CONSULTAS.por_perfil)
+                    .bind(idpef)
+                    .fetch_all(&mut *self.pool.lectura().await?)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("Error al obtener permisos del perfil {}: {:?}", idpef, e);
+                        $crate::errors::AppError::Database(e)
+                    })?;
+
+                tracing::debug!(
+                    "Permisos cargados para perfil {}: {} registros",
+                    idpef,
+                    pagpers.len()
+                );
+                Ok(pagpers)
+            }
+
+            async fn has_permission(
+                &self,
+                idpef: i64,
+                codpag: &str,
+                action: &str,
+            ) -> $crate::errors::AppResult<bool> {
+                let Some(consulta) = CONSULTAS.permiso(action) else {
+                    return Ok(false);
+                };
+
+                let has_perm: Option<bool> = sqlx::query_scalar(consulta)
+                    .bind(idpef)
+                    .bind(codpag)
+                    .fetch_optional(&mut *self.pool.lectura().await?)
+                    .await
+                    .map_err($crate::errors::AppError::Database)?;
+
+                Ok(has_perm.unwrap_or(false))
+            }
+        }
+    };
+}
+
+pub(crate) use pagper_repository_sql;
@@ -0,0 +1,504 @@
+/// Definición única del adaptador SQL de PersonaRepository
+/// Las columnas y las sentencias se arman aquí a partir del dialecto y la macro
+/// `persona_repository_sql!` genera el repositorio de cada motor
+use super::Dialecto;
+use crate::errors::AppError;
+
+/// Columnas de `persona` en el orden que espera `Persona` al leer una fila
+pub const COLUMNAS_PERSONA: &str = "idper, ndocper, tdocper, nomper, apeper, dirper, telper, codubi, idpef, pass, emaper, actper, verper, estper, motsus, fecsus, avaper, telnor";
+
+/// Columnas que se dan al crear una persona; id, versión y demás las pone la base
+const COLUMNAS_INSERCION: [&str; 13] = [
+    "ndocper", "tdocper", "nomper", "apeper", "dirper", "telper", "telnor", "codubi", "idpef",
+    "pass", "emaper", "actper", "estper",
+];
+
+/// Columnas que cambia `update`
+const COLUMNAS_ACTUALIZACION: [&str; 10] = [
+    "ndocper", "tdocper", "nomper", "apeper", "dirper", "telper", "telnor", "codubi", "idpef",
+    "emaper",
+];
+
+/// Columnas que cambia `change_estado`
+const COLUMNAS_ESTADO: [&str; 4] = ["estper", "motsus", "fecsus", "actper"];
+
+/// Sentencias del repositorio de Persona para un dialecto
+/// Se arman una sola vez por motor (ver `persona_repository_sql!`)
+pub struct ConsultasPersona {
+    pub dialecto: Dialecto,
+    pub por_idper: String,
+    pub por_ndocper: String,
+    pub por_emaper: String,
+    pub por_telnor: String,
+    pub todas: String,
+    pub por_idpef: String,
+    pub activas: String,
+    pub insertar: String,
+    pub actualizar: String,
+    pub cambiar_estado: String,
+    pub existe: String,
+    pub cambiar_pass: String,
+    pub cambiar_avatar: String,
+    pub contar: String,
+    /// Inicio de la consulta de `stream`, a la que se agregan los filtros con AND
+    pub filtrar: String,
+}
+
+impl ConsultasPersona {
+    pub fn new(d: Dialecto) -> Self {
+        let seleccionar = format!("SELECT {} FROM persona", COLUMNAS_PERSONA);
+        let returning = d.returning(COLUMNAS_PERSONA);
+
+        Self {
+            dialecto: d,
+            por_idper: format!("{} WHERE idper = {}", seleccionar, d.marcador(1)),
+            por_ndocper: format!("{} WHERE ndocper = {}", seleccionar, d.marcador(1)),
+            por_emaper: format!("{} WHERE emaper = {}", seleccionar, d.marcador(1)),
+            por_telnor: format!(
+                "{} WHERE telnor = {} ORDER BY idper",
+                seleccionar,
+                d.marcador(1)
+            ),
+            todas: format!("{} ORDER BY idper{}", seleccionar, d.paginar(1)),
+            por_idpef: format!(
+                "{} WHERE idpef = {} AND actper = TRUE",
+                seleccionar,
+                d.marcador(1)
+            ),
+            activas: format!(
+                "{} WHERE actper = TRUE ORDER BY idper{}",
+                seleccionar,
+                d.paginar(1)
+            ),
+            insertar: format!(
+                "INSERT INTO persona ({}) VALUES ({}){}",
+                COLUMNAS_INSERCION.join(", "),
+                d.marcadores(1, COLUMNAS_INSERCION.len()),
+                returning
+            ),
+            // El WHERE sobre verper hace que la comprobación y la escritura sean atómicas
+            actualizar: format!(
+                "UPDATE persona SET {}, verper = verper + 1 WHERE idper = {} AND verper = {}{}",
+                d.asignaciones(&COLUMNAS_ACTUALIZACION, 1),
+                d.marcador(11),
+                d.marcador(12),
+                returning
+            ),
+            cambiar_estado: format!(
+                "UPDATE persona SET {}, verper = verper + 1 WHERE idper = {} AND verper = {}{}",
+                d.asignaciones(&COLUMNAS_ESTADO, 1),
+                d.marcador(5),
+                d.marcador(6),
+                returning
+            ),
+            existe: format!(
+                "SELECT EXISTS(SELECT 1 FROM persona WHERE idper = {})",
+                d.marcador(1)
+            ),
+            cambiar_pass: format!(
+                "UPDATE persona SET pass = {} WHERE idper = {}",
+                d.marcador(1),
+                d.marcador(2)
+            ),
+            cambiar_avatar: format!(
+                "UPDATE persona SET avaper = {}, verper = verper + 1 WHERE idper = {}{}",
+                d.marcador(1),
+                d.marcador(2),
+                returning
+            ),
+            contar: "SELECT COUNT(*) FROM persona".to_string(),
+            filtrar: format!("{} WHERE 1 = 1", seleccionar),
+        }
+    }
+}
+
+/// Los índices únicos de email y documento se reportan como conflicto,
+/// igual que en el adaptador en memoria
+pub fn error_escritura(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            AppError::Conflict("Ya existe una persona con ese email o documento".to_string())
+        }
+        _ => AppError::Database(e),
+    }
+}
+
+/// Genera `PersonaRepository` para un motor de SQLx:
+/// `persona_repository_sql!(Nombre, Motor, registrar_version)`
+/// donde `registrar_version` es la del historial del mismo motor
+/// Las consultas leen de una réplica si la hay; las escrituras van a la primaria
+macro_rules! persona_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty, $registrar_version:path) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasPersona> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasPersona::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            db: $crate::infra::adapters::db::sql::Conexion<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(db: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { db: $crate::infra::adapters::db::sql::Conexion::Pool(db.into()) }
+            }
+
+            /// Repositorio que trabaja sobre la conexión indicada (p. ej. una transacción)
+            pub fn con_conexion(db: $crate::infra::adapters::db::sql::Conexion<$motor>) -> Self {
+                Self { db }
+            }
+
+            /// Relee la persona dentro de la transacción (motores sin RETURNING)
+            pub(super) async fn releer(
+                conn: &mut <$motor as sqlx::Database>::Connection,
+                idper: i64,
+            ) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                let persona = sqlx::query_as::<_, $crate::domain::Persona>(&CONSULTAS.por_idper)
+                    .bind(idper)
+                    .fetch_one(&mut *conn)
+                    .await?;
+
+                Ok(persona)
+            }
+
+            /// Ejecuta una escritura sobre una persona y devuelve la fila resultante,
+            /// o `None` si la condición sobre verper no dejó escribir
+            /// Sin RETURNING la fila se relee por `idper` o por el id insertado
+            async fn escribir<'q>(
+                conn: &mut <$motor as sqlx::Database>::Connection,
+                consulta: sqlx::query::Query<'q, $motor, <$motor as sqlx::Database>::Arguments<'q>>,
+                idper: Option<i64>,
+            ) -> $crate::errors::AppResult<Option<$crate::domain::Persona>> {
+                use $crate::infra::adapters::db::sql::{MotorSql, error_escritura};
+
+                if CONSULTAS.dialecto.soporta_returning() {
+                    return match consulta
+                        .fetch_optional(&mut *conn)
+                        .await
+                        .map_err(error_escritura)?
+                    {
+                        Some(fila) => Ok(Some(sqlx::FromRow::from_row(&fila)?)),
+                        None => Ok(None),
+                    };
+                }
+
+                let resultado = consulta.execute(&mut *conn).await.map_err(error_escritura)?;
+                if resultado.rows_affected() == 0 {
+                    return Ok(None);
+                }
+                let idper = idper
+                    .or_else(|| <$motor as MotorSql>::id_insertado(&resultado))
+                    .ok_or_else(|| {
+                        $crate::errors::AppError::Internal("El motor no informó el id insertado".to_string())
+                    })?;
+                Ok(Some(Self::releer(conn, idper).await?))
+            }
+
+            async fn insertar(
+                conn: &mut <$motor as sqlx::Database>::Connection,
+                persona: &$crate::domain::Persona,
+            ) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                let consulta = sqlx::query(&CONSULTAS.insertar)
+                    .bind(&persona.ndocper)
+                    .bind(persona.tdocper)
+                    .bind(&persona.nomper)
+                    .bind(&persona.apeper)
+                    .bind(&persona.dirper)
+                    .bind(&persona.telper)
+                    .bind(&persona.telnor)
+                    .bind(persona.codubi)
+                    .bind(persona.idpef)
+                    .bind(&persona.pass)
+                    .bind(&persona.emaper)
+                    .bind(persona.actper)
+                    .bind(persona.estper.as_str());
+
+                let creada = Self::escribir(&mut *conn, consulta, None).await?.ok_or_else(|| {
+                    $crate::errors::AppError::Internal("El INSERT de persona no devolvió la fila".to_string())
+                })?;
+                $registrar_version(&mut *conn, &creada, chrono::Utc::now()).await?;
+                Ok(creada)
+            }
+
+            /// Distingue entre una persona inexistente y una versión desactualizada
+            /// después de que una escritura condicionada por verper no afectó filas
+            async fn version_conflict(
+                conn: &mut <$motor as sqlx::Database>::Connection,
+                idper: i64,
+            ) -> $crate::errors::AppError {
+                let existe = sqlx::query_scalar::<_, bool>(&CONSULTAS.existe)
+                    .bind(idper)
+                    .fetch_one(&mut *conn)
+                    .await;
+
+                match existe {
+                    Ok(true) => $crate::errors::AppError::PreconditionFailed(
+                        "La persona fue modificada por otro usuario".to_string(),
+                    ),
+                    Ok(false) => $crate::errors::AppError::NotFound("Persona no encontrada".to_string()),
+                    Err(e) => e.into(),
+                }
+            }
+
+            /// Escritura condicionada por verper en su propia transacción (un savepoint
+            /// si la conexión ya está en una): registra la versión y confirma, o deshace
+            /// y explica por qué no se escribió
+            async fn escribir_versionada<'q>(
+                conn: &mut <$motor as sqlx::Database>::Connection,
+                consulta: sqlx::query::Query<'q, $motor, <$motor as sqlx::Database>::Arguments<'q>>,
+                idper: i64,
+            ) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+                match Self::escribir(&mut tx, consulta, Some(idper)).await? {
+                    Some(persona) => {
+                        $registrar_version(&mut tx, &persona, chrono::Utc::now()).await?;
+                        tx.commit().await?;
+                        Ok(persona)
+                    }
+                    None => {
+                        tx.rollback().await?;
+                        Err(Self::version_conflict(conn, idper).await)
+                    }
+                }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::PersonaRepository for $nombre {
+            async fn get_by_idper(&self, idper: i64) -> $crate::errors::AppResult<Option<$crate::domain::Persona>> {
+                let persona = sqlx::query_as::<_, $crate::domain::Persona>(&CONSULTAS.por_idper)
+                    .bind(idper)
+                    .fetch_optional(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(persona)
+            }
+
+            async fn get_by_ndocper(&self, ndocper: &str) -> $crate::errors::AppResult<Option<$crate::domain::Persona>> {
+                let persona = sqlx::query_as::<_, $crate::domain::Persona>(&CONSULTAS.por_ndocper)
+                    .bind(ndocper)
+                    .fetch_optional(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(persona)
+            }
+
+            async fn get_by_emaper(&self, emaper: &str) -> $crate::errors::AppResult<Option<$crate::domain::Persona>> {
+                let persona = sqlx::query_as::<_, $crate::domain::Persona>(&CONSULTAS.por_emaper)
+                    .bind(emaper)
+                    .fetch_optional(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(persona)
+            }
+
+            async fn get_by_telnor(&self, telnor: &str) -> $crate::errors::AppResult<Vec<$crate::domain::Persona>> {
+                let personas = sqlx::query_as::<_, $crate::domain::Persona>(&CONSULTAS.por_telnor)
+                    .bind(telnor)
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(personas)
+            }
+
+            async fn get_all(&self, limit: i64, offset: i64) -> $crate::errors::AppResult<Vec<$crate::domain::Persona>> {
+                let personas = sqlx::query_as::<_, $crate::domain::Persona>(&CONSULTAS.todas)
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(personas)
+            }
+
+            async fn get_all_by_idpef(&self, idpef: i64) -> $crate::errors::AppResult<Vec<$crate::domain::Persona>> {
+                let personas = sqlx::query_as::<_, $crate::domain::Persona>(&CONSULTAS.por_idpef)
+                    .bind(idpef)
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(personas)
+            }
+
+            async fn create(&self, persona: $crate::domain::Persona) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                let mut conn = self.db.adquirir().await?;
+                let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+                let creada = Self::insertar(&mut tx, &persona).await?;
+                tx.commit().await?;
+                Ok(creada)
+            }
+
+            async fn create_many(&self, personas: Vec<$crate::domain::Persona>) -> $crate::errors::AppResult<Vec<$crate::domain::Persona>> {
+                let mut conn = self.db.adquirir().await?;
+                let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+                let mut creadas = Vec::with_capacity(personas.len());
+
+                for persona in &personas {
+                    creadas.push(Self::insertar(&mut tx, persona).await?);
+                }
+
+                // Si algo falla antes de aquí, el drop de `tx` hace rollback
+                tx.commit().await?;
+                Ok(creadas)
+            }
+
+            async fn update(
+                &self,
+                idper: i64,
+                persona: $crate::domain::Persona,
+                verper: i64,
+            ) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                let mut conn = self.db.adquirir().await?;
+                let consulta = sqlx::query(&CONSULTAS.actualizar)
+                    .bind(persona.ndocper)
+                    .bind(persona.tdocper)
+                    .bind(persona.nomper)
+                    .bind(persona.apeper)
+                    .bind(persona.dirper)
+                    .bind(persona.telper)
+                    .bind(persona.telnor)
+                    .bind(persona.codubi)
+                    .bind(persona.idpef)
+                    .bind(persona.emaper)
+                    .bind(idper)
+                    .bind(verper);
+
+                Self::escribir_versionada(&mut conn, consulta, idper).await
+            }
+
+            async fn change_estado(
+                &self,
+                idper: i64,
+                cambio: $crate::domain::CambioEstado,
+                verper: i64,
+            ) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                let mut conn = self.db.adquirir().await?;
+                let actper = cambio.actper();
+                let consulta = sqlx::query(&CONSULTAS.cambiar_estado)
+                    .bind(cambio.estper.as_str())
+                    .bind(cambio.motsus)
+                    .bind(cambio.fecsus)
+                    .bind(actper)
+                    .bind(idper)
+                    .bind(verper);
+
+                Self::escribir_versionada(&mut conn, consulta, idper).await
+            }
+
+            async fn exists(&self, idper: i64) -> $crate::errors::AppResult<bool> {
+                let resultado = sqlx::query_scalar::<_, bool>(&CONSULTAS.existe)
+                    .bind(idper)
+                    .fetch_one(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(resultado)
+            }
+
+            async fn change_password(&self, idper: i64, new_password: &str) -> $crate::errors::AppResult<()> {
+                sqlx::query(&CONSULTAS.cambiar_pass)
+                    .bind(new_password)
+                    .bind(idper)
+                    .execute(&mut *self.db.adquirir().await?)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn set_avatar(
+                &self,
+                idper: i64,
+                avaper: Option<&str>,
+            ) -> $crate::errors::AppResult<$crate::domain::Persona> {
+                let mut conn = self.db.adquirir().await?;
+                let consulta = sqlx::query(&CONSULTAS.cambiar_avatar)
+                    .bind(avaper)
+                    .bind(idper);
+
+                // Sin condición sobre verper, no escribir solo puede ser que no exista
+                Self::escribir_versionada(&mut conn, consulta, idper).await
+            }
+
+            async fn count(&self) -> $crate::errors::AppResult<i64> {
+                let total = sqlx::query_scalar::<_, i64>(&CONSULTAS.contar)
+                    .fetch_one(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(total)
+            }
+
+            async fn get_active(&self, limit: i64, offset: i64) -> $crate::errors::AppResult<Vec<$crate::domain::Persona>> {
+                let personas = sqlx::query_as::<_, $crate::domain::Persona>(&CONSULTAS.activas)
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(&mut *self.db.lectura().await?)
+                    .await?;
+
+                Ok(personas)
+            }
+
+            fn stream(
+                &self,
+                filtro: $crate::domain::PersonaFilter,
+            ) -> futures::stream::BoxStream<'static, $crate::errors::AppResult<$crate::domain::Persona>> {
+                use futures::TryStreamExt;
+
+                // Se decide aquí, dentro de la petición, si leer de la primaria: el
+                // stream se consume después, al enviar el cuerpo de la respuesta
+                let lectura = self.db.lectura_diferida();
+
+                Box::pin(async_stream::try_stream! {
+                    let mut query = sqlx::QueryBuilder::<$motor>::new(CONSULTAS.filtrar.as_str());
+                    if let Some(idpef) = filtro.idpef {
+                        query.push(" AND idpef = ").push_bind(idpef);
+                    }
+                    if let Some(actper) = filtro.actper {
+                        query.push(" AND actper = ").push_bind(actper);
+                    }
+                    if let Some(estper) = filtro.estper {
+                        query.push(" AND estper = ").push_bind(estper.as_str());
+                    }
+                    if let Some(codubi) = filtro.codubi {
+                        query.push(" AND codubi = ").push_bind(codubi);
+                    }
+                    if let Some(q) = filtro.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+                        use $crate::infra::adapters::db::sql::ESCAPE_LIKE;
+
+                        let patron = $crate::infra::adapters::db::sql::contiene(&q.to_lowercase());
+                        query
+                            .push(" AND (LOWER(nomper) LIKE ")
+                            .push_bind(patron.clone())
+                            .push(ESCAPE_LIKE)
+                            .push(" OR LOWER(apeper) LIKE ")
+                            .push_bind(patron.clone())
+                            .push(ESCAPE_LIKE)
+                            .push(" OR LOWER(emaper) LIKE ")
+                            .push_bind(patron)
+                            .push(ESCAPE_LIKE)
+                            .push(")");
+                    }
+                    if let Some(idpef) = filtro.excluir_idpef {
+                        query.push(" AND idpef <> ").push_bind(idpef);
+                    }
+                    if let Some(idpol) = filtro.con_consentimiento {
+                        query
+                            .push(" AND EXISTS (SELECT 1 FROM consentimiento c WHERE c.idper = persona.idper AND c.fecrev IS NULL AND c.idpol = ")
+                            .push_bind(idpol)
+                            .push(")");
+                    }
+                    query.push(" ORDER BY idper");
+
+                    let mut conn = lectura.await?;
+                    let mut filas = query.build_query_as::<$crate::domain::Persona>().fetch(&mut *conn);
+                    while let Some(persona) = filas.try_next().await? {
+                        yield persona;
+                    }
+                })
+            }
+        }
+    };
+}
+
+pub(crate) use persona_repository_sql;
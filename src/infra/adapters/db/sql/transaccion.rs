@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use sqlx::Database;
+
+use super::Conexion;
+use crate::{
+    domain::db::{
+        ConsentimientoRepository, DuplicadoRepository, HabeasDataRepository, PersonaRepository,
+        Transaccion,
+    },
+    errors::AppResult,
+};
+
+/// Transacción de la unidad de trabajo SQL: los repositorios comparten la
+/// misma `Conexion::Transaccion`, que se confirma o deshace al final
+pub struct TransaccionSql<DB: Database> {
+    conexion: Conexion<DB>,
+    persona: Box<dyn PersonaRepository>,
+    consentimiento: Box<dyn ConsentimientoRepository>,
+    duplicado: Box<dyn DuplicadoRepository>,
+    habeas_data: Box<dyn HabeasDataRepository>,
+}
+
+impl<DB: Database> TransaccionSql<DB> {
+    pub fn new(
+        conexion: Conexion<DB>,
+        persona: Box<dyn PersonaRepository>,
+        consentimiento: Box<dyn ConsentimientoRepository>,
+        duplicado: Box<dyn DuplicadoRepository>,
+        habeas_data: Box<dyn HabeasDataRepository>,
+    ) -> Self {
+        Self {
+            conexion,
+            persona,
+            consentimiento,
+            duplicado,
+            habeas_data,
+        }
+    }
+}
+
+#[async_trait]
+impl<DB: Database> Transaccion for TransaccionSql<DB> {
+    fn persona(&self) -> &dyn PersonaRepository {
+        &*self.persona
+    }
+
+    fn consentimiento(&self) -> &dyn ConsentimientoRepository {
+        &*self.consentimiento
+    }
+
+    fn duplicado(&self) -> &dyn DuplicadoRepository {
+        &*self.duplicado
+    }
+
+    fn habeas_data(&self) -> &dyn HabeasDataRepository {
+        &*self.habeas_data
+    }
+
+    async fn commit(self: Box<Self>) -> AppResult<()> {
+        self.conexion.commit().await
+    }
+
+    async fn rollback(self: Box<Self>) -> AppResult<()> {
+        self.conexion.rollback().await
+    }
+}
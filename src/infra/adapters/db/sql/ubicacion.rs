@@ -0,0 +1,149 @@
+/// Definición única del adaptador SQL de UbicacionRepository
+/// Las sentencias se escriben con marcadores `$n` y `Dialecto::sql` las traduce
+use super::{Dialecto, ESCAPE_LIKE};
+
+/// Sentencias del repositorio de ubicaciones para un dialecto
+pub struct ConsultasUbicacion {
+    pub departamentos: String,
+    pub municipios: String,
+    pub municipio: String,
+    pub buscar: String,
+    pub insertar_departamento: String,
+    pub insertar_municipio: String,
+}
+
+impl ConsultasUbicacion {
+    pub fn new(d: Dialecto) -> Self {
+        Self {
+            departamentos: d.sql("SELECT coddep, nomdep FROM departamento ORDER BY nomdep"),
+            municipios: d.sql(
+                "SELECT u.codubi, u.nomubi, u.coddep, d.nomdep
+                 FROM ubicacion u
+                 INNER JOIN departamento d ON u.coddep = d.coddep
+                 WHERE u.coddep = $1
+                 ORDER BY u.nomubi",
+            ),
+            municipio: d.sql(
+                "SELECT u.codubi, u.nomubi, u.coddep, d.nomdep
+                 FROM ubicacion u
+                 INNER JOIN departamento d ON u.coddep = d.coddep
+                 WHERE u.codubi = $1",
+            ),
+            buscar: d.sql(&format!(
+                "SELECT u.codubi, u.nomubi, u.coddep, d.nomdep
+                 FROM ubicacion u
+                 INNER JOIN departamento d ON u.coddep = d.coddep
+                 WHERE LOWER(u.nomubi) LIKE $1{ESCAPE_LIKE}
+                 ORDER BY u.nomubi
+                 LIMIT $2"
+            )),
+            insertar_departamento: format!(
+                "{}{}",
+                d.sql("INSERT INTO departamento (coddep, nomdep) VALUES ($1, $2)"),
+                d.upsert(&["coddep"], &["nomdep"])
+            ),
+            insertar_municipio: format!(
+                "{}{}",
+                d.sql("INSERT INTO ubicacion (codubi, nomubi, coddep) VALUES ($1, $2, $3)"),
+                d.upsert(&["codubi"], &["nomubi", "coddep"])
+            ),
+        }
+    }
+}
+
+/// Genera `UbicacionRepository` para un motor de SQLx:
+/// `ubicacion_repository_sql!(Nombre, Motor)`
+macro_rules! ubicacion_repository_sql {
+    ($(#[$doc:meta])* $nombre:ident, $motor:ty) => {
+        static CONSULTAS: std::sync::LazyLock<$crate::infra::adapters::db::sql::ConsultasUbicacion> =
+            std::sync::LazyLock::new(|| {
+                $crate::infra::adapters::db::sql::ConsultasUbicacion::new(
+                    <$motor as $crate::infra::adapters::db::sql::MotorSql>::DIALECTO,
+                )
+            });
+
+        $(#[$doc])*
+        pub struct $nombre {
+            pool: $crate::config::Cluster<$motor>,
+        }
+
+        impl $nombre {
+            pub fn new(pool: impl Into<$crate::config::Cluster<$motor>>) -> Self {
+                Self { pool: pool.into() }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::domain::db::UbicacionRepository for $nombre {
+            async fn list_departamentos(&self) -> $crate::errors::AppResult<Vec<$crate::domain::Departamento>> {
+                let departamentos = sqlx::query_as::<_, $crate::domain::Departamento>(&CONSULTAS.departamentos)
+                    .fetch_all(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(departamentos)
+            }
+
+            async fn list_municipios(&self, coddep: i64) -> $crate::errors::AppResult<Vec<$crate::domain::Municipio>> {
+                let municipios = sqlx::query_as::<_, $crate::domain::Municipio>(&CONSULTAS.municipios)
+                    .bind(coddep)
+                    .fetch_all(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(municipios)
+            }
+
+            async fn get_municipio(&self, codubi: i64) -> $crate::errors::AppResult<Option<$crate::domain::Municipio>> {
+                let municipio = sqlx::query_as::<_, $crate::domain::Municipio>(&CONSULTAS.municipio)
+                    .bind(codubi)
+                    .fetch_optional(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(municipio)
+            }
+
+            async fn search_municipios(
+                &self,
+                q: &str,
+                limit: i64,
+            ) -> $crate::errors::AppResult<Vec<$crate::domain::Municipio>> {
+                let municipios = sqlx::query_as::<_, $crate::domain::Municipio>(&CONSULTAS.buscar)
+                    .bind($crate::infra::adapters::db::sql::contiene(&q.to_lowercase()))
+                    .bind(limit)
+                    .fetch_all(&mut *self.pool.lectura().await?)
+                    .await?;
+
+                Ok(municipios)
+            }
+
+            async fn import(
+                &self,
+                departamentos: Vec<$crate::domain::Departamento>,
+                municipios: Vec<$crate::domain::Municipio>,
+            ) -> $crate::errors::AppResult<(usize, usize)> {
+                let mut tx = self.pool.transaccion().await?;
+
+                for departamento in &departamentos {
+                    sqlx::query(&CONSULTAS.insertar_departamento)
+                        .bind(departamento.coddep)
+                        .bind(&departamento.nomdep)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+
+                for municipio in &municipios {
+                    sqlx::query(&CONSULTAS.insertar_municipio)
+                        .bind(municipio.codubi)
+                        .bind(&municipio.nomubi)
+                        .bind(municipio.coddep)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+
+                tx.commit().await?;
+                Ok((departamentos.len(), municipios.len()))
+            }
+        }
+    };
+}
+
+pub(crate) use ubicacion_repository_sql;
@@ -0,0 +1,11 @@
+/// Implementación del Puerto AdjuntoRepository sobre SQLite
+/// Las sentencias y el código vienen de `sql::adjunto`, común a todos los motores
+use sqlx::Sqlite;
+
+use crate::infra::adapters::db::sql::adjunto_repository_sql;
+
+adjunto_repository_sql!(
+    /// Repositorio de adjuntos que usa SQLite a través de SQLx
+    AdjuntoRepositorySqlite,
+    Sqlite
+);
@@ -0,0 +1,11 @@
+/// Implementación del Puerto AuditoriaRepository sobre SQLite
+/// Las sentencias y el código vienen de `sql::auditoria`, común a todos los motores
+use sqlx::Sqlite;
+
+use crate::infra::adapters::db::sql::auditoria_repository_sql;
+
+auditoria_repository_sql!(
+    /// Repositorio de auditoría que usa SQLite a través de SQLx
+    AuditoriaRepositorySqlite,
+    Sqlite
+);
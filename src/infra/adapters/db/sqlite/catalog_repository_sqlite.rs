@@ -0,0 +1,11 @@
+/// Implementación del Puerto CatalogRepository sobre SQLite
+/// Las sentencias y el código vienen de `sql::catalog`, común a todos los motores
+use sqlx::Sqlite;
+
+use crate::infra::adapters::db::sql::catalog_repository_sql;
+
+catalog_repository_sql!(
+    /// Repositorio de catálogos que usa SQLite a través de SQLx
+    CatalogRepositorySqlite,
+    Sqlite
+);
@@ -0,0 +1,11 @@
+/// Implementación del Puerto ConsentimientoRepository sobre SQLite
+/// Las sentencias y el código vienen de `sql::consentimiento`, común a todos los motores
+use sqlx::Sqlite;
+
+use crate::infra::adapters::db::sql::consentimiento_repository_sql;
+
+consentimiento_repository_sql!(
+    /// Repositorio de consentimientos que usa SQLite a través de SQLx
+    ConsentimientoRepositorySqlite,
+    Sqlite
+);
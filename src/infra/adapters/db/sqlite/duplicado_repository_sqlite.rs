@@ -0,0 +1,13 @@
+/// Implementación del Puerto DuplicadoRepository sobre SQLite
+/// Las sentencias y el código vienen de `sql::duplicado`, común a todos los motores
+use sqlx::Sqlite;
+
+use super::persona_historial_repository_sqlite::registrar_version;
+use crate::infra::adapters::db::sql::duplicado_repository_sql;
+
+duplicado_repository_sql!(
+    /// Repositorio de duplicados que usa SQLite a través de SQLx
+    DuplicadoRepositorySqlite,
+    Sqlite,
+    registrar_version
+);
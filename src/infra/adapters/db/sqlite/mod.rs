@@ -0,0 +1,28 @@
+mod acceso_repository_sqlite;
+mod adjunto_repository_sqlite;
+mod auditoria_repository_sqlite;
+mod catalog_repository_sqlite;
+mod consentimiento_repository_sqlite;
+mod duplicado_repository_sqlite;
+mod habeas_data_repository_sqlite;
+mod pagper_repository_sqlite;
+mod persona_historial_repository_sqlite;
+mod persona_repository;
+mod ubicacion_repository_sqlite;
+mod unit_of_work_sqlite;
+
+pub use acceso_repository_sqlite::AccesoRepositorySqlite;
+pub use adjunto_repository_sqlite::AdjuntoRepositorySqlite;
+pub use auditoria_repository_sqlite::AuditoriaRepositorySqlite;
+pub use catalog_repository_sqlite::CatalogRepositorySqlite;
+pub use consentimiento_repository_sqlite::ConsentimientoRepositorySqlite;
+pub use duplicado_repository_sqlite::DuplicadoRepositorySqlite;
+pub use habeas_data_repository_sqlite::HabeasDataRepositorySqlite;
+pub use pagper_repository_sqlite::PagperRepositorySqlite;
+pub use persona_historial_repository_sqlite::PersonaHistorialRepositorySqlite;
+pub use persona_repository::PersonaRepositorySqlite;
+pub use ubicacion_repository_sqlite::UbicacionRepositorySqlite;
+pub use unit_of_work_sqlite::UnitOfWorkSqlite;
+
+/// Migraciones del esquema para SQLite (`migrations/sqlite`), embebidas en el binario
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations/sqlite");
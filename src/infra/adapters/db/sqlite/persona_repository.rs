@@ -0,0 +1,13 @@
+/// Implementación del Puerto PersonaRepository sobre SQLite
+/// Las sentencias y el código vienen de `sql::persona`, común a todos los motores
+use sqlx::Sqlite;
+
+use super::persona_historial_repository_sqlite::registrar_version;
+use crate::infra::adapters::db::sql::persona_repository_sql;
+
+persona_repository_sql!(
+    /// Repositorio de Persona que usa SQLite a través de SQLx
+    PersonaRepositorySqlite,
+    Sqlite,
+    registrar_version
+);
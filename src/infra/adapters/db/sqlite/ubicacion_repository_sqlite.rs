@@ -0,0 +1,11 @@
+/// Implementación del Puerto UbicacionRepository sobre SQLite
+/// Las sentencias y el código vienen de `sql::ubicacion`, común a todos los motores
+use sqlx::Sqlite;
+
+use crate::infra::adapters::db::sql::ubicacion_repository_sql;
+
+ubicacion_repository_sql!(
+    /// Repositorio de ubicaciones que usa SQLite a través de SQLx
+    UbicacionRepositorySqlite,
+    Sqlite
+);
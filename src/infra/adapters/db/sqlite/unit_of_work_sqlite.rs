@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use sqlx::Sqlite;
+
+use super::{
+    ConsentimientoRepositorySqlite, DuplicadoRepositorySqlite, HabeasDataRepositorySqlite,
+    PersonaRepositorySqlite,
+};
+use crate::{
+    config::Cluster,
+    domain::db::{Transaccion, UnitOfWork},
+    errors::AppResult,
+    infra::adapters::db::sql::{Conexion, TransaccionSql},
+};
+
+/// Unidad de trabajo sobre SQLite: cada transacción toma una conexión del pool
+/// y la comparten sus repositorios hasta el commit o el rollback
+pub struct UnitOfWorkSqlite {
+    pool: Cluster<Sqlite>,
+}
+
+impl UnitOfWorkSqlite {
+    pub fn new(pool: impl Into<Cluster<Sqlite>>) -> Self {
+        Self { pool: pool.into() }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for UnitOfWorkSqlite {
+    async fn begin(&self) -> AppResult<Box<dyn Transaccion>> {
+        let conexion = Conexion::<Sqlite>::transaccion(self.pool.transaccion().await?);
+
+        Ok(Box::new(TransaccionSql::new(
+            conexion.clone(),
+            Box::new(PersonaRepositorySqlite::con_conexion(conexion.clone())),
+            Box::new(ConsentimientoRepositorySqlite::con_conexion(conexion.clone())),
+            Box::new(DuplicadoRepositorySqlite::con_conexion(conexion.clone())),
+            Box::new(HabeasDataRepositorySqlite::con_conexion(conexion)),
+        )))
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::{
+    domain::storage::ObjectStorage,
+    errors::{AppError, AppResult},
+};
+
+/// Almacenamiento de objetos en un directorio del sistema de archivos
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Ruta del objeto; rechaza claves que salgan del directorio raíz
+    fn path(&self, key: &str) -> AppResult<PathBuf> {
+        let relativa = Path::new(key);
+        let valida = relativa
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+        if key.is_empty() || !valida {
+            return Err(AppError::Internal(format!(
+                "Clave de objeto inválida: {}",
+                key
+            )));
+        }
+        Ok(self.root.join(relativa))
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    async fn put(&self, key: &str, _content_type: &str, data: Bytes) -> AppResult<()> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await.map_err(io_error)?;
+        }
+
+        // Se escribe aparte y se renombra para no dejar archivos a medias
+        let temporal = path.with_extension("part");
+        tokio::fs::write(&temporal, &data).await.map_err(io_error)?;
+        tokio::fs::rename(&temporal, &path)
+            .await
+            .map_err(io_error)?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Bytes> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Bytes::from(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(AppError::NotFound("Archivo no encontrado".to_string()))
+            }
+            Err(e) => Err(io_error(e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_error(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn io_error(e: std::io::Error) -> AppError {
+    AppError::Internal(format!("Error del almacenamiento local: {}", e))
+}
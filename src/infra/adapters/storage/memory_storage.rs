@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use tokio::sync::RwLock;
+
+use crate::{
+    domain::storage::ObjectStorage,
+    errors::{AppError, AppResult},
+};
+
+/// Almacenamiento de objetos en memoria, para pruebas
+/// Los objetos se pierden al terminar el proceso
+#[derive(Default)]
+pub struct MemoryStorage {
+    objetos: RwLock<HashMap<String, Bytes>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for MemoryStorage {
+    async fn put(&self, key: &str, _content_type: &str, data: Bytes) -> AppResult<()> {
+        self.objetos.write().await.insert(key.to_string(), data);
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Bytes> {
+        self.objetos
+            .read()
+            .await
+            .get(key)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Archivo no encontrado".to_string()))
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        self.objetos.write().await.remove(key);
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+mod local_storage;
+mod memory_storage;
+#[cfg(feature = "s3")]
+mod s3_storage;
+
+use std::sync::Arc;
+
+use crate::{
+    config::Config,
+    domain::storage::ObjectStorage,
+    errors::{AppError, AppResult},
+};
+
+pub use local_storage::LocalStorage;
+pub use memory_storage::MemoryStorage;
+#[cfg(feature = "s3")]
+pub use s3_storage::S3Storage;
+
+/// Directorio de los adjuntos si no se configura `STORAGE_PATH`
+const DEFAULT_STORAGE_PATH: &str = "storage";
+
+/// Construye el almacenamiento de objetos según la configuración
+pub async fn from_config(config: &Config) -> AppResult<Arc<dyn ObjectStorage>> {
+    match config.storage_backend.as_deref().unwrap_or("local") {
+        "local" => {
+            let root = config
+                .storage_path
+                .clone()
+                .unwrap_or_else(|| DEFAULT_STORAGE_PATH.to_string());
+            tracing::info!("Almacenamiento de adjuntos en disco: {}", root);
+            Ok(Arc::new(LocalStorage::new(root)))
+        }
+        #[cfg(feature = "s3")]
+        "s3" => {
+            let bucket = config.s3_bucket.clone().ok_or_else(|| {
+                AppError::Internal("S3_BUCKET es requerido con STORAGE_BACKEND=s3".to_string())
+            })?;
+            let region = config
+                .s3_region
+                .clone()
+                .unwrap_or_else(|| "us-east-1".to_string());
+            tracing::info!("Almacenamiento de adjuntos en el bucket S3 {}", bucket);
+            Ok(Arc::new(
+                S3Storage::connect(bucket, region, config.s3_endpoint.clone()).await,
+            ))
+        }
+        #[cfg(not(feature = "s3"))]
+        "s3" => Err(AppError::Internal(
+            "Este binario se compiló sin soporte para S3".to_string(),
+        )),
+        otro => Err(AppError::Internal(format!(
+            "STORAGE_BACKEND desconocido: {}",
+            otro
+        ))),
+    }
+}
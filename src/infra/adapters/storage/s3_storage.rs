@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_s3::{Client, error::DisplayErrorContext, primitives::ByteStream};
+use bytes::Bytes;
+
+use crate::{
+    domain::storage::ObjectStorage,
+    errors::{AppError, AppResult},
+};
+
+/// Almacenamiento de objetos en un bucket S3 o compatible (MinIO)
+/// Las credenciales se toman de la cadena estándar de AWS (`AWS_ACCESS_KEY_ID`, ...)
+pub struct S3Storage {
+    client: Client,
+    bucket: String,
+}
+
+impl S3Storage {
+    pub fn new(client: Client, bucket: String) -> Self {
+        Self { client, bucket }
+    }
+
+    /// Con `endpoint` se apunta a un servicio compatible (p. ej. MinIO en local),
+    /// que requiere direcciones de estilo ruta (`endpoint/bucket/clave`)
+    pub async fn connect(bucket: String, region: String, endpoint: Option<String>) -> Self {
+        let shared = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(region))
+            .load()
+            .await;
+
+        let mut config = aws_sdk_s3::config::Builder::from(&shared);
+        if let Some(endpoint) = endpoint {
+            config = config.endpoint_url(endpoint).force_path_style(true);
+        }
+
+        Self::new(Client::from_conf(config.build()), bucket)
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for S3Storage {
+    async fn put(&self, key: &str, content_type: &str, data: Bytes) -> AppResult<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| s3_error("guardar", key, e))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Bytes> {
+        let objeto = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.as_service_error().is_some_and(|e| e.is_no_such_key()) {
+                    AppError::NotFound("Archivo no encontrado".to_string())
+                } else {
+                    s3_error("leer", key, e)
+                }
+            })?;
+
+        let data = objeto
+            .body
+            .collect()
+            .await
+            .map_err(|e| s3_error("leer", key, e))?;
+        Ok(data.into_bytes())
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        // S3 responde con éxito aunque el objeto no exista
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| s3_error("eliminar", key, e))?;
+
+        Ok(())
+    }
+}
+
+fn s3_error(operacion: &str, key: &str, e: impl std::error::Error) -> AppError {
+    AppError::Internal(format!(
+        "No se pudo {} el objeto {} en S3: {}",
+        operacion,
+        key,
+        DisplayErrorContext(e)
+    ))
+}
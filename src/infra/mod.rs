@@ -1,17 +1,70 @@
 pub mod adapters;
 
 use std::{sync::Arc, fmt::Debug};
-use sqlx::PgPool;
+use ipnet::IpNet;
+use phonenumber::country;
+#[cfg(feature = "mysql")]
+use sqlx::MySql;
+#[cfg(feature = "postgres")]
+use sqlx::Postgres;
+#[cfg(feature = "sqlite")]
+use sqlx::Sqlite;
 
+use crate::config::{Cluster, Database};
+use crate::core::services::telefono;
+use crate::core::services::acceso::AccesoService;
+use crate::core::services::adjunto::AdjuntoService;
+use crate::core::services::auditoria::AuditoriaService;
+use crate::core::services::avatar::AvatarService;
+use crate::core::services::catalog::CatalogService;
+use crate::core::services::consentimiento::ConsentimientoService;
+use crate::core::services::duplicado::DuplicadoService;
+use crate::core::services::habeas_data::HabeasDataService;
+use crate::core::services::historial::HistorialService;
 use crate::core::services::persona::PersonaService;
 use crate::core::services::permission::PermissionService;
-use crate::domain::db::{PagperRepository, PersonaRepository};
-use crate::infra::adapters::db::postgres::{PersonaRepositoryPg, PagperRepositoryPg};
+use crate::core::services::ubicacion::UbicacionService;
+use crate::domain::db::{
+    AccesoRepository, AdjuntoRepository, AuditoriaRepository, CatalogRepository, ConsentimientoRepository, DuplicadoRepository, HabeasDataRepository, PagperRepository, PersonaHistorialRepository, PersonaRepository, UbicacionRepository, UnitOfWork,
+};
+use crate::infra::adapters::cache::MemoryCacheImpl;
+use crate::infra::adapters::db::memory::{
+    AccesoRepositoryMemory, AdjuntoRepositoryMemory, AuditoriaRepositoryMemory, CatalogRepositoryMemory, ConsentimientoRepositoryMemory, DuplicadoRepositoryMemory, HabeasDataRepositoryMemory, MemoryDb, PagperRepositoryMemory, PersonaHistorialRepositoryMemory,
+    PersonaRepositoryMemory, UbicacionRepositoryMemory, UnitOfWorkMemory,
+};
+use crate::infra::adapters::storage::MemoryStorage;
+use crate::domain::storage::ObjectStorage;
+#[cfg(feature = "mysql")]
+use crate::infra::adapters::db::mysql::{
+    AccesoRepositoryMySQL, AdjuntoRepositoryMySQL, AuditoriaRepositoryMySQL, CatalogRepositoryMySQL, ConsentimientoRepositoryMySQL, DuplicadoRepositoryMySQL, HabeasDataRepositoryMySQL, PagperRepositoryMySQL, PersonaHistorialRepositoryMySQL, PersonaRepositoryMySQL,
+    UbicacionRepositoryMySQL, UnitOfWorkMySQL,
+};
+#[cfg(feature = "postgres")]
+use crate::infra::adapters::db::postgres::{
+    AccesoRepositoryPg, AdjuntoRepositoryPg, AuditoriaRepositoryPg, CatalogRepositoryPg, ConsentimientoRepositoryPg, DuplicadoRepositoryPg, HabeasDataRepositoryPg, PagperRepositoryPg, PersonaHistorialRepositoryPg, PersonaRepositoryPg,
+    UbicacionRepositoryPg, UnitOfWorkPg,
+};
+#[cfg(feature = "sqlite")]
+use crate::infra::adapters::db::sqlite::{
+    AccesoRepositorySqlite, AdjuntoRepositorySqlite, AuditoriaRepositorySqlite, CatalogRepositorySqlite, ConsentimientoRepositorySqlite, DuplicadoRepositorySqlite, HabeasDataRepositorySqlite, PagperRepositorySqlite, PersonaHistorialRepositorySqlite, PersonaRepositorySqlite,
+    UbicacionRepositorySqlite, UnitOfWorkSqlite,
+};
 
 /// Agregador de repositorios para inyección de dependencias
 pub struct Repos {
     pub persona: Arc<dyn PersonaRepository>,
     pub pagper: Arc<dyn PagperRepository>,
+    pub ubicacion: Arc<dyn UbicacionRepository>,
+    pub catalog: Arc<dyn CatalogRepository>,
+    pub habeas_data: Arc<dyn HabeasDataRepository>,
+    pub consentimiento: Arc<dyn ConsentimientoRepository>,
+    pub auditoria: Arc<dyn AuditoriaRepository>,
+    pub historial: Arc<dyn PersonaHistorialRepository>,
+    pub acceso: Arc<dyn AccesoRepository>,
+    pub adjunto: Arc<dyn AdjuntoRepository>,
+    pub duplicado: Arc<dyn DuplicadoRepository>,
+    /// Transacciones que agrupan llamadas a varios repositorios
+    pub unit_of_work: Arc<dyn UnitOfWork>,
     // Agregar más repos aquí conforme crezca el proyecto
 }
 
@@ -26,6 +79,106 @@ impl Clone for Repos {
         Self {
             persona: self.persona.clone(),
             pagper: self.pagper.clone(),
+            ubicacion: self.ubicacion.clone(),
+            catalog: self.catalog.clone(),
+            habeas_data: self.habeas_data.clone(),
+            consentimiento: self.consentimiento.clone(),
+            auditoria: self.auditoria.clone(),
+            historial: self.historial.clone(),
+            acceso: self.acceso.clone(),
+            adjunto: self.adjunto.clone(),
+            duplicado: self.duplicado.clone(),
+            unit_of_work: self.unit_of_work.clone(),
+        }
+    }
+}
+
+impl Repos {
+    /// Adaptadores del motor al que apunta la conexión
+    /// Los de SQL leen de las réplicas, si las hay, y escriben en la primaria
+    pub fn from_database(db: &Database) -> Self {
+        match db {
+            #[cfg(feature = "postgres")]
+            Database::Postgres(cluster) => Self::postgres(cluster.clone()),
+            #[cfg(feature = "mysql")]
+            Database::MySql(cluster) => Self::mysql(cluster.clone()),
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(cluster) => Self::sqlite(cluster.clone()),
+        }
+    }
+
+    /// Adaptadores en memoria que comparten la misma base (pruebas y demostraciones)
+    pub fn memory(db: MemoryDb) -> Self {
+        Self {
+            persona: Arc::new(PersonaRepositoryMemory::new(db.clone())),
+            pagper: Arc::new(PagperRepositoryMemory::new(db.clone())),
+            ubicacion: Arc::new(UbicacionRepositoryMemory::new(db.clone())),
+            catalog: Arc::new(CatalogRepositoryMemory::new(db.clone())),
+            habeas_data: Arc::new(HabeasDataRepositoryMemory::new(db.clone())),
+            consentimiento: Arc::new(ConsentimientoRepositoryMemory::new(db.clone())),
+            auditoria: Arc::new(AuditoriaRepositoryMemory::new(db.clone())),
+            historial: Arc::new(PersonaHistorialRepositoryMemory::new(db.clone())),
+            acceso: Arc::new(AccesoRepositoryMemory::new(db.clone())),
+            adjunto: Arc::new(AdjuntoRepositoryMemory::new(db.clone())),
+            duplicado: Arc::new(DuplicadoRepositoryMemory::new(db.clone())),
+            unit_of_work: Arc::new(UnitOfWorkMemory::new(db)),
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn postgres(db: impl Into<Cluster<Postgres>>) -> Self {
+        let db = db.into();
+        Self {
+            persona: Arc::new(PersonaRepositoryPg::new(db.clone())),
+            pagper: Arc::new(PagperRepositoryPg::new(db.clone())),
+            ubicacion: Arc::new(UbicacionRepositoryPg::new(db.clone())),
+            catalog: Arc::new(CatalogRepositoryPg::new(db.clone())),
+            habeas_data: Arc::new(HabeasDataRepositoryPg::new(db.clone())),
+            consentimiento: Arc::new(ConsentimientoRepositoryPg::new(db.clone())),
+            auditoria: Arc::new(AuditoriaRepositoryPg::new(db.clone())),
+            historial: Arc::new(PersonaHistorialRepositoryPg::new(db.clone())),
+            acceso: Arc::new(AccesoRepositoryPg::new(db.clone())),
+            adjunto: Arc::new(AdjuntoRepositoryPg::new(db.clone())),
+            duplicado: Arc::new(DuplicadoRepositoryPg::new(db.clone())),
+            unit_of_work: Arc::new(UnitOfWorkPg::new(db)),
+        }
+    }
+
+    #[cfg(feature = "mysql")]
+    pub fn mysql(db: impl Into<Cluster<MySql>>) -> Self {
+        let db = db.into();
+        Self {
+            persona: Arc::new(PersonaRepositoryMySQL::new(db.clone())),
+            pagper: Arc::new(PagperRepositoryMySQL::new(db.clone())),
+            ubicacion: Arc::new(UbicacionRepositoryMySQL::new(db.clone())),
+            catalog: Arc::new(CatalogRepositoryMySQL::new(db.clone())),
+            habeas_data: Arc::new(HabeasDataRepositoryMySQL::new(db.clone())),
+            consentimiento: Arc::new(ConsentimientoRepositoryMySQL::new(db.clone())),
+            auditoria: Arc::new(AuditoriaRepositoryMySQL::new(db.clone())),
+            historial: Arc::new(PersonaHistorialRepositoryMySQL::new(db.clone())),
+            acceso: Arc::new(AccesoRepositoryMySQL::new(db.clone())),
+            adjunto: Arc::new(AdjuntoRepositoryMySQL::new(db.clone())),
+            duplicado: Arc::new(DuplicadoRepositoryMySQL::new(db.clone())),
+            unit_of_work: Arc::new(UnitOfWorkMySQL::new(db)),
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(db: impl Into<Cluster<Sqlite>>) -> Self {
+        let db = db.into();
+        Self {
+            persona: Arc::new(PersonaRepositorySqlite::new(db.clone())),
+            pagper: Arc::new(PagperRepositorySqlite::new(db.clone())),
+            ubicacion: Arc::new(UbicacionRepositorySqlite::new(db.clone())),
+            catalog: Arc::new(CatalogRepositorySqlite::new(db.clone())),
+            habeas_data: Arc::new(HabeasDataRepositorySqlite::new(db.clone())),
+            consentimiento: Arc::new(ConsentimientoRepositorySqlite::new(db.clone())),
+            auditoria: Arc::new(AuditoriaRepositorySqlite::new(db.clone())),
+            historial: Arc::new(PersonaHistorialRepositorySqlite::new(db.clone())),
+            acceso: Arc::new(AccesoRepositorySqlite::new(db.clone())),
+            adjunto: Arc::new(AdjuntoRepositorySqlite::new(db.clone())),
+            duplicado: Arc::new(DuplicadoRepositorySqlite::new(db.clone())),
+            unit_of_work: Arc::new(UnitOfWorkSqlite::new(db)),
         }
     }
 }
@@ -34,6 +187,16 @@ impl Clone for Repos {
 pub struct Services {
     pub persona: Arc<PersonaService>,
     pub permission: Arc<PermissionService>,
+    pub ubicacion: Arc<UbicacionService<MemoryCacheImpl>>,
+    pub catalog: Arc<CatalogService<MemoryCacheImpl>>,
+    pub habeas_data: Arc<HabeasDataService>,
+    pub consentimiento: Arc<ConsentimientoService>,
+    pub auditoria: Arc<AuditoriaService>,
+    pub historial: Arc<HistorialService>,
+    pub acceso: Arc<AccesoService>,
+    pub adjunto: Arc<AdjuntoService>,
+    pub avatar: Arc<AvatarService>,
+    pub duplicado: Arc<DuplicadoService>,
     // Agregar más servicios aquí conforme crezca el proyecto
 }
 
@@ -48,44 +211,249 @@ impl Clone for Services {
         Self {
             persona: self.persona.clone(),
             permission: self.permission.clone(),
+            ubicacion: self.ubicacion.clone(),
+            catalog: self.catalog.clone(),
+            habeas_data: self.habeas_data.clone(),
+            consentimiento: self.consentimiento.clone(),
+            auditoria: self.auditoria.clone(),
+            historial: self.historial.clone(),
+            acceso: self.acceso.clone(),
+            adjunto: self.adjunto.clone(),
+            avatar: self.avatar.clone(),
+            duplicado: self.duplicado.clone(),
         }
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct AppState {
-    // pub db: PgPool,
-    pub db: PgPool,
+    /// Conexión a la base de datos; `None` si todos los repositorios son en memoria
+    pub db: Option<Database>,
     pub jwt_secret: String,
+    /// Proxies cuyo X-Forwarded-For se acepta como IP del cliente
+    pub proxies_confiables: Arc<[IpNet]>,
     pub repos: Arc<Repos>,
     pub services: Arc<Services>,
 }
 
 impl AppState {
     /// Constructor que inicializa todos los repositorios y servicios una sola vez
-    pub fn new(db: PgPool, jwt_secret: String) -> Self {
-        // 1. Construir repositorios
-        let persona_repo = Arc::new(PersonaRepositoryPg::new(db.clone())) as Arc<dyn PersonaRepository>;
-        let pagper_repo = Arc::new(PagperRepositoryPg::new(db.clone())) as Arc<dyn PagperRepository>;
-        
-        let repos = Arc::new(Repos {
-            persona: persona_repo.clone(),
-            pagper: pagper_repo.clone(),
-        });
+    pub fn new(
+        db: Database,
+        jwt_secret: String,
+        storage: Arc<dyn ObjectStorage>,
+        region_telefono: country::Id,
+    ) -> Self {
+        Self::builder()
+            .database(db)
+            .jwt_secret(jwt_secret)
+            .storage(storage)
+            .region_telefono(region_telefono)
+            .build()
+    }
+
+    /// Builder con repositorios y almacenamiento en memoria por defecto;
+    /// cada adaptador se puede reemplazar por cualquier implementación del puerto
+    pub fn builder() -> AppStateBuilder {
+        AppStateBuilder::new()
+    }
+}
+
+/// Arma un `AppState` a partir de adaptadores arbitrarios
+/// Lo que no se configure queda en memoria, así las pruebas pueden levantar el
+/// router completo sin base de datos ni disco
+pub struct AppStateBuilder {
+    db: Option<Database>,
+    repos: Repos,
+    jwt_secret: String,
+    proxies_confiables: Vec<IpNet>,
+    storage: Arc<dyn ObjectStorage>,
+    region_telefono: country::Id,
+}
+
+impl Default for AppStateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AppStateBuilder {
+    pub fn new() -> Self {
+        Self {
+            db: None,
+            repos: Repos::memory(MemoryDb::new()),
+            // Secreto aleatorio: ningún token emitido fuera de este estado es válido
+            jwt_secret: uuid::Uuid::new_v4().to_string(),
+            // Sin proxies configurados cuenta solo la dirección de la conexión
+            proxies_confiables: Vec::new(),
+            storage: Arc::new(MemoryStorage::new()),
+            region_telefono: telefono::REGION_POR_DEFECTO,
+        }
+    }
+
+    /// Usa la conexión y los repositorios de su motor
+    /// Los repositorios configurados antes se reemplazan; los de después tienen prioridad
+    pub fn database(mut self, db: Database) -> Self {
+        self.repos = Repos::from_database(&db);
+        self.db = Some(db);
+        self
+    }
+
+    /// Reemplaza todos los repositorios a la vez
+    pub fn repos(mut self, repos: Repos) -> Self {
+        self.repos = repos;
+        self
+    }
+
+    pub fn persona(mut self, repo: Arc<dyn PersonaRepository>) -> Self {
+        self.repos.persona = repo;
+        self
+    }
+
+    pub fn pagper(mut self, repo: Arc<dyn PagperRepository>) -> Self {
+        self.repos.pagper = repo;
+        self
+    }
+
+    pub fn ubicacion(mut self, repo: Arc<dyn UbicacionRepository>) -> Self {
+        self.repos.ubicacion = repo;
+        self
+    }
+
+    pub fn catalog(mut self, repo: Arc<dyn CatalogRepository>) -> Self {
+        self.repos.catalog = repo;
+        self
+    }
+
+    pub fn habeas_data(mut self, repo: Arc<dyn HabeasDataRepository>) -> Self {
+        self.repos.habeas_data = repo;
+        self
+    }
+
+    pub fn consentimiento(mut self, repo: Arc<dyn ConsentimientoRepository>) -> Self {
+        self.repos.consentimiento = repo;
+        self
+    }
+
+    pub fn auditoria(mut self, repo: Arc<dyn AuditoriaRepository>) -> Self {
+        self.repos.auditoria = repo;
+        self
+    }
+
+    pub fn historial(mut self, repo: Arc<dyn PersonaHistorialRepository>) -> Self {
+        self.repos.historial = repo;
+        self
+    }
+
+    pub fn acceso(mut self, repo: Arc<dyn AccesoRepository>) -> Self {
+        self.repos.acceso = repo;
+        self
+    }
+
+    pub fn adjunto(mut self, repo: Arc<dyn AdjuntoRepository>) -> Self {
+        self.repos.adjunto = repo;
+        self
+    }
+
+    pub fn duplicado(mut self, repo: Arc<dyn DuplicadoRepository>) -> Self {
+        self.repos.duplicado = repo;
+        self
+    }
+
+    pub fn unit_of_work(mut self, unit_of_work: Arc<dyn UnitOfWork>) -> Self {
+        self.repos.unit_of_work = unit_of_work;
+        self
+    }
+
+    pub fn jwt_secret(mut self, jwt_secret: impl Into<String>) -> Self {
+        self.jwt_secret = jwt_secret.into();
+        self
+    }
+
+    pub fn proxies_confiables(mut self, proxies: Vec<IpNet>) -> Self {
+        self.proxies_confiables = proxies;
+        self
+    }
+
+    pub fn storage(mut self, storage: Arc<dyn ObjectStorage>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    pub fn region_telefono(mut self, region_telefono: country::Id) -> Self {
+        self.region_telefono = region_telefono;
+        self
+    }
+
+    /// Construye los servicios inyectando los repositorios configurados
+    pub fn build(self) -> AppState {
+        let AppStateBuilder {
+            db,
+            repos,
+            jwt_secret,
+            proxies_confiables,
+            storage,
+            region_telefono,
+        } = self;
+
+        // 1. Tomar los repositorios configurados
+        let repos = Arc::new(repos);
+        let persona_repo = repos.persona.clone();
+        let pagper_repo = repos.pagper.clone();
+        let ubicacion_repo = repos.ubicacion.clone();
+        let catalog_repo = repos.catalog.clone();
+        let habeas_data_repo = repos.habeas_data.clone();
+        let consentimiento_repo = repos.consentimiento.clone();
+        let auditoria_repo = repos.auditoria.clone();
+        let historial_repo = repos.historial.clone();
+        let acceso_repo = repos.acceso.clone();
+        let adjunto_repo = repos.adjunto.clone();
+        let duplicado_repo = repos.duplicado.clone();
+        let unit_of_work = repos.unit_of_work.clone();
 
         // 2. Construir servicios inyectando repos
-        let persona_service = Arc::new(PersonaService::new(persona_repo));
+        let cache = Arc::new(MemoryCacheImpl::new());
+        let persona_service = Arc::new(PersonaService::new(persona_repo.clone(), ubicacion_repo.clone(), unit_of_work.clone(), region_telefono));
+        let adjunto_service = Arc::new(AdjuntoService::new(adjunto_repo, storage.clone()));
+        let avatar_service = Arc::new(AvatarService::new(persona_repo.clone(), storage));
+        let duplicado_service = Arc::new(DuplicadoService::new(duplicado_repo, persona_repo.clone(), unit_of_work.clone()));
+        let habeas_data_service = Arc::new(HabeasDataService::new(
+            habeas_data_repo,
+            persona_repo,
+            ubicacion_repo.clone(),
+            consentimiento_repo.clone(),
+            unit_of_work,
+            adjunto_service.clone(),
+            avatar_service.clone(),
+        ));
+        let consentimiento_service = Arc::new(ConsentimientoService::new(consentimiento_repo));
+        let auditoria_service = Arc::new(AuditoriaService::new(auditoria_repo));
+        let acceso_service = Arc::new(AccesoService::new(acceso_repo));
+        let historial_service = Arc::new(HistorialService::new(historial_repo, persona_service.clone()));
         let permission_service = Arc::new(PermissionService::new(pagper_repo));
+        let ubicacion_service = Arc::new(UbicacionService::new(ubicacion_repo, cache.clone()));
+        let catalog_service = Arc::new(CatalogService::new(catalog_repo, cache));
         
         let services = Arc::new(Services {
             persona: persona_service,
             permission: permission_service,
+            ubicacion: ubicacion_service,
+            catalog: catalog_service,
+            habeas_data: habeas_data_service,
+            consentimiento: consentimiento_service,
+            auditoria: auditoria_service,
+            historial: historial_service,
+            acceso: acceso_service,
+            adjunto: adjunto_service,
+            avatar: avatar_service,
+            duplicado: duplicado_service,
         });
 
         // 3. Retornar AppState completo
-        Self {
+        AppState {
             db,
             jwt_secret,
+            proxies_confiables: proxies_confiables.into(),
             repos,
             services,
         }
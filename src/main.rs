@@ -1,13 +1,20 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use axum::
     Router
 ;
 use libropr_rust::{
     api::app_router,
-    config::{Config, Database},
-    infra::AppState,
+    config::{AFINIDAD_POR_DEFECTO, Config, Database, REINTENTO_POR_DEFECTO},
+    core::services::telefono,
+    infra::{AppState, adapters::storage},
 };
+#[cfg(feature = "mysql")]
+use libropr_rust::infra::adapters::db::mysql;
+#[cfg(feature = "postgres")]
+use libropr_rust::infra::adapters::db::postgres;
+#[cfg(feature = "sqlite")]
+use libropr_rust::infra::adapters::db::sqlite;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -15,12 +22,57 @@ async fn main() {
     init_tracing();
 
     let config = Config::from_env().expect("No se pudo cargar la configuración del entorno");
-    let pool = init_database(config.database_url.as_str())
+    let db = init_database(&config)
         .await
         .expect("No se pudo inicializar la base de datos");
+
+    if config.run_migrations.unwrap_or(false) {
+        run_migrations(&db)
+            .await
+            .expect("No se pudieron aplicar las migraciones");
+    }
     
+    let storage = storage::from_config(&config)
+        .await
+        .expect("No se pudo inicializar el almacenamiento de adjuntos");
+
+    let region_telefono = config
+        .phone_default_region
+        .as_deref()
+        .map(telefono::region)
+        .transpose()
+        .expect("PHONE_DEFAULT_REGION inválida")
+        .unwrap_or(telefono::REGION_POR_DEFECTO);
+
+    let proxies_confiables = config
+        .trusted_proxies()
+        .expect("TRUSTED_PROXIES inválida");
+
     // Composition root: construir state con repos y services una sola vez
-    let state = Arc::new(AppState::new(pool, config.jwt_secret.clone()));
+    let state = Arc::new(
+        AppState::builder()
+            .database(db)
+            .jwt_secret(config.jwt_secret.clone())
+            .proxies_confiables(proxies_confiables)
+            .storage(storage)
+            .region_telefono(region_telefono)
+            .build(),
+    );
+
+    init_divipola(&state, config.divipola_csv.as_deref())
+        .await
+        .expect("No se pudo cargar el catálogo DIVIPOLA");
+
+    // Detección periódica de personas duplicadas
+    if let Some(horas) = config.duplicate_scan_hours.filter(|h| *h > 0) {
+        let duplicado = state.services.duplicado.clone();
+        tokio::spawn(async move {
+            duplicado
+                .programar(Duration::from_secs(horas * 60 * 60))
+                .await
+        });
+    }
+
     let app: Router = app_router(state);
 
     let addr = format!("{}:{}", config.host, config.port);
@@ -32,13 +84,64 @@ async fn main() {
     tracing::info!("📚 API disponible en: http://{}/api/v1", addr);
 
 
-    axum::serve(listener, app).await.unwrap();
+    // ConnectInfo deja disponible la IP del cliente para la auditoría
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
 
-async fn init_database(database_url: &str) -> anyhow::Result<sqlx::PgPool> {
-    let db = Database::new(database_url, 10).await?;
+async fn init_database(config: &Config) -> anyhow::Result<Database> {
+    let afinidad = config
+        .db_replica_sticky_ms
+        .map(Duration::from_millis)
+        .unwrap_or(AFINIDAD_POR_DEFECTO);
+    let reintento = config
+        .db_replica_retry_secs
+        .map(Duration::from_secs)
+        .unwrap_or(REINTENTO_POR_DEFECTO);
+
+    let opciones = config.pool_options().map_err(anyhow::Error::msg)?;
+    let db = Database::new(config.database_url.as_str(), &opciones)
+        .await?
+        .con_replicas(&config.replica_urls(), &opciones, afinidad, reintento)?;
     db.ping().await?;
-    Ok(db.pg_pool().clone())
+    Ok(db)
+}
+
+async fn run_migrations(db: &Database) -> anyhow::Result<()> {
+    match db {
+        #[cfg(feature = "postgres")]
+        Database::Postgres(cluster) => postgres::MIGRATOR.run(cluster.primaria()).await?,
+        #[cfg(feature = "mysql")]
+        Database::MySql(cluster) => mysql::MIGRATOR.run(cluster.primaria()).await?,
+        #[cfg(feature = "sqlite")]
+        Database::Sqlite(cluster) => sqlite::MIGRATOR.run(cluster.primaria()).await?,
+    }
+    tracing::info!("✅ Migraciones aplicadas");
+    Ok(())
+}
+
+/// Sin catálogo DIVIPOLA no se pueden registrar personas: se carga desde
+/// `DIVIPOLA_CSV` si está vacío, y si no hay archivo se advierte al arrancar
+async fn init_divipola(state: &AppState, archivo: Option<&str>) -> anyhow::Result<()> {
+    let ubicacion = &state.services.ubicacion;
+    if ubicacion.cargado().await? {
+        return Ok(());
+    }
+
+    match archivo {
+        Some(archivo) => {
+            let bytes = tokio::fs::read(archivo).await?;
+            ubicacion.import(&bytes).await?;
+        }
+        None => tracing::warn!(
+            "El catálogo DIVIPOLA está vacío: no se podrán registrar personas hasta importarlo (DIVIPOLA_CSV o /api/v1/ubicacion/import)"
+        ),
+    }
+    Ok(())
 }
 
 fn init_tracing() {
@@ -46,7 +149,10 @@ fn init_tracing() {
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 // Usa el nombre real del crate para que el filtro funcione
-                .unwrap_or_else(|_| "libropr_rust=info,tower_http=debug,axum=info".into()),
+                // `sqlx::query` registra las sentencias lentas como advertencias
+                .unwrap_or_else(|_| {
+                    "libropr_rust=info,tower_http=debug,axum=info,sqlx::query=warn".into()
+                }),
         )
         .with(tracing_subscriber::fmt::layer())
         .init();
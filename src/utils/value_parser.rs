@@ -71,15 +71,13 @@ fn format_number(n: f64, decimals: usize) -> String {
 }
 
 fn add_thousands_sep(int_part: &str) -> String {
-    let mut chars: Vec<char> = int_part.chars().collect();
+    let chars: Vec<char> = int_part.chars().collect();
     let mut out = String::new();
-    let mut count = 0;
-    for ch in chars.iter().rev() {
+    for (count, ch) in chars.iter().rev().enumerate() {
         if count != 0 && count % 3 == 0 {
             out.push('.');
         }
         out.push(*ch);
-        count += 1;
     }
     out.chars().rev().collect()
 }
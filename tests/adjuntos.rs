@@ -0,0 +1,148 @@
+//! Adjuntos: el tipo se juzga por el contenido y el nombre se sanea antes de guardarlo
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use libropr_rust::{
+    core::services::adjunto::{ArchivoSubido, MAX_TAMANO_ADJUNTO},
+    domain::{CategoriaAdjunto, storage::ObjectStorage},
+    errors::AppError,
+    infra::{AppState, adapters::storage::MemoryStorage},
+};
+
+const PDF: &[u8] = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n1 0 obj\n<<>>\nendobj\n";
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01\x08\x06\0\0\0";
+
+fn archivo(nombre: Option<&str>, content_type: Option<&str>, data: &'static [u8]) -> ArchivoSubido {
+    ArchivoSubido {
+        nombre: nombre.map(str::to_string),
+        content_type: content_type.map(str::to_string),
+        data: Bytes::from_static(data),
+    }
+}
+
+fn mensajes(error: AppError) -> Vec<String> {
+    match error {
+        AppError::Validation(campos) => campos.get("file").cloned().unwrap_or_default(),
+        otro => panic!("se esperaba un error de validación: {:?}", otro),
+    }
+}
+
+#[tokio::test]
+async fn rechaza_lo_vacio_lo_enorme_y_los_tipos_no_permitidos() {
+    let state = AppState::builder().build();
+    let adjunto = &state.services.adjunto;
+    let enorme = ArchivoSubido {
+        data: Bytes::from(vec![0; MAX_TAMANO_ADJUNTO + 1]),
+        ..archivo(None, None, PDF)
+    };
+
+    for (archivo, mensaje) in [
+        (archivo(None, None, b""), "El archivo está vacío"),
+        (enorme, "El archivo supera el tamaño máximo de 10 MB"),
+        (
+            archivo(Some("notas.txt"), Some("text/plain"), b"solo texto"),
+            "Tipo de archivo no permitido, use PDF, JPEG, PNG o WEBP",
+        ),
+    ] {
+        let error = adjunto
+            .subir(1, CategoriaAdjunto::Otro, archivo, 1)
+            .await
+            .unwrap_err();
+        assert_eq!(mensajes(error), [mensaje]);
+    }
+}
+
+#[tokio::test]
+async fn el_tipo_declarado_debe_coincidir_con_el_contenido() {
+    let state = AppState::builder().build();
+    let adjunto = &state.services.adjunto;
+
+    let error = adjunto
+        .subir(
+            1,
+            CategoriaAdjunto::Contrato,
+            archivo(None, Some("image/png"), PDF),
+            1,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        mensajes(error),
+        ["El contenido del archivo no corresponde al tipo declarado"]
+    );
+
+    let error = adjunto
+        .subir(1, CategoriaAdjunto::Foto, archivo(None, None, PDF), 1)
+        .await
+        .unwrap_err();
+    assert_eq!(mensajes(error), ["Una foto debe ser una imagen"]);
+
+    // Parámetros, mayúsculas y el genérico octet-stream no cuentan como otro tipo
+    for declarado in [
+        "Application/PDF; charset=binary",
+        "application/octet-stream",
+    ] {
+        let guardado = adjunto
+            .subir(
+                1,
+                CategoriaAdjunto::Contrato,
+                archivo(None, Some(declarado), PDF),
+                1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(guardado.tipadj, "application/pdf");
+    }
+}
+
+#[tokio::test]
+async fn guarda_el_contenido_con_un_nombre_saneado() {
+    let storage = Arc::new(MemoryStorage::new());
+    let state = AppState::builder().storage(storage.clone()).build();
+    let adjunto = &state.services.adjunto;
+
+    for (nombre, esperado) in [
+        (
+            Some("../../etc/Cédula <escaneada>.png"),
+            "Cédula escaneada.png",
+        ),
+        (Some("C:\\Usuarios\\ana\\foto.png"), "foto.png"),
+        (Some("  .oculto.png"), "oculto.png"),
+        (Some("carpeta/"), "archivo.png"),
+        (None, "archivo.png"),
+    ] {
+        let guardado = adjunto
+            .subir(
+                7,
+                CategoriaAdjunto::Foto,
+                archivo(nombre, Some("image/png"), PNG),
+                3,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(guardado.nomadj, esperado);
+        assert_eq!(guardado.tipadj, "image/png");
+        assert_eq!(guardado.tamadj, PNG.len() as i64);
+        assert_eq!(guardado.hashadj.len(), 64);
+        assert!(guardado.clvadj.starts_with("personas/7/"));
+        assert!(guardado.clvadj.ends_with(".png"));
+        assert_eq!(storage.get(&guardado.clvadj).await.unwrap(), PNG);
+    }
+
+    let nombre_largo = format!("{}.pdf", "a".repeat(300));
+    let guardado = adjunto
+        .subir(
+            7,
+            CategoriaAdjunto::Otro,
+            ArchivoSubido {
+                nombre: Some(nombre_largo),
+                ..archivo(None, None, PDF)
+            },
+            3,
+        )
+        .await
+        .unwrap();
+    assert_eq!(guardado.nomadj.chars().count(), 255);
+}
@@ -0,0 +1,77 @@
+//! Contrato de los almacenamientos de objetos
+//!
+//! Memoria y disco se prueban siempre. S3 necesita un servicio compatible con
+//! un bucket ya creado, así que se pide con `--ignored`:
+//!   TEST_S3_ENDPOINT=http://localhost:9000 TEST_S3_BUCKET=libropr-test \
+//!   AWS_ACCESS_KEY_ID=minioadmin AWS_SECRET_ACCESS_KEY=minioadmin \
+//!   cargo test --test almacenamiento -- --ignored
+
+mod common;
+
+use bytes::Bytes;
+use libropr_rust::{
+    domain::storage::ObjectStorage,
+    errors::AppError,
+    infra::adapters::storage::{LocalStorage, MemoryStorage},
+};
+
+async fn cumplir_contrato(storage: &dyn ObjectStorage) {
+    let clave = format!("personas/{}/documento.pdf", common::sufijo());
+
+    assert!(matches!(
+        storage.get(&clave).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    storage
+        .put(&clave, "application/pdf", Bytes::from_static(b"primero"))
+        .await
+        .unwrap();
+    assert_eq!(storage.get(&clave).await.unwrap(), "primero");
+
+    // Guardar de nuevo reemplaza el contenido
+    storage
+        .put(&clave, "application/pdf", Bytes::from_static(b"segundo"))
+        .await
+        .unwrap();
+    assert_eq!(storage.get(&clave).await.unwrap(), "segundo");
+
+    storage.delete(&clave).await.unwrap();
+    assert!(matches!(
+        storage.get(&clave).await,
+        Err(AppError::NotFound(_))
+    ));
+    // Borrar lo que ya no existe no es un error
+    storage.delete(&clave).await.unwrap();
+}
+
+#[tokio::test]
+async fn memoria() {
+    cumplir_contrato(&MemoryStorage::new()).await;
+}
+
+#[tokio::test]
+async fn disco() {
+    let raiz = std::env::temp_dir().join(format!("libropr-{}", common::sufijo()));
+
+    cumplir_contrato(&LocalStorage::new(&raiz)).await;
+
+    std::fs::remove_dir_all(raiz).unwrap();
+}
+
+#[cfg(feature = "s3")]
+#[tokio::test]
+#[ignore = "requiere TEST_S3_ENDPOINT"]
+async fn s3() {
+    use libropr_rust::infra::adapters::storage::S3Storage;
+
+    let bucket = std::env::var("TEST_S3_BUCKET").unwrap_or_else(|_| "libropr-test".to_string());
+    let storage = S3Storage::connect(
+        bucket,
+        "us-east-1".to_string(),
+        Some(common::url("TEST_S3_ENDPOINT")),
+    )
+    .await;
+
+    cumplir_contrato(&storage).await;
+}
@@ -0,0 +1,463 @@
+//! Recorre el router completo en proceso, sin servidor ni base de datos:
+//! el estado se arma con `AppState::builder()` y adaptadores en memoria
+
+mod common;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{
+        Request, StatusCode,
+        header::{AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH},
+    },
+};
+use libropr_rust::{
+    api::app_router,
+    domain::{
+        Departamento, Municipio, Pagper, Persona, db::PagperRepository,
+    },
+    errors::AppResult,
+    infra::{AppState, Repos, adapters::db::memory::MemoryDb},
+};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+const SECRETO: &str = "secreto-de-prueba";
+const PERFIL_OPERADOR: i64 = 2;
+
+/// Perfil sin ningún permiso, para comprobar que el router usa el adaptador inyectado
+struct SinPermisos;
+
+#[async_trait]
+impl PagperRepository for SinPermisos {
+    async fn find_by_perfil(&self, _idpef: i64) -> AppResult<Vec<Pagper>> {
+        Ok(Vec::new())
+    }
+
+    async fn has_permission(&self, _idpef: i64, _codpag: &str, _action: &str) -> AppResult<bool> {
+        Ok(false)
+    }
+}
+
+fn persona_nueva(emaper: &str, idpef: i64) -> Persona {
+    Persona {
+        emaper: emaper.to_string(),
+        ..common::persona_nueva(idpef, 5001)
+    }
+}
+
+/// Token firmado con el secreto del estado, como lo emite el login
+fn token(persona: &Persona, nompef: &str) -> String {
+    common::token(persona, nompef, SECRETO)
+}
+
+async fn enviar(app: &Router, peticion: Request<Body>) -> (StatusCode, Option<String>, Value) {
+    let respuesta = app.clone().oneshot(peticion).await.unwrap();
+    let status = respuesta.status();
+    let etag = respuesta
+        .headers()
+        .get(ETAG)
+        .map(|v| v.to_str().unwrap().to_string());
+    let cuerpo = to_bytes(respuesta.into_body(), usize::MAX).await.unwrap();
+    let json = serde_json::from_slice(&cuerpo).unwrap_or(Value::Null);
+    (status, etag, json)
+}
+
+/// POST de una transición de estado con la versión esperada en If-Match
+fn transicion(accion: &str, idper: i64, token: &str, verper: i64) -> Request<Body> {
+    Request::post(format!("/api/v1/persona/{}/{}", idper, accion))
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .header(IF_MATCH, format!("\"{}\"", verper))
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "motivo": "Prueba" }).to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn sin_token_responde_401() {
+    let app = app_router(Arc::new(AppState::builder().build()));
+
+    let peticion = Request::get("/api/v1/persona/1")
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, _) = enviar(&app, peticion).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn login_y_consulta_de_persona() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    let persona = state
+        .repos
+        .persona
+        .create(persona_nueva("login@prueba.invalid", PERFIL_OPERADOR))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state));
+
+    let login = Request::post("/api/v1/auth")
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            json!({ "email": persona.emaper, "password": "x" }).to_string(),
+        ))
+        .unwrap();
+    let (status, _, cuerpo) = enviar(&app, login).await;
+    assert_eq!(status, StatusCode::OK);
+    let token = cuerpo["token"].as_str().unwrap();
+
+    let consulta = Request::get(format!("/api/v1/persona/{}", persona.idper))
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let (status, etag, cuerpo) = enviar(&app, consulta).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"1\""));
+    assert_eq!(cuerpo["emaper"], persona.emaper);
+
+    let inexistente = Request::get("/api/v1/persona/999")
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, _) = enviar(&app, inexistente).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn transicion_con_permiso_y_control_de_version() {
+    let db = MemoryDb::new();
+    for codpag in ["persona.suspender", "persona.activar"] {
+        let idpag = db.agregar_pagina(codpag).await;
+        db.conceder(Pagper {
+            idpef: PERFIL_OPERADOR,
+            idpag,
+            can_create: false,
+            can_read: false,
+            can_update: true,
+            can_delete: false,
+        })
+        .await;
+    }
+    let state = AppState::builder()
+        .repos(Repos::memory(db))
+        .jwt_secret(SECRETO)
+        .build();
+    let operador = state
+        .repos
+        .persona
+        .create(persona_nueva("operador@prueba.invalid", PERFIL_OPERADOR))
+        .await
+        .unwrap();
+    let destino = state
+        .repos
+        .persona
+        .create(persona_nueva("destino@prueba.invalid", 3))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state.clone()));
+    let token = token(&operador, "operador");
+
+    let suspender = transicion("suspender", destino.idper, &token, 1);
+    let (status, etag, cuerpo) = enviar(&app, suspender).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"2\""));
+    assert_eq!(cuerpo["estper"], "suspendido");
+
+    // Una escritura con la versión anterior pierde contra la que ya se aplicó
+    let activar = transicion("activar", destino.idper, &token, 1);
+    let (status, _, _) = enviar(&app, activar).await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    let versiones = state
+        .repos
+        .historial
+        .list_by_persona(destino.idper)
+        .await
+        .unwrap();
+    assert_eq!(versiones.len(), 2);
+}
+
+#[tokio::test]
+async fn el_router_usa_los_adaptadores_inyectados() {
+    let state = AppState::builder()
+        .pagper(Arc::new(SinPermisos))
+        .jwt_secret(SECRETO)
+        .build();
+    let operador = state
+        .repos
+        .persona
+        .create(persona_nueva("operador@prueba.invalid", PERFIL_OPERADOR))
+        .await
+        .unwrap();
+    let destino = state
+        .repos
+        .persona
+        .create(persona_nueva("destino@prueba.invalid", 3))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state));
+
+    let suspender = transicion("suspender", destino.idper, &token(&operador, "operador"), 1);
+    let (status, _, _) = enviar(&app, suspender).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn persona_y_consentimiento_se_crean_juntos() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    state
+        .repos
+        .ubicacion
+        .import(
+            vec![Departamento {
+                coddep: 5,
+                nomdep: "Antioquia".to_string(),
+            }],
+            vec![Municipio {
+                codubi: 5001,
+                nomubi: "Medellín".to_string(),
+                coddep: 5,
+                nomdep: String::new(),
+            }],
+        )
+        .await
+        .unwrap();
+    let politica = state
+        .repos
+        .consentimiento
+        .create_politica("1.0", "Política de prueba", chrono::Utc::now())
+        .await
+        .unwrap();
+    let admin = state
+        .repos
+        .persona
+        .create(persona_nueva("admin@prueba.invalid", 1))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state.clone()));
+    let token = token(&admin, "superadmin");
+
+    let crear = |emaper: &str, idpol: i64| {
+        Request::post("/api/v1/persona")
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({
+                    "tdocper": 1,
+                    "nomper": "Nueva",
+                    "apeper": "Persona",
+                    "telper": "300 123 4567",
+                    "codubi": 5001,
+                    "idpef": PERFIL_OPERADOR,
+                    "emaper": emaper,
+                    "consentimiento": { "idpol": idpol, "cancon": "web" },
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    // Una política que no es la vigente deshace también la persona
+    let (status, _, _) = enviar(&app, crear("rechazada@prueba.invalid", politica.idpol + 1)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(
+        state
+            .repos
+            .persona
+            .get_by_emaper("rechazada@prueba.invalid")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let (status, _, cuerpo) = enviar(&app, crear("aceptada@prueba.invalid", politica.idpol)).await;
+    assert!(status.is_success());
+    let idper = cuerpo["idper"].as_i64().unwrap();
+    let consentimientos = state
+        .repos
+        .consentimiento
+        .list_by_persona(idper)
+        .await
+        .unwrap();
+    assert_eq!(consentimientos.len(), 1);
+    assert_eq!(consentimientos[0].idpol, politica.idpol);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn estadisticas_del_pool_solo_para_administradores() {
+    use libropr_rust::{config::Database, infra::adapters::db::sqlite::MIGRATOR};
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+    // Sin llaves foráneas para no tener que crear perfil ni ubicación
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(
+            "sqlite::memory:"
+                .parse::<SqliteConnectOptions>()
+                .unwrap()
+                .foreign_keys(false),
+        )
+        .await
+        .unwrap();
+    MIGRATOR.run(&pool).await.unwrap();
+    let state = AppState::builder()
+        .jwt_secret(SECRETO)
+        .database(Database::Sqlite(pool.into()))
+        .build();
+    let persona = state
+        .repos
+        .persona
+        .create(persona_nueva("admin@prueba.invalid", 1))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state));
+    let consultar = |nompef: &str| {
+        Request::get("/api/v1/database/pool")
+            .header(AUTHORIZATION, format!("Bearer {}", token(&persona, nompef)))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let (status, _, _) = enviar(&app, consultar("operador")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _, cuerpo) = enviar(&app, consultar("admin")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cuerpo["motor"], "SQLite");
+    assert_eq!(cuerpo["primaria"]["maximo"], 1);
+    assert_eq!(cuerpo["replicas"], json!([]));
+
+    // Sin base SQL no hay pool que mostrar
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    state
+        .repos
+        .persona
+        .create(persona_nueva("admin@prueba.invalid", 1))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state));
+    let (status, _, _) = enviar(&app, consultar("admin")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn catalogo_con_etag_estable_e_if_none_match() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    let admin = state
+        .repos
+        .persona
+        .create(persona_nueva("admin@prueba.invalid", 1))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state));
+    let token = token(&admin, "super_admin");
+    let consultar = |if_none_match: Option<&str>| {
+        let peticion = Request::get("/api/v1/catalog/tipo_documento")
+            .header(AUTHORIZATION, format!("Bearer {}", token));
+        match if_none_match {
+            Some(valor) => peticion.header(IF_NONE_MATCH, valor),
+            None => peticion,
+        }
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    let (status, etag, cuerpo) = enviar(&app, consultar(None)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cuerpo.as_array().unwrap().len(), 5);
+    // El mismo contenido da el mismo ETag en cualquier compilación
+    let etag = etag.unwrap();
+    assert_eq!(etag, "\"293f583c7fff7dbb83f715c9c6b7b87d\"");
+
+    let debil = format!("W/{}", etag);
+    let lista = format!("\"otro\", {}", etag);
+    for coincide in [etag.as_str(), debil.as_str(), lista.as_str(), "*"] {
+        let (status, _, _) = enviar(&app, consultar(Some(coincide))).await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED, "{}", coincide);
+    }
+    let (status, _, _) = enviar(&app, consultar(Some("\"otro\""))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Los tipos de documento los fija el código: no se crean desde la API
+    let crear = Request::post("/api/v1/catalog/tipo_documento")
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            json!({ "id": 6, "nombre": "Permiso especial", "activo": true }).to_string(),
+        ))
+        .unwrap();
+    let (status, _, _) = enviar(&app, crear).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn administrar_perfiles_cambia_el_etag_del_catalogo() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    let admin = state
+        .repos
+        .persona
+        .create(persona_nueva("admin@prueba.invalid", 1))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state));
+    let token = token(&admin, "super_admin");
+    let consultar = |if_none_match: &str| {
+        Request::get("/api/v1/catalog/perfil")
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(IF_NONE_MATCH, if_none_match)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let escribir = |peticion: axum::http::request::Builder, cuerpo: Value| {
+        peticion
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(cuerpo.to_string()))
+            .unwrap()
+    };
+
+    let (status, inicial, _) = enviar(&app, consultar("\"ninguno\"")).await;
+    assert_eq!(status, StatusCode::OK);
+    let inicial = inicial.unwrap();
+
+    let crear = escribir(
+        Request::post("/api/v1/catalog/perfil"),
+        json!({ "id": 7, "nombre": "Auditor", "activo": true }),
+    );
+    let (status, _, _) = enviar(&app, crear).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // El caché se invalidó: el ETag que tenía el cliente ya no coincide
+    let (status, creado, cuerpo) = enviar(&app, consultar(&inicial)).await;
+    assert_eq!(status, StatusCode::OK);
+    let creado = creado.unwrap();
+    assert_ne!(creado, inicial);
+    assert!(cuerpo.as_array().unwrap().iter().any(|p| p["nombre"] == "Auditor"));
+
+    let renombrar = escribir(
+        Request::put("/api/v1/catalog/perfil/7"),
+        json!({ "nombre": "Auditoría", "activo": true }),
+    );
+    let (status, _, _) = enviar(&app, renombrar).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, renombrado, _) = enviar(&app, consultar(&creado)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(renombrado.as_deref(), Some(creado.as_str()));
+
+    // Eliminar un perfil lo desactiva: las personas lo siguen referenciando
+    let eliminar = Request::delete("/api/v1/catalog/perfil/7")
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, _) = enviar(&app, eliminar).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _, cuerpo) = enviar(&app, consultar(&renombrado.unwrap())).await;
+    assert_eq!(status, StatusCode::OK);
+    let perfil = cuerpo.as_array().unwrap().iter().find(|p| p["id"] == 7).cloned();
+    assert_eq!(perfil.unwrap()["activo"], false);
+}
@@ -0,0 +1,184 @@
+//! Registro de auditoría: qué cambió, sin copiar datos personales, y desde qué IP
+
+mod common;
+
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::Request,
+};
+use libropr_rust::{
+    core::services::auditoria::EventoAuditoria,
+    domain::{AuditoriaFilter, AuthUser, EstadoPersona, Persona, RequestContext},
+    infra::AppState,
+};
+use serde_json::{Value, json};
+
+fn actor() -> AuthUser {
+    AuthUser {
+        idper: 1,
+        nomper: "Auditor".to_string(),
+        idpef: 1,
+        nompef: "super_admin".to_string(),
+        is_super_admin: true,
+        permissions: HashMap::new(),
+    }
+}
+
+/// Registra el evento y devuelve (antes, despues, cambios) tal como quedaron guardados
+async fn registrar(evento: EventoAuditoria) -> (Option<Value>, Option<Value>, Option<Value>) {
+    let state = AppState::builder().build();
+    let auditoria = &state.services.auditoria;
+    auditoria
+        .registrar(&actor(), &RequestContext::default(), evento)
+        .await;
+
+    let registro = auditoria
+        .buscar(AuditoriaFilter::default())
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    (
+        registro.antes.map(|v| v.0),
+        registro.despues.map(|v| v.0),
+        registro.cambios.map(|v| v.0),
+    )
+}
+
+#[tokio::test]
+async fn el_diff_solo_trae_los_campos_que_cambiaron() {
+    let (_, _, cambios) = registrar(EventoAuditoria::update(
+        "catalog:perfil",
+        3,
+        &json!({ "id": 3, "nombre": "Operador", "activo": true, "orden": 1 }),
+        &json!({ "id": 3, "nombre": "Operador", "activo": false, "nuevo": "x" }),
+    ))
+    .await;
+
+    assert_eq!(
+        cambios.unwrap(),
+        json!({
+            "activo": { "antes": true, "despues": false },
+            "nuevo": { "antes": null, "despues": "x" },
+            "orden": { "antes": 1, "despues": null },
+        })
+    );
+}
+
+#[tokio::test]
+async fn el_diff_de_valores_sueltos_guarda_ambos() {
+    let (_, _, cambios) = registrar(EventoAuditoria::update("contador", 1, &5, &6)).await;
+
+    assert_eq!(cambios.unwrap(), json!({ "antes": 5, "despues": 6 }));
+}
+
+#[tokio::test]
+async fn los_datos_personales_no_quedan_en_el_registro() {
+    let antes = Persona {
+        pass: Some("$2b$12$hash".to_string()),
+        ..common::persona_nueva(2, 5001)
+    };
+    let despues = Persona {
+        nomper: "Otro nombre".to_string(),
+        estper: EstadoPersona::Suspendido,
+        ..antes.clone()
+    };
+
+    let (guardado_antes, guardado_despues, cambios) = registrar(EventoAuditoria::update(
+        "persona",
+        antes.idper,
+        &antes,
+        &despues,
+    ))
+    .await;
+
+    let guardado_antes = guardado_antes.unwrap();
+    assert_eq!(guardado_antes["nomper"], "[redactado]");
+    assert_eq!(guardado_antes["ndocper"], "[redactado]");
+    assert_eq!(guardado_antes["emaper"], "[redactado]");
+    // Lo que no tenía valor sigue vacío y lo que no es personal se conserva
+    assert_eq!(guardado_antes["dirper"], Value::Null);
+    assert_eq!(guardado_antes["codubi"], 5001);
+    assert!(guardado_antes.get("pass").is_none());
+    assert_eq!(guardado_despues.unwrap()["nomper"], "[redactado]");
+
+    // Se sabe que el nombre cambió, pero no de qué a qué
+    assert_eq!(
+        cambios.unwrap(),
+        json!({
+            "nomper": "[redactado]",
+            "estper": { "antes": "activo", "despues": "suspendido" },
+        })
+    );
+}
+
+#[tokio::test]
+async fn los_datos_personales_se_redactan_en_listas() {
+    let personas = vec![common::persona_nueva(2, 5001)];
+
+    let (_, despues, _) =
+        registrar(EventoAuditoria::new("importar", "persona", "lote").despues(&personas)).await;
+
+    assert_eq!(despues.unwrap()[0]["telper"], "[redactado]");
+}
+
+async fn ip(proxies: &str, conexion: &str, reenviada: Option<&str>) -> Option<String> {
+    let state = Arc::new(
+        AppState::builder()
+            .proxies_confiables(
+                proxies
+                    .split(',')
+                    .filter(|p| !p.is_empty())
+                    .map(|p| p.parse().unwrap())
+                    .collect(),
+            )
+            .build(),
+    );
+    let mut peticion = Request::get("/");
+    if let Some(reenviada) = reenviada {
+        peticion = peticion.header("x-forwarded-for", reenviada);
+    }
+    let (mut parts, _) = peticion
+        .extension(ConnectInfo(conexion.parse::<SocketAddr>().unwrap()))
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    RequestContext::from_request_parts(&mut parts, &state)
+        .await
+        .unwrap()
+        .ip
+}
+
+#[tokio::test]
+async fn x_forwarded_for_solo_cuenta_detras_de_un_proxy_de_confianza() {
+    // Sin proxies configurados la cabecera la pudo escribir el cliente
+    assert_eq!(
+        ip("", "203.0.113.7:4000", Some("1.2.3.4")).await.as_deref(),
+        Some("203.0.113.7")
+    );
+    assert_eq!(
+        ip("10.0.0.0/8", "203.0.113.7:4000", Some("1.2.3.4"))
+            .await
+            .as_deref(),
+        Some("203.0.113.7")
+    );
+
+    // Detrás del proxy cuenta el primer salto que no es de confianza, desde la derecha
+    assert_eq!(
+        ip(
+            "10.0.0.0/8",
+            "10.0.0.2:4000",
+            Some("1.2.3.4, 198.51.100.9, 10.0.0.5")
+        )
+        .await
+        .as_deref(),
+        Some("198.51.100.9")
+    );
+    assert_eq!(
+        ip("10.0.0.2/32", "10.0.0.2:4000", None).await.as_deref(),
+        Some("10.0.0.2")
+    );
+}
@@ -0,0 +1,107 @@
+//! Avatares: miniaturas cuadradas en JPEG y la versión de la persona que las acompaña
+
+mod common;
+
+use bytes::Bytes;
+use image::{ImageFormat, Rgba, RgbaImage};
+use libropr_rust::{core::services::avatar::TamanoAvatar, errors::AppError, infra::AppState};
+
+/// PNG de `ancho` x `alto` transparente
+fn png(ancho: u32, alto: u32) -> Bytes {
+    let imagen = RgbaImage::from_pixel(ancho, alto, Rgba([255, 0, 0, 0]));
+    let mut salida = std::io::Cursor::new(Vec::new());
+    imagen.write_to(&mut salida, ImageFormat::Png).unwrap();
+    Bytes::from(salida.into_inner())
+}
+
+#[tokio::test]
+async fn genera_miniaturas_cuadradas_sin_ampliar_y_sobre_blanco() {
+    let state = AppState::builder().build();
+    let persona = state
+        .repos
+        .persona
+        .create(common::persona_nueva(2, 5001))
+        .await
+        .unwrap();
+    let avatar = &state.services.avatar;
+
+    avatar
+        .actualizar(persona.idper, png(600, 300))
+        .await
+        .unwrap();
+
+    for (tamano, lado) in [
+        (TamanoAvatar::Pequeno, 64),
+        (TamanoAvatar::Mediano, 256),
+        // El recorte mide 300: no se amplía a 512
+        (TamanoAvatar::Grande, 300),
+    ] {
+        let (_, jpeg) = avatar.obtener(persona.idper, tamano).await.unwrap();
+        let miniatura = image::load_from_memory_with_format(&jpeg, ImageFormat::Jpeg)
+            .unwrap()
+            .to_rgb8();
+        assert_eq!(miniatura.dimensions(), (lado, lado), "{:?}", tamano);
+        let [r, g, b] = miniatura.get_pixel(lado / 2, lado / 2).0;
+        assert!(r > 250 && g > 250 && b > 250, "{:?}", (r, g, b));
+    }
+}
+
+#[tokio::test]
+async fn rechaza_lo_que_no_es_una_imagen() {
+    let state = AppState::builder().build();
+    let persona = state
+        .repos
+        .persona
+        .create(common::persona_nueva(2, 5001))
+        .await
+        .unwrap();
+
+    for data in [
+        Bytes::new(),
+        Bytes::from_static(b"%PDF-1.4 no es una imagen"),
+    ] {
+        let error = state
+            .services
+            .avatar
+            .actualizar(persona.idper, data)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)), "{:?}", error);
+    }
+}
+
+#[tokio::test]
+async fn cambiar_el_avatar_es_una_version_nueva_de_la_persona() {
+    let state = AppState::builder().build();
+    let persona = state
+        .repos
+        .persona
+        .create(common::persona_nueva(2, 5001))
+        .await
+        .unwrap();
+    let avatar = &state.services.avatar;
+
+    let con_avatar = avatar.actualizar(persona.idper, png(80, 80)).await.unwrap();
+    assert_eq!(con_avatar.verper, persona.verper + 1);
+    let version = con_avatar.avaper.clone().unwrap();
+
+    // La misma imagen conserva la versión del avatar
+    let repetido = avatar.actualizar(persona.idper, png(80, 80)).await.unwrap();
+    assert_eq!(repetido.avaper.as_deref(), Some(version.as_str()));
+
+    let sin_avatar = avatar.eliminar(persona.idper).await.unwrap();
+    assert_eq!(sin_avatar.avaper, None);
+    assert_eq!(sin_avatar.verper, persona.verper + 3);
+    assert!(matches!(
+        avatar.obtener(persona.idper, TamanoAvatar::Mediano).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    let versiones = state
+        .services
+        .historial
+        .versiones(persona.idper)
+        .await
+        .unwrap();
+    assert_eq!(versiones.len() as i64, sin_avatar.verper);
+}
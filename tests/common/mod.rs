@@ -0,0 +1,91 @@
+//! Utilidades compartidas por las pruebas de integración
+// Cada archivo de tests/ compila este módulo por su cuenta y no usa todo
+#![allow(dead_code)]
+
+use libropr_rust::{
+    domain::{Claims, Departamento, EstadoPersona, Municipio, Persona},
+    infra::AppState,
+};
+
+/// Sufijo único por ejecución: las bases reales conservan los datos de pruebas anteriores
+pub fn sufijo() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..12].to_string()
+}
+
+/// Persona activa con documento, email y teléfono únicos, lista para `create`
+pub fn persona_nueva(idpef: i64, codubi: i64) -> Persona {
+    let sufijo = sufijo();
+    let telefono = u64::from_str_radix(&sufijo[..8], 16).unwrap() % 10_000_000;
+    Persona {
+        idper: 0,
+        ndocper: Some(sufijo.clone()),
+        tdocper: 1,
+        nomper: "Prueba".to_string(),
+        apeper: "Integración".to_string(),
+        dirper: None,
+        telper: format!("+57 300 {:07}", telefono),
+        telnor: Some(format!("+57300{:07}", telefono)),
+        codubi,
+        idpef,
+        pass: None,
+        emaper: format!("{}@prueba.invalid", sufijo),
+        actper: true,
+        verper: 0,
+        estper: EstadoPersona::Activo,
+        motsus: None,
+        fecsus: None,
+        avaper: None,
+    }
+}
+
+/// URL de la base de un motor tomada de `variable`
+/// Las pruebas que la usan se marcan `#[ignore]`: si se piden con `--ignored`
+/// sin definirla, fallan en vez de pasar sin haber probado nada
+pub fn url(variable: &str) -> String {
+    std::env::var(variable).unwrap_or_else(|_| {
+        panic!(
+            "{} no está definida; es la base contra la que se prueba",
+            variable
+        )
+    })
+}
+
+/// Token firmado con `secreto`, como lo emite el login
+pub fn token(persona: &Persona, nompef: &str, secreto: &str) -> String {
+    let claims = Claims {
+        sub: persona.idper,
+        exp: 10000000000,
+        idper: persona.idper,
+        nomper: persona.nomper.clone(),
+        idpef: persona.idpef,
+        nompef: nompef.to_string(),
+        emaper: persona.emaper.clone(),
+    };
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(secreto.as_bytes()),
+    )
+    .unwrap()
+}
+
+/// Carga Medellín (5001) en el catálogo DIVIPOLA, sin el cual no se crean personas
+pub async fn cargar_divipola(state: &AppState) {
+    state
+        .repos
+        .ubicacion
+        .import(
+            vec![Departamento {
+                coddep: 5,
+                nomdep: "Antioquia".to_string(),
+            }],
+            vec![Municipio {
+                codubi: 5001,
+                nomubi: "Medellín".to_string(),
+                coddep: 5,
+                nomdep: String::new(),
+            }],
+        )
+        .await
+        .unwrap();
+}
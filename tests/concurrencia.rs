@@ -0,0 +1,114 @@
+//! Control de concurrencia optimista: el ETag de una persona es su versión y
+//! toda escritura debe traerlo en If-Match
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{
+        Request, StatusCode,
+        header::{AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH},
+    },
+};
+use libropr_rust::{api::app_router, domain::Persona, infra::AppState};
+use serde_json::json;
+use tower::ServiceExt;
+
+const SECRETO: &str = "secreto-de-prueba";
+
+async fn preparar() -> (Router, Persona, String) {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    common::cargar_divipola(&state).await;
+    let admin = state
+        .repos
+        .persona
+        .create(common::persona_nueva(1, 5001))
+        .await
+        .unwrap();
+    let persona = state
+        .repos
+        .persona
+        .create(Persona {
+            ndocper: Some("1020304050".to_string()),
+            ..common::persona_nueva(2, 5001)
+        })
+        .await
+        .unwrap();
+    let token = common::token(&admin, "super_admin", SECRETO);
+    (app_router(Arc::new(state)), persona, token)
+}
+
+/// PATCH del nombre con el If-Match indicado; devuelve el estado y el ETag
+async fn renombrar(
+    app: &Router,
+    persona: &Persona,
+    token: &str,
+    if_match: Option<&str>,
+) -> (StatusCode, Option<String>) {
+    let peticion = Request::patch(format!("/api/v1/persona/{}", persona.idper))
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .header(CONTENT_TYPE, "application/json");
+    let peticion = match if_match {
+        Some(valor) => peticion.header(IF_MATCH, valor),
+        None => peticion,
+    };
+    let respuesta = app
+        .clone()
+        .oneshot(
+            peticion
+                .body(Body::from(json!({ "nomper": "Renombrada" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let etag = respuesta
+        .headers()
+        .get(ETAG)
+        .map(|v| v.to_str().unwrap().to_string());
+    (respuesta.status(), etag)
+}
+
+#[tokio::test]
+async fn escribir_exige_if_match_fuerte_y_numerico() {
+    let (app, persona, token) = preparar().await;
+
+    assert_eq!(
+        renombrar(&app, &persona, &token, None).await.0,
+        StatusCode::PRECONDITION_REQUIRED
+    );
+    for invalido in ["W/\"1\"", "1", "\"uno\"", "*"] {
+        assert_eq!(
+            renombrar(&app, &persona, &token, Some(invalido)).await.0,
+            StatusCode::BAD_REQUEST,
+            "{}",
+            invalido
+        );
+    }
+
+    let eliminar = Request::delete(format!("/api/v1/persona/{}", persona.idper))
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let respuesta = app.clone().oneshot(eliminar).await.unwrap();
+    assert_eq!(respuesta.status(), StatusCode::PRECONDITION_REQUIRED);
+}
+
+#[tokio::test]
+async fn la_segunda_escritura_con_la_misma_version_pierde() {
+    let (app, persona, token) = preparar().await;
+
+    let (status, etag) = renombrar(&app, &persona, &token, Some(" \"1\" ")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"2\""));
+
+    // Quien leyó la versión 1 no pisa el cambio que ya se aplicó
+    let (status, _) = renombrar(&app, &persona, &token, Some("\"1\"")).await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    let (status, etag) = renombrar(&app, &persona, &token, Some("\"2\"")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"3\""));
+}
@@ -0,0 +1,74 @@
+//! Opciones de los pools y proxies de confianza leídos de la configuración
+
+use std::time::Duration;
+
+use libropr_rust::config::Config;
+use serde_json::{Value, json};
+
+fn config(extra: Value) -> Config {
+    let mut valores = json!({
+        "log_level": "info",
+        "app_name": "libropr",
+        "host": "127.0.0.1",
+        "app_env": "test",
+        "port": 3000,
+        "database_url": "sqlite::memory:",
+        "db_pool_size": 0,
+        "jwt_expiration_hours": 1,
+        "jwt_secret": "secreto",
+    });
+    valores
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    serde_json::from_value(valores).unwrap()
+}
+
+#[test]
+fn cero_desactiva_los_limites_opcionales() {
+    let opciones = config(json!({
+        "db_idle_timeout_secs": 0,
+        "db_max_lifetime_secs": 0,
+        "db_statement_timeout_ms": 0,
+    }))
+    .pool_options()
+    .unwrap();
+
+    assert_eq!(opciones.max_conexiones, 10);
+    assert_eq!(opciones.espera_conexion, Duration::from_secs(30));
+    assert_eq!(opciones.inactividad, None);
+    assert_eq!(opciones.vida_maxima, None);
+    assert_eq!(opciones.limite_sentencia, None);
+}
+
+#[test]
+fn la_espera_por_conexion_no_puede_ser_cero() {
+    assert!(
+        config(json!({ "db_acquire_timeout_secs": 0 }))
+            .pool_options()
+            .is_err()
+    );
+    assert_eq!(
+        config(json!({ "db_acquire_timeout_secs": 5 }))
+            .pool_options()
+            .unwrap()
+            .espera_conexion,
+        Duration::from_secs(5)
+    );
+}
+
+#[test]
+fn los_proxies_admiten_ips_y_redes() {
+    let proxies = config(json!({ "trusted_proxies": "10.0.0.1, 192.168.0.0/16,," }))
+        .trusted_proxies()
+        .unwrap();
+
+    assert_eq!(proxies.len(), 2);
+    assert!(proxies[0].contains(&"10.0.0.1".parse::<std::net::IpAddr>().unwrap()));
+    assert!(proxies[1].contains(&"192.168.4.2".parse::<std::net::IpAddr>().unwrap()));
+    assert!(
+        config(json!({ "trusted_proxies": "proxy.local" }))
+            .trusted_proxies()
+            .is_err()
+    );
+}
@@ -0,0 +1,123 @@
+//! La exportación, el tratamiento masivo de datos, solo incluye a quien consintió
+
+mod common;
+
+use futures::TryStreamExt;
+use libropr_rust::{
+    domain::{CanalConsentimiento, Persona, PersonaFilter},
+    errors::AppError,
+    infra::AppState,
+};
+
+async fn exportables(state: &AppState) -> Result<Vec<i64>, AppError> {
+    let mut filtro = PersonaFilter::default();
+    state
+        .services
+        .consentimiento
+        .restringir(&mut filtro)
+        .await?;
+    let personas: Vec<_> = state.services.persona.export(filtro).try_collect().await?;
+    Ok(personas.into_iter().map(|p| p.idper).collect())
+}
+
+#[tokio::test]
+async fn sin_politica_vigente_no_se_exporta() {
+    let state = AppState::builder().build();
+
+    let error = exportables(&state).await.unwrap_err();
+
+    assert!(
+        matches!(error, AppError::PreconditionFailed(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn solo_se_exporta_a_quien_tiene_consentimiento_vigente() {
+    let state = AppState::builder().build();
+    let consentimiento = &state.services.consentimiento;
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        let persona = state
+            .repos
+            .persona
+            .create(common::persona_nueva(2, 5001))
+            .await
+            .unwrap();
+        ids.push(persona.idper);
+    }
+    let politica = consentimiento
+        .publicar("1.0", "Política de prueba", None)
+        .await
+        .unwrap();
+    consentimiento
+        .aceptar(ids[0], politica.idpol, CanalConsentimiento::Web)
+        .await
+        .unwrap();
+
+    assert_eq!(exportables(&state).await.unwrap(), [ids[0]]);
+
+    // Una versión nueva deja sin efecto lo aceptado antes
+    consentimiento
+        .publicar("2.0", "Política nueva", None)
+        .await
+        .unwrap();
+    assert!(exportables(&state).await.unwrap().is_empty());
+
+    let vigente = consentimiento.politica_vigente().await.unwrap().unwrap();
+    consentimiento
+        .aceptar(ids[1], vigente.idpol, CanalConsentimiento::Web)
+        .await
+        .unwrap();
+    assert_eq!(exportables(&state).await.unwrap(), [ids[1]]);
+
+    consentimiento.revocar(ids[1]).await.unwrap();
+    assert!(exportables(&state).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn registrarse_con_una_politica_vieja_no_deja_a_la_persona() {
+    let state = AppState::builder().build();
+    common::cargar_divipola(&state).await;
+    let consentimiento = &state.services.consentimiento;
+    let vieja = consentimiento
+        .publicar("1.0", "Política de prueba", None)
+        .await
+        .unwrap();
+    let vigente = consentimiento
+        .publicar("2.0", "Política nueva", None)
+        .await
+        .unwrap();
+    let persona = Persona {
+        ndocper: Some("1020304050".to_string()),
+        ..common::persona_nueva(2, 5001)
+    };
+    let servicio = &state.services.persona;
+
+    let error = servicio
+        .create_con_consentimiento(persona.clone(), vieja.idpol, CanalConsentimiento::Web)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::Validation(_)), "{:?}", error);
+    assert!(
+        servicio
+            .get_by_email(&persona.emaper)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let (creada, aceptado) = servicio
+        .create_con_consentimiento(persona.clone(), vigente.idpol, CanalConsentimiento::Web)
+        .await
+        .unwrap();
+    assert_eq!(aceptado.idper, creada.idper);
+
+    // Las mismas reglas que `create`: el email ya está registrado
+    let error = servicio
+        .create_con_consentimiento(persona, vigente.idpol, CanalConsentimiento::Web)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::Validation(_)), "{:?}", error);
+}
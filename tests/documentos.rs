@@ -0,0 +1,122 @@
+//! Validación de documentos colombianos por tipo y búsqueda por número
+
+mod common;
+
+use libropr_rust::{
+    core::services::documento::{self, calcular_dv_nit, claves_busqueda},
+    domain::{Persona, TipoDocumento},
+    errors::AppError,
+    infra::AppState,
+};
+
+/// Mensajes de un campo en un error de validación
+fn mensajes(error: AppError, campo: &str) -> Vec<String> {
+    match error {
+        AppError::Validation(campos) => campos.get(campo).cloned().unwrap_or_default(),
+        otro => panic!("se esperaba un error de validación: {:?}", otro),
+    }
+}
+
+#[test]
+fn el_dv_del_nit_sigue_el_modulo_11_de_la_dian() {
+    assert_eq!(calcular_dv_nit("800197268"), 4);
+    assert_eq!(calcular_dv_nit("860034313"), 7);
+    assert_eq!(calcular_dv_nit("900123456"), 8);
+    // Residuos 0 y 1 se usan tal cual
+    assert_eq!(calcular_dv_nit("811000001"), 1);
+}
+
+#[test]
+fn el_nit_se_normaliza_y_guarda_con_su_dv() {
+    let nit = documento::validar(5, " 800.197.268-4 ").unwrap();
+    assert_eq!(nit.tipo, TipoDocumento::Nit);
+    assert_eq!(nit.ndocper, "800197268");
+    assert_eq!(nit.dv, Some(4));
+    assert_eq!(nit.almacenado(), "800197268-4");
+
+    // Sin DV se calcula
+    let sin_dv = documento::validar(5, "800197268").unwrap();
+    assert_eq!(sin_dv.almacenado(), "800197268-4");
+
+    let error = documento::validar(5, "800197268-3").unwrap_err();
+    assert_eq!(
+        mensajes(error, "ndocper"),
+        ["El dígito de verificación del NIT no es correcto"]
+    );
+    assert!(documento::validar(5, "1234").is_err());
+}
+
+#[test]
+fn cada_tipo_valida_su_formato() {
+    let cedula = documento::validar(1, "1.020.304.050").unwrap();
+    assert_eq!(cedula.almacenado(), "1020304050");
+    assert_eq!(cedula.dv, None);
+    assert!(documento::validar(1, "0123456").is_err());
+    assert!(documento::validar(1, "12345678901").is_err());
+
+    assert!(documento::validar(2, "1020304050").is_ok());
+    assert!(documento::validar(2, "123456789").is_err());
+
+    assert!(documento::validar(3, "123").is_ok());
+    assert!(documento::validar(3, "12A45").is_err());
+
+    let pasaporte = documento::validar(4, "ab-12 345").unwrap();
+    assert_eq!(pasaporte.ndocper, "AB12345");
+    assert!(documento::validar(4, "AB1").is_err());
+}
+
+#[test]
+fn tipo_desconocido_y_numero_vacio_se_reportan_por_campo() {
+    assert_eq!(
+        mensajes(documento::validar(99, "123456").unwrap_err(), "tdocper"),
+        ["Tipo de documento desconocido"]
+    );
+    assert_eq!(
+        mensajes(documento::validar(1, "  ").unwrap_err(), "ndocper"),
+        ["El número de documento es requerido"]
+    );
+}
+
+#[test]
+fn las_claves_de_busqueda_cubren_el_dv_y_el_formato() {
+    assert_eq!(claves_busqueda("800.197.268"), ["800197268-4", "800197268"]);
+    assert_eq!(
+        claves_busqueda("800197268-4"),
+        ["800197268-4", "8001972684"]
+    );
+    assert_eq!(claves_busqueda("ab 123"), ["AB123"]);
+    assert!(claves_busqueda(" ").is_empty());
+}
+
+#[tokio::test]
+async fn la_busqueda_por_documento_normaliza_la_entrada() {
+    let state = AppState::builder().build();
+    common::cargar_divipola(&state).await;
+    let persona = &state.services.persona;
+    let empresa = persona
+        .create(Persona {
+            tdocper: 5,
+            ndocper: Some("800.197.268-4".to_string()),
+            ..common::persona_nueva(2, 5001)
+        })
+        .await
+        .unwrap();
+    assert_eq!(empresa.ndocper.as_deref(), Some("800197268-4"));
+
+    for escrito in ["800197268-4", "800.197.268-4", "800197268"] {
+        let encontrada = persona.get_by_document(escrito).await.unwrap();
+        assert_eq!(
+            encontrada.map(|p| p.idper),
+            Some(empresa.idper),
+            "{}",
+            escrito
+        );
+    }
+    assert!(
+        persona
+            .get_by_document("800197269")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
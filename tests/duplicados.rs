@@ -0,0 +1,283 @@
+//! Fusión de personas duplicadas sobre los adaptadores en memoria
+
+mod common;
+
+use chrono::Utc;
+use libropr_rust::{
+    core::services::duplicado::{
+        DuplicadoService, Huella, Senales, candidatos, normalizar_email, normalizar_nombre,
+    },
+    domain::{CanalConsentimiento, NuevaSolicitud, Persona, TipoSolicitud},
+    errors::AppError,
+    infra::{Repos, adapters::db::memory::MemoryDb},
+};
+
+async fn crear(repos: &Repos) -> Persona {
+    repos
+        .persona
+        .create(common::persona_nueva(2, 5001))
+        .await
+        .unwrap()
+}
+
+async fn solicitar(repos: &Repos, idper: i64, tipsol: TipoSolicitud) {
+    repos
+        .habeas_data
+        .registrar(NuevaSolicitud {
+            idper,
+            tipsol,
+            idsolicitante: idper,
+            fecsol: Utc::now(),
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn las_solicitudes_de_habeas_data_se_quedan_con_la_absorbida() {
+    let repos = Repos::memory(MemoryDb::new());
+    let servicio = DuplicadoService::new(
+        repos.duplicado.clone(),
+        repos.persona.clone(),
+        repos.unit_of_work.clone(),
+    );
+    let superviviente = crear(&repos).await;
+    let absorbida = crear(&repos).await;
+    solicitar(&repos, absorbida.idper, TipoSolicitud::Acceso).await;
+
+    servicio
+        .fusionar(superviviente.idper, absorbida.idper, None, 1)
+        .await
+        .unwrap();
+
+    let de_la_absorbida = repos
+        .habeas_data
+        .list_by_persona(absorbida.idper)
+        .await
+        .unwrap();
+    assert_eq!(de_la_absorbida.len(), 1);
+    assert!(
+        repos
+            .habeas_data
+            .list_by_persona(superviviente.idper)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn la_superviviente_no_queda_con_dos_aceptaciones_vigentes_de_la_misma_politica() {
+    let repos = Repos::memory(MemoryDb::new());
+    let servicio = DuplicadoService::new(
+        repos.duplicado.clone(),
+        repos.persona.clone(),
+        repos.unit_of_work.clone(),
+    );
+    let superviviente = crear(&repos).await;
+    let absorbida = crear(&repos).await;
+    let consentimientos = &repos.consentimiento;
+    let anterior = consentimientos
+        .create_politica("1.0", "Texto anterior", Utc::now())
+        .await
+        .unwrap();
+    let vigente = consentimientos
+        .create_politica("2.0", "Texto vigente", Utc::now())
+        .await
+        .unwrap();
+    for (idper, idpol) in [
+        (absorbida.idper, anterior.idpol),
+        (absorbida.idper, vigente.idpol),
+        (superviviente.idper, vigente.idpol),
+    ] {
+        consentimientos
+            .aceptar(idper, idpol, CanalConsentimiento::Web, Utc::now())
+            .await
+            .unwrap();
+    }
+
+    let fusion = servicio
+        .fusionar(superviviente.idper, absorbida.idper, None, 1)
+        .await
+        .unwrap();
+    assert_eq!(fusion.detfus.consentimientos, 2);
+    assert_eq!(fusion.detfus.consentimientos_cerrados, 1);
+
+    let historial = consentimientos
+        .list_by_persona(superviviente.idper)
+        .await
+        .unwrap();
+    assert_eq!(
+        historial.len(),
+        3,
+        "el historial de aceptaciones se conserva"
+    );
+    for idpol in [anterior.idpol, vigente.idpol] {
+        let vigentes = historial
+            .iter()
+            .filter(|c| c.idpol == idpol && c.fecrev.is_none())
+            .count();
+        assert_eq!(vigentes, 1, "política {}", idpol);
+    }
+    assert!(
+        consentimientos
+            .list_by_persona(absorbida.idper)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn no_se_fusiona_una_persona_anonimizada() {
+    let repos = Repos::memory(MemoryDb::new());
+    let servicio = DuplicadoService::new(
+        repos.duplicado.clone(),
+        repos.persona.clone(),
+        repos.unit_of_work.clone(),
+    );
+    let superviviente = crear(&repos).await;
+    let absorbida = crear(&repos).await;
+    repos
+        .habeas_data
+        .anonimizar(
+            absorbida.idper,
+            absorbida.verper,
+            NuevaSolicitud {
+                idper: absorbida.idper,
+                tipsol: TipoSolicitud::Supresion,
+                idsolicitante: absorbida.idper,
+                fecsol: Utc::now(),
+            },
+        )
+        .await
+        .unwrap();
+
+    for (idsup, idabs) in [
+        (superviviente.idper, absorbida.idper),
+        (absorbida.idper, superviviente.idper),
+    ] {
+        let error = servicio.fusionar(idsup, idabs, None, 1).await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+    }
+}
+
+/// Persona con identificador propio para comparar sin pasar por un repositorio
+fn persona(idper: i64, nombre: &str, apellido: &str, email: &str) -> Persona {
+    Persona {
+        idper,
+        ndocper: None,
+        nomper: nombre.to_string(),
+        apeper: apellido.to_string(),
+        telnor: None,
+        emaper: email.to_string(),
+        ..common::persona_nueva(2, 5001)
+    }
+}
+
+fn huellas(personas: &[Persona]) -> Vec<Huella> {
+    personas.iter().map(Huella::from).collect()
+}
+
+#[test]
+fn normalizar_email_ignora_etiquetas_mayusculas_y_puntos_de_gmail() {
+    assert_eq!(
+        normalizar_email(" Ana.Perez+ofertas@Gmail.com "),
+        "anaperez@gmail.com"
+    );
+    assert_eq!(
+        normalizar_email("ana.perez@googlemail.com"),
+        "anaperez@gmail.com"
+    );
+    // Fuera de Gmail el punto es parte del usuario
+    assert_eq!(
+        normalizar_email("Ana.Perez+x@empresa.co"),
+        "ana.perez@empresa.co"
+    );
+    assert_eq!(normalizar_email("sin-arroba"), "sin-arroba");
+}
+
+#[test]
+fn normalizar_nombre_quita_tildes_mayusculas_y_espacios() {
+    assert_eq!(
+        normalizar_nombre("  José   MARÍA\tNúñez "),
+        "jose maria nunez"
+    );
+}
+
+#[test]
+fn el_puntaje_suma_las_senales_y_no_pasa_de_uno() {
+    assert_eq!(Senales::default().puntaje(), 0.0);
+    let telefono = Senales {
+        telefono: true,
+        ..Senales::default()
+    };
+    let todo = Senales {
+        documento: true,
+        email: true,
+        telefono: true,
+        nombre: 1.0,
+    };
+    let documento = Senales {
+        documento: true,
+        ..Senales::default()
+    };
+    assert!(telefono.puntaje() < documento.puntaje());
+    assert_eq!(todo.puntaje(), 1.0);
+    // Un nombre parecido bajo el umbral no suma
+    let lejano = Senales {
+        nombre: 0.5,
+        ..Senales::default()
+    };
+    assert_eq!(lejano.puntaje(), 0.0);
+}
+
+#[test]
+fn el_mismo_email_normalizado_es_candidato() {
+    let personas = [
+        persona(1, "Ana", "Pérez", "ana.perez@gmail.com"),
+        persona(2, "Ana María", "Gómez", "AnaPerez+x@gmail.com"),
+        persona(3, "Luis", "Rojas", "luis@empresa.co"),
+    ];
+
+    let encontrados = candidatos(&huellas(&personas), Utc::now());
+
+    assert_eq!(encontrados.len(), 1);
+    assert_eq!((encontrados[0].idper1, encontrados[0].idper2), (1, 2));
+    assert!(encontrados[0].motdup.contains("email"));
+}
+
+#[test]
+fn un_nombre_casi_igual_en_el_mismo_municipio_es_candidato() {
+    let mut personas = [
+        persona(1, "José", "Martínez", "jose@uno.co"),
+        persona(2, "Jose", "Martinez", "jm@dos.co"),
+        persona(3, "Jose", "Martinez", "otro@tres.co"),
+    ];
+    // En otro municipio y sin más señales no se propone
+    personas[2].codubi = 5002;
+
+    let encontrados = candidatos(&huellas(&personas), Utc::now());
+
+    assert_eq!(encontrados.len(), 1);
+    assert_eq!((encontrados[0].idper1, encontrados[0].idper2), (1, 2));
+    assert_eq!(encontrados[0].motdup, "nombre");
+}
+
+#[test]
+fn un_telefono_de_relleno_no_junta_a_todos() {
+    let personas: Vec<Persona> = (1..=300)
+        .map(|i| Persona {
+            telnor: Some("+570000000".to_string()),
+            codubi: 5000 + i,
+            ..persona(
+                i,
+                &format!("Nombre{}", i),
+                "Apellido",
+                &format!("p{}@x.co", i),
+            )
+        })
+        .collect();
+
+    assert!(candidatos(&huellas(&personas), Utc::now()).is_empty());
+}
@@ -0,0 +1,168 @@
+//! Ciclo de vida de la cuenta: transiciones válidas y su efecto en el acceso
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header::AUTHORIZATION},
+};
+use chrono::{Duration, Utc};
+use libropr_rust::{
+    api::app_router,
+    domain::{CambioEstado, EstadoPersona, Persona, TransicionPersona},
+    infra::AppState,
+};
+use tower::ServiceExt;
+
+const SECRETO: &str = "secreto-de-prueba";
+
+#[test]
+fn cada_transicion_parte_solo_de_sus_estados() {
+    use EstadoPersona::*;
+
+    let esperado = [
+        (
+            TransicionPersona::Activar,
+            [Some(Activo), None, Some(Activo), None, None],
+        ),
+        (
+            TransicionPersona::Suspender,
+            [None, Some(Suspendido), None, None, None],
+        ),
+        (
+            TransicionPersona::Bloquear,
+            [None, Some(Bloqueado), Some(Bloqueado), None, None],
+        ),
+        (
+            TransicionPersona::Desbloquear,
+            [None, None, None, Some(Activo), None],
+        ),
+        (
+            TransicionPersona::Archivar,
+            [
+                Some(Archivado),
+                Some(Archivado),
+                Some(Archivado),
+                Some(Archivado),
+                None,
+            ],
+        ),
+    ];
+    let origenes = [Pendiente, Activo, Suspendido, Bloqueado, Archivado];
+
+    for (transicion, destinos) in esperado {
+        for (origen, destino) in origenes.into_iter().zip(destinos) {
+            assert_eq!(
+                transicion.destino(origen),
+                destino,
+                "{:?} desde {:?}",
+                transicion,
+                origen
+            );
+        }
+    }
+}
+
+#[test]
+fn una_suspension_vencida_cuenta_como_activa() {
+    let ahora = Utc::now();
+    let suspendida = |fecsus| Persona {
+        estper: EstadoPersona::Suspendido,
+        fecsus,
+        ..common::persona_nueva(2, 5001)
+    };
+
+    assert_eq!(
+        suspendida(Some(ahora - Duration::minutes(1))).estado_efectivo(ahora),
+        EstadoPersona::Activo
+    );
+    assert_eq!(
+        suspendida(Some(ahora + Duration::minutes(1))).estado_efectivo(ahora),
+        EstadoPersona::Suspendido
+    );
+    // Sin fecha de fin la suspensión no vence sola
+    assert_eq!(
+        suspendida(None).estado_efectivo(ahora),
+        EstadoPersona::Suspendido
+    );
+}
+
+async fn suspender(state: &AppState, persona: &Persona, hasta: chrono::DateTime<Utc>) -> Persona {
+    state
+        .repos
+        .persona
+        .change_estado(
+            persona.idper,
+            CambioEstado {
+                estper: EstadoPersona::Suspendido,
+                motsus: Some("Prueba".to_string()),
+                fecsus: Some(hasta),
+            },
+            persona.verper,
+        )
+        .await
+        .unwrap()
+}
+
+fn consultar(persona: &Persona) -> Request<Body> {
+    let token = common::token(persona, "super_admin", SECRETO);
+    Request::get(format!("/api/v1/persona/{}", persona.idper))
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn un_token_emitido_antes_de_suspender_deja_de_servir() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    let persona = state
+        .repos
+        .persona
+        .create(common::persona_nueva(1, 5001))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state.clone()));
+
+    let respuesta = app.clone().oneshot(consultar(&persona)).await.unwrap();
+    assert_eq!(respuesta.status(), StatusCode::OK);
+
+    suspender(&state, &persona, Utc::now() + Duration::hours(1)).await;
+    let respuesta = app.oneshot(consultar(&persona)).await.unwrap();
+    assert_eq!(respuesta.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn la_suspension_vencida_se_reactiva_al_iniciar_sesion_y_no_al_usar_el_token() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    let persona = state
+        .repos
+        .persona
+        .create(common::persona_nueva(1, 5001))
+        .await
+        .unwrap();
+    let suspendida = suspender(&state, &persona, Utc::now() - Duration::minutes(1)).await;
+    let app = app_router(Arc::new(state.clone()));
+
+    // Con el token pasa, pero la lectura no escribe
+    let respuesta = app.oneshot(consultar(&suspendida)).await.unwrap();
+    assert_eq!(respuesta.status(), StatusCode::OK);
+    let guardada = state
+        .repos
+        .persona
+        .get_by_idper(persona.idper)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(guardada.estper, EstadoPersona::Suspendido);
+
+    let reactivada = state
+        .services
+        .persona
+        .verificar_acceso(guardada)
+        .await
+        .unwrap();
+    assert_eq!(reactivada.estper, EstadoPersona::Activo);
+    assert_eq!(reactivada.fecsus, None);
+}
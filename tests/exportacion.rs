@@ -0,0 +1,120 @@
+//! Exportación: negociación del formato, celdas y el XLSX que llega por partes
+
+mod common;
+
+use std::{io::Cursor, sync::Arc};
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header::AUTHORIZATION},
+};
+use calamine::{Data, Reader, Xlsx};
+use libropr_rust::{
+    api::app_router,
+    core::services::persona::{ExportCell, ExportFormat, export_cells},
+    domain::{CanalConsentimiento, Persona},
+    infra::AppState,
+};
+use tower::ServiceExt;
+
+const SECRETO: &str = "secreto-de-prueba";
+const XLSX: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+#[test]
+fn el_accept_elige_el_primer_formato_conocido() {
+    assert_eq!(
+        ExportFormat::from_accept("text/csv"),
+        Some(ExportFormat::Csv)
+    );
+    assert_eq!(
+        ExportFormat::from_accept("application/json, application/x-ndjson;q=0.9"),
+        Some(ExportFormat::Ndjson)
+    );
+    assert_eq!(
+        ExportFormat::from_accept(&format!("text/html, {}; q=0.8, text/csv", XLSX)),
+        Some(ExportFormat::Xlsx)
+    );
+    assert_eq!(ExportFormat::from_accept("*/*"), None);
+    assert_eq!(ExportFormat::from_accept(""), None);
+
+    assert_eq!(
+        ExportFormat::from_param(" JSONL "),
+        Some(ExportFormat::Ndjson)
+    );
+    assert_eq!(ExportFormat::from_param("pdf"), None);
+}
+
+#[test]
+fn los_ids_y_codigos_no_llevan_separador_de_miles() {
+    let persona = Persona {
+        idper: 1234567,
+        codubi: 5001,
+        verper: 1234,
+        ..common::persona_nueva(2, 5001)
+    };
+
+    let celdas = export_cells(&persona, true);
+
+    assert!(matches!(celdas[0], ExportCell::Number(1234567)));
+    assert!(matches!(celdas[7], ExportCell::Number(5001)));
+    assert!(matches!(&celdas[12], ExportCell::Text(t) if t == "1.234"));
+    assert!(matches!(
+        export_cells(&persona, false)[12],
+        ExportCell::Number(1234)
+    ));
+}
+
+#[tokio::test]
+async fn el_xlsx_trae_solo_a_quien_consintio() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    let admin = state
+        .repos
+        .persona
+        .create(common::persona_nueva(1, 5001))
+        .await
+        .unwrap();
+    let consintio = state
+        .repos
+        .persona
+        .create(common::persona_nueva(2, 5001))
+        .await
+        .unwrap();
+    state
+        .repos
+        .persona
+        .create(common::persona_nueva(2, 5001))
+        .await
+        .unwrap();
+    let consentimiento = &state.services.consentimiento;
+    let politica = consentimiento
+        .publicar("1.0", "Política de prueba", None)
+        .await
+        .unwrap();
+    consentimiento
+        .aceptar(consintio.idper, politica.idpol, CanalConsentimiento::Web)
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state));
+
+    let respuesta = app
+        .oneshot(
+            Request::get("/api/v1/persona/export?format=xlsx")
+                .header(
+                    AUTHORIZATION,
+                    format!("Bearer {}", common::token(&admin, "super_admin", SECRETO)),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(respuesta.status(), StatusCode::OK);
+    let bytes = to_bytes(respuesta.into_body(), usize::MAX).await.unwrap();
+    let mut libro = Xlsx::new(Cursor::new(bytes.to_vec())).unwrap();
+    let hoja = libro.worksheet_range_at(0).unwrap().unwrap();
+    let filas: Vec<_> = hoja.rows().collect();
+    assert_eq!(filas.len(), 2);
+    assert_eq!(filas[0][0], Data::String("idper".to_string()));
+    assert_eq!(filas[1][0], Data::Float(consintio.idper as f64));
+}
@@ -0,0 +1,262 @@
+//! Habeas data: el archivo con todo lo que se guarda del titular, la anonimización
+//! y el registro de accesos de lo que se descarga
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header::AUTHORIZATION},
+};
+use bytes::Bytes;
+use libropr_rust::{
+    api::app_router,
+    core::services::adjunto::ArchivoSubido,
+    domain::{
+        AccesoFilter, CanalConsentimiento, CategoriaAdjunto, DATO_ANONIMO, EstadoPersona, Persona,
+        TipoSolicitud,
+    },
+    errors::AppError,
+    infra::AppState,
+};
+use tower::ServiceExt;
+
+const SECRETO: &str = "secreto-de-prueba";
+
+/// PNG mínimo: basta la firma para reconocer el tipo
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+fn get(ruta: String, persona: &Persona) -> Request<Body> {
+    Request::get(ruta)
+        .header(
+            AUTHORIZATION,
+            format!("Bearer {}", common::token(persona, "operador", SECRETO)),
+        )
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn vias(state: &AppState, idper: i64) -> Vec<(String, String)> {
+    state
+        .services
+        .acceso
+        .por_persona(idper, AccesoFilter::default())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| (a.via, a.campos))
+        .collect()
+}
+
+#[tokio::test]
+async fn descargar_los_datos_personales_queda_registrado() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    let persona = state
+        .repos
+        .persona
+        .create(common::persona_nueva(2, 5001))
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state.clone()));
+
+    let ruta = format!("/api/v1/persona/{}/datos-personales", persona.idper);
+    let respuesta = app.oneshot(get(ruta, &persona)).await.unwrap();
+
+    assert_eq!(respuesta.status(), StatusCode::OK);
+    assert_eq!(
+        vias(&state, persona.idper).await,
+        [(
+            "habeas_data".to_string(),
+            "ndocper,telper,emaper".to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn descargar_un_adjunto_queda_registrado() {
+    let state = AppState::builder().jwt_secret(SECRETO).build();
+    let persona = state
+        .repos
+        .persona
+        .create(common::persona_nueva(2, 5001))
+        .await
+        .unwrap();
+    let adjunto = state
+        .services
+        .adjunto
+        .subir(
+            persona.idper,
+            CategoriaAdjunto::Identificacion,
+            ArchivoSubido {
+                nombre: Some("cedula.png".to_string()),
+                content_type: Some("image/png".to_string()),
+                data: Bytes::from_static(PNG),
+            },
+            persona.idper,
+        )
+        .await
+        .unwrap();
+    let app = app_router(Arc::new(state.clone()));
+
+    let ruta = format!(
+        "/api/v1/persona/{}/adjuntos/{}",
+        persona.idper, adjunto.idadj
+    );
+    let respuesta = app.oneshot(get(ruta, &persona)).await.unwrap();
+
+    assert_eq!(respuesta.status(), StatusCode::OK);
+    assert_eq!(
+        vias(&state, persona.idper).await,
+        [("adjunto".to_string(), "adjunto:identificacion".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn el_archivo_reune_todo_lo_guardado_del_titular() {
+    let state = AppState::builder().build();
+    common::cargar_divipola(&state).await;
+    let persona = state
+        .repos
+        .persona
+        .create(Persona {
+            pass: Some("$2b$12$hash".to_string()),
+            ..common::persona_nueva(2, 5001)
+        })
+        .await
+        .unwrap();
+    let consentimiento = &state.services.consentimiento;
+    let politica = consentimiento
+        .publicar("1.0", "Política de prueba", None)
+        .await
+        .unwrap();
+    consentimiento
+        .aceptar(persona.idper, politica.idpol, CanalConsentimiento::Web)
+        .await
+        .unwrap();
+    state
+        .services
+        .adjunto
+        .subir(
+            persona.idper,
+            CategoriaAdjunto::Foto,
+            ArchivoSubido {
+                nombre: Some("foto.png".to_string()),
+                content_type: None,
+                data: Bytes::from_static(PNG),
+            },
+            persona.idper,
+        )
+        .await
+        .unwrap();
+
+    let archivo = state
+        .services
+        .habeas_data
+        .archivo(persona.idper, 99)
+        .await
+        .unwrap();
+
+    assert_eq!(archivo.titular.emaper, persona.emaper);
+    assert!(archivo.titular.tiene_contrasena);
+    assert_eq!(archivo.ubicacion.as_ref().unwrap().codubi, 5001);
+    assert_eq!(archivo.consentimientos.len(), 1);
+    assert_eq!(archivo.adjuntos.len(), 1);
+    // La consulta misma queda entre las solicitudes, con quien la pidió
+    assert_eq!(archivo.solicitudes.len(), 1);
+    assert_eq!(archivo.solicitudes[0].tipsol, TipoSolicitud::Acceso);
+    assert_eq!(archivo.solicitudes[0].idsolicitante, 99);
+    // El hash de la contraseña no sale en el archivo
+    let json = serde_json::to_string(&archivo).unwrap();
+    assert!(!json.contains("$2b$12$hash"), "{}", json);
+}
+
+#[tokio::test]
+async fn anonimizar_borra_los_datos_personales_una_sola_vez() {
+    let state = AppState::builder().build();
+    let persona = state
+        .repos
+        .persona
+        .create(Persona {
+            dirper: Some("Calle 10 # 20-30".to_string()),
+            ..common::persona_nueva(2, 5001)
+        })
+        .await
+        .unwrap();
+    state
+        .services
+        .adjunto
+        .subir(
+            persona.idper,
+            CategoriaAdjunto::Identificacion,
+            ArchivoSubido {
+                nombre: Some("cedula.png".to_string()),
+                content_type: None,
+                data: Bytes::from_static(PNG),
+            },
+            persona.idper,
+        )
+        .await
+        .unwrap();
+    let habeas_data = &state.services.habeas_data;
+
+    // Con una versión vieja no se anonimiza nada
+    let error = habeas_data
+        .anonimizar(persona.idper, persona.verper + 1, 99)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, AppError::PreconditionFailed(_)),
+        "{:?}",
+        error
+    );
+
+    let anonima = habeas_data
+        .anonimizar(persona.idper, persona.verper, 99)
+        .await
+        .unwrap();
+
+    assert_eq!(anonima.idper, persona.idper);
+    assert_eq!(anonima.verper, persona.verper + 1);
+    assert_eq!(anonima.nomper, DATO_ANONIMO);
+    assert_eq!(anonima.apeper, DATO_ANONIMO);
+    assert_eq!(anonima.ndocper, None);
+    assert_eq!(anonima.dirper, None);
+    assert_eq!(anonima.telnor, None);
+    assert_ne!(anonima.telper, persona.telper);
+    assert_ne!(anonima.emaper, persona.emaper);
+    assert_eq!(anonima.estper, EstadoPersona::Archivado);
+    assert!(
+        state
+            .services
+            .adjunto
+            .listar(persona.idper)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    // Las versiones anteriores también tenían los datos: solo queda la anónima
+    let versiones = state
+        .repos
+        .historial
+        .list_by_persona(persona.idper)
+        .await
+        .unwrap();
+    assert_eq!(versiones.len(), 1);
+
+    let solicitudes = state
+        .repos
+        .habeas_data
+        .list_by_persona(persona.idper)
+        .await
+        .unwrap();
+    assert_eq!(solicitudes.len(), 1);
+    assert_eq!(solicitudes[0].tipsol, TipoSolicitud::Supresion);
+    assert_eq!(solicitudes[0].idsolicitante, 99);
+
+    let error = habeas_data
+        .anonimizar(persona.idper, anonima.verper, 99)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+}
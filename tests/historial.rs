@@ -0,0 +1,135 @@
+//! Historial de personas: versiones con su vigencia, consulta en una fecha y restauración
+
+mod common;
+
+use std::time::Duration;
+
+use chrono::Utc;
+use libropr_rust::{
+    domain::{EstadoPersona, Persona, TransicionPersona},
+    errors::AppError,
+    infra::AppState,
+};
+
+/// Persona creada por el servicio y renombrada una vez: versiones 1 y 2
+async fn con_dos_versiones(state: &AppState) -> (Persona, Persona) {
+    common::cargar_divipola(state).await;
+    let servicio = &state.services.persona;
+    let creada = servicio
+        .create(Persona {
+            ndocper: Some("1020304050".to_string()),
+            ..common::persona_nueva(2, 5001)
+        })
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let renombrada = servicio
+        .update(
+            creada.idper,
+            Persona {
+                nomper: "Renombrada".to_string(),
+                ..creada.clone()
+            },
+            creada.verper,
+        )
+        .await
+        .unwrap();
+    (creada, renombrada)
+}
+
+#[tokio::test]
+async fn cada_escritura_cierra_la_vigencia_de_la_anterior() {
+    let state = AppState::builder().build();
+    let (creada, renombrada) = con_dos_versiones(&state).await;
+    let historial = &state.services.historial;
+
+    let versiones = historial.versiones(creada.idper).await.unwrap();
+
+    // La más reciente primero
+    assert_eq!(versiones.len(), 2);
+    let (segunda, primera) = (&versiones[0], &versiones[1]);
+    assert_eq!((primera.verper, segunda.verper), (1, 2));
+    assert_eq!(primera.nomper, creada.nomper);
+    assert_eq!(segunda.nomper, renombrada.nomper);
+    assert_eq!(primera.vighas, Some(segunda.vigdes));
+    assert_eq!(segunda.vighas, None);
+
+    assert!(matches!(
+        historial.versiones(999).await,
+        Err(AppError::NotFound(_))
+    ));
+    assert!(matches!(
+        historial.version(creada.idper, 7).await,
+        Err(AppError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn en_una_fecha_se_ve_la_version_vigente_entonces() {
+    let state = AppState::builder().build();
+    let antes = Utc::now() - chrono::Duration::seconds(1);
+    let (creada, _) = con_dos_versiones(&state).await;
+    let historial = &state.services.historial;
+    let versiones = historial.versiones(creada.idper).await.unwrap();
+
+    let entre = versiones[0].vigdes - chrono::Duration::milliseconds(1);
+    assert_eq!(historial.al(creada.idper, entre).await.unwrap().verper, 1);
+    let ahora = historial.al(creada.idper, Utc::now()).await.unwrap();
+    assert_eq!(ahora.verper, 2);
+
+    assert!(matches!(
+        historial.al(creada.idper, antes).await,
+        Err(AppError::NotFound(_))
+    ));
+    let futuro = Utc::now() + chrono::Duration::days(1);
+    assert!(matches!(
+        historial.al(creada.idper, futuro).await,
+        Err(AppError::Validation(_))
+    ));
+}
+
+#[tokio::test]
+async fn restaurar_crea_una_version_nueva_y_conserva_el_estado() {
+    let state = AppState::builder().build();
+    let (creada, renombrada) = con_dos_versiones(&state).await;
+    let suspendida = state
+        .services
+        .persona
+        .transicion(
+            creada.idper,
+            TransicionPersona::Suspender,
+            Some("Prueba".to_string()),
+            None,
+            renombrada.verper,
+        )
+        .await
+        .unwrap();
+    let historial = &state.services.historial;
+
+    let error = historial
+        .restaurar(creada.idper, suspendida.verper, suspendida.verper)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+    let error = historial
+        .restaurar(creada.idper, 1, renombrada.verper)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, AppError::PreconditionFailed(_)),
+        "{:?}",
+        error
+    );
+
+    let restaurada = historial
+        .restaurar(creada.idper, 1, suspendida.verper)
+        .await
+        .unwrap();
+
+    assert_eq!(restaurada.verper, suspendida.verper + 1);
+    assert_eq!(restaurada.nomper, creada.nomper);
+    // El ciclo de vida no se restaura: sigue suspendida
+    assert_eq!(restaurada.estper, EstadoPersona::Suspendido);
+    let versiones = historial.versiones(creada.idper).await.unwrap();
+    assert_eq!(versiones.len(), 4);
+}
@@ -0,0 +1,140 @@
+//! Importación masiva: lectura de CSV/XLSX y lotes que se guardan por separado
+
+mod common;
+
+use libropr_rust::{
+    core::services::persona::{ImportFormat, ImportOptions, ROW_ERROR_KEY, parse_rows},
+    infra::AppState,
+};
+use rust_xlsxwriter::Workbook;
+
+const ENCABEZADO: &str = "ndocper,tdocper,nomper,apeper,telper,codubi,idpef,emaper";
+
+#[test]
+fn el_formato_sale_del_content_type_o_de_la_extension() {
+    assert_eq!(
+        ImportFormat::detect(Some("text/csv"), Some("personas.xlsx")),
+        Some(ImportFormat::Csv)
+    );
+    assert_eq!(
+        ImportFormat::detect(Some("application/octet-stream"), Some("Personas.XLSX")),
+        Some(ImportFormat::Xlsx)
+    );
+    assert_eq!(ImportFormat::detect(None, Some("personas.txt")), None);
+    assert_eq!(ImportFormat::detect(None, None), None);
+}
+
+#[test]
+fn cada_fila_del_csv_se_reporta_con_su_numero() {
+    let csv = format!(
+        "{}\n1020304050,1,Ana,Pérez,3001234567,5001,2,ana@prueba.invalid\n,x,Luis,Gómez,3001234568,5001,2,luis@prueba.invalid\n",
+        ENCABEZADO
+    );
+
+    let filas = parse_rows(ImportFormat::Csv, csv.as_bytes()).unwrap();
+
+    assert_eq!(filas.len(), 2);
+    let (fila, persona) = &filas[0];
+    assert_eq!(*fila, 2);
+    let persona = persona.as_ref().unwrap();
+    assert_eq!(persona.nomper, "Ana");
+    assert!(persona.actper);
+
+    // Una celda que no se puede leer invalida la fila completa
+    let (fila, error) = &filas[1];
+    assert_eq!(*fila, 3);
+    assert!(error.as_ref().unwrap_err().contains_key(ROW_ERROR_KEY));
+}
+
+#[test]
+fn el_xlsx_se_lee_igual_y_sin_las_filas_vacias_del_final() {
+    let mut libro = Workbook::new();
+    let hoja = libro.add_worksheet();
+    for (columna, encabezado) in ENCABEZADO.split(',').enumerate() {
+        hoja.write_string(0, columna as u16, encabezado).unwrap();
+    }
+    let fila = [
+        "1020304050",
+        "1",
+        "Ana",
+        "Pérez",
+        "3001234567",
+        "5001",
+        "2",
+        "ana@prueba.invalid",
+    ];
+    for (columna, valor) in fila.into_iter().enumerate() {
+        match valor.parse::<f64>() {
+            Ok(numero) if columna != 0 => hoja.write_number(1, columna as u16, numero),
+            _ => hoja.write_string(1, columna as u16, valor),
+        }
+        .unwrap();
+    }
+    hoja.write_string(4, 0, "").unwrap();
+    let bytes = libro.save_to_buffer().unwrap();
+
+    let filas = parse_rows(ImportFormat::Xlsx, &bytes).unwrap();
+
+    assert_eq!(filas.len(), 1);
+    let persona = filas[0].1.as_ref().unwrap();
+    assert_eq!(persona.tdocper, 1);
+    assert_eq!(persona.codubi, 5001);
+    assert_eq!(persona.ndocper.as_deref(), Some("1020304050"));
+}
+
+#[test]
+fn un_archivo_ilegible_es_un_error_de_la_solicitud() {
+    assert!(parse_rows(ImportFormat::Xlsx, b"no es un xlsx").is_err());
+}
+
+#[tokio::test]
+async fn si_un_lote_falla_se_reporta_lo_que_ya_quedo_guardado() {
+    let state = AppState::builder().build();
+    common::cargar_divipola(&state).await;
+    // El documento repetido solo lo detecta la base, al escribir el segundo lote
+    let csv = format!(
+        "{}\n1020304050,1,Ana,Pérez,3001234567,5001,2,ana@prueba.invalid\n1020304050,1,Luis,Gómez,3001234568,5001,2,luis@prueba.invalid\n",
+        ENCABEZADO
+    );
+    let filas = parse_rows(ImportFormat::Csv, csv.as_bytes()).unwrap();
+
+    let report = state
+        .services
+        .persona
+        .import(
+            filas,
+            ImportOptions {
+                batch_size: Some(1),
+                ..ImportOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.validas, 2);
+    assert_eq!(report.importadas, 1);
+    assert_eq!(report.lotes, 1);
+    let motivo = report.interrumpida.unwrap();
+    assert!(motivo.starts_with("El lote 2 no se guardó"), "{}", motivo);
+    assert_eq!(state.services.persona.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn si_falla_el_primer_lote_no_hay_nada_que_reportar() {
+    let state = AppState::builder().build();
+    common::cargar_divipola(&state).await;
+    let csv = format!(
+        "{}\n1020304050,1,Ana,Pérez,3001234567,5001,2,ana@prueba.invalid\n1020304050,1,Luis,Gómez,3001234568,5001,2,luis@prueba.invalid\n",
+        ENCABEZADO
+    );
+    let filas = parse_rows(ImportFormat::Csv, csv.as_bytes()).unwrap();
+
+    let resultado = state
+        .services
+        .persona
+        .import(filas, ImportOptions::default())
+        .await;
+
+    assert!(resultado.is_err());
+    assert_eq!(state.services.persona.count().await.unwrap(), 0);
+}
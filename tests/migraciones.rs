@@ -0,0 +1,399 @@
+//! Comprueba, sin base de datos, que las migraciones de cada motor y las
+//! consultas de sus adaptadores hablan del mismo esquema
+#![cfg(all(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use libropr_rust::{
+    domain::CATALOGOS,
+    infra::adapters::db::{mysql, postgres, sqlite},
+};
+use regex::Regex;
+
+/// tabla -> (columna -> tipo en mayúsculas)
+type Esquema = BTreeMap<String, BTreeMap<String, String>>;
+
+const DIALECTOS: [&str; 3] = ["postgres", "mysql", "sqlite"];
+
+/// Palabras que pueden seguir a una tabla y no son un alias
+const NO_ALIAS: &[&str] = &[
+    "WHERE",
+    "ORDER",
+    "GROUP",
+    "SET",
+    "ON",
+    "INNER",
+    "LEFT",
+    "JOIN",
+    "VALUES",
+    "LIMIT",
+    "RETURNING",
+    "AS",
+];
+
+fn raiz() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn quitar_comentarios(sql: &str) -> String {
+    sql.lines()
+        .map(|linea| linea.split("--").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parte por las comas que no están dentro de paréntesis
+fn partir(lista: &str) -> Vec<String> {
+    let mut partes = Vec::new();
+    let (mut actual, mut nivel) = (String::new(), 0);
+    for c in lista.chars() {
+        match c {
+            '(' => nivel += 1,
+            ')' => nivel -= 1,
+            ',' if nivel == 0 => {
+                partes.push(actual.trim().to_string());
+                actual.clear();
+                continue;
+            }
+            _ => {}
+        }
+        actual.push(c);
+    }
+    if !actual.trim().is_empty() {
+        partes.push(actual.trim().to_string());
+    }
+    partes
+}
+
+/// Esquema resultante de aplicar en orden las migraciones de un motor
+fn esquema(dialecto: &str) -> Esquema {
+    let mut archivos: Vec<_> = fs::read_dir(raiz().join("migrations").join(dialecto))
+        .expect("directorio de migraciones")
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "sql"))
+        .collect();
+    archivos.sort();
+
+    let crear = Regex::new(r"CREATE TABLE (\w+) \(").unwrap();
+    let agregar = Regex::new(r"ALTER TABLE (\w+) ADD COLUMN (\w+) (\w+)").unwrap();
+    let mut esquema = Esquema::new();
+    for archivo in archivos {
+        let sql = quitar_comentarios(&fs::read_to_string(&archivo).unwrap());
+        for captura in crear.captures_iter(&sql) {
+            let inicio = captura.get(0).unwrap().end();
+            let mut nivel = 1;
+            let fin = sql[inicio..]
+                .char_indices()
+                .find(|&(_, c)| {
+                    nivel += match c {
+                        '(' => 1,
+                        ')' => -1,
+                        _ => 0,
+                    };
+                    nivel == 0
+                })
+                .map(|(i, _)| inicio + i)
+                .expect("paréntesis de CREATE TABLE sin cerrar");
+
+            let columnas = partir(&sql[inicio..fin])
+                .into_iter()
+                .filter_map(|definicion| {
+                    let mut palabras = definicion.split_whitespace();
+                    let nombre = palabras.next()?;
+                    let restricciones = [
+                        "CONSTRAINT",
+                        "PRIMARY",
+                        "INDEX",
+                        "UNIQUE",
+                        "FOREIGN",
+                        "CHECK",
+                        "KEY",
+                    ];
+                    if restricciones.contains(&nombre) {
+                        return None;
+                    }
+                    let tipo = palabras.next()?.split('(').next()?.to_string();
+                    Some((nombre.to_string(), tipo))
+                })
+                .collect();
+            esquema.insert(captura[1].to_string(), columnas);
+        }
+        for captura in agregar.captures_iter(&sql) {
+            esquema
+                .get_mut(&captura[1])
+                .unwrap_or_else(|| panic!("ALTER TABLE sobre {} inexistente", &captura[1]))
+                .insert(captura[2].to_string(), captura[3].to_string());
+        }
+    }
+    esquema
+}
+
+/// Literales de cadena de un archivo Rust (normales y `r#"..."#`), sin comentarios
+fn literales(fuente: &str) -> Vec<String> {
+    let mut literales = Vec::new();
+    let bytes = fuente.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if fuente[i..].starts_with("//") {
+            i += fuente[i..].find('\n').unwrap_or(fuente.len() - i);
+        } else if fuente[i..].starts_with("r#\"") {
+            let fin = fuente[i + 3..].find("\"#").unwrap() + i + 3;
+            literales.push(fuente[i + 3..fin].to_string());
+            i = fin + 2;
+        } else if bytes[i] == b'"' {
+            let mut fin = i + 1;
+            while bytes[fin] != b'"' {
+                fin += if bytes[fin] == b'\\' { 2 } else { 1 };
+            }
+            literales.push(fuente[i + 1..fin].to_string());
+            i = fin + 1;
+        } else if bytes[i] == b'\'' && bytes.get(i + 2) == Some(&b'\'') {
+            // Literal de carácter (p. ej. '"'), no abre una cadena
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    literales
+}
+
+/// Sentencias SQL completas de los adaptadores de un motor, con el archivo de origen
+/// Incluye las definiciones comunes de `sql/`, escritas con marcadores `$n`
+fn consultas(dialecto: &str) -> Vec<(String, String)> {
+    let constantes = Regex::new(r#"const (\w+): &str =\s*"([^"]*)""#).unwrap();
+    let mut consultas = Vec::new();
+    for directorio in [dialecto, "sql"] {
+        let directorio = raiz().join("src/infra/adapters/db").join(directorio);
+        for entrada in fs::read_dir(directorio).unwrap() {
+            let ruta = entrada.unwrap().path();
+            let fuente = fs::read_to_string(&ruta).unwrap();
+            let constantes: Vec<(String, String)> = constantes
+                .captures_iter(&fuente)
+                .map(|c| (format!("{{{}}}", &c[1]), c[2].to_string()))
+                .collect();
+            for literal in literales(&fuente) {
+                let mut sql = literal.split_whitespace().collect::<Vec<_>>().join(" ");
+                for (nombre, valor) in &constantes {
+                    sql = sql.replace(nombre, valor);
+                }
+                let inicio = ["SELECT ", "INSERT ", "UPDATE ", "DELETE "];
+                if inicio.iter().any(|k| sql.starts_with(k)) {
+                    let archivo = ruta.file_name().unwrap().to_string_lossy().to_string();
+                    consultas.push((archivo, sql));
+                }
+            }
+        }
+    }
+    consultas
+}
+
+/// Errores de una sentencia frente al esquema
+fn revisar(sql: &str, esquema: &Esquema, dialecto: &str) -> Vec<String> {
+    let mut errores = Vec::new();
+
+    if Regex::new(r",\s*FROM\b").unwrap().is_match(sql) {
+        errores.push("coma antes de FROM".to_string());
+    }
+
+    // Tablas referenciadas y sus alias
+    let tablas_re =
+        Regex::new(r"(?:\bFROM|\bJOIN|\bINTO|^UPDATE) (\w+)(?: AS)?(?: (\w+))?").unwrap();
+    let mut alias: BTreeMap<String, String> = BTreeMap::new();
+    let mut principal = None;
+    for captura in tablas_re.captures_iter(sql) {
+        let tabla = captura[1].to_string();
+        if !esquema.contains_key(&tabla) {
+            errores.push(format!("tabla inexistente `{}`", tabla));
+            continue;
+        }
+        principal.get_or_insert(tabla.clone());
+        alias.insert(tabla.clone(), tabla.clone());
+        if let Some(a) = captura.get(2).map(|m| m.as_str())
+            && !NO_ALIAS.contains(&a)
+        {
+            alias.insert(a.to_string(), tabla);
+        }
+    }
+    let Some(principal) = principal else {
+        // Tabla dinámica (`{}`): no hay contra qué comparar las columnas
+        return errores;
+    };
+
+    let existe = |columna: &str| -> Result<(), String> {
+        let (tablas, columna): (Vec<&String>, &str) = match columna.split_once('.') {
+            Some(("EXCLUDED", c)) => (vec![&principal], c),
+            Some((a, c)) => match alias.get(a) {
+                Some(t) => (vec![t], c),
+                None => return Err(format!("alias desconocido `{}`", a)),
+            },
+            None => (alias.values().collect(), columna),
+        };
+        if tablas.iter().any(|t| esquema[*t].contains_key(columna)) {
+            Ok(())
+        } else {
+            Err(format!("columna inexistente `{}` en {:?}", columna, tablas))
+        }
+    };
+    let mut exigir = |columna: &str| {
+        if let Err(e) = existe(columna) {
+            errores.push(e);
+        }
+    };
+
+    let ident = |parte: &str| {
+        let parte = parte.split(" AS ").next().unwrap_or_default().trim();
+        let simple = parte
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        (simple && parte.starts_with(|c: char| c.is_ascii_lowercase())).then(|| parte.to_string())
+    };
+
+    if let Some(c) = Regex::new(r"^SELECT (.+?) FROM ").unwrap().captures(sql) {
+        partir(&c[1])
+            .iter()
+            .filter_map(|p| ident(p))
+            .for_each(|c| exigir(&c));
+    }
+    if let Some(c) = Regex::new(r"INTO \w+ \(([^)]*)\)").unwrap().captures(sql) {
+        partir(&c[1])
+            .iter()
+            .filter_map(|p| ident(p))
+            .for_each(|c| exigir(&c));
+    }
+    if let Some(c) = Regex::new(r"RETURNING (.+)$").unwrap().captures(sql) {
+        partir(&c[1])
+            .iter()
+            .filter_map(|p| ident(p))
+            .for_each(|c| exigir(&c));
+    }
+    let comparacion =
+        Regex::new(r"([a-zA-Z_][\w.]*)\)?(?: ?(?:=|<>|<=|>=|<|>) ?| IS | LIKE )").unwrap();
+    for captura in comparacion.captures_iter(sql) {
+        let columna = &captura[1];
+        if columna
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase())
+            || columna.contains('.')
+        {
+            exigir(columna);
+        }
+    }
+
+    // PostgreSQL no compara booleanos con enteros
+    if dialecto == "postgres" {
+        let entero = Regex::new(r"(\w+) = [01]\b").unwrap();
+        for captura in entero.captures_iter(sql) {
+            let booleana = alias.values().any(|t| {
+                esquema[t]
+                    .get(&captura[1])
+                    .is_some_and(|tipo| tipo == "BOOLEAN")
+            });
+            if booleana {
+                errores.push(format!(
+                    "`{}` es BOOLEAN y se compara con un entero",
+                    &captura[1]
+                ));
+            }
+        }
+    }
+
+    errores
+}
+
+#[test]
+fn los_migradores_embebidos_tienen_las_mismas_versiones() {
+    let versiones = |m: &sqlx::migrate::Migrator| {
+        m.iter()
+            .map(|m| (m.version, m.description.to_string()))
+            .collect::<Vec<_>>()
+    };
+    let pg = versiones(&postgres::MIGRATOR);
+    assert!(!pg.is_empty());
+    assert!(pg.windows(2).all(|v| v[0].0 < v[1].0));
+    assert_eq!(pg, versiones(&mysql::MIGRATOR));
+    assert_eq!(pg, versiones(&sqlite::MIGRATOR));
+}
+
+#[test]
+fn todos_los_motores_crean_las_mismas_tablas_y_columnas() {
+    let columnas = |e: Esquema| {
+        e.into_iter()
+            .map(|(t, c)| (t, c.into_keys().collect::<BTreeSet<_>>()))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let pg = columnas(esquema("postgres"));
+    assert_eq!(pg, columnas(esquema("mysql")));
+    assert_eq!(pg, columnas(esquema("sqlite")));
+}
+
+#[test]
+fn el_esquema_cubre_el_nucleo() {
+    let esquema = esquema("postgres");
+    for tabla in ["persona", "perfil", "pagina", "pagper"] {
+        assert!(esquema.contains_key(tabla), "falta la tabla {}", tabla);
+    }
+    assert!(esquema["pagina"].contains_key("codpag"));
+}
+
+#[test]
+fn los_catalogos_existen_en_el_esquema() {
+    for dialecto in DIALECTOS {
+        let esquema = esquema(dialecto);
+        for catalogo in CATALOGOS {
+            let tabla = esquema
+                .get(catalogo.table)
+                .unwrap_or_else(|| panic!("{}: falta la tabla {}", dialecto, catalogo.table));
+            let columnas = [
+                Some(catalogo.id_col),
+                Some(catalogo.label_col),
+                catalogo.active_col,
+            ];
+            for columna in columnas.into_iter().flatten() {
+                assert!(
+                    tabla.contains_key(columna),
+                    "{}: falta {}.{}",
+                    dialecto,
+                    catalogo.table,
+                    columna
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn las_consultas_de_los_adaptadores_coinciden_con_el_esquema() {
+    let mut errores = Vec::new();
+    for dialecto in DIALECTOS {
+        let esquema = esquema(dialecto);
+        let consultas = consultas(dialecto);
+        assert!(
+            !consultas.is_empty(),
+            "{}: no se encontraron consultas",
+            dialecto
+        );
+        for (archivo, sql) in consultas {
+            for error in revisar(&sql, &esquema, dialecto) {
+                errores.push(format!("{}/{}: {}\n    {}", dialecto, archivo, error, sql));
+            }
+        }
+    }
+    assert!(errores.is_empty(), "\n{}", errores.join("\n"));
+}
+
+#[test]
+fn la_revision_detecta_los_errores_conocidos() {
+    let esquema = esquema("postgres");
+    let revisar = |sql: &str| revisar(sql, &esquema, "postgres");
+
+    assert!(!revisar("INSERT INTO personas (nomper) VALUES ($1)").is_empty());
+    assert!(!revisar("SELECT pp.idpef, pp.can_delete, FROM pagper AS pp").is_empty());
+    assert!(!revisar("SELECT idper FROM persona WHERE actper = 1").is_empty());
+    assert!(!revisar("UPDATE persona SET nomperr = $1 WHERE idper = $2").is_empty());
+    assert!(revisar("SELECT idper FROM persona WHERE actper = TRUE").is_empty());
+}
@@ -0,0 +1,256 @@
+//! Enrutamiento de lecturas con `Cluster` sobre dos bases SQLite en memoria
+//! independientes, una como primaria y otra como réplica: lo que devuelve cada
+//! consulta indica de cuál leyó
+#![cfg(feature = "sqlite")]
+
+mod common;
+
+use std::time::Duration;
+
+use futures::TryStreamExt;
+use libropr_rust::{
+    config::{Cluster, Database, OpcionesPool, como_cliente},
+    domain::{Departamento, Municipio, PersonaFilter},
+    infra::{Repos, adapters::db::sqlite::MIGRATOR},
+};
+use sqlx::{Sqlite, SqlitePool, sqlite::SqlitePoolOptions};
+
+const AFINIDAD: Duration = Duration::from_millis(200);
+
+async fn base(nomdep: &str) -> SqlitePool {
+    // Cada conexión a `sqlite::memory:` es una base distinta: una sola conexión
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    MIGRATOR.run(&pool).await.unwrap();
+    Repos::sqlite(pool.clone())
+        .ubicacion
+        .import(
+            vec![Departamento {
+                coddep: 1,
+                nomdep: nomdep.to_string(),
+            }],
+            Vec::new(),
+        )
+        .await
+        .unwrap();
+    pool
+}
+
+async fn cluster() -> (Cluster<Sqlite>, SqlitePool) {
+    let primaria = base("Primaria").await;
+    let replica = base("Réplica").await;
+    let cluster = Cluster::new(
+        primaria,
+        vec![replica.clone()],
+        AFINIDAD,
+        Duration::from_secs(30),
+    );
+    (cluster, replica)
+}
+
+/// Nombre del departamento 1: dice de qué base se leyó
+async fn leida_de(repos: &Repos) -> String {
+    repos
+        .ubicacion
+        .list_departamentos()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|d| d.coddep == 1)
+        .unwrap()
+        .nomdep
+}
+
+async fn escribir(repos: &Repos) {
+    repos
+        .ubicacion
+        .import(
+            vec![Departamento {
+                coddep: 2,
+                nomdep: "Nuevo".to_string(),
+            }],
+            Vec::new(),
+        )
+        .await
+        .unwrap();
+}
+
+/// Deja una persona solo en la primaria: exportar dice de qué base se leyó
+async fn persona_en_la_primaria(primaria: &SqlitePool) {
+    sqlx::query(
+        "INSERT INTO pagina (codpag, nompag, rutpag) VALUES ('replicas', 'Réplicas', '/replicas')",
+    )
+    .execute(primaria)
+    .await
+    .unwrap();
+    let idpef: i64 = sqlx::query_scalar(
+        "INSERT INTO perfil (nompef, pagpri)
+         SELECT 'replicas', idpag FROM pagina WHERE codpag = 'replicas'
+         RETURNING idpef",
+    )
+    .fetch_one(primaria)
+    .await
+    .unwrap();
+    let repos = Repos::sqlite(primaria.clone());
+    repos
+        .ubicacion
+        .import(
+            Vec::new(),
+            vec![Municipio {
+                codubi: 1001,
+                nomubi: "Primaria".to_string(),
+                coddep: 1,
+                nomdep: String::new(),
+            }],
+        )
+        .await
+        .unwrap();
+    repos
+        .persona
+        .create(common::persona_nueva(idpef, 1001))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn lecturas_a_la_replica_y_escrituras_a_la_primaria() {
+    let (cluster, replica) = cluster().await;
+    let repos = Repos::sqlite(cluster.clone());
+
+    assert_eq!(leida_de(&repos).await, "Réplica");
+
+    escribir(&repos).await;
+    assert_eq!(
+        Repos::sqlite(cluster.primaria().clone())
+            .ubicacion
+            .list_departamentos()
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(
+        Repos::sqlite(replica)
+            .ubicacion
+            .list_departamentos()
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn quien_escribe_lee_de_la_primaria_por_un_rato() {
+    let (cluster, _) = cluster().await;
+    let repos = Repos::sqlite(cluster);
+
+    como_cliente(1, async {
+        assert_eq!(leida_de(&repos).await, "Réplica");
+        escribir(&repos).await;
+        assert_eq!(leida_de(&repos).await, "Primaria");
+    })
+    .await;
+
+    // La siguiente petición del mismo cliente sigue en la primaria; las de otros no
+    como_cliente(1, async { assert_eq!(leida_de(&repos).await, "Primaria") }).await;
+    como_cliente(2, async { assert_eq!(leida_de(&repos).await, "Réplica") }).await;
+
+    tokio::time::sleep(AFINIDAD * 2).await;
+    como_cliente(1, async { assert_eq!(leida_de(&repos).await, "Réplica") }).await;
+}
+
+#[tokio::test]
+async fn una_exportacion_lee_de_donde_lo_decidio_su_peticion() {
+    let (cluster, _) = cluster().await;
+    persona_en_la_primaria(cluster.primaria()).await;
+    let repos = Repos::sqlite(cluster);
+
+    // El cuerpo de la respuesta se consume cuando la petición ya terminó
+    let del_que_escribio = como_cliente(1, async {
+        escribir(&repos).await;
+        repos.persona.stream(PersonaFilter::default())
+    })
+    .await;
+    let de_otro = como_cliente(2, async { repos.persona.stream(PersonaFilter::default()) }).await;
+
+    let exportadas: Vec<_> = del_que_escribio.try_collect().await.unwrap();
+    assert_eq!(exportadas.len(), 1);
+    let exportadas: Vec<_> = de_otro.try_collect().await.unwrap();
+    assert!(exportadas.is_empty());
+}
+
+#[tokio::test]
+async fn sin_replica_disponible_se_lee_de_la_primaria() {
+    let (cluster, replica) = cluster().await;
+    let repos = Repos::sqlite(cluster);
+
+    replica.close().await;
+    assert_eq!(leida_de(&repos).await, "Primaria");
+    assert_eq!(leida_de(&repos).await, "Primaria");
+}
+
+#[tokio::test]
+async fn las_estadisticas_cuentan_las_conexiones_de_cada_pool() {
+    let (cluster, replica) = cluster().await;
+    let repos = Repos::sqlite(cluster.clone());
+
+    leida_de(&repos).await;
+    leida_de(&repos).await;
+    escribir(&repos).await;
+    let (primaria, replicas) = cluster.estadisticas();
+    assert_eq!(primaria.adquisiciones, 1);
+    assert_eq!(replicas.len(), 1);
+    assert_eq!(replicas[0].adquisiciones, 2);
+    assert_eq!(replicas[0].maximo, 1);
+    assert_eq!(
+        replicas[0].en_uso + replicas[0].inactivas,
+        replicas[0].abiertas
+    );
+    assert!(replicas[0].disponible);
+
+    replica.close().await;
+    leida_de(&repos).await;
+    let (primaria, replicas) = cluster.estadisticas();
+    assert_eq!(primaria.adquisiciones, 2);
+    assert_eq!(replicas[0].fallidas, 1);
+    assert!(!replicas[0].disponible);
+}
+
+#[tokio::test]
+async fn el_pool_usa_las_opciones_configuradas() {
+    let opciones = OpcionesPool {
+        max_conexiones: 4,
+        ..OpcionesPool::default()
+    };
+    let db = Database::new("sqlite::memory:", &opciones).await.unwrap();
+
+    let estadisticas = db.estadisticas();
+    assert_eq!(estadisticas.motor, "SQLite");
+    // En memoria la base vive en una sola conexión que nunca se cierra
+    assert_eq!(estadisticas.primaria.maximo, 1);
+    assert_eq!(estadisticas.primaria.abiertas, 1);
+    assert!(estadisticas.replicas.is_empty());
+}
+
+#[tokio::test]
+async fn las_replicas_deben_ser_del_mismo_motor() {
+    let opciones = OpcionesPool::default();
+    let db = Database::new("sqlite::memory:", &opciones).await.unwrap();
+
+    let sin_replicas = db
+        .clone()
+        .con_replicas(&[], &opciones, AFINIDAD, Duration::from_secs(30));
+    assert!(sin_replicas.is_ok());
+
+    let sqlite = db.con_replicas(
+        &["sqlite::memory:".to_string()],
+        &opciones,
+        AFINIDAD,
+        Duration::from_secs(30),
+    );
+    assert!(sqlite.is_err());
+}
@@ -0,0 +1,69 @@
+//! El teléfono se juzga en un solo lugar: la librería de numeración, en el servicio
+
+mod common;
+
+use libropr_rust::{
+    api::dtos::CreatePersonaDTO,
+    core::services::telefono::{self, REGION_POR_DEFECTO},
+    domain::Persona,
+    errors::AppError,
+    infra::AppState,
+};
+use serde_json::json;
+use validator::Validate;
+
+#[test]
+fn cualquier_formato_valido_queda_en_e164() {
+    for escrito in [
+        "3001234567",
+        "300 123 4567",
+        "(300) 123-4567",
+        "+57 300.123.4567",
+        "+57 (300) 123 45 67",
+    ] {
+        let telefono = telefono::normalizar(escrito, REGION_POR_DEFECTO).unwrap();
+        assert_eq!(telefono.e164, "+573001234567", "{}", escrito);
+    }
+
+    // Con indicativo manda el indicativo, no la región por defecto
+    let extranjero = telefono::normalizar("+1 202-555-0143", REGION_POR_DEFECTO);
+    assert!(extranjero.is_ok());
+}
+
+#[test]
+fn tener_los_digitos_justos_no_basta() {
+    for escrito in ["12345678", "300 123", "+57 999 999 9999", "   "] {
+        let error = telefono::normalizar(escrito, REGION_POR_DEFECTO).unwrap_err();
+        assert!(matches!(error, AppError::Validation(_)), "{}", escrito);
+    }
+}
+
+#[tokio::test]
+async fn el_dto_no_juzga_el_telefono_y_el_servicio_si() {
+    let dto: CreatePersonaDTO = serde_json::from_value(json!({
+        "tdocper": 1,
+        "ndocper": "1020304050",
+        "nomper": "Ana",
+        "apeper": "Pérez",
+        "telper": "12345678",
+        "codubi": 5001,
+        "idpef": 2,
+        "emaper": "ana@prueba.invalid",
+    }))
+    .unwrap();
+    assert!(dto.validate().is_ok());
+
+    let state = AppState::builder().build();
+    common::cargar_divipola(&state).await;
+
+    let error = state
+        .services
+        .persona
+        .create(Persona::from(dto))
+        .await
+        .unwrap_err();
+    let AppError::Validation(campos) = error else {
+        panic!("se esperaba un error de validación: {:?}", error);
+    };
+    assert_eq!(campos["telper"], ["El teléfono no es un número válido"]);
+}
@@ -0,0 +1,80 @@
+//! Lectura del CSV DIVIPOLA del DANE y su papel en el registro de personas
+
+mod common;
+
+use libropr_rust::{
+    core::services::ubicacion::parse_divipola, domain::Persona, errors::AppError, infra::AppState,
+};
+
+const ENCABEZADO: &str =
+    "Código Departamento,Nombre Departamento,Código Municipio,Nombre Municipio";
+
+#[test]
+fn lee_departamentos_y_municipios_sin_repetir_ni_bom() {
+    let csv = format!(
+        "\u{feff}{}\n05,ANTIOQUIA,05001,MEDELLÍN\n05,ANTIOQUIA,05002,ABEJORRAL\n11,BOGOTÁ D.C.,11001,BOGOTÁ D.C.\n",
+        ENCABEZADO
+    );
+
+    let (departamentos, municipios) = parse_divipola(csv.as_bytes()).unwrap();
+
+    let coddeps: Vec<i64> = departamentos.iter().map(|d| d.coddep).collect();
+    assert_eq!(coddeps, [5, 11]);
+    let codubis: Vec<i64> = municipios.iter().map(|m| m.codubi).collect();
+    assert_eq!(codubis, [5001, 5002, 11001]);
+    assert_eq!(municipios[0].nomubi, "MEDELLÍN");
+    assert_eq!(municipios[0].nomdep, "ANTIOQUIA");
+}
+
+#[test]
+fn acepta_punto_y_coma_latin1_y_codigo_de_tres_digitos() {
+    let mut csv = Vec::new();
+    // "Código" y "Bogotá" en Latin-1, con el municipio sin el departamento
+    csv.extend_from_slice(
+        b"C\xf3digo Departamento;Nombre Departamento;C\xf3digo Municipio;Nombre Municipio\n",
+    );
+    csv.extend_from_slice(b"11;Bogot\xe1;001;Bogot\xe1\n;;;\n");
+
+    let (departamentos, municipios) = parse_divipola(&csv).unwrap();
+
+    assert_eq!(departamentos[0].nomdep, "Bogotá");
+    assert_eq!(municipios.len(), 1);
+    assert_eq!(municipios[0].codubi, 11001);
+}
+
+#[test]
+fn rechaza_archivos_sin_las_columnas_o_sin_municipios() {
+    let sin_columna = "Código Departamento,Nombre Departamento,Nombre Municipio\n05,A,B\n";
+    assert!(matches!(
+        parse_divipola(sin_columna.as_bytes()),
+        Err(AppError::BadRequest(_))
+    ));
+
+    let vacio = format!("{}\n", ENCABEZADO);
+    assert!(matches!(
+        parse_divipola(vacio.as_bytes()),
+        Err(AppError::BadRequest(_))
+    ));
+
+    let codigo_invalido = format!("{}\n05,ANTIOQUIA,05x01,MEDELLÍN\n", ENCABEZADO);
+    let error = parse_divipola(codigo_invalido.as_bytes()).unwrap_err();
+    assert!(error.to_string().contains("Fila 2"), "{}", error);
+}
+
+#[tokio::test]
+async fn sin_catalogo_se_pide_cargarlo_en_vez_de_culpar_al_municipio() {
+    let state = AppState::builder().build();
+
+    let error = state
+        .services
+        .persona
+        .create(Persona {
+            ndocper: Some("1020304050".to_string()),
+            ..common::persona_nueva(2, 5001)
+        })
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+    assert!(!state.services.ubicacion.cargado().await.unwrap());
+}
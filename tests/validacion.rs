@@ -0,0 +1,121 @@
+//! Reglas de formato de persona: las mismas en la API y en la importación
+
+use libropr_rust::{
+    api::dtos::{CreatePersonaDTO, PatchPersonaDTO, UpdatePersonaDTO},
+    core::services::persona::{ImportFormat, parse_rows},
+    errors::{FieldErrors, field_errors},
+};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use validator::Validate;
+
+fn valida() -> Value {
+    json!({
+        "ndocper": "1020304050",
+        "tdocper": 1,
+        "nomper": "Ana",
+        "apeper": "Pérez",
+        "dirper": "Calle 1 # 2-3",
+        "telper": "300 123 4567",
+        "codubi": 5001,
+        "idpef": 2,
+        "pass": "secreta123",
+        "emaper": "ana@prueba.invalid",
+    })
+}
+
+/// La persona válida con `invalidos` encima
+fn invalida() -> Value {
+    let mut persona = valida();
+    let invalidos = json!({
+        "ndocper": "",
+        "tdocper": 0,
+        "nomper": "",
+        "apeper": "x".repeat(101),
+        "dirper": "x".repeat(201),
+        "codubi": 0,
+        "idpef": -1,
+        "pass": "corta",
+        "emaper": "no-es-email",
+    });
+    for (campo, valor) in invalidos.as_object().unwrap() {
+        persona[campo] = valor.clone();
+    }
+    persona
+}
+
+fn errores<T: DeserializeOwned + Validate>(cuerpo: Value) -> FieldErrors {
+    let dto: T = serde_json::from_value(cuerpo).unwrap();
+    dto.validate()
+        .map(|_| FieldErrors::new())
+        .unwrap_or_else(|e| field_errors(&e))
+}
+
+fn esperados(con_contrasena: bool) -> FieldErrors {
+    let mut esperados = FieldErrors::from(
+        [
+            (
+                "ndocper",
+                "El número de documento debe tener entre 1 y 20 caracteres",
+            ),
+            ("tdocper", "Tipo de documento inválido"),
+            ("nomper", "El nombre debe tener entre 1 y 100 caracteres"),
+            ("apeper", "El apellido debe tener entre 1 y 100 caracteres"),
+            ("dirper", "La dirección no puede superar 200 caracteres"),
+            ("codubi", "Ubicación inválida"),
+            ("idpef", "Perfil inválido"),
+            ("pass", "La contraseña debe tener al menos 8 caracteres"),
+            ("emaper", "El email no es válido"),
+        ]
+        .map(|(campo, mensaje)| (campo.to_string(), vec![mensaje.to_string()])),
+    );
+    if !con_contrasena {
+        esperados.remove("pass");
+    }
+    esperados
+}
+
+#[test]
+fn una_persona_bien_escrita_pasa_en_todos_los_cuerpos() {
+    assert!(errores::<CreatePersonaDTO>(valida()).is_empty());
+    assert!(errores::<UpdatePersonaDTO>(valida()).is_empty());
+    assert!(errores::<PatchPersonaDTO>(valida()).is_empty());
+    // En PATCH lo que no viene no se valida
+    assert!(errores::<PatchPersonaDTO>(json!({})).is_empty());
+}
+
+#[test]
+fn cada_campo_se_reporta_con_el_mismo_mensaje_en_todos_los_cuerpos() {
+    assert_eq!(errores::<CreatePersonaDTO>(invalida()), esperados(true));
+    // PUT y PATCH no cambian la contraseña
+    assert_eq!(errores::<UpdatePersonaDTO>(invalida()), esperados(false));
+    assert_eq!(errores::<PatchPersonaDTO>(invalida()), esperados(false));
+}
+
+#[test]
+fn la_importacion_aplica_las_mismas_reglas() {
+    let persona = invalida();
+    let columnas = [
+        "ndocper", "tdocper", "nomper", "apeper", "dirper", "telper", "codubi", "idpef", "pass",
+        "emaper",
+    ];
+    let celda = |campo: &str| match &persona[campo] {
+        Value::String(texto) => texto.clone(),
+        otro => otro.to_string(),
+    };
+    let csv = format!(
+        "{}\n{}\n",
+        columnas.join(","),
+        columnas.map(celda).join(",")
+    );
+
+    let filas = parse_rows(ImportFormat::Csv, csv.as_bytes()).unwrap();
+
+    assert_eq!(filas.len(), 1);
+    let (fila, resultado) = &filas[0];
+    assert_eq!(*fila, 2);
+    // Un documento vacío en el archivo es "sin documento", no un error
+    let mut esperados = esperados(true);
+    esperados.remove("ndocper");
+    assert_eq!(resultado.as_ref().unwrap_err(), &esperados);
+}